@@ -0,0 +1,54 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use clap::Parser;
+use restaurant_menu_app::server;
+use restaurant_menu_app::server::config::{ServerArgs, ServerConfig};
+use std::path::PathBuf;
+
+/// Standalone restaurant API server.
+///
+/// Runs the server without the interactive client, e.g. on a back-office machine.
+/// Settings are taken from the optional configuration file and can be overridden
+/// on the command line.
+#[derive(Parser)]
+#[command(name = "restaurant_server")]
+struct Args {
+    /// TOML configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    #[command(flatten)]
+    server: ServerArgs,
+}
+
+/// Starts the server and runs it until it receives `SIGINT` or `SIGTERM`.
+///
+/// # Returns
+/// * `std::io::Result<()>` - Result indicating success or failure.
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+
+    let config = match &args.config {
+        Some(path) => ServerConfig::from_file(path)?,
+        None => ServerConfig::default(),
+    };
+    let config = args.server.apply_to(config)?;
+
+    let scheme = if config.tls.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    println!(
+        "Restaurant server listening on {}://{}",
+        scheme,
+        config.bind_address()
+    );
+
+    server::main::main(config).await?;
+
+    println!("Restaurant server stopped.");
+    Ok(())
+}
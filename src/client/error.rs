@@ -0,0 +1,107 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use std::fmt;
+
+/// Enum representing errors that can occur when calling the restaurant API.
+///
+/// # Variants
+/// - `Config(String)`: The client could not be configured, e.g. an unreadable CA certificate.
+/// - `Request(reqwest::Error)`: The request could not be sent or timed out.
+/// - `Api { status, message }`: The server answered with an error response.
+/// - `InvalidResponse(String)`: The server's response could not be decoded.
+#[derive(Debug)]
+pub enum ClientError {
+    /// Represents an error while configuring the client.
+    ///
+    /// The string provides additional information about the error.
+    Config(String),
+
+    /// Represents a transport level failure, such as a refused connection or a timeout.
+    Request(reqwest::Error),
+
+    /// Represents an error response returned by the server.
+    ///
+    /// - `status`: The HTTP status code of the response.
+    /// - `message`: The error message from the response body, or the status reason if the body had none.
+    Api { status: u16, message: String },
+
+    /// Represents a response whose body did not match the expected format.
+    ///
+    /// The string provides additional information about the error.
+    InvalidResponse(String),
+}
+
+impl ClientError {
+    /// Returns the HTTP status code if the server answered with an error response.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the server rejected the request because a table or menu item was not found.
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+
+    /// Returns `true` if the server rejected the request (HTTP 4xx).
+    pub fn is_client_error(&self) -> bool {
+        matches!(self.status(), Some(400..=499))
+    }
+
+    /// Returns `true` if the server failed to process the request (HTTP 5xx).
+    pub fn is_server_error(&self) -> bool {
+        matches!(self.status(), Some(500..=599))
+    }
+}
+
+impl fmt::Display for ClientError {
+    /// Formats the error message for each variant of `ClientError`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Config(err) => write!(f, "Client configuration error: {}", err),
+            ClientError::Request(err) => write!(f, "Request failed: {}", err),
+            ClientError::Api { status, message } => write!(f, "Error ({}): {}", status, message),
+            ClientError::InvalidResponse(err) => write!(f, "Invalid response from server: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Request(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_status_helpers() {
+        let err = ClientError::Api {
+            status: 404,
+            message: "Table not found for table id:7".to_string(),
+        };
+        assert_eq!(err.status(), Some(404));
+        assert!(err.is_not_found());
+        assert!(err.is_client_error());
+        assert!(!err.is_server_error());
+        assert_eq!(
+            err.to_string(),
+            "Error (404): Table not found for table id:7"
+        );
+    }
+
+    #[test]
+    fn test_non_api_error_has_no_status() {
+        let err = ClientError::InvalidResponse("missing data".to_string());
+        assert_eq!(err.status(), None);
+        assert!(!err.is_client_error());
+        assert!(!err.is_server_error());
+    }
+}
@@ -0,0 +1,723 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::error::ClientError;
+use crate::client::output;
+use crate::client::restaurant_client::RestaurantClient;
+use crate::client::simulation::run_workers;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// An operation of the v1 API driven by the load test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Endpoint {
+    /// `GET /api/v1/menus`
+    Menus,
+    /// `GET /api/v1/tables`
+    Tables,
+    /// `POST /api/v1/add_item/{table_id}/{item_id}`
+    Add,
+    /// `DELETE /api/v1/remove_item/{table_id}/{item_id}`
+    Remove,
+    /// `GET /api/v1/get_items/{table_id}`
+    Orders,
+    /// `GET /api/v1/get_item/{table_id}/{item_id}`
+    Item,
+}
+
+impl Endpoint {
+    /// All endpoints, in the order they are reported.
+    pub const ALL: [Endpoint; 6] = [
+        Endpoint::Menus,
+        Endpoint::Tables,
+        Endpoint::Add,
+        Endpoint::Remove,
+        Endpoint::Orders,
+        Endpoint::Item,
+    ];
+
+    /// Returns the name used in `--mix` and in reports, e.g. `add`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Endpoint::Menus => "menus",
+            Endpoint::Tables => "tables",
+            Endpoint::Add => "add",
+            Endpoint::Remove => "remove",
+            Endpoint::Orders => "orders",
+            Endpoint::Item => "item",
+        }
+    }
+
+    /// Returns the HTTP method and path of the endpoint.
+    pub fn route(&self) -> &'static str {
+        match self {
+            Endpoint::Menus => "GET /api/v1/menus",
+            Endpoint::Tables => "GET /api/v1/tables",
+            Endpoint::Add => "POST /api/v1/add_item/{table_id}/{item_id}",
+            Endpoint::Remove => "DELETE /api/v1/remove_item/{table_id}/{item_id}",
+            Endpoint::Orders => "GET /api/v1/get_items/{table_id}",
+            Endpoint::Item => "GET /api/v1/get_item/{table_id}/{item_id}",
+        }
+    }
+
+    /// Returns `true` if the request needs a menu item previously ordered for a table.
+    fn needs_order(&self) -> bool {
+        matches!(self, Endpoint::Remove | Endpoint::Orders | Endpoint::Item)
+    }
+}
+
+/// The relative weight of each endpoint in the generated traffic.
+pub type Mix = BTreeMap<Endpoint, u32>;
+
+/// Returns the default mix, dominated by adding and reading orders as during service.
+pub fn default_mix() -> Mix {
+    Mix::from([
+        (Endpoint::Menus, 1),
+        (Endpoint::Tables, 1),
+        (Endpoint::Add, 3),
+        (Endpoint::Remove, 2),
+        (Endpoint::Orders, 2),
+        (Endpoint::Item, 1),
+    ])
+}
+
+/// Parses a mix such as `add=3,remove=2,orders=5`. Omitted endpoints are not requested.
+///
+/// # Arguments
+/// * `value` - Comma separated `endpoint=weight` pairs.
+///
+/// # Returns
+/// * `Ok(Mix)` - The parsed weights.
+/// * `Err(String)` - If an endpoint or weight is invalid, or all weights are 0.
+pub fn parse_mix(value: &str) -> Result<Mix, String> {
+    let mut mix = Mix::new();
+    for pair in value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let (name, weight) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Expected ENDPOINT=WEIGHT, got '{}'", pair))?;
+        let endpoint = Endpoint::ALL
+            .into_iter()
+            .find(|endpoint| endpoint.name() == name.trim())
+            .ok_or_else(|| {
+                let names: Vec<&str> = Endpoint::ALL.iter().map(Endpoint::name).collect();
+                format!(
+                    "Unknown endpoint '{}', expected one of: {}",
+                    name.trim(),
+                    names.join(", ")
+                )
+            })?;
+        let weight: u32 = weight
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid weight '{}' for {}", weight.trim(), name.trim()))?;
+        mix.insert(endpoint, weight);
+    }
+    if mix.values().all(|&weight| weight == 0) {
+        return Err("At least one endpoint needs a weight above 0".to_string());
+    }
+    Ok(mix)
+}
+
+/// Configuration of a load test run.
+///
+/// - `duration`: How long requests are sent.
+/// - `concurrency`: Number of parallel workers, each with at most one request in flight.
+/// - `rate`: Target number of requests per second over all workers. Workers send as fast
+///   as the server answers when `None`.
+/// - `mix`: The relative weight of each endpoint.
+/// - `tables`: Number of tables used, starting with the lowest IDs. All tables of the server when `None`.
+/// - `seed`: Seed of the random number generators. A random seed is chosen when `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadTest {
+    pub duration: Duration,
+    pub concurrency: usize,
+    pub rate: Option<f64>,
+    pub mix: Mix,
+    pub tables: Option<usize>,
+    pub seed: Option<u64>,
+}
+
+impl Default for LoadTest {
+    /// Provides the default load test: 10 workers for 10 seconds with the default mix.
+    fn default() -> Self {
+        LoadTest {
+            duration: Duration::from_secs(10),
+            concurrency: 10,
+            rate: None,
+            mix: default_mix(),
+            tables: None,
+            seed: None,
+        }
+    }
+}
+
+impl LoadTest {
+    /// Checks that the load test can be run.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the configuration is valid.
+    /// * `Err(String)` - A message describing the first invalid field.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.duration.is_zero() {
+            return Err("The duration must be at least 1 second".to_string());
+        }
+        if self.concurrency == 0 {
+            return Err("The concurrency must be at least 1".to_string());
+        }
+        if let Some(rate) = self.rate {
+            if !rate.is_finite() || rate <= 0.0 {
+                return Err("The rate must be above 0 requests per second".to_string());
+            }
+        }
+        if self.mix.values().all(|&weight| weight == 0) {
+            return Err("At least one endpoint needs a weight above 0".to_string());
+        }
+        if self.tables == Some(0) {
+            return Err("The number of tables must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Command line arguments for configuring a load test.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct LoadTestArgs {
+    /// Duration of the test in seconds [default: 10]
+    #[arg(long)]
+    pub duration: Option<u64>,
+
+    /// Number of parallel workers [default: 10]
+    #[arg(long)]
+    pub concurrency: Option<usize>,
+
+    /// Target requests per second over all workers [default: as fast as possible]
+    #[arg(long)]
+    pub rate: Option<f64>,
+
+    /// Weight of each endpoint, e.g. add=3,remove=2,orders=5
+    /// [default: menus=1,tables=1,add=3,remove=2,orders=2,item=1]
+    #[arg(long, value_parser = parse_mix)]
+    pub mix: Option<Mix>,
+
+    /// Number of tables used, starting with the lowest IDs [default: all tables]
+    #[arg(long)]
+    pub tables: Option<usize>,
+
+    /// Seed of the random number generators [default: random]
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Also write the report as CSV to this file
+    #[arg(long)]
+    pub csv: Option<PathBuf>,
+
+    /// Also write the report as JSON to this file
+    #[arg(long)]
+    pub json: Option<PathBuf>,
+}
+
+impl LoadTestArgs {
+    /// Applies the command line arguments on top of the defaults.
+    ///
+    /// # Returns
+    /// * `Ok(LoadTest)` - The validated configuration.
+    /// * `Err(String)` - If the resulting configuration is invalid.
+    pub fn load(&self) -> Result<LoadTest, String> {
+        let defaults = LoadTest::default();
+        let load_test = LoadTest {
+            duration: self.duration.map_or(defaults.duration, Duration::from_secs),
+            concurrency: self.concurrency.unwrap_or(defaults.concurrency),
+            rate: self.rate.or(defaults.rate),
+            mix: self.mix.clone().unwrap_or(defaults.mix),
+            tables: self.tables.or(defaults.tables),
+            seed: self.seed.or(defaults.seed),
+        };
+        load_test.validate()?;
+        Ok(load_test)
+    }
+}
+
+/// Latency and error statistics of one endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EndpointReport {
+    pub endpoint: String,
+    pub route: String,
+    pub requests: usize,
+    pub errors: usize,
+    /// Number of failed requests per error kind, e.g. `HTTP 500` or `timeout`.
+    pub error_breakdown: BTreeMap<String, usize>,
+    /// Requests per second over the whole test.
+    pub throughput: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// The outcome of a load test run.
+///
+/// Latencies include failed requests and are measured from sending a request until its
+/// response has been read.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoadTestReport {
+    pub seed: u64,
+    pub duration_secs: f64,
+    /// The statistics of each requested endpoint, in the order of `Endpoint::ALL`.
+    pub endpoints: Vec<EndpointReport>,
+    /// The statistics over all endpoints.
+    pub total: EndpointReport,
+}
+
+impl LoadTestReport {
+    /// Formats the report as a table of endpoints, followed by the error breakdown.
+    pub fn to_table(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .endpoints
+            .iter()
+            .chain(std::iter::once(&self.total))
+            .map(|report| {
+                vec![
+                    report.endpoint.clone(),
+                    report.requests.to_string(),
+                    report.errors.to_string(),
+                    format!("{:.1}", report.throughput),
+                    format!("{:.2}", report.p50_ms),
+                    format!("{:.2}", report.p95_ms),
+                    format!("{:.2}", report.p99_ms),
+                ]
+            })
+            .collect();
+        let mut lines = vec![output::render_table(
+            &[
+                "Endpoint", "Requests", "Errors", "Req/s", "p50 (ms)", "p95 (ms)", "p99 (ms)",
+            ],
+            &rows,
+        )];
+
+        for report in &self.endpoints {
+            if !report.error_breakdown.is_empty() {
+                lines.push(output::error(&format!(
+                    "{} errors: {}",
+                    report.endpoint,
+                    format_breakdown(&report.error_breakdown, ", ")
+                )));
+            }
+        }
+        lines.push(format!(
+            "{} requests in {:.1} s, {:.1} requests/s (seed {})",
+            self.total.requests, self.duration_secs, self.total.throughput, self.seed
+        ));
+        lines.join("\n")
+    }
+
+    /// Formats the report as CSV with one row per endpoint and a final `total` row.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![
+            "endpoint,route,requests,errors,throughput,p50_ms,p95_ms,p99_ms,error_breakdown"
+                .to_string(),
+        ];
+        for report in self.endpoints.iter().chain(std::iter::once(&self.total)) {
+            lines.push(format!(
+                "{},{},{},{},{:.3},{:.3},{:.3},{:.3},{}",
+                report.endpoint,
+                report.route,
+                report.requests,
+                report.errors,
+                report.throughput,
+                report.p50_ms,
+                report.p95_ms,
+                report.p99_ms,
+                format_breakdown(&report.error_breakdown, ";")
+            ));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Formats the report as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Formats an error breakdown, e.g. `HTTP 500=3;timeout=1`.
+fn format_breakdown(breakdown: &BTreeMap<String, usize>, separator: &str) -> String {
+    breakdown
+        .iter()
+        .map(|(kind, count)| format!("{}={}", kind, count))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Returns the nearest-rank percentile of sorted latencies, or zero if there are none.
+///
+/// # Arguments
+/// * `sorted` - The latencies, in ascending order.
+/// * `percentile` - The percentile, between 0 and 100.
+pub fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Classifies a failed request for the error breakdown.
+fn error_kind(err: &ClientError) -> String {
+    match err {
+        ClientError::Api { status, .. } => format!("HTTP {}", status),
+        ClientError::Request(err) if err.is_timeout() => "timeout".to_string(),
+        ClientError::Request(err) if err.is_connect() => "connection".to_string(),
+        ClientError::Request(_) => "request".to_string(),
+        ClientError::InvalidResponse(_) => "invalid response".to_string(),
+        ClientError::Config(_) => "configuration".to_string(),
+    }
+}
+
+/// The latencies and errors recorded for one endpoint.
+#[derive(Debug, Clone, Default)]
+struct Samples {
+    latencies: Vec<Duration>,
+    errors: BTreeMap<String, usize>,
+}
+
+impl Samples {
+    fn merge(&mut self, other: Samples) {
+        self.latencies.extend(other.latencies);
+        for (kind, count) in other.errors {
+            *self.errors.entry(kind).or_default() += count;
+        }
+    }
+
+    fn report(mut self, endpoint: &str, route: &str, elapsed: Duration) -> EndpointReport {
+        self.latencies.sort_unstable();
+        let millis = |latency: Duration| latency.as_secs_f64() * 1000.0;
+        EndpointReport {
+            endpoint: endpoint.to_string(),
+            route: route.to_string(),
+            requests: self.latencies.len(),
+            errors: self.errors.values().sum(),
+            throughput: self.latencies.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            p50_ms: millis(percentile(&self.latencies, 50.0)),
+            p95_ms: millis(percentile(&self.latencies, 95.0)),
+            p99_ms: millis(percentile(&self.latencies, 99.0)),
+            error_breakdown: self.errors,
+        }
+    }
+}
+
+/// Spaces the requests of all workers to reach a target rate.
+struct Pacer {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl Pacer {
+    fn new(rate: f64, start: Instant) -> Self {
+        Pacer {
+            interval: Duration::from_secs_f64(1.0 / rate),
+            next: Mutex::new(start),
+        }
+    }
+
+    /// Reserves the next free send slot and returns it.
+    fn reserve(&self) -> Instant {
+        let now = Instant::now();
+        match self.next.lock() {
+            Ok(mut next) => {
+                let slot = (*next).max(now);
+                *next = slot + self.interval;
+                slot
+            }
+            Err(_) => now,
+        }
+    }
+}
+
+/// Runs a load test against the server.
+///
+/// `load_test.concurrency` workers are started with the parallel task machinery of the
+/// simulation. Each worker picks endpoints at random according to the mix and sends one
+/// request at a time until the duration has elapsed, waiting for its send slot if a rate
+/// is set. Requests that need an ordered menu item (remove, orders, item) use an item
+/// the worker has added itself, so that they do not fail because of other workers; a
+/// worker without such an item sends an add request instead.
+///
+/// # Arguments
+/// * `client` - The client used to send the requests. It should not retry requests, as
+///   retries would hide errors and inflate latencies.
+/// * `load_test` - The load test to run.
+///
+/// # Returns
+/// * `Ok(LoadTestReport)` - The statistics of the run.
+/// * `Err(String)` - If the tables and menu items could not be loaded.
+pub async fn run_load_test(
+    client: &RestaurantClient,
+    load_test: &LoadTest,
+) -> Result<LoadTestReport, String> {
+    let seed = load_test.seed.unwrap_or_else(rand::random);
+    let (mut table_ids, mut menu_ids) =
+        match tokio::try_join!(client.get_tables(), client.get_menus()) {
+            Ok((tables, menus)) => (
+                tables,
+                menus.iter().map(|menu| menu.item.id).collect::<Vec<_>>(),
+            ),
+            Err(err) => return Err(format!("Error preparing the load test: {}", err)),
+        };
+    table_ids.sort_unstable();
+    menu_ids.sort_unstable();
+    if let Some(tables) = load_test.tables {
+        if tables > table_ids.len() {
+            return Err(format!(
+                "The load test needs {} tables, but the server only has {}",
+                tables,
+                table_ids.len()
+            ));
+        }
+        table_ids.truncate(tables);
+    }
+    if table_ids.is_empty() || menu_ids.is_empty() {
+        return Err("The server has no tables or no menu items".to_string());
+    }
+
+    let endpoints: Vec<Endpoint> = load_test
+        .mix
+        .iter()
+        .filter(|(_, &weight)| weight > 0)
+        .map(|(&endpoint, _)| endpoint)
+        .collect();
+    let weights = WeightedIndex::new(endpoints.iter().map(|endpoint| load_test.mix[endpoint]))
+        .map_err(|e| format!("Invalid mix: {}", e))?;
+
+    let start = Instant::now();
+    let deadline = start + load_test.duration;
+    let pacer = load_test.rate.map(|rate| Arc::new(Pacer::new(rate, start)));
+    let results: Arc<Mutex<Vec<HashMap<Endpoint, Samples>>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let worker_client = client.clone();
+    let worker_results = Arc::clone(&results);
+    let (table_ids, menu_ids) = (Arc::new(table_ids), Arc::new(menu_ids));
+    let workers: Vec<u64> = (0..load_test.concurrency as u64).collect();
+    run_workers(workers, load_test.concurrency, move |worker: u64| {
+        let client = worker_client.clone();
+        let results = Arc::clone(&worker_results);
+        let (endpoints, weights) = (endpoints.clone(), weights.clone());
+        let (table_ids, menu_ids) = (Arc::clone(&table_ids), Arc::clone(&menu_ids));
+        let pacer = pacer.clone();
+        async move {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(worker));
+            // The (table, menu item) pairs this worker has ordered and not yet removed
+            let mut ordered: Vec<(u32, u32)> = Vec::new();
+            let mut samples: HashMap<Endpoint, Samples> = HashMap::new();
+
+            loop {
+                if let Some(pacer) = &pacer {
+                    let slot = pacer.reserve();
+                    if slot >= deadline {
+                        break;
+                    }
+                    tokio::time::sleep_until(slot.into()).await;
+                } else if Instant::now() >= deadline {
+                    break;
+                }
+
+                let mut endpoint = endpoints[weights.sample(&mut rng)];
+                if endpoint.needs_order() && ordered.is_empty() {
+                    endpoint = Endpoint::Add;
+                }
+                let order = if endpoint.needs_order() {
+                    Some(rng.gen_range(0..ordered.len()))
+                } else {
+                    None
+                };
+
+                let sent = Instant::now();
+                let result = match endpoint {
+                    Endpoint::Menus => client.get_menus().await.map(|_| ()),
+                    Endpoint::Tables => client.get_tables().await.map(|_| ()),
+                    Endpoint::Add => {
+                        let table_id = *table_ids.choose(&mut rng).unwrap_or(&1);
+                        let item_id = *menu_ids.choose(&mut rng).unwrap_or(&1);
+                        let result = client.add_item(table_id, item_id).await;
+                        if result.is_ok() {
+                            ordered.push((table_id, item_id));
+                        }
+                        result.map(|_| ())
+                    }
+                    Endpoint::Remove => {
+                        let (table_id, item_id) = ordered.swap_remove(order.unwrap_or(0));
+                        client.remove_item(table_id, item_id).await.map(|_| ())
+                    }
+                    Endpoint::Orders => {
+                        let (table_id, _) = ordered[order.unwrap_or(0)];
+                        client.get_items(table_id).await.map(|_| ())
+                    }
+                    Endpoint::Item => {
+                        let (table_id, item_id) = ordered[order.unwrap_or(0)];
+                        client.get_item(table_id, item_id).await.map(|_| ())
+                    }
+                };
+
+                let endpoint_samples = samples.entry(endpoint).or_default();
+                endpoint_samples.latencies.push(sent.elapsed());
+                if let Err(err) = result {
+                    *endpoint_samples.errors.entry(error_kind(&err)).or_default() += 1;
+                }
+            }
+
+            if let Ok(mut results) = results.lock() {
+                results.push(samples);
+            }
+        }
+    })
+    .await;
+    let elapsed = start.elapsed();
+
+    let mut merged: BTreeMap<Endpoint, Samples> = BTreeMap::new();
+    let worker_samples = match results.lock() {
+        Ok(mut results) => std::mem::take(&mut *results),
+        Err(_) => Vec::new(),
+    };
+    for samples in worker_samples {
+        for (endpoint, samples) in samples {
+            merged.entry(endpoint).or_default().merge(samples);
+        }
+    }
+
+    let mut total = Samples::default();
+    let endpoints = merged
+        .into_iter()
+        .map(|(endpoint, samples)| {
+            total.merge(samples.clone());
+            samples.report(endpoint.name(), endpoint.route(), elapsed)
+        })
+        .collect();
+    Ok(LoadTestReport {
+        seed,
+        duration_secs: elapsed.as_secs_f64(),
+        endpoints,
+        total: total.report("total", "", elapsed),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_percentile() {
+        let latencies = millis(&(1..=100).collect::<Vec<_>>());
+        assert_eq!(percentile(&latencies, 50.0), Duration::from_millis(50));
+        assert_eq!(percentile(&latencies, 95.0), Duration::from_millis(95));
+        assert_eq!(percentile(&latencies, 99.0), Duration::from_millis(99));
+        assert_eq!(percentile(&latencies, 100.0), Duration::from_millis(100));
+        assert_eq!(percentile(&millis(&[7]), 99.0), Duration::from_millis(7));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_mix() {
+        assert_eq!(
+            parse_mix("add=3, orders=5,remove=0"),
+            Ok(Mix::from([
+                (Endpoint::Add, 3),
+                (Endpoint::Orders, 5),
+                (Endpoint::Remove, 0)
+            ]))
+        );
+        assert!(parse_mix("add").is_err());
+        assert!(parse_mix("bill=1").is_err());
+        assert!(parse_mix("add=-1").is_err());
+        assert!(parse_mix("add=0").is_err());
+    }
+
+    #[test]
+    fn test_validate_load_test() {
+        assert!(LoadTest::default().validate().is_ok());
+        for load_test in [
+            LoadTest {
+                duration: Duration::ZERO,
+                ..Default::default()
+            },
+            LoadTest {
+                concurrency: 0,
+                ..Default::default()
+            },
+            LoadTest {
+                rate: Some(0.0),
+                ..Default::default()
+            },
+            LoadTest {
+                tables: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(load_test.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_samples_report() {
+        let mut samples = Samples {
+            latencies: millis(&[30, 10, 20]),
+            errors: BTreeMap::from([("HTTP 500".to_string(), 1)]),
+        };
+        samples.merge(Samples {
+            latencies: millis(&[40]),
+            errors: BTreeMap::from([("HTTP 500".to_string(), 2)]),
+        });
+        let report = samples.report("add", Endpoint::Add.route(), Duration::from_secs(2));
+        assert_eq!(report.requests, 4);
+        assert_eq!(report.errors, 3);
+        assert_eq!(report.throughput, 2.0);
+        assert_eq!(report.p50_ms, 20.0);
+        assert_eq!(report.p99_ms, 40.0);
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let endpoint = Samples {
+            latencies: millis(&[10]),
+            errors: BTreeMap::from([("HTTP 404".to_string(), 1), ("timeout".to_string(), 2)]),
+        }
+        .report("item", Endpoint::Item.route(), Duration::from_secs(1));
+        let report = LoadTestReport {
+            seed: 1,
+            duration_secs: 1.0,
+            endpoints: vec![endpoint.clone()],
+            total: EndpointReport {
+                endpoint: "total".to_string(),
+                route: String::new(),
+                ..endpoint
+            },
+        };
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "item,GET /api/v1/get_item/{table_id}/{item_id},1,3,1.000,10.000,10.000,10.000,HTTP 404=1;timeout=2"
+        );
+        assert!(lines[2].starts_with("total,,1,3,"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["endpoints"][0]["error_breakdown"]["timeout"], 2);
+        assert!(report
+            .to_table()
+            .contains("item errors: HTTP 404=1, timeout=2"));
+    }
+}
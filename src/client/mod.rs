@@ -0,0 +1,13 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+pub mod error;
+pub mod load_test;
+pub mod output;
+pub mod repl;
+pub mod reservation;
+pub mod restaurant_client;
+pub mod session;
+pub mod simulation;
+pub mod split;
+pub mod tui;
@@ -0,0 +1,1512 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::error::ClientError;
+use crate::server::data_model::dietary;
+use crate::server::data_model::inventory::{Ingredient, LowStockReport};
+use crate::server::data_model::menu::{CategorizedMenu, Category};
+use crate::server::data_model::models::Bill;
+use crate::server::data_model::modifier::OrderedItem;
+use crate::server::data_model::money::Money;
+use crate::server::data_model::payment::{PaymentReceipt, TableAccount};
+use crate::server::data_model::reservation::{Reservation, TableStatus};
+use crate::server::data_model::session::TableSession;
+use crate::server::data_model::split::SplitBill;
+use crate::server::data_model::stock::{MenuListing, StockLevel};
+use serde_json::json;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether ANSI colors are written. Disabled until `init_colors` or `set_colors` is called.
+static COLORS: AtomicBool = AtomicBool::new(false);
+
+/// Enables colored output if stdout is a terminal and the `NO_COLOR` environment variable is not set.
+pub fn init_colors() {
+    set_colors(std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none());
+}
+
+/// Enables or disables colored output.
+pub fn set_colors(enabled: bool) {
+    COLORS.store(enabled, Ordering::Relaxed);
+}
+
+/// Formats an error message in red.
+pub fn error(text: &str) -> String {
+    paint(text, "31")
+}
+
+/// Formats a success message in green.
+pub fn success(text: &str) -> String {
+    paint(text, "32")
+}
+
+/// Formats a warning in yellow.
+pub fn warning(text: &str) -> String {
+    paint(text, "33")
+}
+
+/// Formats text in bold.
+fn bold(text: &str) -> String {
+    paint(text, "1")
+}
+
+fn paint(text: &str, code: &str) -> String {
+    paint_with(COLORS.load(Ordering::Relaxed), text, code)
+}
+
+/// Wraps `text` in the ANSI escape sequence `code` if `enabled` is set.
+fn paint_with(enabled: bool, text: &str, code: &str) -> String {
+    if enabled {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// The result of a successful request, ready to be printed.
+///
+/// `to_table` renders the result for staff, `to_json` renders the JSON envelope the
+/// server sent (the `--raw` view).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Output {
+    /// All menu items of the restaurant.
+    Menu(Vec<MenuListing>),
+    /// The menu grouped by category.
+    CategorizedMenu(CategorizedMenu),
+    /// The categories of the menu.
+    Categories(Vec<Category>),
+    /// A single category, e.g. after changing whether it is served.
+    Category(Category),
+    /// The stock of counted or sold out menu items.
+    Stock(Vec<StockLevel>),
+    /// The stock of a single menu item, e.g. after marking it sold out.
+    StockLevel(StockLevel),
+    /// The ingredients in the inventory.
+    Ingredients(Vec<Ingredient>),
+    /// A single ingredient, e.g. after restocking it.
+    Ingredient(Ingredient),
+    /// The ingredients low on stock and the menu items that cannot be made.
+    LowStock(LowStockReport),
+    /// All tables with their capacity and current reservation.
+    Tables(Vec<TableStatus>),
+    /// The menu items ordered for a table.
+    Orders {
+        table_id: u32,
+        items: Vec<OrderedItem>,
+    },
+    /// A single menu item ordered for a table.
+    Item(OrderedItem),
+    /// The bill of a table.
+    Bill(Bill),
+    /// The checks a table's bill was split into.
+    Checks(SplitBill),
+    /// A recorded payment or tip with the resulting account.
+    Receipt(PaymentReceipt),
+    /// The payments and balance of a table's bill.
+    Account(TableAccount),
+    /// A session of a table, e.g. after opening or closing the table.
+    Session(TableSession),
+    /// Closed sessions.
+    Sessions(Vec<TableSession>),
+    /// Reservations, e.g. of one day.
+    Reservations(Vec<Reservation>),
+    /// A single reservation, e.g. after making or cancelling it.
+    Reservation(Reservation),
+    /// The confirmation message of an add or remove request.
+    Message(String),
+}
+
+impl Output {
+    /// Renders the result as a table, or as JSON if `raw` is set.
+    pub fn render(&self, raw: bool) -> String {
+        if raw {
+            self.to_json()
+        } else {
+            self.to_table()
+        }
+    }
+
+    /// Formats the result in the same JSON envelope the server uses.
+    pub fn to_json(&self) -> String {
+        let body = match self {
+            Output::Menu(items) => json!({ "status": "ok", "data": items }),
+            Output::Orders { items, .. } => json!({ "status": "ok", "data": items }),
+            Output::CategorizedMenu(menu) => json!({ "status": "ok", "data": menu }),
+            Output::Categories(categories) => json!({ "status": "ok", "data": categories }),
+            Output::Category(category) => json!({ "status": "ok", "data": category }),
+            Output::Stock(levels) => json!({ "status": "ok", "data": levels }),
+            Output::StockLevel(level) => json!({ "status": "ok", "data": level }),
+            Output::Ingredients(ingredients) => json!({ "status": "ok", "data": ingredients }),
+            Output::Ingredient(ingredient) => json!({ "status": "ok", "data": ingredient }),
+            Output::LowStock(report) => json!({ "status": "ok", "data": report }),
+            Output::Tables(tables) => json!({ "status": "ok", "data": tables }),
+            Output::Item(item) => json!({ "status": "ok", "data": item }),
+            Output::Bill(bill) => json!({ "status": "ok", "data": bill }),
+            Output::Checks(split) => json!({ "status": "ok", "data": split }),
+            Output::Receipt(receipt) => json!({ "status": "ok", "data": receipt }),
+            Output::Account(account) => json!({ "status": "ok", "data": account }),
+            Output::Session(session) => json!({ "status": "ok", "data": session }),
+            Output::Sessions(sessions) => json!({ "status": "ok", "data": sessions }),
+            Output::Reservations(reservations) => json!({ "status": "ok", "data": reservations }),
+            Output::Reservation(reservation) => json!({ "status": "ok", "data": reservation }),
+            Output::Message(message) => json!({ "status": "ok", "message": message }),
+        };
+        serde_json::to_string_pretty(&body).unwrap_or_default()
+    }
+
+    /// Formats the result as an aligned table followed by a summary line.
+    pub fn to_table(&self) -> String {
+        match self {
+            Output::Menu(items) => format!(
+                "{}\n{}",
+                render_menu_items(items),
+                plural(items.len(), "menu item")
+            ),
+            Output::CategorizedMenu(menu) => format!(
+                "{}\n{} in {}",
+                render_categorized_menu(menu),
+                plural(menu.item_count(), "menu item"),
+                plural(menu.sections.len(), "category")
+            ),
+            Output::Categories(categories) => format!(
+                "{}\n{}",
+                render_categories(categories),
+                plural(categories.len(), "category")
+            ),
+            Output::Category(category) => render_categories(std::slice::from_ref(category)),
+            Output::Stock(levels) => format!(
+                "{}\n{}",
+                render_stock(levels),
+                plural(levels.len(), "tracked menu item")
+            ),
+            Output::StockLevel(level) => render_stock(std::slice::from_ref(level)),
+            Output::Ingredients(ingredients) => format!(
+                "{}\n{}, {} low on stock",
+                render_ingredients(ingredients),
+                plural(ingredients.len(), "ingredient"),
+                ingredients.iter().filter(|i| i.is_low()).count()
+            ),
+            Output::Ingredient(ingredient) => render_ingredients(std::slice::from_ref(ingredient)),
+            Output::LowStock(report) => render_low_stock(report),
+            Output::Tables(tables) => {
+                format!(
+                    "{}\n{}, {} reserved",
+                    render_tables(tables),
+                    plural(tables.len(), "table"),
+                    tables.iter().filter(|table| table.reserved).count()
+                )
+            }
+            Output::Orders { table_id, items } => render_orders(*table_id, items),
+            Output::Item(item) => render_ordered_item(item),
+            Output::Bill(bill) => render_bill(bill),
+            Output::Checks(split) => render_checks(split),
+            Output::Receipt(receipt) => render_receipt(receipt),
+            Output::Account(account) => render_account(account),
+            Output::Session(session) => render_session(session),
+            Output::Reservations(reservations) => format!(
+                "{}\n{}",
+                render_reservations(reservations),
+                plural(reservations.len(), "reservation")
+            ),
+            Output::Reservation(reservation) => {
+                render_reservations(std::slice::from_ref(reservation))
+            }
+            Output::Sessions(sessions) => format!(
+                "{}\n{}",
+                render_sessions(sessions),
+                plural(sessions.len(), "session")
+            ),
+            Output::Message(message) => success(message),
+        }
+    }
+}
+
+/// Formats a failed request, e.g. `Error adding menu item: Error (404): ...`.
+///
+/// In `raw` mode, error responses from the server are rendered as the JSON envelope the
+/// server sent, so that scripts can parse them.
+///
+/// # Arguments
+/// * `context` - Describes the operation that failed.
+/// * `err` - The error returned by the client.
+/// * `raw` - Whether the JSON view is used.
+pub fn render_error(context: &str, err: &ClientError, raw: bool) -> String {
+    match err {
+        ClientError::Api { message, .. } if raw => {
+            let body = json!({ "status": "error", "message": message });
+            serde_json::to_string_pretty(&body).unwrap_or_default()
+        }
+        _ => error(&format!("{}: {}", context, err)),
+    }
+}
+
+/// Renders menu items as a table with their ID, name, cooking time and price.
+///
+/// Their allergens and diets are only shown if any menu item is tagged with them.
+///
+/// # Arguments
+/// * `listings` - The listed menu items to render.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_menu_items(listings: &[MenuListing]) -> String {
+    let tagged = listings
+        .iter()
+        .any(|listing| !listing.item.allergens.is_empty() || !listing.item.diets.is_empty());
+    let stocked = listings
+        .iter()
+        .any(|listing| listing.sold_out || listing.remaining.is_some());
+    let tags = |values: String| {
+        if values.is_empty() {
+            "-".to_string()
+        } else {
+            values
+        }
+    };
+    let rows: Vec<Vec<String>> = listings
+        .iter()
+        .map(|listing| {
+            let item = &listing.item;
+            let mut row = vec![
+                item.id.to_string(),
+                item.name.clone(),
+                item.cooking_time_minutes.to_string(),
+                item.price.to_string(),
+            ];
+            if tagged {
+                row.push(tags(dietary::join(&item.allergens)));
+                row.push(tags(dietary::join(&item.diets)));
+            }
+            if stocked {
+                row.push(stock_cell(listing.sold_out, listing.remaining));
+            }
+            row
+        })
+        .collect();
+    let mut headers = vec!["ID", "Name", "Cooking Time (min)", "Price"];
+    if tagged {
+        headers.extend(["Allergens", "Diet"]);
+    }
+    if stocked {
+        headers.push("Stock");
+    }
+    render_table(&headers, &rows)
+}
+
+/// Formats the stock of a menu item: `sold out`, `3 left`, or `-` if not counted.
+fn stock_cell(sold_out: bool, remaining: Option<u32>) -> String {
+    match (sold_out, remaining) {
+        (true, _) => "sold out".to_string(),
+        (false, Some(remaining)) => format!("{} left", remaining),
+        (false, None) => "-".to_string(),
+    }
+}
+
+/// Renders ingredients as a table, marking those low on stock.
+///
+/// # Arguments
+/// * `ingredients` - The ingredients returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_ingredients(ingredients: &[Ingredient]) -> String {
+    let rows: Vec<Vec<String>> = ingredients
+        .iter()
+        .map(|ingredient| {
+            vec![
+                ingredient.id.to_string(),
+                ingredient.name.clone(),
+                format!("{} {}", ingredient.on_hand, ingredient.unit),
+                format!("{} {}", ingredient.low_stock_threshold, ingredient.unit),
+                if ingredient.is_low() { "low" } else { "ok" }.to_string(),
+            ]
+        })
+        .collect();
+    render_table(&["ID", "Ingredient", "On Hand", "Low At", "Status"], &rows)
+}
+
+/// Renders the low stock report: the ingredients low on stock followed by the menu items
+/// that cannot be made.
+///
+/// # Arguments
+/// * `report` - The report returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered report.
+pub fn render_low_stock(report: &LowStockReport) -> String {
+    let ingredients = if report.ingredients.is_empty() {
+        "No ingredients low on stock".to_string()
+    } else {
+        render_ingredients(&report.ingredients)
+    };
+    let items = if report.unavailable_item_ids.is_empty() {
+        "All menu items can be made".to_string()
+    } else {
+        let ids: Vec<String> = report
+            .unavailable_item_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        warning(&format!(
+            "Menu items that cannot be made: {}",
+            ids.join(", ")
+        ))
+    };
+    format!("{}\n{}", ingredients, items)
+}
+
+/// Renders the stock levels of menu items as a table.
+///
+/// # Arguments
+/// * `levels` - The stock levels returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_stock(levels: &[StockLevel]) -> String {
+    let rows: Vec<Vec<String>> = levels
+        .iter()
+        .map(|level| {
+            vec![
+                level.item_id.to_string(),
+                level
+                    .remaining
+                    .map_or_else(|| "-".to_string(), |remaining| remaining.to_string()),
+                if level.sold_out { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    render_table(&["Item ID", "Remaining", "Sold Out"], &rows)
+}
+
+/// Renders the menu grouped by category: a heading per category followed by its menu items.
+///
+/// Categories that are not served are marked in the heading, and menu items without a
+/// category are listed last under `Other`. Empty categories are skipped.
+///
+/// # Arguments
+/// * `menu` - The menu returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered menu.
+pub fn render_categorized_menu(menu: &CategorizedMenu) -> String {
+    let mut sections: Vec<String> = menu
+        .sections
+        .iter()
+        .filter(|section| !section.items.is_empty())
+        .map(|section| {
+            let heading = if section.available {
+                bold(&section.name)
+            } else {
+                format!("{} {}", bold(&section.name), warning("(not served)"))
+            };
+            format!("{}\n{}", heading, render_menu_items(&section.items))
+        })
+        .collect();
+    if !menu.uncategorized.is_empty() {
+        sections.push(format!(
+            "{}\n{}",
+            bold("Other"),
+            render_menu_items(&menu.uncategorized)
+        ));
+    }
+    sections.join("\n\n")
+}
+
+/// Renders categories as a table with their ID, name, number of menu items and availability.
+///
+/// # Arguments
+/// * `categories` - The categories to render, in display order.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_categories(categories: &[Category]) -> String {
+    let rows: Vec<Vec<String>> = categories
+        .iter()
+        .map(|category| {
+            vec![
+                category.id.to_string(),
+                category.name.clone(),
+                category.item_ids.len().to_string(),
+                if category.available { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    render_table(&["ID", "Category", "Items", "Served"], &rows)
+}
+
+/// Renders the orders of a table, grouping repeated menu items into a quantity.
+///
+/// The table is followed by a summary with the number of ordered items and the longest
+/// cooking time, i.e. the time until the whole order is ready.
+///
+/// # Arguments
+/// * `table_id` - The ID of the table.
+/// * `items` - The menu items ordered for the table, in order of arrival.
+///
+/// The modifiers, notes and seats of the items are only shown if any item has them. The
+/// numbers of the order lines of each row are shown if the server numbered them, so that
+/// a specific line can be removed with `remove --line`.
+///
+/// # Returns
+/// * `String` - The rendered table and summary.
+pub fn render_orders(table_id: u32, items: &[OrderedItem]) -> String {
+    let with_options = has_options(items);
+    let with_seats = has_seats(items);
+    let with_lines = items.iter().any(|item| item.line_id != 0);
+    let rows: Vec<Vec<String>> = group_order_items(items)
+        .iter()
+        .map(|(item, quantity)| {
+            let mut row = vec![
+                item.id.to_string(),
+                item.name.clone(),
+                quantity.to_string(),
+                item.cooking_time_minutes.to_string(),
+            ];
+            if with_options {
+                row.extend(option_cells(item));
+            }
+            if with_seats {
+                row.push(seat_cell(item));
+            }
+            if with_lines {
+                row.push(
+                    items
+                        .iter()
+                        .filter(|other| same_order_group(item, other))
+                        .map(|other| other.line_id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                );
+            }
+            row
+        })
+        .collect();
+    let mut headers = vec!["ID", "Name", "Qty", "Cooking Time (min)"];
+    if with_options {
+        headers.extend(["Modifiers", "Note"]);
+    }
+    if with_seats {
+        headers.push("Seat");
+    }
+    if with_lines {
+        headers.push("Lines");
+    }
+    let table = render_table(&headers, &rows);
+
+    let summary = match items.iter().map(|item| item.cooking_time_minutes).max() {
+        Some(longest) => format!(
+            "Table {}: {}, longest cooking time {} min",
+            table_id,
+            plural(items.len(), "item"),
+            longest
+        ),
+        None => format!("Table {}: no items ordered", table_id),
+    };
+    format!("{}\n{}", table, summary)
+}
+
+/// Renders a single ordered menu item with its price, modifiers, note and seat.
+///
+/// # Arguments
+/// * `item` - The ordered menu item.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_ordered_item(item: &OrderedItem) -> String {
+    let mut row = vec![
+        item.id.to_string(),
+        item.name.clone(),
+        item.cooking_time_minutes.to_string(),
+        item.price.to_string(),
+    ];
+    let mut headers = vec!["ID", "Name", "Cooking Time (min)", "Price"];
+    if has_options(std::slice::from_ref(item)) {
+        row.extend(option_cells(item));
+        headers.extend(["Modifiers", "Note"]);
+    }
+    if has_seats(std::slice::from_ref(item)) {
+        row.push(seat_cell(item));
+        headers.push("Seat");
+    }
+    render_table(&headers, &[row])
+}
+
+/// Returns whether any of the ordered items has modifiers or a note.
+fn has_options(items: &[OrderedItem]) -> bool {
+    items
+        .iter()
+        .any(|item| !item.modifiers.is_empty() || item.note.is_some())
+}
+
+/// Returns whether any of the ordered items was ordered from a seat.
+fn has_seats(items: &[OrderedItem]) -> bool {
+    items.iter().any(|item| item.seat.is_some())
+}
+
+/// Returns the seat cell of an ordered item, `-` if it has none.
+fn seat_cell(item: &OrderedItem) -> String {
+    item.seat
+        .map(|seat| seat.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Returns the modifiers and note cells of an ordered item, `-` if there are none.
+fn option_cells(item: &OrderedItem) -> [String; 2] {
+    let modifiers = item.modifier_names();
+    [
+        if modifiers.is_empty() {
+            "-".to_string()
+        } else {
+            modifiers
+        },
+        item.note.clone().unwrap_or_else(|| "-".to_string()),
+    ]
+}
+
+/// Renders the bill of a table: one line per menu item, followed by the subtotal, tax and total.
+///
+/// # Arguments
+/// * `bill` - The bill returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered bill.
+pub fn render_bill(bill: &Bill) -> String {
+    let rows: Vec<Vec<String>> = bill
+        .lines
+        .iter()
+        .map(|line| {
+            vec![
+                line.item_id.to_string(),
+                line_name(&line.name, &line.modifiers),
+                line.quantity.to_string(),
+                line.unit_price.to_string(),
+                line.amount.to_string(),
+            ]
+        })
+        .collect();
+    let table = render_table(&["ID", "Name", "Qty", "Unit Price", "Amount"], &rows);
+
+    let totals = [
+        ("Subtotal".to_string(), bill.subtotal.to_string()),
+        (format!("Tax ({}%)", bill.tax_rate), bill.tax.to_string()),
+        ("Total".to_string(), bill.total.to_string()),
+    ];
+    let label_width = totals
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let amount_width = totals
+        .iter()
+        .map(|(_, amount)| amount.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![format!("Bill for table {}", bill.table_id), table];
+    for (label, amount) in totals {
+        let line = format!("{:<label_width$}  {:>amount_width$}", label, amount);
+        lines.push(if label == "Total" { bold(&line) } else { line });
+    }
+    lines.join("\n")
+}
+
+/// Names a bill line after its menu item, followed by its modifiers in parentheses.
+fn line_name(name: &str, modifiers: &[String]) -> String {
+    if modifiers.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} ({})", name, modifiers.join(", "))
+    }
+}
+
+/// Renders the checks of a split bill: one row per check, followed by the table's total.
+///
+/// # Arguments
+/// * `split` - The split bill returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered checks.
+pub fn render_checks(split: &SplitBill) -> String {
+    let rows: Vec<Vec<String>> = split
+        .checks
+        .iter()
+        .map(|check| {
+            let items = check
+                .lines
+                .iter()
+                .map(|line| {
+                    format!(
+                        "{}x {}",
+                        line.quantity,
+                        line_name(&line.name, &line.modifiers)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            vec![
+                check.check_id.to_string(),
+                check.label.clone(),
+                if items.is_empty() {
+                    "-".to_string()
+                } else {
+                    items
+                },
+                check.subtotal.to_string(),
+                check.tax.to_string(),
+                check.total.to_string(),
+            ]
+        })
+        .collect();
+    let table = render_table(
+        &["Check", "Label", "Items", "Subtotal", "Tax", "Total"],
+        &rows,
+    );
+    format!(
+        "Checks for table {}\n{}\n{}",
+        split.bill.table_id,
+        table,
+        bold(&format!(
+            "{} totaling {}",
+            plural(split.checks.len(), "check"),
+            split.bill.total
+        ))
+    )
+}
+
+/// Renders the payments of a table's bill, followed by the total, paid amount, tips and balance.
+///
+/// # Arguments
+/// * `account` - The account returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered account.
+pub fn render_account(account: &TableAccount) -> String {
+    let rows: Vec<Vec<String>> = account
+        .payments
+        .iter()
+        .map(|payment| {
+            vec![
+                payment.payment_id.to_string(),
+                payment
+                    .check_id
+                    .map_or_else(|| "-".to_string(), |id| id.to_string()),
+                payment.method.to_string(),
+                payment.amount.to_string(),
+                payment.tip.to_string(),
+                payment.reference.clone(),
+            ]
+        })
+        .collect();
+    let table = render_table(
+        &["#", "Check", "Method", "Amount", "Tip", "Reference"],
+        &rows,
+    );
+
+    let totals = [
+        ("Total", account.total.to_string()),
+        ("Paid", account.paid.to_string()),
+        ("Tips", account.tips.to_string()),
+        ("Balance", account.balance.to_string()),
+    ];
+    let amount_width = totals
+        .iter()
+        .map(|(_, amount)| amount.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![format!("Payments for table {}", account.table_id), table];
+    for (label, amount) in totals {
+        lines.push(format!("{:<7}  {:>amount_width$}", label, amount));
+    }
+    if account.settled {
+        lines.push(success(&format!(
+            "Table {} is paid and free again",
+            account.table_id
+        )));
+    }
+    lines.join("\n")
+}
+
+/// Renders a recorded payment: a confirmation line followed by the account of the bill.
+///
+/// # Arguments
+/// * `receipt` - The receipt returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered receipt.
+pub fn render_receipt(receipt: &PaymentReceipt) -> String {
+    let payment = &receipt.payment;
+    format!(
+        "{}\n{}",
+        success(&format!(
+            "Recorded {} payment of {} with a tip of {} ({})",
+            payment.method, payment.amount, payment.tip, payment.reference
+        )),
+        render_account(&receipt.account)
+    )
+}
+
+/// Renders a single session, e.g. `Table 7, session 12: party of 4, open since 2026-10-18 19:05 UTC`.
+///
+/// # Arguments
+/// * `session` - The session returned by the server.
+///
+/// # Returns
+/// * `String` - The rendered session.
+pub fn render_session(session: &TableSession) -> String {
+    let party = session.party_size.map_or_else(
+        || "party size unknown".to_string(),
+        |size| plural(size as usize, "guest"),
+    );
+    let tips: Money = session.payments.iter().map(|payment| payment.tip).sum();
+    let summary = match (session.closed_at, &session.bill) {
+        (Some(closed_at), Some(bill)) if tips > Money::ZERO => format!(
+            "Table {}, session {}: {}, closed at {} with a total of {} and {} in tips",
+            session.table_id,
+            session.session_id,
+            party,
+            format_timestamp(closed_at),
+            bill.total,
+            tips
+        ),
+        (Some(closed_at), Some(bill)) => format!(
+            "Table {}, session {}: {}, closed at {} with a total of {}",
+            session.table_id,
+            session.session_id,
+            party,
+            format_timestamp(closed_at),
+            bill.total
+        ),
+        _ => format!(
+            "Table {}, session {}: {}, open since {}",
+            session.table_id,
+            session.session_id,
+            party,
+            format_timestamp(session.opened_at)
+        ),
+    };
+    if session.allergies.is_empty() {
+        summary
+    } else {
+        warning(&format!(
+            "{}\nAllergies: {}",
+            summary,
+            dietary::join(&session.allergies)
+        ))
+    }
+}
+
+/// Renders sessions as a table with their ID, table, party size, opening and closing time and total.
+///
+/// # Arguments
+/// * `sessions` - The sessions to render.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_sessions(sessions: &[TableSession]) -> String {
+    let rows: Vec<Vec<String>> = sessions
+        .iter()
+        .map(|session| {
+            vec![
+                session.session_id.to_string(),
+                session.table_id.to_string(),
+                session
+                    .party_size
+                    .map_or_else(|| "-".to_string(), |size| size.to_string()),
+                format_timestamp(session.opened_at),
+                session
+                    .closed_at
+                    .map_or_else(|| "-".to_string(), format_timestamp),
+                session
+                    .bill
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), |bill| bill.total.to_string()),
+            ]
+        })
+        .collect();
+    render_table(
+        &["Session", "Table", "Party", "Opened", "Closed", "Total"],
+        &rows,
+    )
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time, e.g. `2026-10-18 19:05 UTC`.
+pub fn format_timestamp(unix_seconds: u64) -> String {
+    let days = (unix_seconds / 86_400) as i64;
+    let minutes = unix_seconds % 86_400 / 60;
+
+    // Converts days since 1970-01-01 to a civil date in the proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// Groups repeated menu items of an order, keeping the order of their first arrival.
+///
+/// Items are only grouped if they were ordered with the same modifiers, note and seat.
+///
+/// # Arguments
+/// * `items` - The menu items ordered for a table.
+///
+/// # Returns
+/// * `Vec<(&OrderedItem, usize)>` - Each distinct menu item with its quantity.
+pub fn group_order_items(items: &[OrderedItem]) -> Vec<(&OrderedItem, usize)> {
+    let mut grouped: Vec<(&OrderedItem, usize)> = Vec::new();
+    for item in items {
+        match grouped
+            .iter_mut()
+            .find(|(existing, _)| same_order_group(existing, item))
+        {
+            Some((_, quantity)) => *quantity += 1,
+            None => grouped.push((item, 1)),
+        }
+    }
+    grouped
+}
+
+/// Returns whether two ordered items are grouped into one row by `group_order_items`.
+fn same_order_group(a: &OrderedItem, b: &OrderedItem) -> bool {
+    a.id == b.id && a.modifiers == b.modifiers && a.note == b.note && a.seat == b.seat
+}
+
+/// Renders tables with the number of guests they seat and the reservation in progress.
+///
+/// # Arguments
+/// * `tables` - The tables to render.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_tables(tables: &[TableStatus]) -> String {
+    let rows: Vec<Vec<String>> = tables
+        .iter()
+        .map(|table| {
+            vec![
+                table.table_id.to_string(),
+                table.capacity.to_string(),
+                table.reservation.as_ref().map_or_else(
+                    || "-".to_string(),
+                    |reservation| {
+                        format!(
+                            "{}, {} until {}",
+                            reservation.contact_name,
+                            plural(reservation.party_size as usize, "guest"),
+                            format_timestamp(reservation.ends_at())
+                        )
+                    },
+                ),
+            ]
+        })
+        .collect();
+    render_table(&["Table ID", "Seats", "Reserved"], &rows)
+}
+
+/// Renders reservations as a table with their ID, table, party size, contact name and slot.
+///
+/// # Arguments
+/// * `reservations` - The reservations to render.
+///
+/// # Returns
+/// * `String` - The rendered table.
+pub fn render_reservations(reservations: &[Reservation]) -> String {
+    let rows: Vec<Vec<String>> = reservations
+        .iter()
+        .map(|reservation| {
+            vec![
+                reservation.reservation_id.to_string(),
+                reservation.table_id.to_string(),
+                reservation.party_size.to_string(),
+                reservation.contact_name.clone(),
+                format_timestamp(reservation.starts_at),
+                format_timestamp(reservation.ends_at()),
+            ]
+        })
+        .collect();
+    render_table(
+        &["Reservation", "Table", "Party", "Contact", "From", "Until"],
+        &rows,
+    )
+}
+
+/// Formats rows as a left-aligned text table with a bold header and a separator line.
+///
+/// # Arguments
+/// * `headers` - The column headers.
+/// * `rows` - The rows of cells. Cells beyond the number of headers are ignored.
+///
+/// # Returns
+/// * `String` - The rendered table, without a trailing newline.
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![bold(&format_row(headers.to_vec()))];
+    lines.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("  "),
+    );
+    for row in rows {
+        lines.push(format_row(row.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
+
+/// Formats a count with a singular or plural noun, e.g. `1 item` or `3 items`.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else if let Some(stem) = noun.strip_suffix('y') {
+        format!("{} {}ies", count, stem)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::dietary::{Allergen, Diet};
+    use crate::server::data_model::models::MenuItem;
+    use crate::server::data_model::modifier::SelectedModifier;
+    use crate::server::data_model::money::{PriceDelta, TaxRate};
+    use crate::server::data_model::payment::{Payment, PaymentMethod};
+    use crate::server::data_model::split::SplitRequest;
+
+    fn menu_item(id: u32, name: &str, cooking_time_minutes: u64) -> MenuItem {
+        MenuItem::new(id, name, cooking_time_minutes, Money::from_cents(1000))
+    }
+
+    fn ordered_item(id: u32, name: &str, cooking_time_minutes: u64) -> OrderedItem {
+        menu_item(id, name, cooking_time_minutes).into()
+    }
+
+    #[test]
+    fn test_render_table_aligns_columns() {
+        let rows = vec![
+            vec!["1".to_string(), "Salad".to_string()],
+            vec!["10".to_string(), "Pizza Margherita".to_string()],
+        ];
+        let table = render_table(&["ID", "Name"], &rows);
+        assert_eq!(
+            table,
+            "ID  Name\n\
+             --  ----------------\n\
+             1   Salad\n\
+             10  Pizza Margherita"
+        );
+    }
+
+    #[test]
+    fn test_render_menu_items_empty() {
+        let table = render_menu_items(&[]);
+        assert_eq!(
+            table,
+            "ID  Name  Cooking Time (min)  Price\n--  ----  ------------------  -----"
+        );
+    }
+
+    #[test]
+    fn test_render_orders_groups_items() {
+        let items = vec![
+            ordered_item(2, "Burger", 10),
+            ordered_item(5, "Steak", 15),
+            ordered_item(2, "Burger", 10),
+        ];
+        assert_eq!(
+            render_orders(4, &items),
+            "ID  Name    Qty  Cooking Time (min)\n\
+             --  ------  ---  ------------------\n\
+             2   Burger  2    10\n\
+             5   Steak   1    15\n\
+             Table 4: 3 items, longest cooking time 15 min"
+        );
+    }
+
+    #[test]
+    fn test_render_bill() {
+        let items = vec![
+            ordered_item(2, "Burger", 10),
+            ordered_item(5, "Steak", 15),
+            ordered_item(2, "Burger", 10),
+        ];
+        let bill = Bill::new(4, &items, "8.25".parse().unwrap());
+        assert_eq!(
+            render_bill(&bill),
+            "Bill for table 4\n\
+             ID  Name    Qty  Unit Price  Amount\n\
+             --  ------  ---  ----------  ------\n\
+             2   Burger  2    10.00       20.00\n\
+             5   Steak   1    10.00       10.00\n\
+             Subtotal     30.00\n\
+             Tax (8.25%)   2.48\n\
+             Total        32.48"
+        );
+        let json: serde_json::Value = serde_json::from_str(&Output::Bill(bill).to_json()).unwrap();
+        assert_eq!(json["data"]["total"], "32.48");
+    }
+
+    #[test]
+    fn test_render_checks() {
+        let items: Vec<OrderedItem> = [
+            ordered_item(2, "Burger", 10),
+            ordered_item(5, "Steak", 15),
+            ordered_item(2, "Burger", 10),
+        ]
+        .into_iter()
+        .zip(1..)
+        .map(|(item, line_id)| OrderedItem { line_id, ..item })
+        .collect();
+        let bill = Bill::new(4, &items, "8.25".parse().unwrap());
+        let request = SplitRequest::Items {
+            checks: vec![vec![1, 3], vec![2]],
+        };
+        let split = SplitBill::new(bill, &items, &request).unwrap();
+        assert_eq!(
+            render_checks(&split),
+            "Checks for table 4\n\
+             Check  Label    Items      Subtotal  Tax   Total\n\
+             -----  -------  ---------  --------  ----  -----\n\
+             1      Check 1  2x Burger  20.00     1.65  21.65\n\
+             2      Check 2  1x Steak   10.00     0.83  10.83\n\
+             2 checks totaling 32.48"
+        );
+    }
+
+    #[test]
+    fn test_render_account() {
+        let payment = Payment {
+            payment_id: 1,
+            table_id: 4,
+            check_id: Some(2),
+            method: PaymentMethod::Card,
+            amount: Money::from_cents(1083),
+            tip: Money::from_cents(200),
+            reference: "card-4242-1".to_string(),
+        };
+        let account = TableAccount::new(4, Money::from_cents(3248), vec![payment]);
+        assert_eq!(
+            render_account(&account),
+            "Payments for table 4\n\
+             #  Check  Method  Amount  Tip   Reference\n\
+             -  -----  ------  ------  ----  -----------\n\
+             1  2      card    10.83   2.00  card-4242-1\n\
+             Total    32.48\n\
+             Paid     10.83\n\
+             Tips      2.00\n\
+             Balance  21.65"
+        );
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_timestamp(1_792_350_300), "2026-10-18 19:05 UTC");
+    }
+
+    #[test]
+    fn test_render_sessions() {
+        let open = TableSession {
+            session_id: 12,
+            table_id: 7,
+            party_size: Some(4),
+            opened_at: 1_792_350_300,
+            closed_at: None,
+            bill: None,
+            payments: Vec::new(),
+            allergies: Vec::new(),
+        };
+        assert_eq!(
+            render_session(&open),
+            "Table 7, session 12: 4 guests, open since 2026-10-18 19:05 UTC"
+        );
+        let allergic = TableSession {
+            allergies: vec![Allergen::Nuts, Allergen::Dairy],
+            ..open.clone()
+        };
+        assert_eq!(
+            render_session(&allergic),
+            "Table 7, session 12: 4 guests, open since 2026-10-18 19:05 UTC\n\
+             Allergies: nuts, dairy"
+        );
+        let closed = TableSession {
+            party_size: None,
+            closed_at: Some(1_792_353_900),
+            bill: Some(Bill::new(
+                7,
+                &[ordered_item(2, "Burger", 10)],
+                TaxRate::default(),
+            )),
+            ..open
+        };
+        assert_eq!(
+            render_session(&closed),
+            "Table 7, session 12: party size unknown, closed at 2026-10-18 20:05 UTC with a total of 10.00"
+        );
+        let tipped = TableSession {
+            payments: vec![Payment {
+                payment_id: 1,
+                table_id: 7,
+                check_id: None,
+                method: PaymentMethod::Cash,
+                amount: Money::from_cents(1000),
+                tip: Money::from_cents(200),
+                reference: "cash-1".to_string(),
+            }],
+            ..closed.clone()
+        };
+        assert_eq!(
+            render_session(&tipped),
+            "Table 7, session 12: party size unknown, closed at 2026-10-18 20:05 UTC with a total of 10.00 and 2.00 in tips"
+        );
+        assert_eq!(
+            render_sessions(&[closed]),
+            "Session  Table  Party  Opened                Closed                Total\n\
+             -------  -----  -----  --------------------  --------------------  -----\n\
+             12       7      -      2026-10-18 19:05 UTC  2026-10-18 20:05 UTC  10.00"
+        );
+    }
+
+    #[test]
+    fn test_render_orders_with_modifiers() {
+        let mut bacon = ordered_item(2, "Burger", 10);
+        bacon.modifiers.push(SelectedModifier {
+            group_id: 1,
+            modifier_id: 2,
+            name: "Bacon".to_string(),
+            price_delta: PriceDelta::from_cents(150),
+        });
+        bacon.price = Money::from_cents(1150);
+        bacon.note = Some("Allergic to nuts".to_string());
+        let items = vec![ordered_item(2, "Burger", 10), bacon.clone(), bacon];
+        assert_eq!(
+            render_orders(4, &items),
+            "ID  Name    Qty  Cooking Time (min)  Modifiers  Note\n\
+             --  ------  ---  ------------------  ---------  ----------------\n\
+             2   Burger  1    10                  -          -\n\
+             2   Burger  2    10                  Bacon      Allergic to nuts\n\
+             Table 4: 3 items, longest cooking time 10 min"
+        );
+        let bill = Bill::new(4, &items, TaxRate::default());
+        assert!(render_bill(&bill).contains("2   Burger (Bacon)  2    11.50       23.00"));
+    }
+
+    #[test]
+    fn test_render_orders_with_seats() {
+        let seated = |seat| OrderedItem {
+            seat,
+            ..ordered_item(2, "Burger", 10)
+        };
+        let items = vec![
+            seated(Some(1)),
+            seated(Some(2)),
+            seated(Some(1)),
+            seated(None),
+        ];
+        assert_eq!(
+            render_orders(4, &items),
+            "ID  Name    Qty  Cooking Time (min)  Seat\n\
+             --  ------  ---  ------------------  ----\n\
+             2   Burger  2    10                  1\n\
+             2   Burger  1    10                  2\n\
+             2   Burger  1    10                  -\n\
+             Table 4: 4 items, longest cooking time 10 min"
+        );
+    }
+
+    #[test]
+    fn test_render_orders_with_line_ids() {
+        let line = |line_id, item_id, name| OrderedItem {
+            line_id,
+            ..ordered_item(item_id, name, 10)
+        };
+        let items = vec![
+            line(1, 2, "Burger"),
+            line(3, 5, "Steak"),
+            line(4, 2, "Burger"),
+        ];
+        assert_eq!(
+            render_orders(4, &items),
+            "ID  Name    Qty  Cooking Time (min)  Lines\n\
+             --  ------  ---  ------------------  -----\n\
+             2   Burger  2    10                  1, 4\n\
+             5   Steak   1    10                  3\n\
+             Table 4: 3 items, longest cooking time 10 min"
+        );
+    }
+
+    #[test]
+    fn test_render_menu_items_with_tags() {
+        let salad = MenuItem {
+            diets: vec![Diet::Vegetarian, Diet::Vegan],
+            ..menu_item(1, "Salad", 1)
+        };
+        let curry = MenuItem {
+            allergens: vec![Allergen::Dairy, Allergen::Nuts],
+            ..menu_item(14, "Curry", 12)
+        };
+        assert_eq!(
+            render_menu_items(&[salad.into(), curry.into()]),
+            "ID  Name   Cooking Time (min)  Price  Allergens    Diet\n\
+             --  -----  ------------------  -----  -----------  -----------------\n\
+             1   Salad  1                   10.00  -            vegetarian, vegan\n\
+             14  Curry  12                  10.00  dairy, nuts  -"
+        );
+    }
+
+    #[test]
+    fn test_render_menu_items_with_stock() {
+        let steak = MenuListing {
+            sold_out: true,
+            ..menu_item(5, "Steak", 15).into()
+        };
+        let salmon = MenuListing {
+            remaining: Some(3),
+            ..menu_item(20, "Salmon", 15).into()
+        };
+        assert_eq!(
+            render_menu_items(&[menu_item(1, "Salad", 1).into(), steak, salmon]),
+            "ID  Name    Cooking Time (min)  Price  Stock\n\
+             --  ------  ------------------  -----  --------\n\
+             1   Salad   1                   10.00  -\n\
+             5   Steak   15                  10.00  sold out\n\
+             20  Salmon  15                  10.00  3 left"
+        );
+        let levels = vec![
+            StockLevel {
+                item_id: 5,
+                remaining: None,
+                sold_out: true,
+            },
+            StockLevel {
+                item_id: 20,
+                remaining: Some(3),
+                sold_out: false,
+            },
+        ];
+        assert_eq!(
+            Output::Stock(levels).to_table(),
+            "Item ID  Remaining  Sold Out\n\
+             -------  ---------  --------\n\
+             5        -          yes\n\
+             20       3          no\n\
+             2 tracked menu items"
+        );
+    }
+
+    #[test]
+    fn test_render_ingredients_and_low_stock() {
+        let ingredient = |id: u32, name: &str, on_hand: u32| Ingredient {
+            id,
+            name: name.to_string(),
+            unit: "g".to_string(),
+            on_hand,
+            low_stock_threshold: 500,
+        };
+        let ingredients = vec![
+            ingredient(1, "Lettuce", 2000),
+            ingredient(10, "Salmon", 150),
+        ];
+        assert_eq!(
+            Output::Ingredients(ingredients.clone()).to_table(),
+            "ID  Ingredient  On Hand  Low At  Status\n\
+             --  ----------  -------  ------  ------\n\
+             1   Lettuce     2000 g   500 g   ok\n\
+             10  Salmon      150 g    500 g   low\n\
+             2 ingredients, 1 low on stock"
+        );
+        let report = LowStockReport {
+            ingredients: vec![ingredients[1].clone()],
+            unavailable_item_ids: vec![13, 20],
+        };
+        assert!(render_low_stock(&report).ends_with("Menu items that cannot be made: 13, 20"));
+        let report = LowStockReport {
+            ingredients: Vec::new(),
+            unavailable_item_ids: Vec::new(),
+        };
+        assert_eq!(
+            render_low_stock(&report),
+            "No ingredients low on stock\nAll menu items can be made"
+        );
+    }
+
+    #[test]
+    fn test_render_tables_and_reservations() {
+        let reservation = Reservation {
+            reservation_id: 3,
+            table_id: 41,
+            party_size: 4,
+            contact_name: "Ada".to_string(),
+            starts_at: 1_792_350_000,
+            duration_minutes: 90,
+        };
+        let tables = vec![
+            TableStatus {
+                table_id: 40,
+                capacity: 2,
+                reserved: false,
+                reservation: None,
+            },
+            TableStatus {
+                table_id: 41,
+                capacity: 4,
+                reserved: true,
+                reservation: Some(reservation.clone()),
+            },
+        ];
+        assert_eq!(
+            Output::Tables(tables).to_table(),
+            "Table ID  Seats  Reserved\n\
+             --------  -----  ----------------------------------------\n\
+             40        2      -\n\
+             41        4      Ada, 4 guests until 2026-10-18 20:30 UTC\n\
+             2 tables, 1 reserved"
+        );
+        assert_eq!(
+            Output::Reservations(vec![reservation]).to_table(),
+            "Reservation  Table  Party  Contact  From                  Until\n\
+             -----------  -----  -----  -------  --------------------  --------------------\n\
+             3            41     4      Ada      2026-10-18 19:00 UTC  2026-10-18 20:30 UTC\n\
+             1 reservation"
+        );
+    }
+
+    #[test]
+    fn test_render_orders_empty() {
+        assert!(render_orders(4, &[]).ends_with("Table 4: no items ordered"));
+    }
+
+    #[test]
+    fn test_output_to_json_uses_server_envelope() {
+        let output = Output::Item(ordered_item(1, "Salad", 1));
+        let json: serde_json::Value = serde_json::from_str(&output.to_json()).unwrap();
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["data"]["name"], "Salad");
+
+        let output = Output::Message("Menu item added".to_string());
+        let json: serde_json::Value = serde_json::from_str(&output.render(true)).unwrap();
+        assert_eq!(json["message"], "Menu item added");
+    }
+
+    #[test]
+    fn test_output_to_table_counts() {
+        let table = |table_id: u32| TableStatus {
+            table_id,
+            capacity: 2,
+            reserved: false,
+            reservation: None,
+        };
+        let output = Output::Tables(vec![table(1), table(2)]);
+        assert!(output.to_table().ends_with("\n2 tables, 0 reserved"));
+        let output = Output::Menu(vec![menu_item(1, "Salad", 1).into()]);
+        assert!(output.to_table().ends_with("\n1 menu item"));
+    }
+
+    #[test]
+    fn test_render_categorized_menu() {
+        let categories = vec![
+            Category {
+                id: 2,
+                name: "Desserts".to_string(),
+                display_order: 2,
+                available: false,
+                item_ids: vec![3],
+            },
+            Category {
+                id: 1,
+                name: "Starters".to_string(),
+                display_order: 1,
+                available: true,
+                item_ids: vec![1],
+            },
+        ];
+        let items = [
+            menu_item(1, "Salad", 1).into(),
+            menu_item(2, "Burger", 10).into(),
+            menu_item(3, "Cake", 2).into(),
+        ];
+        let output = Output::CategorizedMenu(CategorizedMenu::new(categories.clone(), &items));
+        assert_eq!(
+            output.to_table(),
+            "Starters\n\
+             ID  Name   Cooking Time (min)  Price\n\
+             --  -----  ------------------  -----\n\
+             1   Salad  1                   10.00\n\
+             \n\
+             Desserts (not served)\n\
+             ID  Name  Cooking Time (min)  Price\n\
+             --  ----  ------------------  -----\n\
+             3   Cake  2                   10.00\n\
+             \n\
+             Other\n\
+             ID  Name    Cooking Time (min)  Price\n\
+             --  ------  ------------------  -----\n\
+             2   Burger  10                  10.00\n\
+             3 menu items in 2 categories"
+        );
+        assert_eq!(
+            render_categories(&categories),
+            "ID  Category  Items  Served\n\
+             --  --------  -----  ------\n\
+             2   Desserts  1      no\n\
+             1   Starters  1      yes"
+        );
+    }
+
+    #[test]
+    fn test_render_error() {
+        let err = ClientError::Api {
+            status: 404,
+            message: "Table not found".to_string(),
+        };
+        assert_eq!(
+            render_error("Error retrieving orders", &err, false),
+            "Error retrieving orders: Error (404): Table not found"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_error("Error retrieving orders", &err, true)).unwrap();
+        assert_eq!(json["status"], "error");
+        assert_eq!(json["message"], "Table not found");
+    }
+
+    #[test]
+    fn test_paint_with() {
+        assert_eq!(paint_with(true, "failed", "31"), "\x1b[31mfailed\x1b[0m");
+        assert_eq!(paint_with(false, "failed", "31"), "failed");
+    }
+}
@@ -0,0 +1,627 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::error::ClientError;
+use crate::client::output::{self, Output};
+use crate::client::restaurant_client::RestaurantClient;
+use crate::client::simulation::{run_simulation, Scenario};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::path::PathBuf;
+
+/// Help text listing the commands understood by the REPL.
+pub const HELP: &str = "\
+Available commands (the number can be typed instead of the name):
+  1. menus                      Retrieve available menus, grouped by category
+  2. tables                     Get active tables
+  3. add [TABLE] [ITEM]         Add a menu item to a table
+  4. remove [TABLE] [ITEM]      Remove a menu item from a table
+  5. orders [TABLE]             Get all orders for a table
+  6. item [TABLE] [ITEM]        Get specific menu item ordered for a table
+  7. simulate [TABLES] [SEED]   Run simulation (parallel add/remove menu items for random tables)
+  8. exit                       Exit the application
+     bill [TABLE]               Print the bill of a table
+     refresh                    Reload the tables and menu items used to validate input
+     help                       Show this help
+Missing IDs are prompted for. Use the arrow keys to browse the command history.";
+
+/// A command entered in the interactive client.
+///
+/// IDs that were not given on the command line are `None` and are prompted for
+/// before the command is executed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplCommand {
+    Menus,
+    Tables,
+    Add {
+        table: Option<u32>,
+        item: Option<u32>,
+    },
+    Remove {
+        table: Option<u32>,
+        item: Option<u32>,
+    },
+    Orders {
+        table: Option<u32>,
+    },
+    Item {
+        table: Option<u32>,
+        item: Option<u32>,
+    },
+    Simulate {
+        tables: Option<usize>,
+        seed: Option<u64>,
+    },
+    Bill {
+        table: Option<u32>,
+    },
+    Refresh,
+    Help,
+    Exit,
+}
+
+/// Parses a line entered in the interactive client.
+///
+/// # Arguments
+/// * `line` - The line entered by the user, e.g. `add 3 5` or `3`.
+///
+/// # Returns
+/// * `Ok(Some(ReplCommand))` - The parsed command.
+/// * `Ok(None)` - If the line is empty.
+/// * `Err(String)` - If the command is unknown or its arguments are invalid.
+pub fn parse_command(line: &str) -> Result<Option<ReplCommand>, String> {
+    let mut words = line.split_whitespace();
+    let name = match words.next() {
+        Some(name) => name.to_lowercase(),
+        None => return Ok(None),
+    };
+    let args: Vec<&str> = words.collect();
+
+    let (command, max_args) = match name.as_str() {
+        "1" | "menus" => (ReplCommand::Menus, 0),
+        "2" | "tables" => (ReplCommand::Tables, 0),
+        "3" | "add" => (
+            ReplCommand::Add {
+                table: parse_optional_id(args.first(), "table ID")?,
+                item: parse_optional_id(args.get(1), "menu item ID")?,
+            },
+            2,
+        ),
+        "4" | "remove" => (
+            ReplCommand::Remove {
+                table: parse_optional_id(args.first(), "table ID")?,
+                item: parse_optional_id(args.get(1), "menu item ID")?,
+            },
+            2,
+        ),
+        "5" | "orders" => (
+            ReplCommand::Orders {
+                table: parse_optional_id(args.first(), "table ID")?,
+            },
+            1,
+        ),
+        "6" | "item" => (
+            ReplCommand::Item {
+                table: parse_optional_id(args.first(), "table ID")?,
+                item: parse_optional_id(args.get(1), "menu item ID")?,
+            },
+            2,
+        ),
+        "7" | "simulate" => (
+            ReplCommand::Simulate {
+                tables: args
+                    .first()
+                    .map(|tables| parse_id(tables, "number of tables").map(|t| t as usize))
+                    .transpose()?,
+                seed: args
+                    .get(1)
+                    .map(|seed| {
+                        seed.parse::<u64>().map_err(|_| {
+                            format!("Invalid seed '{}'. Must be a non-negative integer.", seed)
+                        })
+                    })
+                    .transpose()?,
+            },
+            2,
+        ),
+        "8" | "exit" | "quit" => (ReplCommand::Exit, 0),
+        "bill" => (
+            ReplCommand::Bill {
+                table: parse_optional_id(args.first(), "table ID")?,
+            },
+            1,
+        ),
+        "refresh" => (ReplCommand::Refresh, 0),
+        "help" | "?" => (ReplCommand::Help, 0),
+        _ => {
+            return Err(format!(
+                "Unknown command '{}'. Type 'help' to list the available commands.",
+                name
+            ))
+        }
+    };
+
+    if args.len() > max_args {
+        return Err(format!(
+            "Too many arguments for '{}'. Type 'help' to see its usage.",
+            name
+        ));
+    }
+    Ok(Some(command))
+}
+
+/// Parses an ID typed by the user.
+///
+/// # Arguments
+/// * `input` - The text entered by the user.
+/// * `name` - The name of the ID (used for error messages).
+///
+/// # Returns
+/// * `Ok(u32)` - The parsed ID.
+/// * `Err(String)` - If the input is not a positive integer.
+pub fn parse_id(input: &str, name: &str) -> Result<u32, String> {
+    match input.trim().parse::<u32>() {
+        Ok(id) if id > 0 => Ok(id),
+        _ => Err(format!(
+            "Invalid {} '{}'. Must be a positive integer.",
+            name,
+            input.trim()
+        )),
+    }
+}
+
+/// Parses an optional command argument as an ID.
+fn parse_optional_id(input: Option<&&str>, name: &str) -> Result<Option<u32>, String> {
+    input.map(|input| parse_id(input, name)).transpose()
+}
+
+/// The table and menu item IDs known to the server.
+///
+/// Used to reject unknown IDs before a request is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownIds {
+    tables: Vec<u32>,
+    menu_items: Vec<u32>,
+}
+
+impl KnownIds {
+    /// Creates the set of known IDs.
+    pub fn new(mut tables: Vec<u32>, mut menu_items: Vec<u32>) -> Self {
+        tables.sort_unstable();
+        menu_items.sort_unstable();
+        KnownIds { tables, menu_items }
+    }
+
+    /// Retrieves the tables and menu items from the server.
+    ///
+    /// # Returns
+    /// * `Ok(KnownIds)` - The IDs known to the server.
+    /// * `Err(ClientError)` - If the tables or menu items could not be retrieved.
+    pub async fn fetch(client: &RestaurantClient) -> Result<Self, ClientError> {
+        let (tables, menus) = tokio::try_join!(client.get_tables(), client.get_menus())?;
+        Ok(Self::new(
+            tables,
+            menus.into_iter().map(|listing| listing.item.id).collect(),
+        ))
+    }
+
+    /// Checks that a table exists.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the table exists.
+    /// * `Err(String)` - A message naming the valid table IDs otherwise.
+    pub fn check_table(&self, table_id: u32) -> Result<(), String> {
+        Self::check(&self.tables, table_id, "Table")
+    }
+
+    /// Checks that a menu item exists.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the menu item exists.
+    /// * `Err(String)` - A message naming the valid menu item IDs otherwise.
+    pub fn check_menu_item(&self, item_id: u32) -> Result<(), String> {
+        Self::check(&self.menu_items, item_id, "Menu item")
+    }
+
+    fn check(ids: &[u32], id: u32, name: &str) -> Result<(), String> {
+        if ids.binary_search(&id).is_ok() {
+            return Ok(());
+        }
+        let valid = match (ids.first(), ids.last()) {
+            (Some(first), Some(last)) if (last - first) as usize + 1 == ids.len() => {
+                format!("{}-{}", first, last)
+            }
+            (Some(_), Some(_)) => ids
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => "none".to_string(),
+        };
+        Err(format!(
+            "{} {} does not exist. Valid IDs: {}",
+            name, id, valid
+        ))
+    }
+}
+
+/// Returns the default location of the command history file, `~/.restaurant_client_history`.
+pub fn default_history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".restaurant_client_history"))
+}
+
+/// Interactive read-eval-print loop of the client.
+///
+/// Reads commands with line editing and history, re-prompts on invalid input and
+/// validates IDs against the server's tables and menus before sending a request.
+/// Errors returned by the server are printed and never end the loop. Responses are
+/// rendered as tables, or as the JSON returned by the server with `raw_output`.
+pub struct Repl {
+    client: RestaurantClient,
+    editor: DefaultEditor,
+    history_path: Option<PathBuf>,
+    known_ids: Option<KnownIds>,
+    raw: bool,
+}
+
+impl Repl {
+    /// Creates the REPL and loads the command history.
+    ///
+    /// # Arguments
+    /// * `client` - The client used to send the requests.
+    /// * `history_path` - Optional file the command history is loaded from and saved to.
+    ///
+    /// # Returns
+    /// * `Ok(Repl)` - The REPL, ready to run.
+    /// * `Err(ReadlineError)` - If the terminal could not be initialized.
+    pub fn new(
+        client: RestaurantClient,
+        history_path: Option<PathBuf>,
+    ) -> Result<Self, ReadlineError> {
+        let mut editor = DefaultEditor::new()?;
+        if let Some(path) = &history_path {
+            // A missing history file is expected on the first start
+            let _ = editor.load_history(path);
+        }
+        Ok(Repl {
+            client,
+            editor,
+            history_path,
+            known_ids: None,
+            raw: false,
+        })
+    }
+
+    /// Prints responses as the JSON returned by the server instead of tables.
+    pub fn raw_output(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Runs the loop until the user exits or closes the input (Ctrl+D).
+    pub async fn run(&mut self) {
+        self.refresh_known_ids().await;
+        println!("{}", HELP);
+
+        loop {
+            let line = match self.editor.readline("\nrestaurant> ") {
+                Ok(line) => line,
+                // Ctrl+C discards the current line
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(err) => {
+                    println!("Error reading input: {}", err);
+                    break;
+                }
+            };
+            if !line.trim().is_empty() {
+                let _ = self.editor.add_history_entry(line.as_str());
+            }
+
+            match parse_command(&line) {
+                Ok(Some(ReplCommand::Exit)) => break,
+                Ok(Some(command)) => self.execute(command).await,
+                Ok(None) => {}
+                Err(err) => println!("{}", output::error(&err)),
+            }
+        }
+
+        println!("Exiting the application. Goodbye!");
+        if let Some(path) = &self.history_path {
+            if let Err(err) = self.editor.save_history(path) {
+                println!("Could not save the command history: {}", err);
+            }
+        }
+    }
+
+    /// Executes a command, prompting for missing IDs first.
+    async fn execute(&mut self, command: ReplCommand) {
+        let (context, result) = match command {
+            ReplCommand::Menus => (
+                "Error retrieving menus",
+                self.client
+                    .get_categorized_menu()
+                    .await
+                    .map(Output::CategorizedMenu),
+            ),
+            ReplCommand::Tables => (
+                "Error retrieving tables",
+                self.client.get_table_statuses().await.map(Output::Tables),
+            ),
+            ReplCommand::Add { table, item } => {
+                let Some((table_id, item_id)) = self.table_and_menu_item(table, item) else {
+                    return;
+                };
+                (
+                    "Error adding menu item",
+                    self.client
+                        .add_item(table_id, item_id)
+                        .await
+                        .map(Output::Message),
+                )
+            }
+            ReplCommand::Remove { table, item } => {
+                let Some((table_id, item_id)) = self.table_and_menu_item(table, item) else {
+                    return;
+                };
+                (
+                    "Error removing menu item",
+                    self.client
+                        .remove_item(table_id, item_id)
+                        .await
+                        .map(Output::Message),
+                )
+            }
+            ReplCommand::Orders { table } => {
+                let Some(table_id) = self.table(table) else {
+                    return;
+                };
+                (
+                    "Error retrieving orders",
+                    self.client
+                        .get_items(table_id)
+                        .await
+                        .map(|items| Output::Orders { table_id, items }),
+                )
+            }
+            ReplCommand::Item { table, item } => {
+                let Some((table_id, item_id)) = self.table_and_menu_item(table, item) else {
+                    return;
+                };
+                (
+                    "Error retrieving menu item details",
+                    self.client
+                        .get_item(table_id, item_id)
+                        .await
+                        .map(Output::Item),
+                )
+            }
+            ReplCommand::Bill { table } => {
+                let Some(table_id) = self.table(table) else {
+                    return;
+                };
+                (
+                    "Error retrieving the bill",
+                    self.client.get_bill(table_id).await.map(Output::Bill),
+                )
+            }
+            ReplCommand::Simulate { tables, seed } => {
+                let defaults = Scenario::default();
+                let scenario = Scenario {
+                    seed,
+                    tables: tables.unwrap_or(defaults.tables),
+                    ..defaults
+                };
+                let report = run_simulation(&self.client, &scenario).await;
+                if report.failures > 0 {
+                    println!(
+                        "{}",
+                        output::error(&format!(
+                            "{} requests failed during the simulation.",
+                            report.failures
+                        ))
+                    );
+                }
+                return;
+            }
+            ReplCommand::Refresh => {
+                self.refresh_known_ids().await;
+                if self.known_ids.is_some() {
+                    println!("{}", output::success("Tables and menu items reloaded."));
+                }
+                return;
+            }
+            ReplCommand::Help => {
+                println!("{}", HELP);
+                return;
+            }
+            ReplCommand::Exit => return,
+        };
+
+        match result {
+            Ok(result) => println!("{}", result.render(self.raw)),
+            Err(err) => println!("{}", output::render_error(context, &err, self.raw)),
+        }
+    }
+
+    /// Reloads the known IDs. If the server cannot be reached, validation is left to the server.
+    async fn refresh_known_ids(&mut self) {
+        match KnownIds::fetch(&self.client).await {
+            Ok(known_ids) => self.known_ids = Some(known_ids),
+            Err(err) => {
+                println!(
+                    "{}",
+                    output::warning(&format!(
+                        "Could not load tables and menu items, IDs are not validated before sending: {}",
+                        err
+                    ))
+                );
+                self.known_ids = None;
+            }
+        }
+    }
+
+    /// Returns a validated table ID and menu item ID, prompting for the missing ones.
+    fn table_and_menu_item(&mut self, table: Option<u32>, item: Option<u32>) -> Option<(u32, u32)> {
+        let table_id = self.table(table)?;
+        let item_id =
+            self.id_or_prompt(item, "menu item ID", |known, id| known.check_menu_item(id))?;
+        Some((table_id, item_id))
+    }
+
+    /// Returns a validated table ID, prompting for it if it is missing.
+    fn table(&mut self, table: Option<u32>) -> Option<u32> {
+        self.id_or_prompt(table, "table ID", |known, id| known.check_table(id))
+    }
+
+    /// Validates a given ID, or prompts until a valid ID is entered.
+    ///
+    /// # Returns
+    /// * `Some(u32)` - The valid ID.
+    /// * `None` - If a given ID is invalid, or the prompt was cancelled with an empty line or Ctrl+C.
+    fn id_or_prompt(
+        &mut self,
+        id: Option<u32>,
+        name: &str,
+        check: impl Fn(&KnownIds, u32) -> Result<(), String>,
+    ) -> Option<u32> {
+        let validate = |known_ids: &Option<KnownIds>, id: u32| match known_ids {
+            Some(known_ids) => check(known_ids, id),
+            None => Ok(()),
+        };
+
+        if let Some(id) = id {
+            return match validate(&self.known_ids, id) {
+                Ok(()) => Some(id),
+                Err(err) => {
+                    println!("{}", output::error(&err));
+                    None
+                }
+            };
+        }
+
+        loop {
+            let input = match self
+                .editor
+                .readline(&format!("Enter {} (empty to cancel): ", name))
+            {
+                Ok(input) => input,
+                Err(_) => return None,
+            };
+            if input.trim().is_empty() {
+                return None;
+            }
+            match parse_id(&input, name).and_then(|id| validate(&self.known_ids, id).map(|_| id)) {
+                Ok(id) => return Some(id),
+                Err(err) => println!("{}", output::error(&err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command_names_and_numbers() {
+        assert_eq!(parse_command("menus"), Ok(Some(ReplCommand::Menus)));
+        assert_eq!(parse_command("  2 "), Ok(Some(ReplCommand::Tables)));
+        assert_eq!(parse_command("QUIT"), Ok(Some(ReplCommand::Exit)));
+        assert_eq!(parse_command("8"), Ok(Some(ReplCommand::Exit)));
+        assert_eq!(parse_command(""), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_command_with_arguments() {
+        assert_eq!(
+            parse_command("add 3 5"),
+            Ok(Some(ReplCommand::Add {
+                table: Some(3),
+                item: Some(5)
+            }))
+        );
+        assert_eq!(
+            parse_command("4 3"),
+            Ok(Some(ReplCommand::Remove {
+                table: Some(3),
+                item: None
+            }))
+        );
+        assert_eq!(
+            parse_command("orders"),
+            Ok(Some(ReplCommand::Orders { table: None }))
+        );
+        assert_eq!(
+            parse_command("bill 7"),
+            Ok(Some(ReplCommand::Bill { table: Some(7) }))
+        );
+        assert_eq!(
+            parse_command("simulate"),
+            Ok(Some(ReplCommand::Simulate {
+                tables: None,
+                seed: None
+            }))
+        );
+        assert_eq!(
+            parse_command("simulate 50"),
+            Ok(Some(ReplCommand::Simulate {
+                tables: Some(50),
+                seed: None
+            }))
+        );
+        assert_eq!(
+            parse_command("simulate 50 42"),
+            Ok(Some(ReplCommand::Simulate {
+                tables: Some(50),
+                seed: Some(42)
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_command_invalid_input() {
+        assert_eq!(
+            parse_command("add x 5"),
+            Err("Invalid table ID 'x'. Must be a positive integer.".to_string())
+        );
+        assert!(parse_command("item 3 -1").is_err());
+        assert!(parse_command("orders 3 4").is_err());
+        assert!(parse_command("simulate 10 seed").is_err());
+        assert!(parse_command("simulate 0").is_err());
+        assert!(parse_command("order").is_err());
+    }
+
+    #[test]
+    fn test_parse_id() {
+        assert_eq!(parse_id(" 7 ", "table ID"), Ok(7));
+        assert!(parse_id("0", "table ID").is_err());
+        assert!(parse_id("seven", "table ID").is_err());
+        assert!(parse_id("99999999999", "table ID").is_err());
+    }
+
+    #[test]
+    fn test_known_ids_check() {
+        let known_ids = KnownIds::new(vec![3, 1, 2], vec![10, 4]);
+
+        assert_eq!(known_ids.check_table(2), Ok(()));
+        assert_eq!(
+            known_ids.check_table(4),
+            Err("Table 4 does not exist. Valid IDs: 1-3".to_string())
+        );
+        assert_eq!(known_ids.check_menu_item(10), Ok(()));
+        assert_eq!(
+            known_ids.check_menu_item(5),
+            Err("Menu item 5 does not exist. Valid IDs: 4, 10".to_string())
+        );
+    }
+
+    #[test]
+    fn test_known_ids_check_empty() {
+        let known_ids = KnownIds::new(vec![], vec![]);
+        assert_eq!(
+            known_ids.check_table(1),
+            Err("Table 1 does not exist. Valid IDs: none".to_string())
+        );
+    }
+}
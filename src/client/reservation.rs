@@ -0,0 +1,79 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::reservation::{
+    parse_date_time, ReservationRequest, DEFAULT_DURATION_MINUTES,
+};
+use clap::Args;
+
+/// Command line arguments describing a reservation, for making or changing one.
+///
+/// The start of the slot is written in UTC, e.g. `--at "2026-10-18 19:30"`.
+#[derive(Args, Debug, Clone)]
+pub struct ReservationArgs {
+    /// ID of the table
+    #[arg(long)]
+    pub table: u32,
+    /// Number of guests
+    #[arg(long)]
+    pub party: u32,
+    /// Name the reservation is made under
+    #[arg(long)]
+    pub name: String,
+    /// Start of the slot in UTC, e.g. "2026-10-18 19:30"
+    #[arg(long, value_name = "YYYY-MM-DD HH:MM", value_parser = parse_start)]
+    pub at: u64,
+    /// Length of the slot in minutes
+    #[arg(long, default_value_t = DEFAULT_DURATION_MINUTES)]
+    pub duration: u32,
+}
+
+impl ReservationArgs {
+    /// Converts the arguments into the request sent to the server.
+    pub fn request(&self) -> ReservationRequest {
+        ReservationRequest {
+            table_id: self.table,
+            party_size: self.party,
+            contact_name: self.name.clone(),
+            starts_at: self.at,
+            duration_minutes: self.duration,
+        }
+    }
+}
+
+/// Parses the start of a slot, e.g. `2026-10-18 19:30`.
+fn parse_start(s: &str) -> Result<u64, String> {
+    parse_date_time(s.trim()).ok_or_else(|| {
+        format!(
+            "Invalid time '{}'. Expected YYYY-MM-DD HH:MM in UTC, e.g. 2026-10-18 19:30",
+            s
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request() {
+        assert!(parse_start("18.10.2026 19:30").is_err());
+        let args = ReservationArgs {
+            table: 41,
+            party: 4,
+            name: "Ada".to_string(),
+            at: parse_start("2026-10-18 19:00").unwrap(),
+            duration: 120,
+        };
+        assert_eq!(
+            args.request(),
+            ReservationRequest {
+                table_id: 41,
+                party_size: 4,
+                contact_name: "Ada".to_string(),
+                starts_at: 1_792_350_000,
+                duration_minutes: 120,
+            }
+        );
+    }
+}
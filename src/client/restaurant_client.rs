@@ -0,0 +1,707 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::error::ClientError;
+use crate::client::session::{RecordedCall, SessionRecorder};
+use crate::server::data_model::dietary::{Allergen, AllergiesRequest, MenuFilter};
+use crate::server::data_model::inventory::{Ingredient, LowStockReport, RestockRequest};
+use crate::server::data_model::menu::{CategorizedMenu, Category, CategoryAvailabilityRequest};
+use crate::server::data_model::models::Bill;
+use crate::server::data_model::modifier::{ItemOptions, OrderedItem};
+use crate::server::data_model::payment::{
+    PaymentReceipt, PaymentRequest, TableAccount, TipRequest,
+};
+use crate::server::data_model::reservation::{Reservation, ReservationRequest, TableStatus};
+use crate::server::data_model::session::{OpenTableRequest, TableSession};
+use crate::server::data_model::split::{SplitBill, SplitRequest};
+use crate::server::data_model::stock::{MenuListing, StockLevel, StockRequest};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// The JSON envelope used by every response of the restaurant API.
+///
+/// Successful `GET` requests carry `data`, successful `POST`/`DELETE` requests
+/// and all error responses carry `message`.
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    #[serde(default = "Option::default")]
+    data: Option<T>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Typed asynchronous client for the restaurant API (v1).
+///
+/// Each endpoint is exposed as a method that returns the types from
+/// `server::data_model::models`, and error responses are mapped to `ClientError`.
+///
+/// Requests are bounded by a timeout. `GET` requests are retried on connection
+/// failures, timeouts and `5xx` responses; `POST` and `DELETE` requests are never
+/// retried, because adding or removing an item twice changes the table's order.
+///
+/// With a `SessionRecorder`, every call and its response are appended to a session
+/// file that can be replayed with `client::session::replay_session`.
+///
+/// # Example
+/// ```no_run
+/// # async fn example() -> Result<(), restaurant_menu_app::client::error::ClientError> {
+/// use restaurant_menu_app::client::restaurant_client::RestaurantClient;
+///
+/// let client = RestaurantClient::new("http://127.0.0.1:8081")?;
+/// client.add_item(3, 5).await?;
+/// let items = client.get_items(3).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RestaurantClient {
+    http: reqwest::Client,
+    base_url: String,
+    retries: u32,
+    retry_backoff: Duration,
+    recorder: Option<SessionRecorder>,
+}
+
+impl RestaurantClient {
+    /// Creates a client for the server at `base_url` with the default timeout and retry settings.
+    ///
+    /// # Arguments
+    /// * `base_url` - The server's base URL, e.g. `http://127.0.0.1:8081`.
+    ///
+    /// # Returns
+    /// * `Ok(RestaurantClient)` - The configured client.
+    /// * `Err(ClientError)` - If the underlying HTTP client could not be created.
+    pub fn new(base_url: &str) -> Result<Self, ClientError> {
+        Self::builder(base_url).build()
+    }
+
+    /// Returns a builder to configure timeouts, retries and trusted certificates.
+    ///
+    /// # Arguments
+    /// * `base_url` - The server's base URL, e.g. `https://backoffice:8443`.
+    pub fn builder(base_url: &str) -> RestaurantClientBuilder {
+        RestaurantClientBuilder::new(base_url)
+    }
+
+    /// Returns the base URL of the server this client talks to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Retrieves all menu items (`GET /api/v1/menus`).
+    pub async fn get_menus(&self) -> Result<Vec<MenuListing>, ClientError> {
+        self.get_filtered_menus(&MenuFilter::default()).await
+    }
+
+    /// Retrieves the menu items passing a filter by allergens and diets
+    /// (`GET /api/v1/menus?exclude_allergens=...&diet=...`).
+    pub async fn get_filtered_menus(
+        &self,
+        filter: &MenuFilter,
+    ) -> Result<Vec<MenuListing>, ClientError> {
+        self.get_data(&format!("/api/v1/menus{}", filter.to_query()))
+            .await
+    }
+
+    /// Retrieves the menu grouped by category (`GET /api/v1/categorized_menu`).
+    pub async fn get_categorized_menu(&self) -> Result<CategorizedMenu, ClientError> {
+        self.get_filtered_categorized_menu(&MenuFilter::default())
+            .await
+    }
+
+    /// Retrieves the menu grouped by category, leaving out menu items that do not pass a filter
+    /// by allergens and diets (`GET /api/v1/categorized_menu?exclude_allergens=...&diet=...`).
+    pub async fn get_filtered_categorized_menu(
+        &self,
+        filter: &MenuFilter,
+    ) -> Result<CategorizedMenu, ClientError> {
+        self.get_data(&format!("/api/v1/categorized_menu{}", filter.to_query()))
+            .await
+    }
+
+    /// Retrieves the categories of the menu in display order (`GET /api/v1/categories`).
+    pub async fn get_categories(&self) -> Result<Vec<Category>, ClientError> {
+        self.get_data("/api/v1/categories").await
+    }
+
+    /// Marks a category as served or not served (`POST /api/v1/category_availability/{category_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(Category)` - The updated category.
+    /// * `Err(ClientError)` - If the request failed or the category does not exist.
+    pub async fn set_category_availability(
+        &self,
+        category_id: u32,
+        available: bool,
+    ) -> Result<Category, ClientError> {
+        self.post_data(
+            &format!("/api/v1/category_availability/{}", category_id),
+            &CategoryAvailabilityRequest { available },
+        )
+        .await
+    }
+
+    /// Retrieves the stock of counted or sold out menu items (`GET /api/v1/stock`).
+    pub async fn get_stock(&self) -> Result<Vec<StockLevel>, ClientError> {
+        self.get_data("/api/v1/stock").await
+    }
+
+    /// Sets the stock of a menu item, or marks it as sold out (`POST /api/v1/stock/{item_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(StockLevel)` - The new stock level.
+    /// * `Err(ClientError)` - If the request failed or the menu item does not exist.
+    pub async fn set_stock(
+        &self,
+        item_id: u32,
+        remaining: Option<u32>,
+        sold_out: bool,
+    ) -> Result<StockLevel, ClientError> {
+        self.post_data(
+            &format!("/api/v1/stock/{}", item_id),
+            &StockRequest {
+                remaining,
+                sold_out,
+            },
+        )
+        .await
+    }
+
+    /// Retrieves the ingredients in the inventory (`GET /api/v1/ingredients`).
+    pub async fn get_ingredients(&self) -> Result<Vec<Ingredient>, ClientError> {
+        self.get_data("/api/v1/ingredients").await
+    }
+
+    /// Adds a delivered quantity of an ingredient (`POST /api/v1/restock/{ingredient_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(Ingredient)` - The restocked ingredient.
+    /// * `Err(ClientError)` - If the request failed or the ingredient does not exist.
+    pub async fn restock(
+        &self,
+        ingredient_id: u32,
+        quantity: u32,
+    ) -> Result<Ingredient, ClientError> {
+        self.post_data(
+            &format!("/api/v1/restock/{}", ingredient_id),
+            &RestockRequest { quantity },
+        )
+        .await
+    }
+
+    /// Reports the ingredients low on stock and the menu items that cannot be made
+    /// (`GET /api/v1/low_stock`).
+    pub async fn get_low_stock_report(&self) -> Result<LowStockReport, ClientError> {
+        self.get_data("/api/v1/low_stock").await
+    }
+
+    /// Retrieves all table IDs (`GET /api/v1/tables`).
+    pub async fn get_tables(&self) -> Result<Vec<u32>, ClientError> {
+        self.get_data("/api/v1/tables").await
+    }
+
+    /// Retrieves all tables with their capacity and current reservation
+    /// (`GET /api/v1/tables?status=true`).
+    pub async fn get_table_statuses(&self) -> Result<Vec<TableStatus>, ClientError> {
+        self.get_data("/api/v1/tables?status=true").await
+    }
+
+    /// Retrieves reservations, of one day written as `YYYY-MM-DD` or of all days
+    /// (`GET /api/v1/reservations`).
+    pub async fn get_reservations(
+        &self,
+        date: Option<&str>,
+    ) -> Result<Vec<Reservation>, ClientError> {
+        match date {
+            Some(date) => {
+                self.get_data(&format!("/api/v1/reservations?date={}", date))
+                    .await
+            }
+            None => self.get_data("/api/v1/reservations").await,
+        }
+    }
+
+    /// Reserves a table for a party (`POST /api/v1/reservations`).
+    ///
+    /// # Returns
+    /// * `Ok(Reservation)` - The new reservation.
+    /// * `Err(ClientError)` - If the request failed, the party does not fit the table or the
+    ///   table is already reserved during the slot.
+    pub async fn make_reservation(
+        &self,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, ClientError> {
+        self.post_data("/api/v1/reservations", request).await
+    }
+
+    /// Changes a reservation (`PUT /api/v1/reservations/{reservation_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(Reservation)` - The updated reservation.
+    /// * `Err(ClientError)` - If the request failed, the reservation is unknown, or the new
+    ///   details are rejected like those of a new reservation.
+    pub async fn update_reservation(
+        &self,
+        reservation_id: u64,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, ClientError> {
+        let path = format!("/api/v1/reservations/{}", reservation_id);
+        self.send_data(Method::PUT, &path, Some(request)).await
+    }
+
+    /// Cancels a reservation (`DELETE /api/v1/reservations/{reservation_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(Reservation)` - The cancelled reservation.
+    /// * `Err(ClientError)` - If the request failed or the reservation is unknown.
+    pub async fn cancel_reservation(
+        &self,
+        reservation_id: u64,
+    ) -> Result<Reservation, ClientError> {
+        let path = format!("/api/v1/reservations/{}", reservation_id);
+        self.send_data(Method::DELETE, &path, None::<&Value>).await
+    }
+
+    /// Adds a menu item to a table's order (`POST /api/v1/add_item/{table_id}/{item_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The confirmation message from the server.
+    /// * `Err(ClientError)` - If the request failed or the server rejected it.
+    pub async fn add_item(&self, table_id: u32, item_id: u32) -> Result<String, ClientError> {
+        let path = format!("/api/v1/add_item/{}/{}", table_id, item_id);
+        self.send_for_message(Method::POST, &path).await
+    }
+
+    /// Adds a menu item with modifiers and a kitchen note to a table's order
+    /// (`POST /api/v1/add_item/{table_id}/{item_id}` with a JSON body).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The confirmation message from the server.
+    /// * `Err(ClientError)` - If the request failed or the server rejected the item or its options.
+    pub async fn add_item_with_options(
+        &self,
+        table_id: u32,
+        item_id: u32,
+        options: &ItemOptions,
+    ) -> Result<String, ClientError> {
+        let path = format!("/api/v1/add_item/{}/{}", table_id, item_id);
+        let body = serde_json::to_value(options)
+            .map_err(|e| ClientError::Config(format!("Invalid request body: {}", e)))?;
+        let response: ApiResponse<Value> = self.call(Method::POST, &path, Some(&body)).await?;
+        Ok(response.message.unwrap_or_default())
+    }
+
+    /// Removes a menu item from a table's order (`DELETE /api/v1/remove_item/{table_id}/{item_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The confirmation message from the server.
+    /// * `Err(ClientError)` - If the request failed or the server rejected it.
+    pub async fn remove_item(&self, table_id: u32, item_id: u32) -> Result<String, ClientError> {
+        let path = format!("/api/v1/remove_item/{}/{}", table_id, item_id);
+        self.send_for_message(Method::DELETE, &path).await
+    }
+
+    /// Removes a numbered line from a table's order (`DELETE /api/v1/remove_line/{table_id}/{line_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The confirmation message from the server.
+    /// * `Err(ClientError)` - If the request failed or the server rejected it.
+    pub async fn remove_line(&self, table_id: u32, line_id: u32) -> Result<String, ClientError> {
+        let path = format!("/api/v1/remove_line/{}/{}", table_id, line_id);
+        self.send_for_message(Method::DELETE, &path).await
+    }
+
+    /// Retrieves all menu items ordered for a table (`GET /api/v1/get_items/{table_id}`).
+    pub async fn get_items(&self, table_id: u32) -> Result<Vec<OrderedItem>, ClientError> {
+        self.get_data(&format!("/api/v1/get_items/{}", table_id))
+            .await
+    }
+
+    /// Retrieves a specific menu item ordered for a table (`GET /api/v1/get_item/{table_id}/{item_id}`).
+    pub async fn get_item(&self, table_id: u32, item_id: u32) -> Result<OrderedItem, ClientError> {
+        self.get_data(&format!("/api/v1/get_item/{}/{}", table_id, item_id))
+            .await
+    }
+
+    /// Retrieves the itemized bill of a table (`GET /api/v1/bill/{table_id}`).
+    pub async fn get_bill(&self, table_id: u32) -> Result<Bill, ClientError> {
+        self.get_data(&format!("/api/v1/bill/{}", table_id)).await
+    }
+
+    /// Splits the bill of a table into checks (`POST /api/v1/split/{table_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(SplitBill)` - The bill and the checks it was split into.
+    /// * `Err(ClientError)` - If the request failed or the server rejected the split.
+    pub async fn split_bill(
+        &self,
+        table_id: u32,
+        request: &SplitRequest,
+    ) -> Result<SplitBill, ClientError> {
+        self.post_data(&format!("/api/v1/split/{}", table_id), request)
+            .await
+    }
+
+    /// Retrieves the checks of a table's split bill (`GET /api/v1/checks/{table_id}`).
+    pub async fn get_checks(&self, table_id: u32) -> Result<SplitBill, ClientError> {
+        self.get_data(&format!("/api/v1/checks/{}", table_id)).await
+    }
+
+    /// Records a payment towards the bill of a table (`POST /api/v1/pay/{table_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(PaymentReceipt)` - The recorded payment and the account of the bill.
+    /// * `Err(ClientError)` - If the request failed, or the server rejected or declined the payment.
+    pub async fn pay(
+        &self,
+        table_id: u32,
+        request: &PaymentRequest,
+    ) -> Result<PaymentReceipt, ClientError> {
+        self.post_data(&format!("/api/v1/pay/{}", table_id), request)
+            .await
+    }
+
+    /// Records a tip against the bill of a table (`POST /api/v1/tip/{table_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(PaymentReceipt)` - The recorded tip and the account of the bill.
+    /// * `Err(ClientError)` - If the request failed, or the server rejected or declined the tip.
+    pub async fn tip(
+        &self,
+        table_id: u32,
+        request: &TipRequest,
+    ) -> Result<PaymentReceipt, ClientError> {
+        self.post_data(&format!("/api/v1/tip/{}", table_id), request)
+            .await
+    }
+
+    /// Retrieves the payments and balance of a table's bill (`GET /api/v1/payments/{table_id}`).
+    pub async fn get_payments(&self, table_id: u32) -> Result<TableAccount, ClientError> {
+        self.get_data(&format!("/api/v1/payments/{}", table_id))
+            .await
+    }
+
+    /// Opens a table for a party (`POST /api/v1/open_table/{table_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(TableSession)` - The new session of the table.
+    /// * `Err(ClientError)` - If the request failed, or the table is unknown or already open.
+    pub async fn open_table(
+        &self,
+        table_id: u32,
+        party_size: u32,
+    ) -> Result<TableSession, ClientError> {
+        self.post_data(
+            &format!("/api/v1/open_table/{}", table_id),
+            &OpenTableRequest { party_size },
+        )
+        .await
+    }
+
+    /// Declares the allergies of the party at a table (`POST /api/v1/allergies/{table_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(TableSession)` - The active session of the table with its allergies.
+    /// * `Err(ClientError)` - If the request failed or the table is unknown.
+    pub async fn declare_allergies(
+        &self,
+        table_id: u32,
+        allergies: &[Allergen],
+    ) -> Result<TableSession, ClientError> {
+        self.post_data(
+            &format!("/api/v1/allergies/{}", table_id),
+            &AllergiesRequest {
+                allergies: allergies.to_vec(),
+            },
+        )
+        .await
+    }
+
+    /// Closes a table and archives its session (`POST /api/v1/close_table/{table_id}`).
+    ///
+    /// # Returns
+    /// * `Ok(TableSession)` - The closed session with its final bill.
+    /// * `Err(ClientError)` - If the request failed, the table is not open or its bill is not paid.
+    pub async fn close_table(&self, table_id: u32) -> Result<TableSession, ClientError> {
+        let path = format!("/api/v1/close_table/{}", table_id);
+        let response: ApiResponse<TableSession> = self.call(Method::POST, &path, None).await?;
+        response
+            .data
+            .ok_or_else(|| ClientError::InvalidResponse("Response has no data".to_string()))
+    }
+
+    /// Retrieves the active session of a table (`GET /api/v1/session/{table_id}`).
+    pub async fn get_session(&self, table_id: u32) -> Result<TableSession, ClientError> {
+        self.get_data(&format!("/api/v1/session/{}", table_id))
+            .await
+    }
+
+    /// Retrieves closed sessions, of one table or of all tables (`GET /api/v1/sessions`).
+    pub async fn get_sessions(
+        &self,
+        table_id: Option<u32>,
+    ) -> Result<Vec<TableSession>, ClientError> {
+        match table_id {
+            Some(table_id) => {
+                self.get_data(&format!("/api/v1/sessions?table_id={}", table_id))
+                    .await
+            }
+            None => self.get_data("/api/v1/sessions").await,
+        }
+    }
+
+    /// Sends a request without interpreting the response, e.g. to replay a recorded call.
+    ///
+    /// # Arguments
+    /// * `method` - The HTTP method, e.g. `GET`.
+    /// * `path` - The request path, e.g. `/api/v1/get_items/3`.
+    /// * `body` - The JSON request body, if any.
+    ///
+    /// # Returns
+    /// * `Ok((u16, String))` - The status code and body of the response, also for error responses.
+    /// * `Err(ClientError)` - If the method is invalid or no response could be obtained.
+    pub async fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<(u16, String), ClientError> {
+        let method = Method::from_bytes(method.as_bytes())
+            .map_err(|_| ClientError::Config(format!("Invalid HTTP method: {}", method)))?;
+        let (status, body) = self.send(method, path, body).await?;
+        Ok((status.as_u16(), body))
+    }
+
+    /// Sends a `GET` request and returns the `data` field of the response.
+    async fn get_data<T: DeserializeOwned>(&self, path: &str) -> Result<T, ClientError> {
+        let response: ApiResponse<T> = self.call(Method::GET, path, None).await?;
+        response
+            .data
+            .ok_or_else(|| ClientError::InvalidResponse("Response has no data".to_string()))
+    }
+
+    /// Sends a `POST` request with a JSON body and returns the `data` field of the response.
+    async fn post_data<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, ClientError> {
+        self.send_data(Method::POST, path, Some(body)).await
+    }
+
+    /// Sends a request with an optional JSON body and returns the `data` field of the response.
+    async fn send_data<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+    ) -> Result<T, ClientError> {
+        let body = body
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| ClientError::Config(format!("Invalid request body: {}", e)))?;
+        let response: ApiResponse<T> = self.call(method, path, body.as_ref()).await?;
+        response
+            .data
+            .ok_or_else(|| ClientError::InvalidResponse("Response has no data".to_string()))
+    }
+
+    /// Sends a request and returns the `message` field of the response.
+    async fn send_for_message(&self, method: Method, path: &str) -> Result<String, ClientError> {
+        let response: ApiResponse<Value> = self.call(method, path, None).await?;
+        Ok(response.message.unwrap_or_default())
+    }
+
+    /// Sends a request and decodes the response envelope, mapping error responses to `ClientError::Api`.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        request_body: Option<&Value>,
+    ) -> Result<ApiResponse<T>, ClientError> {
+        let (status, body) = self.send(method, path, request_body).await?;
+
+        if !status.is_success() {
+            let message = serde_json::from_str::<ApiResponse<Value>>(&body)
+                .ok()
+                .and_then(|response| response.message)
+                .unwrap_or_else(|| {
+                    status
+                        .canonical_reason()
+                        .unwrap_or("Unknown error")
+                        .to_string()
+                });
+            return Err(ClientError::Api {
+                status: status.as_u16(),
+                message,
+            });
+        }
+
+        serde_json::from_str(&body).map_err(|e| ClientError::InvalidResponse(e.to_string()))
+    }
+
+    /// Sends a request and records it if a session is being recorded.
+    ///
+    /// # Returns
+    /// * `Ok((StatusCode, String))` - The status and body of the final response.
+    /// * `Err(ClientError)` - If no response could be obtained.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<(StatusCode, String), ClientError> {
+        let Some(recorder) = &self.recorder else {
+            return self.send_with_retries(method, path, body).await;
+        };
+
+        let offset = recorder.elapsed();
+        let sent = Instant::now();
+        let result = self.send_with_retries(method.clone(), path, body).await;
+        recorder.record(
+            &RecordedCall::new(
+                offset,
+                sent.elapsed(),
+                method.as_str(),
+                path,
+                result
+                    .as_ref()
+                    .map(|(status, body)| (status.as_u16(), body.as_str()))
+                    .map_err(|err| err.to_string()),
+            )
+            .with_request_body(body.cloned()),
+        );
+        result
+    }
+
+    /// Sends a request, retrying idempotent `GET` requests on transient failures.
+    ///
+    /// # Returns
+    /// * `Ok((StatusCode, String))` - The status and body of the final response.
+    /// * `Err(ClientError)` - If no response could be obtained.
+    async fn send_with_retries(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<(StatusCode, String), ClientError> {
+        let url = format!("{}{}", self.base_url, path);
+        let max_attempts = if method == Method::GET {
+            self.retries + 1
+        } else {
+            1
+        };
+
+        let mut attempt = 1;
+        loop {
+            let mut request = self.http.request(method.clone(), &url);
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            let result = request.send().await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if retryable && attempt < max_attempts {
+                tokio::time::sleep(self.retry_backoff * attempt).await;
+                attempt += 1;
+                continue;
+            }
+
+            let response = result?;
+            let status = response.status();
+            let body = response.text().await?;
+            return Ok((status, body));
+        }
+    }
+}
+
+/// Builder for `RestaurantClient`.
+///
+/// Defaults: a 10 second request timeout, 2 retries for `GET` requests and a
+/// 200 millisecond backoff that grows linearly with each attempt.
+pub struct RestaurantClientBuilder {
+    base_url: String,
+    timeout: Duration,
+    retries: u32,
+    retry_backoff: Duration,
+    ca_certificates: Vec<PathBuf>,
+    recorder: Option<SessionRecorder>,
+}
+
+impl RestaurantClientBuilder {
+    /// Creates a builder for the server at `base_url`.
+    pub fn new(base_url: &str) -> Self {
+        RestaurantClientBuilder {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            timeout: Duration::from_secs(10),
+            retries: 2,
+            retry_backoff: Duration::from_millis(200),
+            ca_certificates: Vec::new(),
+            recorder: None,
+        }
+    }
+
+    /// Sets the timeout applied to each request attempt.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a failed `GET` request is retried.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry; later retries wait proportionally longer.
+    pub fn retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Adds a PEM encoded CA certificate to trust, e.g. for self-signed test certificates.
+    pub fn ca_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_certificates.push(path.into());
+        self
+    }
+
+    /// Records every call of the client, and of its clones, with the given recorder.
+    pub fn recorder(mut self, recorder: SessionRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Returns
+    /// * `Ok(RestaurantClient)` - The configured client.
+    /// * `Err(ClientError::Config)` - If a CA certificate could not be loaded or the HTTP client could not be created.
+    pub fn build(self) -> Result<RestaurantClient, ClientError> {
+        let mut builder = reqwest::Client::builder().timeout(self.timeout);
+        for path in &self.ca_certificates {
+            let pem = std::fs::read(path).map_err(|e| {
+                ClientError::Config(format!("Cannot read {}: {}", path.display(), e))
+            })?;
+            let certificate = reqwest::Certificate::from_pem(&pem).map_err(|e| {
+                ClientError::Config(format!("Invalid certificate {}: {}", path.display(), e))
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        Ok(RestaurantClient {
+            http: builder
+                .build()
+                .map_err(|e| ClientError::Config(e.to_string()))?,
+            base_url: self.base_url,
+            retries: self.retries,
+            retry_backoff: self.retry_backoff,
+            recorder: self.recorder,
+        })
+    }
+}
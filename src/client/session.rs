@@ -0,0 +1,486 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::output;
+use crate::client::restaurant_client::RestaurantClient;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// An API call recorded in a session file.
+///
+/// Session files contain one call per line as JSON, in the order the responses arrived.
+///
+/// - `offset_ms`: When the request was sent, in milliseconds since the recording started.
+/// - `duration_ms`: How long the request took, including retries.
+/// - `method` and `path`: The request, e.g. `POST` and `/api/v1/add_item/3/5`.
+/// - `request_body`: The JSON body of the request, e.g. of a bill split. Omitted for requests without a body.
+/// - `status` and `body`: The response. The body is stored as JSON if it can be parsed, as a string otherwise.
+/// - `error`: Why no response was received, e.g. a timeout. `status` and `body` are then `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedCall {
+    pub offset_ms: u64,
+    pub duration_ms: u64,
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RecordedCall {
+    /// Creates a call from a response, or from the error that prevented one.
+    ///
+    /// # Arguments
+    /// * `offset` - When the request was sent, relative to the start of the recording.
+    /// * `duration` - How long the request took.
+    /// * `method` - The HTTP method.
+    /// * `path` - The request path.
+    /// * `response` - The status and body of the response, or the error.
+    pub fn new(
+        offset: Duration,
+        duration: Duration,
+        method: &str,
+        path: &str,
+        response: Result<(u16, &str), String>,
+    ) -> Self {
+        let (status, body, error) = match response {
+            Ok((status, body)) => (Some(status), Some(parse_body(body)), None),
+            Err(error) => (None, None, Some(error)),
+        };
+        RecordedCall {
+            offset_ms: offset.as_millis() as u64,
+            duration_ms: duration.as_millis() as u64,
+            method: method.to_string(),
+            path: path.to_string(),
+            request_body: None,
+            status,
+            body,
+            error,
+        }
+    }
+
+    /// Sets the JSON body the request was sent with.
+    pub fn with_request_body(mut self, request_body: Option<Value>) -> Self {
+        self.request_body = request_body;
+        self
+    }
+}
+
+/// Parses a response body as JSON, keeping bodies that are not JSON as a string.
+fn parse_body(body: &str) -> Value {
+    serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()))
+}
+
+/// Appends the calls of a `RestaurantClient` to a session file.
+///
+/// Every call is written and flushed as soon as its response has arrived, so that the
+/// session survives the client being terminated. Clones write to the same file.
+#[derive(Clone)]
+pub struct SessionRecorder {
+    start: Instant,
+    writer: Arc<Mutex<File>>,
+}
+
+impl SessionRecorder {
+    /// Creates the session file, replacing an existing one.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the session file.
+    ///
+    /// # Returns
+    /// * `io::Result<SessionRecorder>` - The recorder, or the error that prevented creating the file.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(SessionRecorder {
+            start: Instant::now(),
+            writer: Arc::new(Mutex::new(File::create(path)?)),
+        })
+    }
+
+    /// Returns the time elapsed since the recording started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Appends a call to the session file.
+    ///
+    /// A call that cannot be written is reported, but does not fail the request it belongs to.
+    pub fn record(&self, call: &RecordedCall) {
+        let result = match (serde_json::to_string(call), self.writer.lock()) {
+            (Ok(line), Ok(mut writer)) => writeln!(writer, "{}", line),
+            (Err(err), _) => Err(io::Error::other(err)),
+            (_, Err(err)) => Err(io::Error::other(err.to_string())),
+        };
+        if let Err(err) = result {
+            eprintln!(
+                "{}",
+                output::warning(&format!("Could not record the API call: {}", err))
+            );
+        }
+    }
+}
+
+/// Loads the calls of a session file, sorted by the time they were sent.
+///
+/// # Arguments
+/// * `path` - Path of the session file.
+///
+/// # Returns
+/// * `Ok(Vec<RecordedCall>)` - The recorded calls.
+/// * `Err(io::Error)` - If the file cannot be read or a line is not a recorded call.
+pub fn load_session(path: &Path) -> io::Result<Vec<RecordedCall>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut calls = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let call: RecordedCall = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Invalid session file {}:{}: {}",
+                    path.display(),
+                    index + 1,
+                    e
+                ),
+            )
+        })?;
+        calls.push(call);
+    }
+    // Calls are written when they complete; replay them in the order they were sent
+    calls.sort_by_key(|call| call.offset_ms);
+    Ok(calls)
+}
+
+/// Options of a session replay.
+///
+/// - `speed`: Speed multiplier for the pauses between calls, e.g. `2.0` replays twice as
+///   fast as recorded. Calls are sent back to back when `None`.
+/// - `ignore_fields`: Names of JSON fields that are not compared, at any depth, e.g.
+///   `cooking_time_minutes` for servers with generated cooking times.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayOptions {
+    pub speed: Option<f64>,
+    pub ignore_fields: Vec<String>,
+}
+
+impl Default for ReplayOptions {
+    /// Provides the default options: replay at the recorded speed and compare all fields.
+    fn default() -> Self {
+        ReplayOptions {
+            speed: Some(1.0),
+            ignore_fields: Vec::new(),
+        }
+    }
+}
+
+/// Command line arguments for replaying a session.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ReplayArgs {
+    /// Session file written with --record
+    pub session: PathBuf,
+
+    /// Speed multiplier for the pauses between calls, e.g. 60 replays an hour in a minute
+    #[arg(long, default_value_t = 1.0, conflicts_with = "no_delay")]
+    pub speed: f64,
+
+    /// Send the calls back to back, without the recorded pauses
+    #[arg(long)]
+    pub no_delay: bool,
+
+    /// JSON field that is not compared, e.g. cooking_time_minutes. Can be repeated.
+    #[arg(long = "ignore-field", value_name = "FIELD")]
+    pub ignore_fields: Vec<String>,
+}
+
+impl ReplayArgs {
+    /// Converts the arguments to replay options.
+    ///
+    /// # Returns
+    /// * `Ok(ReplayOptions)` - The validated options.
+    /// * `Err(String)` - If the speed is not above 0.
+    pub fn options(&self) -> Result<ReplayOptions, String> {
+        if !self.speed.is_finite() || self.speed <= 0.0 {
+            return Err("The speed must be above 0".to_string());
+        }
+        Ok(ReplayOptions {
+            speed: (!self.no_delay).then_some(self.speed),
+            ignore_fields: self.ignore_fields.clone(),
+        })
+    }
+}
+
+/// A replayed call whose response differs from the recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    /// Position of the call in the session, starting at 1.
+    pub index: usize,
+    pub expected: RecordedCall,
+    pub actual: RecordedCall,
+}
+
+/// The outcome of a session replay.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayReport {
+    pub calls: usize,
+    pub mismatches: Vec<Mismatch>,
+    /// Sum of the request durations of the recording.
+    pub recorded_time: Duration,
+    /// Sum of the request durations of the replay.
+    pub replayed_time: Duration,
+}
+
+impl ReplayReport {
+    /// Returns `true` if every response matched the recording.
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Returns `true` if a replayed call got the same response as the recorded one.
+///
+/// The status codes must be equal and the bodies must be equal after removing the
+/// ignored fields. Calls that got no response match if neither got one.
+///
+/// # Arguments
+/// * `expected` - The recorded call.
+/// * `actual` - The replayed call.
+/// * `ignore_fields` - Names of JSON fields that are not compared.
+pub fn responses_match(
+    expected: &RecordedCall,
+    actual: &RecordedCall,
+    ignore_fields: &[String],
+) -> bool {
+    let strip = |body: &Option<Value>| {
+        body.clone().map(|mut body| {
+            remove_fields(&mut body, ignore_fields);
+            body
+        })
+    };
+    expected.status == actual.status
+        && expected.error.is_some() == actual.error.is_some()
+        && strip(&expected.body) == strip(&actual.body)
+}
+
+/// Removes the named fields from all objects in a JSON value.
+fn remove_fields(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !fields.contains(key));
+            object
+                .values_mut()
+                .for_each(|value| remove_fields(value, fields));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| remove_fields(value, fields)),
+        _ => {}
+    }
+}
+
+/// Replays recorded calls against a server and compares the responses with the recording.
+///
+/// Calls are sent one at a time in the order they were originally sent. With a speed
+/// multiplier, each call waits until its recorded offset divided by the speed; a call
+/// whose time has already passed, e.g. because the previous call was slow, is sent
+/// immediately. Mismatches are printed as they are found.
+///
+/// # Arguments
+/// * `client` - The client connected to the server under test.
+/// * `calls` - The recorded calls, sorted by offset.
+/// * `options` - The replay speed and the fields that are not compared.
+///
+/// # Returns
+/// * `ReplayReport` - The number of replayed calls and the mismatches.
+pub async fn replay_session(
+    client: &RestaurantClient,
+    calls: &[RecordedCall],
+    options: &ReplayOptions,
+) -> ReplayReport {
+    let mut report = ReplayReport {
+        calls: calls.len(),
+        ..Default::default()
+    };
+    let first_offset = calls.first().map_or(0, |call| call.offset_ms);
+    let start = Instant::now();
+
+    for (index, expected) in calls.iter().enumerate() {
+        if let Some(speed) = options.speed {
+            let offset = Duration::from_millis(expected.offset_ms - first_offset);
+            tokio::time::sleep_until((start + offset.div_f64(speed)).into()).await;
+        }
+
+        let sent = Instant::now();
+        let response = client
+            .request(
+                &expected.method,
+                &expected.path,
+                expected.request_body.as_ref(),
+            )
+            .await;
+        let duration = sent.elapsed();
+        let actual = RecordedCall::new(
+            sent - start,
+            duration,
+            &expected.method,
+            &expected.path,
+            response
+                .as_ref()
+                .map(|(status, body)| (*status, body.as_str()))
+                .map_err(|err| err.to_string()),
+        )
+        .with_request_body(expected.request_body.clone());
+
+        report.recorded_time += Duration::from_millis(expected.duration_ms);
+        report.replayed_time += duration;
+        if !responses_match(expected, &actual, &options.ignore_fields) {
+            let mismatch = Mismatch {
+                index: index + 1,
+                expected: expected.clone(),
+                actual,
+            };
+            println!("{}", output::error(&format_mismatch(&mismatch)));
+            report.mismatches.push(mismatch);
+        }
+    }
+    report
+}
+
+/// Formats a mismatch, e.g. `#3 POST /api/v1/add_item/1/2: expected 201 {...}, got 404 {...}`.
+pub fn format_mismatch(mismatch: &Mismatch) -> String {
+    format!(
+        "#{} {} {}: expected {}, got {}",
+        mismatch.index,
+        mismatch.expected.method,
+        mismatch.expected.path,
+        format_response(&mismatch.expected),
+        format_response(&mismatch.actual)
+    )
+}
+
+/// Formats the response of a call on a single line.
+fn format_response(call: &RecordedCall) -> String {
+    match (&call.status, &call.body, &call.error) {
+        (Some(status), Some(body), _) => format!("{} {}", status, body),
+        (Some(status), None, _) => status.to_string(),
+        (None, _, Some(error)) => format!("no response ({})", error),
+        (None, _, None) => "no response".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(status: u16, body: &str) -> RecordedCall {
+        RecordedCall::new(
+            Duration::from_millis(5),
+            Duration::from_millis(2),
+            "GET",
+            "/api/v1/get_items/1",
+            Ok((status, body)),
+        )
+    }
+
+    #[test]
+    fn test_recorded_call_round_trip() {
+        let recorded = call(200, r#"{"status":"ok","data":[1,2]}"#);
+        assert_eq!(recorded.body, Some(json!({"status": "ok", "data": [1, 2]})));
+
+        let line = serde_json::to_string(&recorded).unwrap();
+        assert!(!line.contains("error"));
+        assert_eq!(
+            serde_json::from_str::<RecordedCall>(&line).unwrap(),
+            recorded
+        );
+
+        let failed = RecordedCall::new(
+            Duration::ZERO,
+            Duration::ZERO,
+            "POST",
+            "/api/v1/add_item/1/2",
+            Err("timeout".to_string()),
+        );
+        assert_eq!(failed.status, None);
+        assert_eq!(failed.error.as_deref(), Some("timeout"));
+        assert_eq!(call(500, "Internal").body, Some(json!("Internal")));
+
+        let split = call(200, "{}").with_request_body(Some(json!({"mode": "even", "payers": 2})));
+        let line = serde_json::to_string(&split).unwrap();
+        assert!(line.contains(r#""request_body":{"mode":"even","payers":2}"#));
+        assert_eq!(serde_json::from_str::<RecordedCall>(&line).unwrap(), split);
+    }
+
+    #[test]
+    fn test_responses_match() {
+        let expected = call(
+            200,
+            r#"{"data":[{"id":1,"name":"Salad","cooking_time_minutes":5}]}"#,
+        );
+        let actual = call(
+            200,
+            r#"{"data":[{"id":1,"name":"Salad","cooking_time_minutes":9}]}"#,
+        );
+        assert!(!responses_match(&expected, &actual, &[]));
+        assert!(responses_match(
+            &expected,
+            &actual,
+            &["cooking_time_minutes".to_string()]
+        ));
+        assert!(!responses_match(
+            &expected,
+            &call(404, r#"{"data":[]}"#),
+            &["data".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_format_mismatch() {
+        let mismatch = Mismatch {
+            index: 3,
+            expected: call(200, r#"{"status":"ok"}"#),
+            actual: RecordedCall {
+                status: None,
+                body: None,
+                error: Some("connection refused".to_string()),
+                ..call(200, "")
+            },
+        };
+        assert_eq!(
+            format_mismatch(&mismatch),
+            r#"#3 GET /api/v1/get_items/1: expected 200 {"status":"ok"}, got no response (connection refused)"#
+        );
+    }
+
+    #[test]
+    fn test_load_session_sorts_by_offset() {
+        let path = std::env::temp_dir().join(format!("session_{}.jsonl", std::process::id()));
+        let recorder = SessionRecorder::create(&path).unwrap();
+        let mut late = call(200, "{}");
+        late.offset_ms = 50;
+        recorder.record(&late);
+        recorder.record(&call(201, "{}"));
+
+        let calls = load_session(&path).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].status, Some(201));
+        assert_eq!(calls[1].offset_ms, 50);
+
+        std::fs::write(&path, "{\"offset_ms\": 1}\n").unwrap();
+        let err = load_session(&path).unwrap_err();
+        assert!(err.to_string().contains(":1:"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,933 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::error::ClientError;
+use crate::client::output;
+use crate::client::restaurant_client::RestaurantClient;
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Declarative description of a simulation run.
+///
+/// A scenario can be loaded from a TOML file, in which case every field is optional
+/// and falls back to its default. Together with a seed, it fully determines the
+/// requests sent for each table, so that a failing run can be reproduced.
+///
+/// - `seed`: Seed of the random number generator. A random seed is chosen (and printed) when `None`.
+/// - `tables`: Number of randomly selected tables to simulate.
+/// - `items_per_table`: Number of menu items added to each table.
+/// - `remove_ratio`: Fraction of the added items that are removed again, between 0 and 1.
+/// - `concurrency`: Maximum number of tables simulated in parallel. All tables run in parallel when `None`.
+/// - `think_time_ms`: Pause between two requests for the same table, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scenario {
+    /// Seed of the random number generator.
+    pub seed: Option<u64>,
+    /// Number of tables to simulate.
+    pub tables: usize,
+    /// Number of menu items added to each table.
+    pub items_per_table: usize,
+    /// Fraction of the added menu items that are removed again.
+    pub remove_ratio: f64,
+    /// Maximum number of tables simulated in parallel.
+    pub concurrency: Option<usize>,
+    /// Pause between two requests for the same table, in milliseconds.
+    pub think_time_ms: u64,
+}
+
+impl Default for Scenario {
+    /// Provides the default scenario: 10 tables with 3 items each, half of which are removed again.
+    fn default() -> Self {
+        Scenario {
+            seed: None,
+            tables: 10,
+            items_per_table: 3,
+            remove_ratio: 0.5,
+            concurrency: None,
+            think_time_ms: 0,
+        }
+    }
+}
+
+impl Scenario {
+    /// Loads a scenario from a TOML file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the TOML scenario file.
+    ///
+    /// # Returns
+    /// * `Ok(Scenario)` - The parsed scenario, with defaults for omitted fields.
+    /// * `Err(io::Error)` - If the file cannot be read or is not a valid scenario.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid scenario file {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Checks that the scenario can be run.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the scenario is valid.
+    /// * `Err(String)` - A message describing the first invalid field.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tables == 0 {
+            return Err("The number of tables must be at least 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.remove_ratio) {
+            return Err("The remove ratio must be between 0 and 1".to_string());
+        }
+        if self.concurrency == Some(0) {
+            return Err("The concurrency must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the number of menu items removed again from each table.
+    pub fn removes_per_table(&self) -> usize {
+        (self.items_per_table as f64 * self.remove_ratio).round() as usize
+    }
+}
+
+/// Command line arguments for configuring a simulation.
+///
+/// Every argument is optional and, when given, overrides the value from the
+/// scenario file or the defaults.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ScenarioArgs {
+    /// TOML scenario file
+    #[arg(long)]
+    pub scenario: Option<PathBuf>,
+
+    /// Seed to reproduce a previous run [default: random]
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Number of tables to simulate [default: 10]
+    #[arg(long)]
+    pub tables: Option<usize>,
+
+    /// Number of menu items added to each table [default: 3]
+    #[arg(long)]
+    pub items_per_table: Option<usize>,
+
+    /// Fraction of the added menu items that are removed again [default: 0.5]
+    #[arg(long)]
+    pub remove_ratio: Option<f64>,
+
+    /// Maximum number of tables simulated in parallel [default: all]
+    #[arg(long)]
+    pub concurrency: Option<usize>,
+
+    /// Pause between two requests for the same table, in milliseconds [default: 0]
+    #[arg(long)]
+    pub think_time_ms: Option<u64>,
+}
+
+impl ScenarioArgs {
+    /// Loads the scenario file, if any, and applies the command line arguments on top of it.
+    ///
+    /// # Returns
+    /// * `Ok(Scenario)` - The validated scenario.
+    /// * `Err(io::Error)` - If the scenario file cannot be loaded or the resulting scenario is invalid.
+    pub fn load(&self) -> io::Result<Scenario> {
+        let mut scenario = match &self.scenario {
+            Some(path) => Scenario::from_file(path)?,
+            None => Scenario::default(),
+        };
+        if self.seed.is_some() {
+            scenario.seed = self.seed;
+        }
+        if let Some(tables) = self.tables {
+            scenario.tables = tables;
+        }
+        if let Some(items_per_table) = self.items_per_table {
+            scenario.items_per_table = items_per_table;
+        }
+        if let Some(remove_ratio) = self.remove_ratio {
+            scenario.remove_ratio = remove_ratio;
+        }
+        if self.concurrency.is_some() {
+            scenario.concurrency = self.concurrency;
+        }
+        if let Some(think_time_ms) = self.think_time_ms {
+            scenario.think_time_ms = think_time_ms;
+        }
+        scenario
+            .validate()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Ok(scenario)
+    }
+}
+
+/// A request sent for a table during the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Adds the menu item with the given ID.
+    Add(u32),
+    /// Removes the menu item with the given ID.
+    Remove(u32),
+}
+
+/// The requests sent for one table, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TablePlan {
+    pub table_id: u32,
+    pub operations: Vec<Operation>,
+}
+
+/// Generates the requests of a simulation.
+///
+/// The plan only depends on its arguments: the same scenario, seed, tables and menu
+/// produce the same plan every time. Each table first gets `items_per_table` random
+/// menu items added, then a random subset of exactly those items is removed again.
+///
+/// # Arguments
+/// * `scenario` - The scenario to plan.
+/// * `seed` - Seed of the random number generator.
+/// * `table_ids` - The tables available on the server.
+/// * `menu_ids` - The menu items available on the server.
+///
+/// # Returns
+/// * `Ok(Vec<TablePlan>)` - The requests for each selected table.
+/// * `Err(String)` - If the server has fewer tables than requested or no menu items.
+pub fn plan(
+    scenario: &Scenario,
+    seed: u64,
+    table_ids: &[u32],
+    menu_ids: &[u32],
+) -> Result<Vec<TablePlan>, String> {
+    if scenario.tables > table_ids.len() {
+        return Err(format!(
+            "The scenario needs {} tables, but the server only has {}",
+            scenario.tables,
+            table_ids.len()
+        ));
+    }
+    if menu_ids.is_empty() && scenario.items_per_table > 0 {
+        return Err("The server has no menu items".to_string());
+    }
+
+    // Sort the IDs so that the plan does not depend on the order returned by the server
+    let mut table_ids = table_ids.to_vec();
+    table_ids.sort_unstable();
+    let mut menu_ids = menu_ids.to_vec();
+    menu_ids.sort_unstable();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let selected_tables: Vec<u32> = table_ids
+        .choose_multiple(&mut rng, scenario.tables)
+        .cloned()
+        .collect();
+
+    let removes = scenario.removes_per_table();
+    Ok(selected_tables
+        .into_iter()
+        .map(|table_id| {
+            let added: Vec<u32> = (0..scenario.items_per_table)
+                .filter_map(|_| menu_ids.choose(&mut rng).cloned())
+                .collect();
+            let removed: Vec<u32> = added.choose_multiple(&mut rng, removes).cloned().collect();
+
+            let operations = added
+                .into_iter()
+                .map(Operation::Add)
+                .chain(removed.into_iter().map(Operation::Remove))
+                .collect();
+            TablePlan {
+                table_id,
+                operations,
+            }
+        })
+        .collect())
+}
+
+/// The expected and actual orders of a table after a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableCheck {
+    pub table_id: u32,
+    /// The menu item IDs the table should have, sorted.
+    pub expected: Vec<u32>,
+    /// The menu item IDs the server returned, sorted.
+    pub actual: Vec<u32>,
+}
+
+impl TableCheck {
+    /// Returns the menu item IDs that are expected but missing on the server, once per lost item.
+    pub fn lost(&self) -> Vec<u32> {
+        multiset_difference(&self.expected, &self.actual)
+    }
+
+    /// Returns the menu item IDs the server has more often than expected, once per extra item.
+    pub fn duplicated(&self) -> Vec<u32> {
+        multiset_difference(&self.actual, &self.expected)
+    }
+
+    /// Returns `true` if the server has exactly the expected menu items.
+    pub fn is_consistent(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// The outcome of a simulation run.
+///
+/// - `seed`: The seed the run used, to reproduce it.
+/// - `failures`: The number of requests that failed.
+/// - `checks`: The comparison of the expected and actual orders of each verified table.
+/// - `unverified`: Tables whose expected orders are unknown, with the reason, e.g. because a
+///   request timed out and may or may not have been applied by the server.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationReport {
+    pub seed: u64,
+    pub failures: usize,
+    pub checks: Vec<TableCheck>,
+    pub unverified: Vec<(u32, String)>,
+}
+
+impl SimulationReport {
+    /// Returns the checks of the tables whose orders do not match the expected ones.
+    pub fn mismatches(&self) -> Vec<&TableCheck> {
+        self.checks
+            .iter()
+            .filter(|check| !check.is_consistent())
+            .collect()
+    }
+
+    /// Returns `true` if every verified table has exactly the expected orders.
+    pub fn is_consistent(&self) -> bool {
+        self.checks.iter().all(TableCheck::is_consistent)
+    }
+}
+
+/// Computes the orders a table should have after a sequence of requests.
+///
+/// # Arguments
+/// * `baseline` - The menu item IDs ordered for the table before the simulation.
+/// * `applied` - The requests the server confirmed, in order.
+///
+/// # Returns
+/// * `Vec<u32>` - The expected menu item IDs, sorted.
+pub fn expected_items(baseline: &[u32], applied: &[Operation]) -> Vec<u32> {
+    let mut items = baseline.to_vec();
+    for operation in applied {
+        match *operation {
+            Operation::Add(id) => items.push(id),
+            Operation::Remove(id) => {
+                if let Some(position) = items.iter().position(|&item| item == id) {
+                    items.remove(position);
+                }
+            }
+        }
+    }
+    items.sort_unstable();
+    items
+}
+
+/// Returns the elements of `left` that are not matched by an element of `right`, counting duplicates.
+fn multiset_difference(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut remaining = right.to_vec();
+    let mut difference: Vec<u32> = left
+        .iter()
+        .filter(|id| match remaining.iter().position(|other| other == *id) {
+            Some(position) => {
+                remaining.remove(position);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect();
+    difference.sort_unstable();
+    difference
+}
+
+/// The expected menu item IDs of each simulated table, or the reason why they are unknown.
+type Expectations = HashMap<u32, Result<Vec<u32>, String>>;
+
+/// Retrieves the menu item IDs ordered for a table, treating a table without orders as empty.
+async fn fetch_item_ids(client: &RestaurantClient, table_id: u32) -> Result<Vec<u32>, ClientError> {
+    match client.get_items(table_id).await {
+        Ok(items) => Ok(items.iter().map(|item| item.id).collect()),
+        Err(err) if err.is_not_found() => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Runs a simulation scenario against the server and verifies the resulting orders.
+///
+/// The tables of the plan are processed by up to `scenario.concurrency` parallel workers.
+/// Each worker reads the table's orders, then sends the table's requests in the planned
+/// order, separated by the think time. With a concurrency of 1 the requests are sent in
+/// exactly the same order on every run with the same seed. Failed requests are reported
+/// and counted, but never abort the simulation.
+///
+/// Once all tables are done, the orders of each table are read again and compared with
+/// the orders expected from the confirmed requests, so that lost or duplicated items are
+/// reported. The selected tables must not be changed by other clients during the run.
+///
+/// # Arguments
+/// * `client` - The client used to send the requests.
+/// * `scenario` - The scenario to run.
+///
+/// # Returns
+/// * `SimulationReport` - The failed requests and the verification of each table.
+pub async fn run_simulation(client: &RestaurantClient, scenario: &Scenario) -> SimulationReport {
+    let seed = scenario.seed.unwrap_or_else(rand::random);
+    let mut report = SimulationReport {
+        seed,
+        ..Default::default()
+    };
+
+    println!("\n========== Starting Simulation ==========");
+    println!(
+        "1. Select Tables for Simulation: A random selection of {} tables is performed (seed {}).",
+        scenario.tables, seed
+    );
+    println!(
+        "2. Add and Remove Operations: {} menu items are added to each table, then {} of them are removed again, for up to {} tables in parallel.",
+        scenario.items_per_table,
+        scenario.removes_per_table(),
+        scenario
+            .concurrency
+            .map_or("all".to_string(), |c| c.to_string())
+    );
+    println!("3. Retain Some Items After Simulation: The items that were not removed remain on the table.");
+    println!("4. Final Status Printing: The final status of each table is printed in parrellel.");
+    println!("5. Consistency Check: The final orders are compared with the expected ones.\n");
+    println!("Rerun with --seed {} to send the same requests.", seed);
+    println!("==========================================\n");
+
+    let (table_ids, menus) = match tokio::try_join!(client.get_tables(), client.get_menus()) {
+        Ok(result) => result,
+        Err(err) => {
+            println!("Error preparing the simulation: {}", err);
+            report.failures = 1;
+            return report;
+        }
+    };
+    let menu_ids: Vec<u32> = menus.iter().map(|menu| menu.item.id).collect();
+
+    let plans = match plan(scenario, seed, &table_ids, &menu_ids) {
+        Ok(plans) => plans,
+        Err(err) => {
+            println!("Error preparing the simulation: {}", err);
+            report.failures = 1;
+            return report;
+        }
+    };
+    let selected_tables: Vec<u32> = plans.iter().map(|plan| plan.table_id).collect();
+
+    let failures = Arc::new(AtomicUsize::new(0));
+    let expectations: Arc<Mutex<Expectations>> = Arc::new(Mutex::new(HashMap::new()));
+    let think_time = Duration::from_millis(scenario.think_time_ms);
+    let concurrency = scenario.concurrency.unwrap_or(plans.len());
+
+    let worker_client = client.clone();
+    let worker_failures = Arc::clone(&failures);
+    let worker_expectations = Arc::clone(&expectations);
+    let panicked = run_workers(plans, concurrency, move |plan: TablePlan| {
+        let client = worker_client.clone();
+        let failures = Arc::clone(&worker_failures);
+        let expectations = Arc::clone(&worker_expectations);
+        async move {
+            let baseline = fetch_item_ids(&client, plan.table_id).await;
+            let mut unknown = match &baseline {
+                Ok(_) => None,
+                Err(err) => {
+                    failures.fetch_add(1, Ordering::SeqCst);
+                    Some(format!(
+                        "the orders could not be read before the simulation: {}",
+                        err
+                    ))
+                }
+            };
+
+            let mut applied = Vec::new();
+            for (index, operation) in plan.operations.into_iter().enumerate() {
+                if index > 0 && !think_time.is_zero() {
+                    tokio::time::sleep(think_time).await;
+                }
+                match execute_operation(&client, plan.table_id, operation).await {
+                    Ok(()) => applied.push(operation),
+                    // The server answered, so the request was not applied
+                    Err(ClientError::Api { .. }) => {
+                        failures.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // E.g. a timeout: the server may or may not have applied the request
+                    Err(err) => {
+                        failures.fetch_add(1, Ordering::SeqCst);
+                        unknown.get_or_insert(format!(
+                            "the outcome of {:?} is unknown: {}",
+                            operation, err
+                        ));
+                    }
+                }
+            }
+
+            let expected = match (baseline, unknown) {
+                (Ok(baseline), None) => Ok(expected_items(&baseline, &applied)),
+                (_, Some(reason)) => Err(reason),
+                (Err(err), None) => Err(err.to_string()),
+            };
+            if let Ok(mut expectations) = expectations.lock() {
+                expectations.insert(plan.table_id, expected);
+            }
+        }
+    })
+    .await;
+    failures.fetch_add(panicked, Ordering::SeqCst);
+
+    println!("\n========== Final Table Status ==========");
+    let mut status_handles = Vec::new();
+    for &table_id in &selected_tables {
+        let client_clone = client.clone();
+
+        let handle = tokio::spawn(async move {
+            let result = client_clone.get_items(table_id).await;
+            match &result {
+                Ok(menu_items) => {
+                    for item in menu_items {
+                        println!(
+                            "For Table: {}  Menu Item ID: {}, Name: {}, Cooking Time: {} minutes",
+                            table_id, item.id, item.name, item.cooking_time_minutes
+                        );
+                    }
+                }
+                // Every item may have been removed from the table
+                Err(err) if err.is_not_found() => {}
+                Err(err) => {
+                    println!("Failed to retrieve orders for table {}: {}", table_id, err);
+                }
+            }
+            match result {
+                Ok(items) => Ok(items.iter().map(|item| item.id).collect::<Vec<u32>>()),
+                Err(err) if err.is_not_found() => Ok(Vec::new()),
+                Err(err) => Err(err),
+            }
+        });
+
+        status_handles.push((table_id, handle));
+    }
+
+    let mut expectations = match expectations.lock() {
+        Ok(mut expectations) => std::mem::take(&mut *expectations),
+        Err(_) => HashMap::new(),
+    };
+    for (table_id, handle) in status_handles {
+        let expected = expectations
+            .remove(&table_id)
+            .unwrap_or_else(|| Err("the simulation task failed".to_string()));
+        let actual = match handle.await {
+            Ok(Ok(mut actual)) => {
+                actual.sort_unstable();
+                Ok(actual)
+            }
+            Ok(Err(err)) => {
+                failures.fetch_add(1, Ordering::SeqCst);
+                Err(format!("the final orders could not be read: {}", err))
+            }
+            Err(err) => {
+                failures.fetch_add(1, Ordering::SeqCst);
+                Err(format!("the final orders could not be read: {}", err))
+            }
+        };
+        match (expected, actual) {
+            (Ok(expected), Ok(actual)) => report.checks.push(TableCheck {
+                table_id,
+                expected,
+                actual,
+            }),
+            (Err(reason), _) | (_, Err(reason)) => report.unverified.push((table_id, reason)),
+        }
+    }
+    println!("=========================================\n");
+
+    report.failures = failures.load(Ordering::SeqCst);
+    print_report(&report);
+    println!("Simulation complete (seed {}).", seed);
+
+    report
+}
+
+/// Prints the consistency check of a simulation run.
+fn print_report(report: &SimulationReport) {
+    println!("========== Consistency Report ==========");
+    for check in report.mismatches() {
+        let mut problems = Vec::new();
+        if !check.lost().is_empty() {
+            problems.push(format!("lost items {}", format_ids(&check.lost())));
+        }
+        if !check.duplicated().is_empty() {
+            problems.push(format!(
+                "duplicated items {}",
+                format_ids(&check.duplicated())
+            ));
+        }
+        println!(
+            "{}",
+            output::error(&format!(
+                "Table {}: {} (expected {}, found {})",
+                check.table_id,
+                problems.join(", "),
+                format_ids(&check.expected),
+                format_ids(&check.actual)
+            ))
+        );
+    }
+    for (table_id, reason) in &report.unverified {
+        println!(
+            "{}",
+            output::warning(&format!("Table {}: not verified, {}", table_id, reason))
+        );
+    }
+
+    let mismatches = report.mismatches().len();
+    let summary = format!(
+        "{} of {} verified tables match the expected orders.",
+        report.checks.len() - mismatches,
+        report.checks.len()
+    );
+    if mismatches == 0 {
+        println!("{}", output::success(&summary));
+    } else {
+        println!("{}", output::error(&summary));
+    }
+    println!("=========================================\n");
+}
+
+/// Formats menu item IDs as a bracketed list, e.g. `[2, 5, 5]`.
+fn format_ids(ids: &[u32]) -> String {
+    let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+    format!("[{}]", ids.join(", "))
+}
+
+/// Sends a single planned request and prints its outcome.
+///
+/// # Returns
+/// * `Result<(), ClientError>` - The error if the request failed.
+async fn execute_operation(
+    client: &RestaurantClient,
+    table_id: u32,
+    operation: Operation,
+) -> Result<(), ClientError> {
+    match operation {
+        Operation::Add(menu_item_id) => {
+            println!("Ordering menu item {} for table {}", menu_item_id, table_id);
+            match client.add_item(table_id, menu_item_id).await {
+                Ok(_) => {
+                    println!(
+                        "Successfully ordered menu item {} for table {}",
+                        menu_item_id, table_id
+                    );
+                    Ok(())
+                }
+                Err(err) => {
+                    println!(
+                        "Failed to order menu item {} for table {}: {}",
+                        menu_item_id, table_id, err
+                    );
+                    Err(err)
+                }
+            }
+        }
+        Operation::Remove(menu_item_id) => {
+            println!(
+                "Removing menu item {} from table {}",
+                menu_item_id, table_id
+            );
+            match client.remove_item(table_id, menu_item_id).await {
+                Ok(_) => {
+                    println!(
+                        "Successfully removed menu item {} from table {}",
+                        menu_item_id, table_id
+                    );
+                    Ok(())
+                }
+                Err(err) => {
+                    println!(
+                        "Failed to remove menu item {} from table {}: {}",
+                        menu_item_id, table_id, err
+                    );
+                    Err(err)
+                }
+            }
+        }
+    }
+}
+
+/// Processes jobs with up to `concurrency` parallel tasks.
+///
+/// Jobs are started in the order given; each task takes the next job once its current
+/// job has completed.
+///
+/// # Arguments
+/// * `jobs` - The jobs to process.
+/// * `concurrency` - The maximum number of jobs processed at the same time.
+/// * `work` - Creates the future that processes a job.
+///
+/// # Returns
+/// * `usize` - The number of tasks that panicked.
+pub(crate) async fn run_workers<T, F, Fut>(jobs: Vec<T>, concurrency: usize, work: F) -> usize
+where
+    T: Send + 'static,
+    F: Fn(T) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let workers = concurrency.clamp(1, jobs.len().max(1));
+    let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
+
+    let handles: Vec<JoinHandle<()>> = (0..workers)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let work = work.clone();
+            tokio::spawn(async move {
+                loop {
+                    let job = match queue.lock() {
+                        Ok(mut queue) => queue.pop_front(),
+                        Err(_) => None,
+                    };
+                    match job {
+                        Some(job) => work(job).await,
+                        None => break,
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let panicked = AtomicUsize::new(0);
+    wait_for_tasks(handles, &panicked).await;
+    panicked.load(Ordering::SeqCst)
+}
+
+/// Waits for the simulation tasks, counting every task that panicked as a failure.
+async fn wait_for_tasks(handles: Vec<JoinHandle<()>>, failures: &AtomicUsize) {
+    for handle in handles {
+        if let Err(err) = handle.await {
+            failures.fetch_add(1, Ordering::SeqCst);
+            println!("Simulation task failed: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables() -> Vec<u32> {
+        (1..=100).collect()
+    }
+
+    fn menu() -> Vec<u32> {
+        (1..=10).collect()
+    }
+
+    #[test]
+    fn test_plan_is_deterministic() {
+        let scenario = Scenario {
+            tables: 20,
+            items_per_table: 5,
+            ..Default::default()
+        };
+        let first = plan(&scenario, 42, &tables(), &menu()).unwrap();
+        let mut shuffled_tables = tables();
+        shuffled_tables.reverse();
+        let second = plan(&scenario, 42, &shuffled_tables, &menu()).unwrap();
+        assert_eq!(first, second);
+
+        let other_seed = plan(&scenario, 43, &tables(), &menu()).unwrap();
+        assert_ne!(first, other_seed);
+    }
+
+    #[test]
+    fn test_plan_only_removes_added_items() {
+        let scenario = Scenario {
+            tables: 100,
+            items_per_table: 4,
+            remove_ratio: 0.5,
+            ..Default::default()
+        };
+        let plans = plan(&scenario, 7, &tables(), &menu()).unwrap();
+        assert_eq!(plans.len(), 100);
+
+        for table_plan in plans {
+            let mut added: Vec<u32> = Vec::new();
+            let mut removed = 0;
+            for operation in table_plan.operations {
+                match operation {
+                    Operation::Add(id) => added.push(id),
+                    Operation::Remove(id) => {
+                        let position = added.iter().position(|&a| a == id).unwrap();
+                        added.remove(position);
+                        removed += 1;
+                    }
+                }
+            }
+            assert_eq!(added.len(), 2);
+            assert_eq!(removed, 2);
+        }
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let scenario = Scenario {
+            tables: 101,
+            ..Default::default()
+        };
+        assert!(plan(&scenario, 1, &tables(), &menu()).is_err());
+        assert!(plan(&Scenario::default(), 1, &tables(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_expected_items() {
+        let applied = [
+            Operation::Add(3),
+            Operation::Add(1),
+            Operation::Add(3),
+            Operation::Remove(3),
+            Operation::Remove(8),
+        ];
+        assert_eq!(expected_items(&[5], &applied), vec![1, 3, 5]);
+        assert_eq!(expected_items(&[], &[]), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_table_check_reports_lost_and_duplicated_items() {
+        let check = TableCheck {
+            table_id: 4,
+            expected: vec![1, 2, 2, 3],
+            actual: vec![1, 2, 3, 3, 3],
+        };
+        assert!(!check.is_consistent());
+        assert_eq!(check.lost(), vec![2]);
+        assert_eq!(check.duplicated(), vec![3, 3]);
+
+        let report = SimulationReport {
+            checks: vec![
+                check.clone(),
+                TableCheck {
+                    table_id: 5,
+                    expected: vec![1],
+                    actual: vec![1],
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(!report.is_consistent());
+        assert_eq!(report.mismatches(), vec![&check]);
+        assert!(SimulationReport::default().is_consistent());
+        assert_eq!(format_ids(&check.actual), "[1, 2, 3, 3, 3]");
+    }
+
+    #[test]
+    fn test_parse_scenario() {
+        let scenario: Scenario = toml::from_str(
+            r#"
+            seed = 42
+            tables = 50
+            items_per_table = 6
+            remove_ratio = 0.25
+            concurrency = 8
+            think_time_ms = 20
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            scenario,
+            Scenario {
+                seed: Some(42),
+                tables: 50,
+                items_per_table: 6,
+                remove_ratio: 0.25,
+                concurrency: Some(8),
+                think_time_ms: 20,
+            }
+        );
+        assert_eq!(scenario.removes_per_table(), 2);
+
+        let result: Result<Scenario, _> = toml::from_str("table = 5");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_scenario() {
+        assert!(Scenario::default().validate().is_ok());
+        for scenario in [
+            Scenario {
+                tables: 0,
+                ..Default::default()
+            },
+            Scenario {
+                remove_ratio: 1.5,
+                ..Default::default()
+            },
+            Scenario {
+                concurrency: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(scenario.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_args_override_scenario() {
+        let args = ScenarioArgs {
+            seed: Some(9),
+            tables: Some(3),
+            ..Default::default()
+        };
+        let scenario = args.load().unwrap();
+        assert_eq!(scenario.seed, Some(9));
+        assert_eq!(scenario.tables, 3);
+        assert_eq!(scenario.items_per_table, 3);
+
+        let args = ScenarioArgs {
+            remove_ratio: Some(-0.1),
+            ..Default::default()
+        };
+        assert_eq!(args.load().unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_run_workers_limits_concurrency() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let done = Arc::new(AtomicUsize::new(0));
+
+        let (r, m, d) = (
+            Arc::clone(&running),
+            Arc::clone(&max_running),
+            Arc::clone(&done),
+        );
+        let panicked = run_workers((0..20).collect(), 3, move |_job: u32| {
+            let (running, max_running, done) = (Arc::clone(&r), Arc::clone(&m), Arc::clone(&d));
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                done.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .await;
+
+        assert_eq!(panicked, 0);
+        assert_eq!(done.load(Ordering::SeqCst), 20);
+        assert!(max_running.load(Ordering::SeqCst) <= 3);
+    }
+}
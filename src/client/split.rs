@@ -0,0 +1,87 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::split::SplitRequest;
+use clap::Args;
+
+/// Command line arguments describing how a bill is split.
+///
+/// Exactly one of the modes is given:
+/// - `--even 3`: Three checks with equal amounts.
+/// - `--seats`: One check per seat with the menu items ordered from it.
+/// - `--check 1,3 --check 2`: One check per group of order lines.
+#[derive(Args, Debug, Clone)]
+#[group(required = true, multiple = false)]
+pub struct SplitArgs {
+    /// Split the total evenly between this many payers
+    #[arg(long, value_name = "PAYERS")]
+    pub even: Option<u32>,
+
+    /// One check per seat, from the seats the items were ordered from
+    #[arg(long)]
+    pub seats: bool,
+
+    /// Order line IDs of one check, as listed by `orders`, e.g. 1,3 (repeatable)
+    #[arg(long, value_name = "LINES", value_parser = parse_line_ids)]
+    pub check: Vec<Vec<u32>>,
+}
+
+impl SplitArgs {
+    /// Converts the arguments into the request sent to the server.
+    ///
+    /// # Returns
+    /// * `SplitRequest` - The split request.
+    pub fn request(&self) -> SplitRequest {
+        if let Some(payers) = self.even {
+            return SplitRequest::Even { payers };
+        }
+        if self.seats {
+            return SplitRequest::Seats {};
+        }
+        SplitRequest::Items {
+            checks: self.check.clone(),
+        }
+    }
+}
+
+/// Parses a comma separated list of order line IDs, e.g. `1,3`.
+fn parse_line_ids(s: &str) -> Result<Vec<u32>, String> {
+    s.split(',')
+        .map(|id| {
+            id.trim()
+                .parse()
+                .map_err(|_| format!("Invalid order line ID '{}'", id.trim()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(even: Option<u32>, seats: bool, check: Vec<Vec<u32>>) -> SplitArgs {
+        SplitArgs { even, seats, check }
+    }
+
+    #[test]
+    fn test_parse_line_ids() {
+        assert_eq!(parse_line_ids("1, 3,4"), Ok(vec![1, 3, 4]));
+        assert!(parse_line_ids("1,,3").is_err());
+        assert!(parse_line_ids("x").is_err());
+    }
+
+    #[test]
+    fn test_request() {
+        assert_eq!(
+            args(Some(3), false, vec![]).request(),
+            SplitRequest::Even { payers: 3 }
+        );
+        assert_eq!(args(None, true, vec![]).request(), SplitRequest::Seats {});
+        assert_eq!(
+            args(None, false, vec![vec![2], vec![1, 3]]).request(),
+            SplitRequest::Items {
+                checks: vec![vec![2], vec![1, 3]]
+            }
+        );
+    }
+}
@@ -0,0 +1,693 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::client::error::ClientError;
+use crate::client::output::group_order_items;
+use crate::client::restaurant_client::RestaurantClient;
+use crate::server::data_model::models::MenuItem;
+use crate::server::data_model::modifier::OrderedItem;
+use futures::future::join_all;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Width of a table cell in the floor grid, including the gap to the next cell.
+const CELL_WIDTH: u16 = 16;
+
+/// How long the dashboard waits for a key press before checking whether a refresh is due.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A table together with the menu items ordered for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSummary {
+    pub table_id: u32,
+    pub items: Vec<OrderedItem>,
+}
+
+impl TableSummary {
+    /// Returns the number of ordered menu items.
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the longest cooking time of the ordered menu items, i.e. when the whole order is ready.
+    pub fn longest_cooking_time(&self) -> Option<u64> {
+        self.items
+            .iter()
+            .map(|item| item.cooking_time_minutes)
+            .max()
+    }
+}
+
+/// Retrieves every table together with its orders.
+///
+/// A table without orders is answered with `404` by the server and is reported with no items.
+///
+/// # Returns
+/// * `Ok(Vec<TableSummary>)` - All tables, in the order returned by the server.
+/// * `Err(ClientError)` - If the tables or any table's orders could not be retrieved.
+pub async fn fetch_floor(client: &RestaurantClient) -> Result<Vec<TableSummary>, ClientError> {
+    let table_ids = client.get_tables().await?;
+    let orders = join_all(table_ids.iter().map(|&table_id| client.get_items(table_id))).await;
+
+    table_ids
+        .into_iter()
+        .zip(orders)
+        .map(|(table_id, items)| match items {
+            Ok(items) => Ok(TableSummary { table_id, items }),
+            Err(err) if err.is_not_found() => Ok(TableSummary {
+                table_id,
+                items: Vec::new(),
+            }),
+            Err(err) => Err(err),
+        })
+        .collect()
+}
+
+/// An operation requested by a key press, executed by the dashboard's event loop.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    Add { table_id: u32, item_id: u32 },
+    Remove { table_id: u32, item_id: u32 },
+}
+
+/// Which list the popup shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PopupKind {
+    /// The restaurant's menu, to add an item to the selected table.
+    Add,
+    /// The selected table's orders, to remove an item from it.
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Popup {
+    kind: PopupKind,
+    selected: usize,
+}
+
+/// State of the floor dashboard.
+///
+/// Key presses are translated into `Action`s by `handle_key`, so the state can be
+/// driven without a terminal. `render` draws the state into a frame.
+pub struct Dashboard {
+    tables: Vec<TableSummary>,
+    menu: Vec<MenuItem>,
+    selected: usize,
+    popup: Option<Popup>,
+    status: Option<(String, bool)>,
+    last_refresh: Option<Instant>,
+    /// Number of grid columns at the last render, used to move the selection up and down.
+    columns: usize,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dashboard {
+    /// Creates an empty dashboard. Tables and menu items are filled in by `set_floor` and `set_menu`.
+    pub fn new() -> Self {
+        Dashboard {
+            tables: Vec::new(),
+            menu: Vec::new(),
+            selected: 0,
+            popup: None,
+            status: None,
+            last_refresh: None,
+            columns: 1,
+        }
+    }
+
+    /// Replaces the tables shown on the floor, keeping the selected table if it still exists.
+    pub fn set_floor(&mut self, tables: Vec<TableSummary>) {
+        let selected_id = self.selected_table().map(|table| table.table_id);
+        self.tables = tables;
+        self.selected = selected_id
+            .and_then(|id| self.tables.iter().position(|table| table.table_id == id))
+            .unwrap_or(0);
+        self.last_refresh = Some(Instant::now());
+    }
+
+    /// Replaces the menu items offered when adding an item.
+    pub fn set_menu(&mut self, menu: Vec<MenuItem>) {
+        self.menu = menu;
+    }
+
+    /// Shows a message in the status bar.
+    pub fn set_status(&mut self, message: impl Into<String>, is_error: bool) {
+        self.status = Some((message.into(), is_error));
+    }
+
+    /// Returns the currently selected table.
+    pub fn selected_table(&self) -> Option<&TableSummary> {
+        self.tables.get(self.selected)
+    }
+
+    /// Handles a key press.
+    ///
+    /// Without a popup, the arrow keys (or `h`/`j`/`k`/`l`) move the selection, `a` opens
+    /// the menu to add an item, `r` opens the table's orders to remove an item, `f`
+    /// refreshes and `q`, `Esc` or `Ctrl+C` quit. In a popup, the arrow keys select an
+    /// entry, `Enter` confirms and `Esc` closes it.
+    ///
+    /// # Returns
+    /// * `Action` - The operation to execute.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
+        match self.popup {
+            Some(popup) => self.handle_popup_key(popup, key.code),
+            None => self.handle_floor_key(key.code),
+        }
+    }
+
+    fn handle_floor_key(&mut self, code: KeyCode) -> Action {
+        let last = self.tables.len().saturating_sub(1);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('f') | KeyCode::F(5) => return Action::Refresh,
+            KeyCode::Left | KeyCode::Char('h') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(self.columns)
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected + self.columns <= last => {
+                self.selected += self.columns
+            }
+            KeyCode::Char('a') => {
+                if self.menu.is_empty() {
+                    self.set_status("The menu is empty", true);
+                } else {
+                    self.open_popup(PopupKind::Add);
+                }
+            }
+            KeyCode::Char('r') => {
+                if self.selected_table().is_some_and(|t| !t.items.is_empty()) {
+                    self.open_popup(PopupKind::Remove);
+                } else {
+                    self.set_status("No items ordered for this table", true);
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn handle_popup_key(&mut self, mut popup: Popup, code: KeyCode) -> Action {
+        let options = self.popup_options(popup.kind);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.popup = None;
+                return Action::None;
+            }
+            KeyCode::Up | KeyCode::Char('k') => popup.selected = popup.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                popup.selected = (popup.selected + 1).min(options.len().saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                self.popup = None;
+                let (Some(table), Some(item)) =
+                    (self.selected_table(), options.get(popup.selected))
+                else {
+                    return Action::None;
+                };
+                let (table_id, item_id) = (table.table_id, item.id);
+                return match popup.kind {
+                    PopupKind::Add => Action::Add { table_id, item_id },
+                    PopupKind::Remove => Action::Remove { table_id, item_id },
+                };
+            }
+            _ => {}
+        }
+        self.popup = Some(popup);
+        Action::None
+    }
+
+    fn open_popup(&mut self, kind: PopupKind) {
+        self.popup = Some(Popup { kind, selected: 0 });
+    }
+
+    /// Returns the menu items listed by a popup.
+    fn popup_options(&self, kind: PopupKind) -> Vec<OrderedItem> {
+        match kind {
+            PopupKind::Add => self.menu.iter().cloned().map(OrderedItem::from).collect(),
+            PopupKind::Remove => self
+                .selected_table()
+                .map(|table| {
+                    group_order_items(&table.items)
+                        .into_iter()
+                        .map(|(item, _)| item.clone())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Draws the dashboard: the floor grid, the detail pane of the selected table and the status bar.
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [main, status_bar] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+        let [floor, detail] =
+            Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .areas(main);
+
+        self.render_floor(frame, floor);
+        self.render_detail(frame, detail);
+        self.render_status_bar(frame, status_bar);
+        if let Some(popup) = self.popup {
+            self.render_popup(frame, popup, main);
+        }
+    }
+
+    fn render_floor(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Floor  (#table  items  longest cooking time) ");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        self.columns = usize::from((inner.width / CELL_WIDTH).max(1));
+        let visible_rows = usize::from(inner.height.max(1));
+        let selected_row = self.selected / self.columns;
+        let first_row = (selected_row + 1).saturating_sub(visible_rows);
+
+        let lines: Vec<Line> = self
+            .tables
+            .chunks(self.columns)
+            .enumerate()
+            .skip(first_row)
+            .take(visible_rows)
+            .map(|(row, tables)| {
+                let spans: Vec<Span> = tables
+                    .iter()
+                    .enumerate()
+                    .map(|(column, table)| {
+                        let index = row * self.columns + column;
+                        table_cell(table, index == self.selected)
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_detail(&self, frame: &mut Frame, area: Rect) {
+        let Some(table) = self.selected_table() else {
+            let block = Block::default().borders(Borders::ALL).title(" Table ");
+            frame.render_widget(Paragraph::new("No tables").block(block), area);
+            return;
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Table {} ", table.table_id));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let [orders, summary] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+
+        let rows: Vec<Row> = group_order_items(&table.items)
+            .into_iter()
+            .map(|(item, quantity)| {
+                Row::new(vec![
+                    item.id.to_string(),
+                    item_label(item),
+                    quantity.to_string(),
+                    item.cooking_time_minutes.to_string(),
+                ])
+            })
+            .collect();
+        let orders_table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Min(8),
+                Constraint::Length(4),
+                Constraint::Length(5),
+            ],
+        )
+        .header(
+            Row::new(vec!["ID", "Name", "Qty", "Min"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(orders_table, orders);
+
+        let summary_text = match table.longest_cooking_time() {
+            Some(longest) => format!("{} items, ready in {} min", table.item_count(), longest),
+            None => "No items ordered".to_string(),
+        };
+        frame.render_widget(Paragraph::new(summary_text), summary);
+    }
+
+    fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
+        let mut spans = vec![Span::styled(
+            " ←↑↓→ select  a add  r remove  f refresh  q quit ",
+            Style::default().add_modifier(Modifier::REVERSED),
+        )];
+        if let Some(last_refresh) = self.last_refresh {
+            spans.push(Span::raw(format!(
+                "  updated {}s ago",
+                last_refresh.elapsed().as_secs()
+            )));
+        }
+        if let Some((message, is_error)) = &self.status {
+            let color = if *is_error { Color::Red } else { Color::Green };
+            spans.push(Span::styled(
+                format!("  {}", message),
+                Style::default().fg(color),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn render_popup(&self, frame: &mut Frame, popup: Popup, area: Rect) {
+        let options = self.popup_options(popup.kind);
+        let table_id = self.selected_table().map_or(0, |table| table.table_id);
+        let title = match popup.kind {
+            PopupKind::Add => format!(" Add to table {} ", table_id),
+            PopupKind::Remove => format!(" Remove from table {} ", table_id),
+        };
+
+        let items: Vec<ListItem> = options
+            .iter()
+            .map(|item| {
+                ListItem::new(format!(
+                    "{:>3}  {}  ({} min)",
+                    item.id,
+                    item_label(item),
+                    item.cooking_time_minutes
+                ))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(popup.selected));
+
+        let popup_area = centered(area, 44, (options.len() as u16).saturating_add(2));
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(list, popup_area, &mut state);
+    }
+}
+
+/// Names an ordered menu item for the kitchen, followed by its modifiers and note.
+fn item_label(item: &OrderedItem) -> String {
+    let mut label = item.name.clone();
+    let modifiers = item.modifier_names();
+    if !modifiers.is_empty() {
+        label.push_str(&format!(" ({})", modifiers));
+    }
+    if let Some(note) = &item.note {
+        label.push_str(&format!(" \"{}\"", note));
+    }
+    label
+}
+
+/// Formats a table of the floor grid, highlighting occupied and selected tables.
+fn table_cell(table: &TableSummary, selected: bool) -> Span<'static> {
+    let text = match table.longest_cooking_time() {
+        Some(longest) => format!(
+            " #{:<3} {:>2}x {:>3}m ",
+            table.table_id,
+            table.item_count(),
+            longest
+        ),
+        None => format!(" #{:<3}   -       ", table.table_id),
+    };
+    let style = match (selected, table.items.is_empty()) {
+        (true, _) => Style::default().add_modifier(Modifier::REVERSED),
+        (false, true) => Style::default().fg(Color::DarkGray),
+        (false, false) => Style::default().fg(Color::Yellow),
+    };
+    Span::styled(
+        format!("{:<width$}", text, width = CELL_WIDTH as usize),
+        style,
+    )
+}
+
+/// Returns a rectangle of at most `width` x `height` centered in `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Runs the full-screen dashboard until the user quits.
+///
+/// The floor is polled every `refresh_interval`, and immediately after an item was
+/// added or removed. Failed requests are shown in the status bar and never end the dashboard.
+///
+/// # Arguments
+/// * `client` - The client used to send the requests.
+/// * `refresh_interval` - How often the floor is refreshed.
+///
+/// # Returns
+/// * `io::Result<()>` - An error if the terminal could not be used.
+pub async fn run(client: &RestaurantClient, refresh_interval: Duration) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, client, refresh_interval).await;
+    ratatui::try_restore()?;
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    client: &RestaurantClient,
+    refresh_interval: Duration,
+) -> io::Result<()> {
+    let mut dashboard = Dashboard::new();
+    match client.get_menus().await {
+        Ok(menu) => dashboard.set_menu(menu.into_iter().map(|listing| listing.item).collect()),
+        Err(err) => dashboard.set_status(format!("Error retrieving menus: {}", err), true),
+    }
+    refresh(&mut dashboard, client).await;
+    let mut next_refresh = Instant::now() + refresh_interval;
+
+    loop {
+        terminal.draw(|frame| dashboard.render(frame))?;
+
+        if event::poll(INPUT_POLL_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match dashboard.handle_key(key) {
+                    Action::None => {}
+                    Action::Quit => return Ok(()),
+                    Action::Refresh => next_refresh = Instant::now(),
+                    Action::Add { table_id, item_id } => {
+                        match client.add_item(table_id, item_id).await {
+                            Ok(message) => dashboard.set_status(message, false),
+                            Err(err) => dashboard.set_status(err.to_string(), true),
+                        }
+                        next_refresh = Instant::now();
+                    }
+                    Action::Remove { table_id, item_id } => {
+                        match client.remove_item(table_id, item_id).await {
+                            Ok(message) => dashboard.set_status(message, false),
+                            Err(err) => dashboard.set_status(err.to_string(), true),
+                        }
+                        next_refresh = Instant::now();
+                    }
+                }
+            }
+        }
+
+        if Instant::now() >= next_refresh {
+            refresh(&mut dashboard, client).await;
+            next_refresh = Instant::now() + refresh_interval;
+        }
+    }
+}
+
+/// Reloads the floor, keeping the previous state if the server cannot be reached.
+async fn refresh(dashboard: &mut Dashboard, client: &RestaurantClient) {
+    match fetch_floor(client).await {
+        Ok(tables) => dashboard.set_floor(tables),
+        Err(err) => dashboard.set_status(format!("Refresh failed: {}", err), true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::Money;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn menu_item(id: u32, name: &str, cooking_time_minutes: u64) -> MenuItem {
+        MenuItem::new(id, name, cooking_time_minutes, Money::from_cents(1000))
+    }
+
+    fn ordered_item(id: u32, name: &str, cooking_time_minutes: u64) -> OrderedItem {
+        menu_item(id, name, cooking_time_minutes).into()
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn dashboard() -> Dashboard {
+        let mut dashboard = Dashboard::new();
+        dashboard.set_menu(vec![menu_item(1, "Salad", 5), menu_item(2, "Steak", 20)]);
+        dashboard.set_floor(
+            (1..=10)
+                .map(|table_id| TableSummary {
+                    table_id,
+                    items: if table_id == 2 {
+                        vec![
+                            ordered_item(2, "Steak", 20),
+                            OrderedItem {
+                                note: Some("No egg".to_string()),
+                                ..ordered_item(1, "Salad", 5)
+                            },
+                        ]
+                    } else {
+                        Vec::new()
+                    },
+                })
+                .collect(),
+        );
+        dashboard.columns = 4;
+        dashboard
+    }
+
+    #[test]
+    fn test_table_summary() {
+        let table = TableSummary {
+            table_id: 1,
+            items: vec![ordered_item(1, "Salad", 5), ordered_item(2, "Steak", 20)],
+        };
+        assert_eq!(table.item_count(), 2);
+        assert_eq!(table.longest_cooking_time(), Some(20));
+        let empty = TableSummary {
+            table_id: 2,
+            items: Vec::new(),
+        };
+        assert_eq!(empty.longest_cooking_time(), None);
+    }
+
+    #[test]
+    fn test_navigation_stays_in_bounds() {
+        let mut dashboard = dashboard();
+        dashboard.handle_key(key(KeyCode::Left));
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 1);
+        dashboard.handle_key(key(KeyCode::Down));
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 5);
+        dashboard.handle_key(key(KeyCode::Down));
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 9);
+        // There is no table below table 9 in a 4 column grid of 10 tables
+        dashboard.handle_key(key(KeyCode::Down));
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 9);
+        dashboard.handle_key(key(KeyCode::Right));
+        dashboard.handle_key(key(KeyCode::Right));
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 10);
+        dashboard.handle_key(key(KeyCode::Up));
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 6);
+    }
+
+    #[test]
+    fn test_add_item_from_popup() {
+        let mut dashboard = dashboard();
+        assert_eq!(dashboard.handle_key(key(KeyCode::Char('a'))), Action::None);
+        dashboard.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            dashboard.handle_key(key(KeyCode::Enter)),
+            Action::Add {
+                table_id: 1,
+                item_id: 2
+            }
+        );
+        // The popup is closed after confirming
+        assert_eq!(dashboard.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+    }
+
+    #[test]
+    fn test_remove_item_from_popup() {
+        let mut dashboard = dashboard();
+        dashboard.handle_key(key(KeyCode::Char('r')));
+        assert!(dashboard.popup.is_none());
+        assert!(dashboard.status.as_ref().unwrap().1);
+
+        dashboard.handle_key(key(KeyCode::Right));
+        dashboard.handle_key(key(KeyCode::Char('r')));
+        dashboard.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            dashboard.handle_key(key(KeyCode::Enter)),
+            Action::Remove {
+                table_id: 2,
+                item_id: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_popup_escape_and_ctrl_c() {
+        let mut dashboard = dashboard();
+        dashboard.handle_key(key(KeyCode::Char('a')));
+        assert_eq!(dashboard.handle_key(key(KeyCode::Esc)), Action::None);
+        assert!(dashboard.popup.is_none());
+        assert_eq!(
+            dashboard.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Quit
+        );
+        assert_eq!(
+            dashboard.handle_key(key(KeyCode::Char('f'))),
+            Action::Refresh
+        );
+    }
+
+    #[test]
+    fn test_set_floor_keeps_selected_table() {
+        let mut dashboard = dashboard();
+        dashboard.handle_key(key(KeyCode::Down));
+        dashboard.set_floor(
+            (4..=6)
+                .map(|table_id| TableSummary {
+                    table_id,
+                    items: Vec::new(),
+                })
+                .collect(),
+        );
+        assert_eq!(dashboard.selected_table().unwrap().table_id, 5);
+    }
+
+    #[test]
+    fn test_render_shows_floor_and_detail() {
+        let mut dashboard = dashboard();
+        dashboard.handle_key(key(KeyCode::Right));
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal.draw(|frame| dashboard.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("#2    2x  20m"));
+        assert!(screen.contains("Table 2"));
+        assert!(screen.contains("Steak"));
+        assert!(screen.contains("Salad \"No egg\""));
+        assert!(screen.contains("2 items, ready in 20 min"));
+    }
+}
@@ -1,4 +1,5 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+pub mod client;
 pub mod server;
@@ -1,38 +1,335 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
-use clap::Parser;
-use rand::prelude::SliceRandom;
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
-use reqwest::Client;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueEnum};
+use restaurant_menu_app::client::error::ClientError;
+use restaurant_menu_app::client::load_test::{run_load_test, LoadTestArgs};
+use restaurant_menu_app::client::output::{self, Output};
+use restaurant_menu_app::client::repl::{self, Repl};
+use restaurant_menu_app::client::reservation::ReservationArgs;
+use restaurant_menu_app::client::restaurant_client::RestaurantClient;
+use restaurant_menu_app::client::session::{self, ReplayArgs, SessionRecorder};
+use restaurant_menu_app::client::simulation::{run_simulation, ScenarioArgs};
+use restaurant_menu_app::client::split::SplitArgs;
+use restaurant_menu_app::client::tui;
 use restaurant_menu_app::server;
-use serde_json::Value;
-use std::collections::HashMap;
-use std::net::TcpListener;
-use std::sync::Arc;
+use restaurant_menu_app::server::config::{ServerArgs, ServerConfig};
+use restaurant_menu_app::server::data_model::dietary::{Allergen, Diet, MenuFilter};
+use restaurant_menu_app::server::data_model::modifier::ItemOptions;
+use restaurant_menu_app::server::data_model::money::Money;
+use restaurant_menu_app::server::data_model::payment::{PaymentMethod, PaymentRequest, TipRequest};
+use restaurant_menu_app::server::main::ServerShutdownHandle;
+use std::path::PathBuf;
+use std::sync::mpsc;
 use std::thread;
-use std::time::Duration;
-use tokio::sync::Mutex;
-use tokio::task;
-use tokio::time::sleep;
+use tokio::sync::watch;
 
 /// Command line argument parsing using `clap`
 #[derive(Parser)]
 struct Args {
-    /// Port number of the server
-    #[arg(short, long, default_value_t = 8081)]
-    port: u16,
+    /// URL of an already running server, e.g. https://backoffice:8443.
+    /// When given, no embedded server is started.
+    #[arg(long, conflicts_with = "ServerArgs")]
+    server_url: Option<String>,
+
+    /// Additional PEM CA certificate to trust, e.g. for self-signed test certificates
+    #[arg(long)]
+    ca_cert: Option<PathBuf>,
+
+    #[command(flatten)]
+    server: ServerArgs,
+
+    /// Output format used by the subcommands
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Print the JSON returned by the server instead of tables, also in the interactive client.
+    /// Same as `--format json` for the subcommands.
+    #[arg(long)]
+    raw: bool,
+
+    /// Record every API call with its response and timing to this session file
+    #[arg(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Start the full-screen floor dashboard instead of the interactive prompt
+    #[arg(long)]
+    tui: bool,
+
+    /// Seconds between two refreshes of the dashboard
+    #[arg(long, default_value_t = 2, requires = "tui", value_parser = clap::value_parser!(u64).range(1..))]
+    refresh_interval: u64,
+
+    /// Operation to run non-interactively. Without a subcommand the interactive menu is started.
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-#[derive(serde::Deserialize, Debug)]
-struct MenuItem {
-    id: u32,
-    name: String,
-    cooking_time_minutes: u32,
+/// Operations that can be run non-interactively, e.g. from shell scripts or cron jobs.
+#[derive(Subcommand)]
+enum Command {
+    /// List all available menu items, grouped by category
+    Menus {
+        /// List the menu items without grouping them by category
+        #[arg(long)]
+        flat: bool,
+        /// Leave out menu items containing this allergen, can be repeated
+        #[arg(long = "exclude-allergen", value_name = "ALLERGEN")]
+        exclude_allergens: Vec<Allergen>,
+        /// Only list menu items suitable for this diet, can be repeated
+        #[arg(long = "diet", value_name = "DIET")]
+        diets: Vec<Diet>,
+    },
+    /// List the categories of the menu
+    Categories,
+    /// Mark a category of the menu as served or not served
+    Category {
+        /// ID of the category
+        #[arg(long)]
+        id: u32,
+        /// Whether the category is served, true or false
+        #[arg(long, action = ArgAction::Set)]
+        available: bool,
+    },
+    /// List the stock of counted or sold out menu items
+    Stock,
+    /// Set the stock of a menu item, or mark it as sold out
+    SetStock {
+        /// ID of the menu item
+        #[arg(long)]
+        item: u32,
+        /// Portions in stock. Without it, the stock of the item is not counted
+        #[arg(long)]
+        remaining: Option<u32>,
+        /// Mark the menu item as sold out ("86" it)
+        #[arg(long)]
+        sold_out: bool,
+    },
+    /// List the ingredients in the inventory
+    Ingredients,
+    /// Add a delivered quantity of an ingredient to the inventory
+    Restock {
+        /// ID of the ingredient
+        #[arg(long)]
+        ingredient: u32,
+        /// Quantity delivered, in the unit of the ingredient
+        #[arg(long)]
+        quantity: u32,
+    },
+    /// Report the ingredients low on stock and the menu items that cannot be made
+    LowStock,
+    /// List all tables
+    Tables,
+    /// Add a menu item to a table
+    Add {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// ID of the menu item
+        #[arg(long)]
+        item: u32,
+        /// ID of a modifier to choose, can be repeated
+        #[arg(long = "modifier")]
+        modifiers: Vec<u32>,
+        /// Note for the kitchen, e.g. an allergy
+        #[arg(long)]
+        note: Option<String>,
+        /// Seat the item is ordered from, used to split the bill by seat
+        #[arg(long)]
+        seat: Option<u32>,
+        /// Add the item even if it contains allergies declared for the table
+        #[arg(long)]
+        allergen_override: bool,
+    },
+    /// Remove a menu item, or a specific order line, from a table
+    Remove {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// ID of the menu item; removes its first order line
+        #[arg(long, required_unless_present = "line", conflicts_with = "line")]
+        item: Option<u32>,
+        /// Number of the order line, as listed by `orders`
+        #[arg(long)]
+        line: Option<u32>,
+    },
+    /// List all menu items ordered for a table
+    Orders {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+    },
+    /// Show a specific menu item ordered for a table
+    Item {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// ID of the menu item
+        #[arg(long)]
+        item: u32,
+    },
+    /// Print the itemized bill of a table with subtotal, tax and total
+    Bill {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+    },
+    /// Split the bill of a table into checks, by item, by seat or evenly
+    Split {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        #[command(flatten)]
+        split: SplitArgs,
+    },
+    /// Show the checks the bill of a table was split into
+    Checks {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+    },
+    /// Record a payment towards the bill of a table, in cash or with --card
+    Pay {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// Amount paid towards the bill, e.g. 25.00
+        #[arg(long)]
+        amount: Money,
+        /// Tip paid on top of the amount, e.g. 3.00
+        #[arg(long)]
+        tip: Option<Money>,
+        /// Check of the split bill the payment is for
+        #[arg(long)]
+        check: Option<u32>,
+        /// Pay by card with this card number instead of cash
+        #[arg(long, value_name = "NUMBER")]
+        card: Option<String>,
+    },
+    /// Record a tip on its own, in cash or with --card
+    Tip {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// The tip, e.g. 3.00
+        #[arg(long)]
+        amount: Money,
+        /// Check of the split bill the tip is for
+        #[arg(long)]
+        check: Option<u32>,
+        /// Pay the tip by card with this card number instead of cash
+        #[arg(long, value_name = "NUMBER")]
+        card: Option<String>,
+    },
+    /// Show the payments and the balance of a table's bill
+    Payments {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+    },
+    /// Open a table for a party
+    Open {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// Number of guests
+        #[arg(long)]
+        party: u32,
+    },
+    /// Close a table whose bill is paid and archive its session
+    Close {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+    },
+    /// Show the active session of a table
+    Session {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+    },
+    /// Declare the allergies of the party at a table
+    Allergies {
+        /// ID of the table
+        #[arg(long)]
+        table: u32,
+        /// An allergy of the party, can be repeated. Without any, the allergies are cleared
+        #[arg(long = "allergen", value_name = "ALLERGEN")]
+        allergies: Vec<Allergen>,
+    },
+    /// Show closed sessions, of all tables or of one table
+    History {
+        /// Only sessions of this table
+        #[arg(long)]
+        table: Option<u32>,
+    },
+    /// List reservations, of all days or of one day
+    Reservations {
+        /// Only reservations starting on this day in UTC, e.g. 2026-10-18
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        date: Option<String>,
+    },
+    /// Reserve a table for a party
+    Reserve {
+        #[command(flatten)]
+        reservation: ReservationArgs,
+    },
+    /// Change the table, party, name or slot of a reservation
+    Reschedule {
+        /// ID of the reservation
+        #[arg(long = "reservation")]
+        reservation_id: u64,
+        #[command(flatten)]
+        reservation: ReservationArgs,
+    },
+    /// Cancel a reservation
+    CancelReservation {
+        /// ID of the reservation
+        #[arg(long = "reservation")]
+        reservation_id: u64,
+    },
+    /// Run the parallel add/remove simulation
+    Simulate {
+        #[command(flatten)]
+        scenario: ScenarioArgs,
+    },
+    /// Send a mix of requests for a fixed duration and report latencies and throughput
+    LoadTest {
+        #[command(flatten)]
+        load_test: LoadTestArgs,
+    },
+    /// Replay a recorded session and compare the responses with the recording
+    Replay {
+        #[command(flatten)]
+        replay: ReplayArgs,
+    },
 }
 
-/// The main function starts the server and enters the interactive options loop for the client.
+/// Output format of the subcommands.
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// The JSON body returned by the server
+    Json,
+    /// Aligned, human readable tables
+    Table,
+}
+
+/// Exit code when the command succeeded.
+const EXIT_SUCCESS: i32 = 0;
+/// Exit code when the server could not be reached or returned an unreadable response.
+const EXIT_REQUEST_FAILED: i32 = 1;
+/// Exit code when the command line arguments or the scenario file are invalid, as for clap usage errors.
+const EXIT_USAGE_ERROR: i32 = 2;
+/// Exit code when the server rejected the request (HTTP 4xx), e.g. an unknown table or menu item.
+const EXIT_CLIENT_ERROR: i32 = 3;
+/// Exit code when the server failed to process the request (HTTP 5xx).
+const EXIT_SERVER_ERROR: i32 = 4;
+/// Exit code when the orders on the server do not match the expected ones after a simulation,
+/// or when replayed responses differ from the recorded session.
+const EXIT_INCONSISTENT: i32 = 5;
+
+/// The main function starts the embedded server, unless `--server-url` points at a remote one,
+/// and enters the interactive options loop for the client.
 ///
 /// # Returns
 /// * `std::io::Result<()>` - Result indicating success or failure.
@@ -40,36 +337,152 @@ struct MenuItem {
 async fn main() -> std::io::Result<()> {
     // Parse command-line arguments
     let args = Args::parse();
+    if args.tui && args.command.is_some() {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--tui cannot be used with a subcommand",
+            )
+            .exit();
+    }
 
-    // Start the server in a separate thread
-    start_server_in_thread(args.port);
+    let (base_url, server) = match &args.server_url {
+        Some(server_url) => (server_url.trim_end_matches('/').to_string(), None),
+        None => {
+            let server_config = args.server.apply_to(ServerConfig::default())?;
+            let base_url = match server_config.tls {
+                Some(_) => format!("https://localhost:{}", server_config.port),
+                None => format!("http://127.0.0.1:{}", server_config.port),
+            };
+
+            // Start the server in a separate thread and wait until it is listening
+            let server = EmbeddedServer::start(server_config)?;
+
+            // Shut the server down gracefully when the client is interrupted or terminated
+            let signal_server = server.clone();
+            tokio::spawn(async move {
+                server::main::shutdown_signal().await;
+                println!("\nShutdown signal received, stopping the server...");
+                signal_server.shutdown().await;
+                std::process::exit(0);
+            });
+
+            (base_url, Some(server))
+        }
+    };
 
-    // Wait for the server to start
-    wait_for_server_start(args.port).await?;
+    let raw = args.raw || matches!(args.format, OutputFormat::Json);
+    output::init_colors();
 
-    // Display the introduction message
-    display_intro(args.port);
+    // Create the API client
+    let mut builder = RestaurantClient::builder(&base_url);
+    if let Some(ca_cert) = &args.ca_cert {
+        builder = builder.ca_certificate(ca_cert);
+    }
+    if let Some(path) = &args.record {
+        builder = builder.recorder(SessionRecorder::create(path)?);
+    }
+    // Retries would hide errors and inflate the latencies measured by the load test
+    if matches!(args.command, Some(Command::LoadTest { .. })) {
+        builder = builder.retries(0);
+    }
+    let client = builder.build().map_err(std::io::Error::other)?;
 
-    // Create an HTTP client
-    let client = Client::new();
-    let base_url = format!("http://127.0.0.1:{}", args.port);
+    // Run a single operation and exit with a code reflecting its outcome
+    if let Some(command) = args.command {
+        let exit_code = run_command(&client, command, raw).await;
+        if let Some(server) = server {
+            server.shutdown().await;
+        }
+        std::process::exit(exit_code);
+    }
+
+    // Show the floor dashboard until the user quits
+    if args.tui {
+        let refresh_interval = std::time::Duration::from_secs(args.refresh_interval);
+        if let Err(err) = tui::run(&client, refresh_interval).await {
+            println!("Could not start the dashboard: {}", err);
+        }
+        if let Some(server) = server {
+            server.shutdown().await;
+        }
+        return Ok(());
+    }
+
+    // Display the introduction message
+    display_intro(&base_url);
 
     // Enter the interactive loop
-    interactive_loop(&client, &base_url).await;
+    match Repl::new(client, repl::default_history_path()) {
+        Ok(repl) => repl.raw_output(raw).run().await,
+        Err(err) => println!("Could not start the interactive client: {}", err),
+    }
+
+    // Drain in-flight requests and flush the embedded server state before exiting
+    if let Some(server) = server {
+        server.shutdown().await;
+    }
 
     Ok(())
 }
 
-fn start_server_in_thread(port: u16) {
-    thread::spawn(move || {
-        let runtime = tokio::runtime::Runtime::new().unwrap();
-        runtime.block_on(async {
-            server::main::main(Some(port)).await.unwrap();
+/// The server running in a background thread of the client process.
+#[derive(Clone)]
+struct EmbeddedServer {
+    handle: ServerShutdownHandle,
+    /// Set to the server's final result once it has stopped and flushed its state.
+    finished: watch::Receiver<Option<Result<(), String>>>,
+}
+
+impl EmbeddedServer {
+    /// Starts the server in a separate thread and waits until it is listening.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration for the embedded server.
+    ///
+    /// # Returns
+    /// * `std::io::Result<EmbeddedServer>` - The running server, or the error that prevented it from starting.
+    fn start(config: ServerConfig) -> std::io::Result<Self> {
+        let (started_tx, started_rx) = mpsc::channel();
+        let (finished_tx, finished) = watch::channel(None);
+
+        thread::spawn(move || {
+            let handle_tx = started_tx.clone();
+            let result = tokio::runtime::Runtime::new().and_then(|runtime| {
+                runtime.block_on(server::main::run(config, move |handle| {
+                    let _ = handle_tx.send(Ok(handle));
+                }))
+            });
+            let result = result.map_err(|e| e.to_string());
+            // Only received if the server failed before it started listening
+            if let Err(err) = &result {
+                let _ = started_tx.send(Err(err.clone()));
+            }
+            let _ = finished_tx.send(Some(result));
         });
-    });
+
+        match started_rx.recv() {
+            Ok(Ok(handle)) => Ok(EmbeddedServer { handle, finished }),
+            Ok(Err(err)) => Err(std::io::Error::other(err)),
+            Err(_) => Err(std::io::Error::other("Server thread exited unexpectedly")),
+        }
+    }
+
+    /// Gracefully stops the server and waits until its state has been flushed.
+    async fn shutdown(&self) {
+        self.handle.stop().await;
+        let mut finished = self.finished.clone();
+        let error = match finished.wait_for(|result| result.is_some()).await {
+            Ok(result) => result.as_ref().and_then(|result| result.clone().err()),
+            Err(_) => None,
+        };
+        if let Some(err) = error {
+            println!("Server stopped with an error: {}", err);
+        }
+    }
 }
 
-fn display_intro(port: u16) {
+fn display_intro(base_url: &str) {
     println!("\n==================== Restaurant Management Client ====================\n");
     println!("Welcome to the Restaurant Management Client.");
     println!("This application allows you to interact with a virtual restaurant system,");
@@ -83,385 +496,283 @@ fn display_intro(port: u16) {
         "For API documentation, including the OpenAPI specification and Swagger UI, please visit:"
     );
     println!(
-        "Swagger UI: http://127.0.0.1:{}/swagger-ui/\nOpenAPI JSON: http://127.0.0.1:{}/api-doc/openapi.json\n",
-        port, port
+        "Swagger UI: {}/swagger-ui/\nOpenAPI JSON: {}/api-doc/openapi.json\n",
+        base_url, base_url
     );
     println!("=======================================================================\n");
 }
 
-async fn interactive_loop(client: &Client, base_url: &str) {
-    loop {
-        display_menu_options();
-
-        let input = read_user_input().trim().to_string();
-
-        match input.as_str() {
-            "1" => get_menus(client, base_url).await,
-            "2" => get_tables(client, base_url).await,
-            "3" => add_menu_item(client, base_url).await,
-            "4" => remove_menu_item(client, base_url).await,
-            "5" => get_table_orders(client, base_url).await,
-            "6" => get_specific_menu_item(client, base_url).await,
-            "7" => run_simulation(client, base_url).await,
-            "8" => {
-                println!("Exiting the application. Goodbye!");
-                break;
-            }
-            _ => println!("Invalid option. Please try again."),
-        }
-    }
-}
-
-fn display_menu_options() {
-    println!(
-        "\nPlease select an operation:\n\
-         1. Retrieve Available Menus \n\
-         2. Get Active Tables \n\
-         3. Add a Menu Item to a Table \n\
-         4. Remove a Menu Item from a Table \n\
-         5. Get All Orders for a Table\n\
-         6. Get Specific Menu Item Ordered for a Table\n\
-         7. Run Simulation (Parallel add/remove menu items for upto 100 tables)\n\
-         8. Exit"
-    );
-}
-
-fn read_user_input() -> String {
-    let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read input");
-    input
-}
-
-async fn get_menus(client: &Client, base_url: &str) {
-    let url = format!("{}/api/v1/menus", base_url);
-    let response = client.get(&url).send().await;
-
-    match response {
-        Ok(res) => println!("Menus: {:?}", res.text().await.unwrap()),
-        Err(err) => println!("Error retrieving menus: {}", err),
-    }
-}
-
-async fn get_tables(client: &Client, base_url: &str) {
-    let url = format!("{}/api/v1/tables", base_url);
-    let response = client.get(&url).send().await;
-
-    match response {
-        Ok(res) => println!("Tables: {:?}", res.text().await.unwrap()),
-        Err(err) => println!("Error retrieving tables: {}", err),
-    }
-}
-
-async fn add_menu_item(client: &Client, base_url: &str) {
-    let (table_id, menu_item_id) = get_table_and_menu_ids();
-
-    let url = format!("{}/api/v1/add_item/{}/{}", base_url, table_id, menu_item_id);
-    let response = client.post(&url).send().await;
-
-    match response {
-        Ok(res) => println!(
-            "Menu item added successfully: {:?}",
-            res.text().await.unwrap()
-        ),
-        Err(err) => println!("Error adding menu item: {}", err),
-    }
-}
-
-async fn remove_menu_item(client: &Client, base_url: &str) {
-    let (table_id, menu_item_id) = get_table_and_menu_ids();
-
-    let url = format!(
-        "{}/api/v1/remove_item/{}/{}",
-        base_url, table_id, menu_item_id
-    );
-    let response = client.delete(&url).send().await;
-
-    match response {
-        Ok(res) => println!(
-            "Menu item removed successfully: {:?}",
-            res.text().await.unwrap()
-        ),
-        Err(err) => println!("Error removing menu item: {}", err),
-    }
-}
-
-async fn get_table_orders(client: &Client, base_url: &str) {
-    let table_id = get_table_id();
-    let url = format!("{}/api/v1/get_items/{}", base_url, table_id);
-
-    let response = client.get(&url).send().await;
-    match response {
-        Ok(res) => println!(
-            "Orders for table {}: {:?}",
-            table_id,
-            res.text().await.unwrap()
-        ),
-        Err(err) => println!("Error retrieving orders: {}", err),
+/// Returns the payment method of a `pay` or `tip` subcommand: card when a card number is given, cash otherwise.
+fn payment_method(card: &Option<String>) -> PaymentMethod {
+    if card.is_some() {
+        PaymentMethod::Card
+    } else {
+        PaymentMethod::Cash
     }
 }
 
-async fn get_specific_menu_item(client: &Client, base_url: &str) {
-    let (table_id, menu_item_id) = get_table_and_menu_ids();
-
-    let url = format!("{}/api/v1/get_item/{}/{}", base_url, table_id, menu_item_id);
-    let response = client.get(&url).send().await;
-
-    match response {
-        Ok(res) => println!(
-            "Details of menu item {} for table {}: {:?}",
-            menu_item_id,
-            table_id,
-            res.text().await.unwrap()
-        ),
-        Err(err) => println!("Error retrieving menu item details: {}", err),
-    }
-}
-
-async fn run_simulation(client: &reqwest::Client, base_url: &str) {
-    // Ask the user for the number of tables to simulate
-    println!("Enter the number of tables for the simulation (max 100, default 10): ");
-    let input = read_user_input().trim().to_string();
-
-    // Parse input and ensure it's within the allowed range
-    let num_tables: usize = input.trim().parse().unwrap_or(10);
-    if num_tables > 100 {
-        println!("Error: The maximum number of tables allowed for simulation is 100.");
-        return;
-    }
-
-    println!("\n========== Starting Simulation ==========");
-    println!(
-        "1. Select Tables for Simulation: A random selection of {} tables is performed.",
-        num_tables
-    );
-    println!("2. Simultaneous Add and Remove Operations: Menu items are added parallel and then removed in parallel, ensuring that only items that were added are removed.");
-    println!("3. Retain Some Items After Simulation: Some items are randomly selected to remain on the table.");
-    println!("4. Final Status Printing: The final status of each table is printed in parrellel.\n");
-    println!("==========================================\n");
-
-    let num_tables: usize = input.trim().parse().unwrap_or(10);
-    let num_tables = num_tables.min(100); // Ensure max 100
-
-    let tables_response = client
-        .get(&format!("{}/api/v1/tables", base_url))
-        .send()
-        .await
-        .unwrap();
-    let json_response: Value =
-        serde_json::from_str(&tables_response.text().await.unwrap()).unwrap();
-    let table_ids: Vec<u32> = serde_json::from_value(json_response["data"].clone()).unwrap();
-
-    let menus_response = client
-        .get(&format!("{}/api/v1/menus", base_url))
-        .send()
-        .await
-        .unwrap();
-
-    let json_response: Value = serde_json::from_str(&menus_response.text().await.unwrap()).unwrap();
-    let menus: Vec<MenuItem> = serde_json::from_value(json_response["data"].clone()).unwrap();
-    let menu_ids: Vec<u32> = menus.iter().map(|menu| menu.id).collect();
-
-    let mut rng = StdRng::from_entropy();
-    // Select the user-defined number of random tables for simulation
-    let selected_tables: Vec<u32> = table_ids
-        .choose_multiple(&mut rng, num_tables)
-        .cloned()
-        .collect();
-    let table_items: Arc<Mutex<HashMap<u32, Vec<u32>>>> = Arc::new(Mutex::new(HashMap::new()));
-
-    let mut add_handles = Vec::new();
-    for &table_id in &selected_tables {
-        let client_clone = client.clone();
-        let base_url_clone = base_url.to_string();
-        let table_items_clone = Arc::clone(&table_items);
-        let menu_ids_clone = menu_ids.clone();
-
-        let add_handle = task::spawn(async move {
-            let mut rng = StdRng::from_entropy();
-            let menu_items_to_add: Vec<u32> = menu_ids_clone
-                .choose_multiple(&mut rng, 3)
-                .cloned()
-                .collect();
-
-            {
-                let mut table_items_lock = table_items_clone.lock().await;
-                table_items_lock
-                    .entry(table_id)
-                    .or_default()
-                    .extend(menu_items_to_add.clone());
+/// Runs a single non-interactive operation and prints its result in the requested format.
+///
+/// # Returns
+/// * `i32` - The process exit code reflecting the outcome of the operation.
+async fn run_command(client: &RestaurantClient, command: Command, raw: bool) -> i32 {
+    let result = match command {
+        Command::Menus {
+            flat,
+            exclude_allergens,
+            diets,
+        } => {
+            let filter = MenuFilter {
+                exclude_allergens,
+                diets,
+            };
+            if flat {
+                client.get_filtered_menus(&filter).await.map(Output::Menu)
+            } else {
+                client
+                    .get_filtered_categorized_menu(&filter)
+                    .await
+                    .map(Output::CategorizedMenu)
             }
-
-            for &menu_item_id in &menu_items_to_add {
-                let url_add = format!(
-                    "{}/api/v1/add_item/{}/{}",
-                    base_url_clone, table_id, menu_item_id
-                );
-                println!("Ordering menu item {} for table {}", menu_item_id, table_id);
-                let response = client_clone.post(&url_add).send().await;
-                match response {
-                    Ok(res) => {
-                        if res.status().is_success() {
-                            println!(
-                                "Successfully ordered menu item {} for table {}",
-                                menu_item_id, table_id
-                            );
-                        } else {
-                            println!(
-                                "Failed to order menu item {} for table {}: {}",
-                                menu_item_id,
-                                table_id,
-                                res.status()
-                            );
-                        }
-                    }
-                    Err(err) => {
-                        println!(
-                            "Error occurred while ordering menu item {} for table {}: {}",
-                            menu_item_id, table_id, err
-                        );
-                    }
+        }
+        Command::Categories => client.get_categories().await.map(Output::Categories),
+        Command::Category { id, available } => client
+            .set_category_availability(id, available)
+            .await
+            .map(Output::Category),
+        Command::Stock => client.get_stock().await.map(Output::Stock),
+        Command::SetStock {
+            item,
+            remaining,
+            sold_out,
+        } => client
+            .set_stock(item, remaining, sold_out)
+            .await
+            .map(Output::StockLevel),
+        Command::Ingredients => client.get_ingredients().await.map(Output::Ingredients),
+        Command::Restock {
+            ingredient,
+            quantity,
+        } => client
+            .restock(ingredient, quantity)
+            .await
+            .map(Output::Ingredient),
+        Command::LowStock => client.get_low_stock_report().await.map(Output::LowStock),
+        Command::Tables => client.get_table_statuses().await.map(Output::Tables),
+        Command::Add {
+            table,
+            item,
+            modifiers,
+            note,
+            seat,
+            allergen_override,
+        } => {
+            let options = ItemOptions {
+                modifiers,
+                note,
+                seat,
+                allergen_override,
+            };
+            client
+                .add_item_with_options(table, item, &options)
+                .await
+                .map(Output::Message)
+        }
+        Command::Remove { table, item, line } => match (item, line) {
+            (Some(item), _) => client.remove_item(table, item).await,
+            (None, Some(line)) => client.remove_line(table, line).await,
+            (None, None) => Err(ClientError::Config(
+                "Either --item or --line is required".to_string(),
+            )),
+        }
+        .map(Output::Message),
+        Command::Orders { table } => client.get_items(table).await.map(|items| Output::Orders {
+            table_id: table,
+            items,
+        }),
+        Command::Item { table, item } => client.get_item(table, item).await.map(Output::Item),
+        Command::Bill { table } => client.get_bill(table).await.map(Output::Bill),
+        Command::Split { table, split } => client
+            .split_bill(table, &split.request())
+            .await
+            .map(Output::Checks),
+        Command::Checks { table } => client.get_checks(table).await.map(Output::Checks),
+        Command::Pay {
+            table,
+            amount,
+            tip,
+            check,
+            card,
+        } => {
+            let request = PaymentRequest {
+                method: payment_method(&card),
+                amount,
+                tip: tip.unwrap_or_default(),
+                check_id: check,
+                card_number: card,
+            };
+            client.pay(table, &request).await.map(Output::Receipt)
+        }
+        Command::Tip {
+            table,
+            amount,
+            check,
+            card,
+        } => {
+            let request = TipRequest {
+                method: payment_method(&card),
+                tip: amount,
+                check_id: check,
+                card_number: card,
+            };
+            client.tip(table, &request).await.map(Output::Receipt)
+        }
+        Command::Payments { table } => client.get_payments(table).await.map(Output::Account),
+        Command::Open { table, party } => {
+            client.open_table(table, party).await.map(Output::Session)
+        }
+        Command::Close { table } => client.close_table(table).await.map(Output::Session),
+        Command::Session { table } => client.get_session(table).await.map(Output::Session),
+        Command::Allergies { table, allergies } => client
+            .declare_allergies(table, &allergies)
+            .await
+            .map(Output::Session),
+        Command::History { table } => client.get_sessions(table).await.map(Output::Sessions),
+        Command::Reservations { date } => client
+            .get_reservations(date.as_deref())
+            .await
+            .map(Output::Reservations),
+        Command::Reserve { reservation } => client
+            .make_reservation(&reservation.request())
+            .await
+            .map(Output::Reservation),
+        Command::Reschedule {
+            reservation_id,
+            reservation,
+        } => client
+            .update_reservation(reservation_id, &reservation.request())
+            .await
+            .map(Output::Reservation),
+        Command::CancelReservation { reservation_id } => client
+            .cancel_reservation(reservation_id)
+            .await
+            .map(Output::Reservation),
+        Command::Simulate { scenario } => {
+            let scenario = match scenario.load() {
+                Ok(scenario) => scenario,
+                Err(err) => {
+                    eprintln!("{}", output::error(&err.to_string()));
+                    return EXIT_USAGE_ERROR;
                 }
-            }
-        });
-        add_handles.push(add_handle);
-    }
-
-    for handle in add_handles {
-        handle.await.unwrap();
-    }
-
-    let mut remove_handles = Vec::new();
-    for &table_id in &selected_tables {
-        let client_clone = client.clone();
-        let base_url_clone = base_url.to_string();
-        let table_items_clone = Arc::clone(&table_items);
-
-        let remove_handle = task::spawn(async move {
-            let items_to_remove: Vec<u32>;
-            {
-                let table_items_lock = table_items_clone.lock().await;
-                if let Some(items) = table_items_lock.get(&table_id) {
-                    items_to_remove = items
-                        .choose_multiple(
-                            &mut StdRng::from_entropy(),
-                            rand::thread_rng().gen_range(0..items.len()),
-                        )
-                        .cloned()
-                        .collect();
-                } else {
-                    return;
+            };
+            let report = run_simulation(client, &scenario).await;
+            return if !report.is_consistent() {
+                EXIT_INCONSISTENT
+            } else if report.failures > 0 {
+                EXIT_REQUEST_FAILED
+            } else {
+                EXIT_SUCCESS
+            };
+        }
+        Command::LoadTest { load_test: args } => {
+            let load_test = match args.load() {
+                Ok(load_test) => load_test,
+                Err(err) => {
+                    eprintln!("{}", output::error(&err));
+                    return EXIT_USAGE_ERROR;
                 }
+            };
+            eprintln!(
+                "Running load test against {} for {} s with {} workers...",
+                client.base_url(),
+                load_test.duration.as_secs(),
+                load_test.concurrency
+            );
+            let report = match run_load_test(client, &load_test).await {
+                Ok(report) => report,
+                Err(err) => {
+                    eprintln!("{}", output::error(&err));
+                    return EXIT_REQUEST_FAILED;
+                }
+            };
+            if raw {
+                println!("{}", report.to_json());
+            } else {
+                println!("{}", report.to_table());
             }
-
-            for &menu_item_id in &items_to_remove {
-                let url_remove = format!(
-                    "{}/api/v1/remove_item/{}/{}",
-                    base_url_clone, table_id, menu_item_id
-                );
-                println!(
-                    "Removing menu item {} from table {}",
-                    menu_item_id, table_id
-                );
-                let response = client_clone.delete(&url_remove).send().await;
-
-                match response {
-                    Ok(res) => {
-                        if res.status().is_success() {
-                            println!(
-                                "Successfully removed menu item {} from table {}",
-                                menu_item_id, table_id
-                            );
-                        } else {
-                            println!(
-                                "Failed to remove menu item {} from table {}: {}",
-                                menu_item_id,
-                                table_id,
-                                res.status()
-                            );
-                        }
-                    }
-                    Err(err) => {
-                        println!(
-                            "Error occurred while removing menu item {} from table {}: {}",
-                            menu_item_id, table_id, err
+            let files = [(&args.csv, report.to_csv()), (&args.json, report.to_json())];
+            for (path, contents) in files {
+                if let Some(path) = path {
+                    if let Err(err) = std::fs::write(path, contents) {
+                        eprintln!(
+                            "{}",
+                            output::error(&format!("Could not write {}: {}", path.display(), err))
                         );
+                        return EXIT_REQUEST_FAILED;
                     }
                 }
             }
-        });
-        remove_handles.push(remove_handle);
-    }
-
-    for handle in remove_handles {
-        handle.await.unwrap();
-    }
-
-    println!("\n========== Final Table Status ==========");
-    let mut status_handles = Vec::new();
-    for &table_id in &selected_tables {
-        let client_clone = client.clone();
-        let base_url_clone = base_url.to_string();
-
-        let handle = tokio::spawn(async move {
-            let url_get_items = format!("{}/api/v1/get_items/{}", base_url_clone, table_id);
-            let response = client_clone.get(&url_get_items).send().await.unwrap();
-            let json_response: Value =
-                serde_json::from_str(&response.text().await.unwrap()).unwrap();
-            let menu_items: Vec<MenuItem> =
-                serde_json::from_value(json_response["data"].clone()).unwrap();
-
-            for item in menu_items {
-                println!(
-                    "For Table: {}  Menu Item ID: {}, Name: {}, Cooking Time: {} minutes",
-                    table_id, item.id, item.name, item.cooking_time_minutes
-                );
-            }
-        });
-
-        status_handles.push(handle);
-    }
+            return EXIT_SUCCESS;
+        }
+        Command::Replay { replay } => {
+            let (options, calls) = match replay.options().and_then(|options| {
+                session::load_session(&replay.session)
+                    .map(|calls| (options, calls))
+                    .map_err(|err| err.to_string())
+            }) {
+                Ok(result) => result,
+                Err(err) => {
+                    eprintln!("{}", output::error(&err));
+                    return EXIT_USAGE_ERROR;
+                }
+            };
+            eprintln!(
+                "Replaying {} calls against {}...",
+                calls.len(),
+                client.base_url()
+            );
+            let report = session::replay_session(client, &calls, &options).await;
+            let summary = format!(
+                "{} of {} responses match the recording (request time {} ms recorded, {} ms replayed).",
+                report.calls - report.mismatches.len(),
+                report.calls,
+                report.recorded_time.as_millis(),
+                report.replayed_time.as_millis()
+            );
+            return if report.is_consistent() {
+                println!("{}", output::success(&summary));
+                EXIT_SUCCESS
+            } else {
+                println!("{}", output::error(&summary));
+                EXIT_INCONSISTENT
+            };
+        }
+    };
 
-    // Wait for all status checks to complete
-    for handle in status_handles {
-        handle.await.unwrap();
+    match result {
+        Ok(result) => {
+            println!("{}", result.render(raw));
+            EXIT_SUCCESS
+        }
+        // Error responses are part of the JSON view, so that scripts can parse them
+        Err(err @ ClientError::Api { .. }) if raw => {
+            println!("{}", output::render_error("Error", &err, raw));
+            err.status()
+                .map_or(EXIT_REQUEST_FAILED, exit_code_for_status)
+        }
+        Err(err) => {
+            eprintln!("{}", output::error(&err.to_string()));
+            err.status()
+                .map_or(EXIT_REQUEST_FAILED, exit_code_for_status)
+        }
     }
-
-    println!("=========================================\n");
-    println!("Simulation complete.");
-}
-
-fn get_table_and_menu_ids() -> (u32, u32) {
-    println!("Enter table number(positive interger):");
-    let table_id = read_user_input().trim().parse().unwrap();
-
-    println!("Enter menu item number(positive interger):");
-    let menu_item_id = read_user_input().trim().parse().unwrap();
-
-    (table_id, menu_item_id)
-}
-
-fn get_table_id() -> u32 {
-    println!("Enter table number(positive interger):");
-    read_user_input().trim().parse().unwrap()
 }
 
-async fn wait_for_server_start(port: u16) -> std::io::Result<()> {
-    let addr = format!("127.0.0.1:{}", port);
-    let mut retries = 10;
-
-    while retries > 0 {
-        if TcpListener::bind(&addr).is_ok() {
-            // Server is ready
-            return Ok(());
-        }
-        retries -= 1;
-        sleep(Duration::from_secs(1)).await;
+/// Maps the HTTP status of an error response to the process exit code.
+fn exit_code_for_status(status: u16) -> i32 {
+    if status >= 500 {
+        EXIT_SERVER_ERROR
+    } else {
+        EXIT_CLIENT_ERROR
     }
-
-    Err(std::io::Error::new(
-        std::io::ErrorKind::AddrNotAvailable,
-        "Server failed to start",
-    ))
 }
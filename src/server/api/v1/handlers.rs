@@ -3,16 +3,33 @@
 
 #[allow(unused_imports)]
 use crate::server::api::v1::openapi::{
-    ErrorResponse, SuccessResponseMenuItem, SuccessResponseMenuItems, SuccessResponseMessage,
-    SuccessResponseTables,
+    ErrorResponse, SuccessResponseBill, SuccessResponseCategories, SuccessResponseCategorizedMenu,
+    SuccessResponseCategory, SuccessResponseIngredient, SuccessResponseIngredients,
+    SuccessResponseLowStockReport, SuccessResponseMenuItems, SuccessResponseMessage,
+    SuccessResponseOrderedItem, SuccessResponseOrderedItems, SuccessResponsePaymentReceipt,
+    SuccessResponseReservation, SuccessResponseReservations, SuccessResponseSplitBill,
+    SuccessResponseStockLevel, SuccessResponseStockLevels, SuccessResponseTableAccount,
+    SuccessResponseTableSession, SuccessResponseTableSessions, SuccessResponseTables,
 };
+use crate::server::data_model::dietary::{self, AllergiesRequest, MenuFilter};
+use crate::server::data_model::inventory::RestockRequest;
+use crate::server::data_model::menu::CategoryAvailabilityRequest;
 use crate::server::data_model::models::Restaurant;
-use crate::server::utils::param_validation::parse_path_param;
+use crate::server::data_model::modifier::ItemOptions;
+use crate::server::data_model::payment::{PaymentRequest, TipRequest};
+use crate::server::data_model::reservation::{self, ReservationRequest};
+use crate::server::data_model::session::OpenTableRequest;
+use crate::server::data_model::split::SplitRequest;
+use crate::server::data_model::stock::StockRequest;
+use crate::server::utils::error::RestaurantError;
+use crate::server::utils::param_validation::{parse_path_param, parse_u64_path_param};
 use crate::server::utils::response::restaurant_error_to_response;
 use crate::server::utils::response::{error_response, success_message_response, success_response};
 
 use actix_web::{web, Responder};
+use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::IntoParams;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -21,190 +38,1120 @@ pub struct AppState {
 
 /// Adds a menu item to the specified table.
 ///
+/// The request body is optional. It may choose modifiers of the menu item and add a note
+/// for the kitchen, e.g. `{"modifiers": [3, 5], "note": "no salt"}`.
+///
+/// Menu items containing allergies declared for the table are rejected unless the body sets
+/// `"allergen_override": true`; the confirmation then carries a warning.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `params` - Path parameters containing the table ID and menu item ID.
+/// * `body` - Optional JSON body with the chosen modifiers, the note and the allergen override.
+///
+/// # Responses
+///
+/// * `200` - Menu item added successfully.
+/// * `404` - Table or menu item not found.
+/// * `400` - Bad request, e.g. modifiers that do not match the menu item or a seat beyond the party.
+/// * `409` - The menu item contains allergies declared for the table, or is not available.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/add_item/{table_id}/{menu_item_id}",
+    request_body(content = ItemOptions, description = "Modifiers, kitchen note, seat and allergen override, optional"),
+    responses(
+        (status = 200, description = "Menu item added successfully", body = SuccessResponseMessage),
+        (status = 404, description = "Table or menu item not found", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 409, description = "Menu item contains declared allergies or is not available", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table"),
+        ("menu_item_id" = u32, description = "ID of the menu item")
+    )
+)]
+pub async fn add_item(
+    data: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    body: web::Bytes,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&params.0, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+
+    let item_id = match parse_path_param(&params.1, "item ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    let options: ItemOptions = if body.iter().all(u8::is_ascii_whitespace) {
+        ItemOptions::default()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(options) => options,
+            Err(e) => return error_response(400, &format!("Invalid request body: {}", e)),
+        }
+    };
+    match restaurant.add_item_with_options(table_id, item_id, &options) {
+        Ok(overridden) if !overridden.is_empty() => success_message_response(&format!(
+            "Menu item with item id: {} added successfully for table with table id {}. Warning: it contains {}, declared as allergies of the table",
+            item_id,
+            table_id,
+            dietary::join(&overridden)
+        )),
+        Ok(_) => success_message_response(&format!(
+            "Menu item with item id: {} added successfully for table with table id {}",
+            item_id, table_id
+        )),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Removes a menu item from the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `params` - Path parameters containing the table ID and menu item ID.
+///
+/// # Responses
+///
+/// * `200` - Menu item removed successfully.
+/// * `404` - Table or menu item not found.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/remove_item/{table_id}/{menu_item_id}",
+    responses(
+        (status = 200, description = "Menu item removed successfully", body = SuccessResponseMessage),
+        (status = 404, description = "Table or menu item not found", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table"),
+        ("menu_item_id" = u32, description = "ID of the menu item to remove")
+    )
+)]
+pub async fn remove_item(
+    data: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+) -> impl Responder {
+    let table_id = match parse_path_param(&params.0, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+
+    let item_id = match parse_path_param(&params.1, "item ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    let restaurant = &data.restaurant;
+    match restaurant.remove_item(table_id, item_id) {
+        Ok(_) => success_message_response(&format!(
+            "Menu item with item id:{} removed from table with table id:{} successfully",
+            item_id, table_id
+        )),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Removes a line from the order of the specified table by its number.
+///
+/// Unlike `remove_item`, which removes the first line of a menu item, this removes
+/// exactly the line with the number listed by `get_items`, with its modifiers, note and seat.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `params` - Path parameters containing the table ID and line number.
+///
+/// # Responses
+///
+/// * `200` - Order line removed successfully.
+/// * `404` - Table or order line not found.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/remove_line/{table_id}/{line_id}",
+    responses(
+        (status = 200, description = "Order line removed successfully", body = SuccessResponseMessage),
+        (status = 404, description = "Table or order line not found", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table"),
+        ("line_id" = u32, description = "Number of the order line to remove")
+    )
+)]
+pub async fn remove_line(
+    data: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+) -> impl Responder {
+    let table_id = match parse_path_param(&params.0, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+
+    let line_id = match parse_path_param(&params.1, "line ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    let restaurant = &data.restaurant;
+    match restaurant.remove_line(table_id, line_id) {
+        Ok(_) => success_message_response(&format!(
+            "Order line {} removed from table with table id:{} successfully",
+            line_id, table_id
+        )),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves all menu items added to the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+///
+/// # Responses
+///
+/// * `200` - List of menu items added for the table.
+/// * `404` - Table not found or no menu items added to the table.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/get_items/{table_id}",
+    responses(
+        (status = 200, description = "List of menu items added for the table", body = SuccessResponseOrderedItems),
+        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn get_items(data: web::Data<AppState>, table_id: web::Path<String>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.get_items(table_id) {
+        Ok(items) => success_response(items),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves details of a specific menu item added to the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `params` - Path parameters containing the table ID and menu item ID.
+///
+/// # Responses
+///
+/// * `200` - Menu item details.
+/// * `404` - Table or menu item not found or the menu item not added to the table.
+/// * `400` - Bad Request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/get_item/{table_id}/{menu_item_id}",
+    responses(
+        (status = 200, description = "Menu item details", body = SuccessResponseOrderedItem),
+        (status = 404, description = "Table or menu item not found or menu item not added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table"),
+        ("menu_item_id" = u32, description = "ID of the menu item")
+    )
+)]
+pub async fn get_item(
+    data: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&params.0, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+
+    let item_id = match parse_path_param(&params.1, "item ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.get_item(table_id, item_id) {
+        Ok(item) => success_response(item),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves the itemized bill of the specified table, built from its current order.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+///
+/// # Responses
+///
+/// * `200` - The bill with its lines, subtotal, tax and total.
+/// * `404` - Table not found or no menu items added to the table.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/bill/{table_id}",
+    responses(
+        (status = 200, description = "The bill of the table", body = SuccessResponseBill),
+        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn get_bill(data: web::Data<AppState>, table_id: web::Path<String>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.get_bill(table_id) {
+        Ok(bill) => success_response(bill),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Splits the bill of the specified table into checks.
+///
+/// The checks replace any previous split of the table. Their amounts always add up
+/// exactly to the table's bill.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+/// * `request` - JSON body describing how the bill is divided.
+///
+/// # Responses
+///
+/// * `200` - The bill and its checks.
+/// * `404` - Table not found or no menu items added to the table.
+/// * `400` - Bad request, or the split does not match the table's order.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/split/{table_id}",
+    request_body = SplitRequest,
+    responses(
+        (status = 200, description = "The bill and its checks", body = SuccessResponseSplitBill),
+        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request or invalid split", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn split_bill(
+    data: web::Data<AppState>,
+    table_id: web::Path<String>,
+    request: web::Json<SplitRequest>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.split_bill(table_id, &request) {
+        Ok(split) => success_response(split),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves the checks the bill of the specified table was split into.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+///
+/// # Responses
+///
+/// * `200` - The bill and its checks.
+/// * `404` - Table not found, or the bill of the table is not split.
+/// * `409` - The order of the table changed after the split.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/checks/{table_id}",
+    responses(
+        (status = 200, description = "The bill and its checks", body = SuccessResponseSplitBill),
+        (status = 404, description = "Table not found or bill not split", body = ErrorResponse),
+        (status = 409, description = "Order changed after the split", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn get_checks(data: web::Data<AppState>, table_id: web::Path<String>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.get_checks(table_id) {
+        Ok(split) => success_response(split),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Records a payment, with an optional tip, towards the bill of the specified table.
+///
+/// When the payment settles the bill, the table's order is cleared and the table is free.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+/// * `request` - JSON body with the payment method, amount, tip and card.
+///
+/// # Responses
+///
+/// * `200` - The recorded payment and the account of the bill.
+/// * `404` - Table not found or no menu items added to the table.
+/// * `400` - Bad request or invalid payment.
+/// * `402` - The payment was declined.
+/// * `409` - The amount exceeds the balance of the table or check.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/pay/{table_id}",
+    request_body = PaymentRequest,
+    responses(
+        (status = 200, description = "The recorded payment and the account of the bill", body = SuccessResponsePaymentReceipt),
+        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request or invalid payment", body = ErrorResponse),
+        (status = 402, description = "Payment declined", body = ErrorResponse),
+        (status = 409, description = "Amount exceeds the balance", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn pay(
+    data: web::Data<AppState>,
+    table_id: web::Path<String>,
+    request: web::Json<PaymentRequest>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.pay(table_id, &request) {
+        Ok(receipt) => success_response(receipt),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Records a tip on its own against the bill of the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+/// * `request` - JSON body with the payment method, tip and card.
+///
+/// # Responses
+///
+/// * `200` - The recorded tip and the account of the bill.
+/// * `404` - Table not found or no menu items added to the table.
+/// * `400` - Bad request or invalid tip.
+/// * `402` - The tip was declined.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tip/{table_id}",
+    request_body = TipRequest,
+    responses(
+        (status = 200, description = "The recorded tip and the account of the bill", body = SuccessResponsePaymentReceipt),
+        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request or invalid tip", body = ErrorResponse),
+        (status = 402, description = "Tip declined", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn tip(
+    data: web::Data<AppState>,
+    table_id: web::Path<String>,
+    request: web::Json<TipRequest>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.pay(table_id, &request.into_inner().into()) {
+        Ok(receipt) => success_response(receipt),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves the payments and the balance of the bill of the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+///
+/// # Responses
+///
+/// * `200` - The account of the table's bill.
+/// * `404` - Table not found or no menu items added to the table.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/{table_id}",
+    responses(
+        (status = 200, description = "The account of the table's bill", body = SuccessResponseTableAccount),
+        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn get_payments(
+    data: web::Data<AppState>,
+    table_id: web::Path<String>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.get_account(table_id) {
+        Ok(account) => success_response(account),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Query parameters of the session history.
+///
+/// - `table_id`: Only sessions of this table if set, of all tables otherwise.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SessionHistoryQuery {
+    /// Only sessions of this table.
+    pub table_id: Option<u32>,
+}
+
+/// Query parameters of the menu endpoints.
+///
+/// - `exclude_allergens`: Comma separated allergens, e.g. `nuts,dairy`. Menu items containing
+///   any of them are left out.
+/// - `diet`: Comma separated diets, e.g. `vegan`. Only menu items suitable for all of them are kept.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MenuQuery {
+    /// Allergens to leave out, comma separated.
+    pub exclude_allergens: Option<String>,
+    /// Diets the menu items must be suitable for, comma separated.
+    pub diet: Option<String>,
+}
+
+impl MenuQuery {
+    /// Parses the query into a filter of the menu.
+    fn filter(&self) -> Result<MenuFilter, RestaurantError> {
+        MenuFilter::parse(self.exclude_allergens.as_deref(), self.diet.as_deref())
+    }
+}
+
+/// Opens the specified table for a party.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+/// * `request` - JSON body with the party size.
+///
+/// # Responses
+///
+/// * `200` - The new session of the table.
+/// * `404` - Table not found.
+/// * `400` - Bad request or invalid party size.
+/// * `409` - The table is already open.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/open_table/{table_id}",
+    request_body = OpenTableRequest,
+    responses(
+        (status = 200, description = "The new session of the table", body = SuccessResponseTableSession),
+        (status = 404, description = "Table not found", body = ErrorResponse),
+        (status = 400, description = "Bad request or invalid party size", body = ErrorResponse),
+        (status = 409, description = "Table already open", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn open_table(
+    data: web::Data<AppState>,
+    table_id: web::Path<String>,
+    request: web::Json<OpenTableRequest>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.open_table(table_id, request.party_size) {
+        Ok(session) => success_response(session),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Closes the specified table, archiving its session with the final bill.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+///
+/// # Responses
+///
+/// * `200` - The closed session.
+/// * `404` - Table not found or not open.
+/// * `409` - The bill of the table is not paid in full.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/close_table/{table_id}",
+    responses(
+        (status = 200, description = "The closed session", body = SuccessResponseTableSession),
+        (status = 404, description = "Table not found or not open", body = ErrorResponse),
+        (status = 409, description = "Bill not paid in full", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn close_table(data: web::Data<AppState>, table_id: web::Path<String>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.close_table(table_id) {
+        Ok(session) => success_response(session),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves the active session of the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+///
+/// # Responses
+///
+/// * `200` - The active session.
+/// * `404` - Table not found or not open.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/session/{table_id}",
+    responses(
+        (status = 200, description = "The active session", body = SuccessResponseTableSession),
+        (status = 404, description = "Table not found or not open", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn get_session(data: web::Data<AppState>, table_id: web::Path<String>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.get_session(table_id) {
+        Ok(session) => success_response(session),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Declares the allergies of the party at the specified table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `table_id` - Path parameter containing the table ID.
+/// * `request` - JSON body with the allergies.
+///
+/// # Responses
+///
+/// * `200` - The active session with its allergies.
+/// * `404` - Table not found.
+/// * `400` - Bad request or unknown allergen.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/allergies/{table_id}",
+    request_body = AllergiesRequest,
+    responses(
+        (status = 200, description = "The active session with its allergies", body = SuccessResponseTableSession),
+        (status = 404, description = "Table not found", body = ErrorResponse),
+        (status = 400, description = "Bad request or unknown allergen", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("table_id" = u32, description = "ID of the table")
+    )
+)]
+pub async fn declare_allergies(
+    data: web::Data<AppState>,
+    table_id: web::Path<String>,
+    request: web::Json<AllergiesRequest>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    let table_id = match parse_path_param(&table_id, "table ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match restaurant.declare_allergies(table_id, request.into_inner().allergies) {
+        Ok(session) => success_response(session),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves closed sessions, optionally of a single table.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `query` - Query parameters with the optional table ID.
+///
+/// # Responses
+///
+/// * `200` - The closed sessions, in the order they were closed.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/sessions",
+    responses(
+        (status = 200, description = "The closed sessions", body = SuccessResponseTableSessions),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(SessionHistoryQuery)
+)]
+pub async fn get_sessions(
+    data: web::Data<AppState>,
+    query: web::Query<SessionHistoryQuery>,
+) -> impl Responder {
+    match data.restaurant.get_session_history(query.table_id) {
+        Ok(sessions) => success_response(sessions),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Query parameters of the reservation list.
+///
+/// - `date`: Only reservations starting on this day, written as `YYYY-MM-DD` in UTC.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReservationQuery {
+    /// Day of the reservations, e.g. `2026-10-18`.
+    pub date: Option<String>,
+}
+
+impl ReservationQuery {
+    /// Parses the date of the query into midnight of the day, in seconds since the Unix epoch.
+    fn day(&self) -> Result<Option<u64>, RestaurantError> {
+        self.date
+            .as_deref()
+            .map(|date| {
+                reservation::parse_date(date).ok_or_else(|| {
+                    RestaurantError::InvalidReservation(format!(
+                        "invalid date '{}'. Expected YYYY-MM-DD",
+                        date
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Query parameters of the table list.
+///
+/// - `status`: Whether to list every table with its capacity and reservation state instead
+///   of only its ID. Defaults to `false`, so clients reading IDs keep working.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TableQuery {
+    /// List the tables with their capacity and reservation state.
+    #[serde(default)]
+    pub status: bool,
+}
+
+/// Retrieves a list of all available tables in the restaurant. With `?status=true`, every
+/// table comes with the number of guests it seats, and tables whose reservation slot is in
+/// progress are flagged as reserved.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `query` - Query parameters with the optional `status` flag.
+///
+/// # Responses
+///
+/// * `200` - List of available table IDs, or of table statuses with `?status=true`.
+/// * `400` - The `status` flag is not `true` or `false`.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tables",
+    responses(
+        (status = 200, description = "List of available table IDs, or of table statuses (`SuccessResponseTableStatuses`) with `status=true`", body = SuccessResponseTables),
+        (status = 400, description = "The status flag is not true or false", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(TableQuery)
+)]
+pub async fn get_tables(
+    data: web::Data<AppState>,
+    query: web::Query<TableQuery>,
+) -> impl Responder {
+    let restaurant = &data.restaurant;
+    if query.status {
+        return match restaurant.get_table_statuses() {
+            Ok(tables) => success_response(tables),
+            Err(e) => restaurant_error_to_response(e),
+        };
+    }
+    match restaurant.get_all_tables() {
+        Ok(tables) => success_response(tables),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves a list of all available menu items in the restaurant, optionally filtered by
+/// allergens and diets.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `query` - Query parameters with the optional allergens and diets.
+///
+/// # Responses
+///
+/// * `200` - List of available menus.
+/// * `400` - Unknown allergen or diet.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/menus",
+    responses(
+        (status = 200, description = "List of available menus", body = SuccessResponseMenuItems),
+        (status = 400, description = "Unknown allergen or diet", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(MenuQuery)
+)]
+pub async fn get_menus(data: web::Data<AppState>, query: web::Query<MenuQuery>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    match query
+        .filter()
+        .and_then(|filter| restaurant.get_menus(&filter))
+    {
+        Ok(menus) => success_response(menus),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Retrieves the menu grouped by category, with the categories in display order, optionally
+/// filtered by allergens and diets.
+///
 /// # Arguments
 ///
 /// * `data` - Application state that contains the restaurant.
-/// * `params` - Path parameters containing the table ID and menu item ID.
+/// * `query` - Query parameters with the optional allergens and diets.
 ///
 /// # Responses
 ///
-/// * `200` - Menu item added successfully.
-/// * `404` - Table or menu item not found.
-/// * `400` - Bad request.
+/// * `200` - The menu grouped by category.
+/// * `400` - Unknown allergen or diet.
 /// * `500` - Internal server error.
 #[utoipa::path(
-    post,
-    path = "/api/v1/add_item/{table_id}/{menu_item_id}",
+    get,
+    path = "/api/v1/categorized_menu",
     responses(
-        (status = 200, description = "Menu item added successfully", body = SuccessResponseMessage),
-        (status = 404, description = "Table or menu item not found", body = ErrorResponse),
-        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 200, description = "The menu grouped by category", body = SuccessResponseCategorizedMenu),
+        (status = 400, description = "Unknown allergen or diet", body = ErrorResponse),
         (status = 500, description = "Internal server error")
     ),
-    params(
-        ("table_id" = u32, description = "ID of the table"),
-        ("menu_item_id" = u32, description = "ID of the menu item")
-    )
+    params(MenuQuery)
 )]
-pub async fn add_item(
+pub async fn get_categorized_menu(
     data: web::Data<AppState>,
-    params: web::Path<(String, String)>,
+    query: web::Query<MenuQuery>,
 ) -> impl Responder {
     let restaurant = &data.restaurant;
-    let table_id = match parse_path_param(&params.0, "table ID") {
-        Ok(id) => id,
-        Err(e) => return error_response(400, &e),
-    };
+    match query
+        .filter()
+        .and_then(|filter| restaurant.get_categorized_menu(&filter))
+    {
+        Ok(menu) => success_response(menu),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
 
-    let item_id = match parse_path_param(&params.1, "item ID") {
-        Ok(id) => id,
-        Err(e) => return error_response(400, &e),
-    };
-    match restaurant.add_item(table_id, item_id) {
-        Ok(_) => success_message_response(&format!(
-            "Menu item with item id: {} added successfully for table with table id {}",
-            item_id, table_id
-        )),
+/// Retrieves the categories of the menu in display order.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+///
+/// # Responses
+///
+/// * `200` - List of categories.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/categories",
+    responses(
+        (status = 200, description = "List of categories", body = SuccessResponseCategories),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_categories(data: web::Data<AppState>) -> impl Responder {
+    let restaurant = &data.restaurant;
+    match restaurant.get_categories() {
+        Ok(categories) => success_response(categories),
         Err(e) => restaurant_error_to_response(e),
     }
 }
 
-/// Removes a menu item from the specified table.
+/// Marks the specified category of the menu as served or not served.
 ///
 /// # Arguments
 ///
 /// * `data` - Application state that contains the restaurant.
-/// * `params` - Path parameters containing the table ID and menu item ID.
+/// * `category_id` - Path parameter containing the category ID.
+/// * `request` - JSON body with the new availability.
 ///
 /// # Responses
 ///
-/// * `200` - Menu item removed successfully.
-/// * `404` - Table or menu item not found.
+/// * `200` - The updated category.
+/// * `404` - Category not found.
 /// * `400` - Bad request.
 /// * `500` - Internal server error.
 #[utoipa::path(
-    delete,
-    path = "/api/v1/remove_item/{table_id}/{menu_item_id}",
+    post,
+    path = "/api/v1/category_availability/{category_id}",
+    request_body = CategoryAvailabilityRequest,
     responses(
-        (status = 200, description = "Menu item removed successfully", body = SuccessResponseMessage),
-        (status = 404, description = "Table or menu item not found", body = ErrorResponse),
+        (status = 200, description = "The updated category", body = SuccessResponseCategory),
+        (status = 404, description = "Category not found", body = ErrorResponse),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 500, description = "Internal server error")
     ),
     params(
-        ("table_id" = u32, description = "ID of the table"),
-        ("menu_item_id" = u32, description = "ID of the menu item to remove")
+        ("category_id" = u32, description = "ID of the category")
     )
 )]
-pub async fn remove_item(
+pub async fn set_category_availability(
     data: web::Data<AppState>,
-    params: web::Path<(String, String)>,
+    category_id: web::Path<String>,
+    request: web::Json<CategoryAvailabilityRequest>,
 ) -> impl Responder {
-    let table_id = match parse_path_param(&params.0, "table ID") {
+    let restaurant = &data.restaurant;
+    let category_id = match parse_path_param(&category_id, "category ID") {
         Ok(id) => id,
         Err(e) => return error_response(400, &e),
     };
+    match restaurant.set_category_availability(category_id, request.available) {
+        Ok(category) => success_response(category),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
 
-    let item_id = match parse_path_param(&params.1, "item ID") {
-        Ok(id) => id,
-        Err(e) => return error_response(400, &e),
-    };
-    let restaurant = &data.restaurant;
-    match restaurant.remove_item(table_id, item_id) {
-        Ok(_) => success_message_response(&format!(
-            "Menu item with item id:{} removed from table with table id:{} successfully",
-            item_id, table_id
-        )),
+/// Retrieves the stock of menu items whose stock is counted or that are marked sold out.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+///
+/// # Responses
+///
+/// * `200` - List of stock levels, ordered by item ID.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    get,
+    path = "/api/v1/stock",
+    responses(
+        (status = 200, description = "List of stock levels", body = SuccessResponseStockLevels),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_stock(data: web::Data<AppState>) -> impl Responder {
+    match data.restaurant.get_stock() {
+        Ok(levels) => success_response(levels),
         Err(e) => restaurant_error_to_response(e),
     }
 }
 
-/// Retrieves all menu items added to the specified table.
+/// Sets the stock of the specified menu item, or marks it as sold out ("86").
+///
+/// `{"remaining": 5}` counts five portions, `{"sold_out": true}` takes the item off the
+/// menu and `{}` stops tracking its stock.
 ///
 /// # Arguments
 ///
 /// * `data` - Application state that contains the restaurant.
-/// * `table_id` - Path parameter containing the table ID.
+/// * `item_id` - Path parameter containing the menu item ID.
+/// * `request` - JSON body with the portions in stock and the sold out mark.
 ///
 /// # Responses
 ///
-/// * `200` - List of menu items added for the table.
-/// * `404` - Table not found or no menu items added to the table.
+/// * `200` - The new stock level.
+/// * `404` - Menu item not found.
 /// * `400` - Bad request.
 /// * `500` - Internal server error.
 #[utoipa::path(
-    get,
-    path = "/api/v1/get_items/{table_id}",
+    post,
+    path = "/api/v1/stock/{item_id}",
+    request_body = StockRequest,
     responses(
-        (status = 200, description = "List of menu items added for the table", body = SuccessResponseMenuItems),
-        (status = 404, description = "Table not found or no menu items added to the table", body = ErrorResponse),
+        (status = 200, description = "The new stock level", body = SuccessResponseStockLevel),
+        (status = 404, description = "Menu item not found", body = ErrorResponse),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 500, description = "Internal server error")
     ),
     params(
-        ("table_id" = u32, description = "ID of the table")
+        ("item_id" = u32, description = "ID of the menu item")
     )
 )]
-pub async fn get_items(data: web::Data<AppState>, table_id: web::Path<String>) -> impl Responder {
+pub async fn set_stock(
+    data: web::Data<AppState>,
+    item_id: web::Path<String>,
+    request: web::Json<StockRequest>,
+) -> impl Responder {
     let restaurant = &data.restaurant;
-    let table_id = match parse_path_param(&table_id, "table ID") {
+    let item_id = match parse_path_param(&item_id, "item ID") {
         Ok(id) => id,
         Err(e) => return error_response(400, &e),
     };
-    match restaurant.get_items(table_id) {
-        Ok(items) => success_response(items),
+    match restaurant.set_stock(item_id, request.remaining, request.sold_out) {
+        Ok(level) => success_response(level),
         Err(e) => restaurant_error_to_response(e),
     }
 }
 
-/// Retrieves details of a specific menu item added to the specified table.
+/// Retrieves the ingredients in the inventory with their quantities in stock.
 ///
 /// # Arguments
 ///
 /// * `data` - Application state that contains the restaurant.
-/// * `params` - Path parameters containing the table ID and menu item ID.
 ///
 /// # Responses
 ///
-/// * `200` - Menu item details.
-/// * `404` - Table or menu item not found or the menu item not added to the table.
-/// * `400` - Bad Request.
+/// * `200` - List of ingredients, ordered by ID.
 /// * `500` - Internal server error.
 #[utoipa::path(
     get,
-    path = "/api/v1/get_item/{table_id}/{menu_item_id}",
+    path = "/api/v1/ingredients",
     responses(
-        (status = 200, description = "Menu item details", body = SuccessResponseMenuItem),
-        (status = 404, description = "Table or menu item not found or menu item not added to the table", body = ErrorResponse),
+        (status = 200, description = "List of ingredients", body = SuccessResponseIngredients),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_ingredients(data: web::Data<AppState>) -> impl Responder {
+    match data.restaurant.get_ingredients() {
+        Ok(ingredients) => success_response(ingredients),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Adds a delivered quantity of the specified ingredient to the inventory.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `ingredient_id` - Path parameter containing the ingredient ID.
+/// * `request` - JSON body with the quantity delivered.
+///
+/// # Responses
+///
+/// * `200` - The restocked ingredient.
+/// * `404` - Ingredient not found.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/restock/{ingredient_id}",
+    request_body = RestockRequest,
+    responses(
+        (status = 200, description = "The restocked ingredient", body = SuccessResponseIngredient),
+        (status = 404, description = "Ingredient not found", body = ErrorResponse),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 500, description = "Internal server error")
     ),
     params(
-        ("table_id" = u32, description = "ID of the table"),
-        ("menu_item_id" = u32, description = "ID of the menu item")
+        ("ingredient_id" = u32, description = "ID of the ingredient")
     )
 )]
-pub async fn get_item(
+pub async fn restock(
     data: web::Data<AppState>,
-    params: web::Path<(String, String)>,
+    ingredient_id: web::Path<String>,
+    request: web::Json<RestockRequest>,
 ) -> impl Responder {
     let restaurant = &data.restaurant;
-    let table_id = match parse_path_param(&params.0, "table ID") {
-        Ok(id) => id,
-        Err(e) => return error_response(400, &e),
-    };
-
-    let item_id = match parse_path_param(&params.1, "item ID") {
+    let ingredient_id = match parse_path_param(&ingredient_id, "ingredient ID") {
         Ok(id) => id,
         Err(e) => return error_response(400, &e),
     };
-    match restaurant.get_item(table_id, item_id) {
-        Ok(item) => success_response(item),
+    match restaurant.restock(ingredient_id, request.quantity) {
+        Ok(ingredient) => success_response(ingredient),
         Err(e) => restaurant_error_to_response(e),
     }
 }
 
-/// Retrieves a list of all available tables in the restaurant.
+/// Reports the ingredients at or below their low stock threshold and the menu items that
+/// can no longer be made.
 ///
 /// # Arguments
 ///
@@ -212,46 +1159,183 @@ pub async fn get_item(
 ///
 /// # Responses
 ///
-/// * `200` - List of available tables.
+/// * `200` - The low stock report.
 /// * `500` - Internal server error.
 #[utoipa::path(
     get,
-    path = "/api/v1/tables",
+    path = "/api/v1/low_stock",
     responses(
-        (status = 200, description = "List of available tables", body = SuccessResponseTables),
+        (status = 200, description = "The low stock report", body = SuccessResponseLowStockReport),
         (status = 500, description = "Internal server error")
     )
 )]
-pub async fn get_tables(data: web::Data<AppState>) -> impl Responder {
-    let restaurant = &data.restaurant;
-    match restaurant.get_all_tables() {
-        Ok(tables) => success_response(tables),
+pub async fn get_low_stock_report(data: web::Data<AppState>) -> impl Responder {
+    match data.restaurant.get_low_stock_report() {
+        Ok(report) => success_response(report),
         Err(e) => restaurant_error_to_response(e),
     }
 }
 
-/// Retrieves a list of all available menu items in the restaurant.
+/// Retrieves reservations, optionally only those of one day.
 ///
 /// # Arguments
 ///
 /// * `data` - Application state that contains the restaurant.
+/// * `query` - Query parameters with the optional day.
 ///
 /// # Responses
 ///
-/// * `200` - List of available menus.
+/// * `200` - The reservations, ordered by the start of their slot.
+/// * `400` - Bad request or invalid date.
 /// * `500` - Internal server error.
 #[utoipa::path(
     get,
-    path = "/api/v1/menus",
+    path = "/api/v1/reservations",
     responses(
-        (status = 200, description = "List of available menus", body = SuccessResponseMenuItems),
+        (status = 200, description = "The reservations", body = SuccessResponseReservations),
+        (status = 400, description = "Bad request or invalid date", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(ReservationQuery)
+)]
+pub async fn get_reservations(
+    data: web::Data<AppState>,
+    query: web::Query<ReservationQuery>,
+) -> impl Responder {
+    match query
+        .day()
+        .and_then(|day| data.restaurant.get_reservations(day))
+    {
+        Ok(reservations) => success_response(reservations),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Reserves a table for a party.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `request` - JSON body with the table, party size, contact name and slot.
+///
+/// # Responses
+///
+/// * `200` - The new reservation.
+/// * `404` - Table not found.
+/// * `400` - Bad request, or the party does not fit the table.
+/// * `409` - The table is already reserved during the slot.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reservations",
+    request_body = ReservationRequest,
+    responses(
+        (status = 200, description = "The new reservation", body = SuccessResponseReservation),
+        (status = 404, description = "Table not found", body = ErrorResponse),
+        (status = 400, description = "Bad request, or the party does not fit the table", body = ErrorResponse),
+        (status = 409, description = "The table is already reserved during the slot", body = ErrorResponse),
         (status = 500, description = "Internal server error")
     )
 )]
-pub async fn get_menus(data: web::Data<AppState>) -> impl Responder {
-    let restaurant = &data.restaurant;
-    match restaurant.get_all_menus() {
-        Ok(menus) => success_response(menus),
+pub async fn make_reservation(
+    data: web::Data<AppState>,
+    request: web::Json<ReservationRequest>,
+) -> impl Responder {
+    match data.restaurant.make_reservation(&request) {
+        Ok(reservation) => success_response(reservation),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Changes the table, party size, contact name or slot of the specified reservation.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `reservation_id` - Path parameter containing the reservation ID.
+/// * `request` - JSON body with the new details of the reservation.
+///
+/// # Responses
+///
+/// * `200` - The updated reservation.
+/// * `404` - Reservation or table not found.
+/// * `400` - Bad request, or the party does not fit the table.
+/// * `409` - The table is already reserved during the slot.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    put,
+    path = "/api/v1/reservations/{reservation_id}",
+    request_body = ReservationRequest,
+    responses(
+        (status = 200, description = "The updated reservation", body = SuccessResponseReservation),
+        (status = 404, description = "Reservation or table not found", body = ErrorResponse),
+        (status = 400, description = "Bad request, or the party does not fit the table", body = ErrorResponse),
+        (status = 409, description = "The table is already reserved during the slot", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("reservation_id" = u64, description = "ID of the reservation")
+    )
+)]
+pub async fn update_reservation(
+    data: web::Data<AppState>,
+    reservation_id: web::Path<String>,
+    request: Result<web::Json<ReservationRequest>, actix_web::Error>,
+) -> impl Responder {
+    let reservation_id = match parse_u64_path_param(&reservation_id, "reservation ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    // An unknown reservation is reported before a body that cannot be read
+    if let Err(e) = data.restaurant.get_reservation(reservation_id) {
+        return restaurant_error_to_response(e);
+    }
+    let request = match request {
+        Ok(request) => request,
+        Err(e) => return error_response(400, &e.to_string()),
+    };
+    match data.restaurant.update_reservation(reservation_id, &request) {
+        Ok(reservation) => success_response(reservation),
+        Err(e) => restaurant_error_to_response(e),
+    }
+}
+
+/// Cancels the specified reservation.
+///
+/// # Arguments
+///
+/// * `data` - Application state that contains the restaurant.
+/// * `reservation_id` - Path parameter containing the reservation ID.
+///
+/// # Responses
+///
+/// * `200` - The cancelled reservation.
+/// * `404` - Reservation not found.
+/// * `400` - Bad request.
+/// * `500` - Internal server error.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/reservations/{reservation_id}",
+    responses(
+        (status = 200, description = "The cancelled reservation", body = SuccessResponseReservation),
+        (status = 404, description = "Reservation not found", body = ErrorResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("reservation_id" = u64, description = "ID of the reservation")
+    )
+)]
+pub async fn cancel_reservation(
+    data: web::Data<AppState>,
+    reservation_id: web::Path<String>,
+) -> impl Responder {
+    let reservation_id = match parse_u64_path_param(&reservation_id, "reservation ID") {
+        Ok(id) => id,
+        Err(e) => return error_response(400, &e),
+    };
+    match data.restaurant.cancel_reservation(reservation_id) {
+        Ok(reservation) => success_response(reservation),
         Err(e) => restaurant_error_to_response(e),
     }
 }
@@ -263,6 +1347,8 @@ mod tests {
     use crate::server::data_model::models::{
         MenuItem, MockMenuStore, MockOrderStore, MockTableStore,
     };
+    use crate::server::data_model::modifier::OrderLine;
+    use crate::server::data_model::money::Money;
     use crate::server::restaurant::SimpleRestaurant;
     use actix_web::{http::StatusCode, test, web, App};
     use mockall::predicate::*;
@@ -279,26 +1365,31 @@ mod tests {
             .returning(|| Ok(vec![1, 2, 3]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
-            Ok(vec![MenuItem {
-                id: 1,
-                name: "Burger".to_string(),
-                cooking_time_minutes: 10,
-            }])
+            Ok(vec![MenuItem::new(
+                1,
+                "Burger",
+                10,
+                Money::from_cents(1250),
+            )])
         });
 
+        mock_menu_store
+            .expect_get_categories()
+            .returning(|| Ok(Vec::new()));
+
         mock_order_store
-            .expect_add_item()
-            .with(eq(1), eq(1))
+            .expect_add_line()
+            .with(eq(1), eq(OrderLine::plain(1)))
             .returning(|_, _| Ok(()));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -308,7 +1399,7 @@ mod tests {
         let req = test::TestRequest::post()
             .uri("/api/v1/add_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::OK);
     }
@@ -324,23 +1415,24 @@ mod tests {
             .returning(|| Ok(vec![2, 3]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
-            Ok(vec![MenuItem {
-                id: 1,
-                name: "Burger".to_string(),
-                cooking_time_minutes: 10,
-            }])
+            Ok(vec![MenuItem::new(
+                1,
+                "Burger",
+                10,
+                Money::from_cents(1250),
+            )])
         });
 
-        mock_order_store.expect_add_item().returning(|_, _| Ok(()));
+        mock_order_store.expect_add_line().returning(|_, _| Ok(()));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -350,7 +1442,7 @@ mod tests {
         let req = test::TestRequest::post()
             .uri("/api/v1/add_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
@@ -366,23 +1458,24 @@ mod tests {
             .returning(|| Ok(vec![1, 2, 3]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
-            Ok(vec![MenuItem {
-                id: 10,
-                name: "Burger".to_string(),
-                cooking_time_minutes: 10,
-            }])
+            Ok(vec![MenuItem::new(
+                10,
+                "Burger",
+                10,
+                Money::from_cents(1250),
+            )])
         });
 
-        mock_order_store.expect_add_item().returning(|_, _| Ok(()));
+        mock_order_store.expect_add_line().returning(|_, _| Ok(()));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -392,7 +1485,7 @@ mod tests {
         let req = test::TestRequest::post()
             .uri("/api/v1/add_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
@@ -411,14 +1504,20 @@ mod tests {
             .with(eq(1), eq(1))
             .returning(|_, _| Ok(()));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(MockMenuStore::new()),
-        });
+        // The removed item is looked up to restore its ingredients
+        let mut mock_menu_store = MockMenuStore::new();
+        mock_menu_store
+            .expect_get_all_menus()
+            .returning(|| Ok(Vec::new()));
+
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -428,7 +1527,7 @@ mod tests {
         let req = test::TestRequest::delete()
             .uri("/api/v1/remove_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::OK);
     }
@@ -446,14 +1545,14 @@ mod tests {
             .expect_remove_item()
             .returning(|_, _| Ok(()));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(MockMenuStore::new()),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(MockMenuStore::new()),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -463,7 +1562,7 @@ mod tests {
         let req = test::TestRequest::delete()
             .uri("/api/v1/remove_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
@@ -479,33 +1578,25 @@ mod tests {
             .returning(|| Ok(vec![1, 2, 3]));
 
         mock_order_store
-            .expect_get_item_ids()
+            .expect_get_lines()
             .with(eq(1))
-            .returning(|_| Ok(vec![1]));
+            .returning(|_| Ok(vec![OrderLine::plain(1)]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
             Ok(vec![
-                MenuItem {
-                    id: 1,
-                    name: "Burger".to_string(),
-                    cooking_time_minutes: 10,
-                },
-                MenuItem {
-                    id: 2,
-                    name: "Pizza".to_string(),
-                    cooking_time_minutes: 15,
-                },
+                MenuItem::new(1, "Burger", 10, Money::from_cents(1250)),
+                MenuItem::new(2, "Pizza", 15, Money::from_cents(1350)),
             ])
         });
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -515,7 +1606,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_items/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::OK);
 
@@ -538,21 +1629,21 @@ mod tests {
             .returning(|| Ok(vec![2, 3]));
 
         mock_order_store
-            .expect_get_item_ids()
+            .expect_get_lines()
             .returning(|_| Ok(vec![]));
 
         mock_menu_store
             .expect_get_all_menus()
             .returning(|| Ok(vec![]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -562,7 +1653,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_items/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
@@ -581,30 +1672,26 @@ mod tests {
             .expect_get_item_id()
             .with(eq(1), eq(1))
             .returning(|_, _| Ok(1));
+        mock_order_store
+            .expect_get_lines()
+            .with(eq(1))
+            .returning(|_| Ok(vec![OrderLine::plain(1)]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
             Ok(vec![
-                MenuItem {
-                    id: 1,
-                    name: "Burger".to_string(),
-                    cooking_time_minutes: 10,
-                },
-                MenuItem {
-                    id: 2,
-                    name: "Pizza".to_string(),
-                    cooking_time_minutes: 15,
-                },
+                MenuItem::new(1, "Burger", 10, Money::from_cents(1250)),
+                MenuItem::new(2, "Pizza", 15, Money::from_cents(1350)),
             ])
         });
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -614,7 +1701,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::OK);
 
@@ -643,14 +1730,14 @@ mod tests {
             .expect_get_all_menus()
             .returning(|| Ok(vec![]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -660,7 +1747,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_item/1/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
@@ -676,21 +1763,22 @@ mod tests {
             .returning(|| Ok(vec![1, 2, 3]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
-            Ok(vec![MenuItem {
-                id: 1,
-                name: "Burger".to_string(),
-                cooking_time_minutes: 10,
-            }])
+            Ok(vec![MenuItem::new(
+                1,
+                "Burger",
+                10,
+                Money::from_cents(1250),
+            )])
         });
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -701,7 +1789,7 @@ mod tests {
         let req = test::TestRequest::post()
             .uri("/api/v1/add_item/invalid/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
@@ -717,21 +1805,22 @@ mod tests {
             .returning(|| Ok(vec![1, 2, 3]));
 
         mock_menu_store.expect_get_all_menus().returning(|| {
-            Ok(vec![MenuItem {
-                id: 1,
-                name: "Burger".to_string(),
-                cooking_time_minutes: 10,
-            }])
+            Ok(vec![MenuItem::new(
+                1,
+                "Burger",
+                10,
+                Money::from_cents(1250),
+            )])
         });
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -741,7 +1830,7 @@ mod tests {
         let req = test::TestRequest::post()
             .uri("/api/v1/add_item/1/invalid")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
@@ -755,14 +1844,14 @@ mod tests {
             .expect_get_all_tables()
             .returning(|| Ok(vec![1, 2, 3]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(MockMenuStore::new()),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(MockMenuStore::new()),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -773,7 +1862,7 @@ mod tests {
         let req = test::TestRequest::delete()
             .uri("/api/v1/remove_item/invalid/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
@@ -787,14 +1876,14 @@ mod tests {
             .expect_get_all_tables()
             .returning(|| Ok(vec![1, 2, 3]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(MockMenuStore::new()),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(MockMenuStore::new()),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -804,7 +1893,7 @@ mod tests {
         let req = test::TestRequest::delete()
             .uri("/api/v1/remove_item/1/invalid")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
@@ -819,14 +1908,14 @@ mod tests {
             .expect_get_all_tables()
             .returning(|| Ok(vec![1, 2, 3]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -837,7 +1926,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_items/invalid")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
@@ -852,14 +1941,14 @@ mod tests {
             .expect_get_all_tables()
             .returning(|| Ok(vec![1, 2, 3]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -870,7 +1959,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_item/invalid/1")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
@@ -885,14 +1974,14 @@ mod tests {
             .expect_get_all_tables()
             .returning(|| Ok(vec![1, 2, 3]));
 
-        let restaurant = Arc::new(SimpleRestaurant {
-            table_store: Box::new(mock_table_store),
-            order_store: Box::new(mock_order_store),
-            menu_store: Box::new(mock_menu_store),
-        });
+        let restaurant = Arc::new(SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        ));
 
         let app_state = AppState { restaurant };
-        let mut app = test::init_service(
+        let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
                 .configure(configure_routes),
@@ -903,7 +1992,7 @@ mod tests {
         let req = test::TestRequest::get()
             .uri("/api/v1/get_item/1/invalid")
             .to_request();
-        let resp = test::call_service(&mut app, req).await;
+        let resp = test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
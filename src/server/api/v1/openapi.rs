@@ -2,8 +2,17 @@
 #![deny(clippy::all)]
 
 use crate::server::api::v1;
+use crate::server::data_model::dietary;
+use crate::server::data_model::inventory::{self, Ingredient, LowStockReport};
+use crate::server::data_model::menu::{self, CategorizedMenu, Category};
 use crate::server::data_model::models;
-use crate::server::data_model::models::MenuItem;
+use crate::server::data_model::models::Bill;
+use crate::server::data_model::modifier::{self, OrderedItem};
+use crate::server::data_model::payment::{self, PaymentReceipt, TableAccount};
+use crate::server::data_model::reservation::{self, Reservation, TableStatus};
+use crate::server::data_model::session::{self, TableSession};
+use crate::server::data_model::split::{self, SplitBill};
+use crate::server::data_model::stock::{self, MenuListing, StockLevel};
 use serde::Serialize;
 use utoipa::OpenApi;
 use utoipa::ToSchema;
@@ -23,26 +32,195 @@ pub struct SuccessResponseMessage {
 
 /// Struct representing a success response with a list of menu items.
 ///
-/// This is used in API responses that return a list of `MenuItem`s, such as a request
+/// This is used in API responses that return a list of `MenuListing`s, such as a request
 /// for all available menu items.
 #[derive(Serialize, ToSchema)]
 pub struct SuccessResponseMenuItems {
     /// Status of the response, typically "ok" for success.
     pub status: String,
-    /// A list of menu items returned by the request.
-    pub data: Vec<MenuItem>,
+    /// A list of menu items with their stock returned by the request.
+    pub data: Vec<MenuListing>,
 }
 
-/// Struct representing a success response with a single menu item.
+/// Struct representing a success response with the menu items ordered for a table.
 ///
-/// This is used in API responses that return a single `MenuItem`, such as a request
-/// for a specific menu item.
+/// This is used in API responses that return a list of `OrderedItem`s, i.e. menu
+/// items with their modifiers and kitchen notes.
 #[derive(Serialize, ToSchema)]
-pub struct SuccessResponseMenuItem {
+pub struct SuccessResponseOrderedItems {
     /// Status of the response, typically "ok" for success.
     pub status: String,
-    /// The specific menu item returned by the request.
-    pub data: MenuItem,
+    /// The ordered menu items returned by the request.
+    pub data: Vec<OrderedItem>,
+}
+
+/// Struct representing a success response with a single ordered menu item.
+///
+/// This is used in API responses that return a single `OrderedItem`, such as a request
+/// for a specific menu item ordered for a table.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseOrderedItem {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The specific ordered menu item returned by the request.
+    pub data: OrderedItem,
+}
+
+/// Struct representing a success response with the bill of a table.
+///
+/// This is used in API responses that return a `Bill`, i.e. the itemized
+/// bill of a table with its subtotal, tax and total.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseBill {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The bill returned by the request.
+    pub data: Bill,
+}
+
+/// Struct representing a success response with a split bill.
+///
+/// This is used in API responses that return a `SplitBill`, i.e. the bill
+/// of a table together with the checks it was split into.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseSplitBill {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The split bill returned by the request.
+    pub data: SplitBill,
+}
+
+/// Struct representing a success response with a payment receipt.
+///
+/// This is used in API responses that return a `PaymentReceipt`, i.e. a recorded
+/// payment or tip together with the account of the table's bill.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponsePaymentReceipt {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The receipt returned by the request.
+    pub data: PaymentReceipt,
+}
+
+/// Struct representing a success response with the account of a table.
+///
+/// This is used in API responses that return a `TableAccount`, i.e. the payments
+/// and the balance of a table's bill.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseTableAccount {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The account returned by the request.
+    pub data: TableAccount,
+}
+
+/// Struct representing a success response with a table session.
+///
+/// This is used in API responses that return a single `TableSession`, such as
+/// opening or closing a table.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseTableSession {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The session returned by the request.
+    pub data: TableSession,
+}
+
+/// Struct representing a success response with a list of table sessions.
+///
+/// This is used in API responses that return the history of closed sessions.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseTableSessions {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The sessions returned by the request.
+    pub data: Vec<TableSession>,
+}
+
+/// Struct representing a success response with a single menu category.
+///
+/// This is used in API responses that return a `Category`, such as changing
+/// whether the category is served.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseCategory {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The category returned by the request.
+    pub data: Category,
+}
+
+/// Struct representing a success response with a list of menu categories.
+///
+/// This is used in API responses that return the categories of the menu.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseCategories {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The categories, in display order.
+    pub data: Vec<Category>,
+}
+
+/// Struct representing a success response with the menu grouped by category.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseCategorizedMenu {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The menu grouped by category.
+    pub data: CategorizedMenu,
+}
+
+/// Struct representing a success response with a single stock level.
+///
+/// This is used in API responses that return a `StockLevel`, such as setting the stock
+/// of a menu item.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseStockLevel {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The stock level returned by the request.
+    pub data: StockLevel,
+}
+
+/// Struct representing a success response with a list of stock levels.
+///
+/// This is used in API responses that return the stock of counted or sold out menu items.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseStockLevels {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The stock levels, ordered by item ID.
+    pub data: Vec<StockLevel>,
+}
+
+/// Struct representing a success response with a single ingredient.
+///
+/// This is used in API responses that return an `Ingredient`, such as restocking it.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseIngredient {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The ingredient returned by the request.
+    pub data: Ingredient,
+}
+
+/// Struct representing a success response with a list of ingredients.
+///
+/// This is used in API responses that return the ingredients in the inventory.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseIngredients {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The ingredients, ordered by ID.
+    pub data: Vec<Ingredient>,
+}
+
+/// Struct representing a success response with the low stock report of the inventory.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseLowStockReport {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The low stock report.
+    pub data: LowStockReport,
 }
 
 /// Struct representing a success response with a list of tables.
@@ -57,6 +235,39 @@ pub struct SuccessResponseTables {
     pub data: Vec<u32>,
 }
 
+/// Struct representing a success response with a list of table statuses.
+///
+/// This is the response of `GET /api/v1/tables?status=true`, which returns the tables
+/// of the restaurant with their capacity and whether they are reserved right now.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseTableStatuses {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The tables of the restaurant.
+    pub data: Vec<TableStatus>,
+}
+
+/// Struct representing a success response with a single reservation.
+///
+/// This is used in API responses that return a `Reservation`, such as making,
+/// changing or cancelling it.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseReservation {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The reservation returned by the request.
+    pub data: Reservation,
+}
+
+/// Struct representing a success response with a list of reservations.
+#[derive(Serialize, ToSchema)]
+pub struct SuccessResponseReservations {
+    /// Status of the response, typically "ok" for success.
+    pub status: String,
+    /// The reservations, ordered by the start of their slot.
+    pub data: Vec<Reservation>,
+}
+
 /// Struct representing an error response.
 ///
 /// This is used in API responses where an error occurred,
@@ -78,17 +289,94 @@ pub struct ErrorResponse {
     paths(
         v1::handlers::add_item,
         v1::handlers::remove_item,
+        v1::handlers::remove_line,
         v1::handlers::get_items,
         v1::handlers::get_item,
+        v1::handlers::get_bill,
+        v1::handlers::split_bill,
+        v1::handlers::get_checks,
+        v1::handlers::pay,
+        v1::handlers::tip,
+        v1::handlers::get_payments,
+        v1::handlers::open_table,
+        v1::handlers::close_table,
+        v1::handlers::get_session,
+        v1::handlers::declare_allergies,
+        v1::handlers::get_sessions,
         v1::handlers::get_tables,
         v1::handlers::get_menus,
+        v1::handlers::get_categorized_menu,
+        v1::handlers::get_categories,
+        v1::handlers::set_category_availability,
+        v1::handlers::get_stock,
+        v1::handlers::set_stock,
+        v1::handlers::get_ingredients,
+        v1::handlers::restock,
+        v1::handlers::get_low_stock_report,
+        v1::handlers::get_reservations,
+        v1::handlers::make_reservation,
+        v1::handlers::update_reservation,
+        v1::handlers::cancel_reservation,
     ),
     components(schemas(
         models::MenuItem,
+        dietary::Allergen,
+        dietary::Diet,
+        dietary::AllergiesRequest,
+        modifier::Modifier,
+        modifier::ModifierGroup,
+        modifier::SelectedModifier,
+        modifier::ItemOptions,
+        modifier::OrderedItem,
+        menu::Category,
+        menu::CategoryAvailabilityRequest,
+        menu::MenuSection,
+        menu::CategorizedMenu,
+        stock::MenuListing,
+        stock::StockLevel,
+        stock::StockRequest,
+        inventory::Ingredient,
+        inventory::RecipeIngredient,
+        inventory::RestockRequest,
+        inventory::LowStockReport,
+        models::Bill,
+        models::BillLine,
+        split::SplitRequest,
+        split::Check,
+        split::SplitBill,
+        payment::PaymentMethod,
+        payment::PaymentRequest,
+        payment::TipRequest,
+        payment::Payment,
+        payment::TableAccount,
+        payment::PaymentReceipt,
+        session::TableSession,
+        session::OpenTableRequest,
+        reservation::Reservation,
+        reservation::ReservationRequest,
+        reservation::TableStatus,
         SuccessResponseMessage,
         SuccessResponseMenuItems,
-        SuccessResponseMenuItem,
+        SuccessResponseOrderedItems,
+        SuccessResponseOrderedItem,
+        SuccessResponseBill,
+        SuccessResponseSplitBill,
+        SuccessResponsePaymentReceipt,
+        SuccessResponseTableAccount,
+        SuccessResponseTableSession,
+        SuccessResponseTableSessions,
+        SuccessResponseCategory,
+        SuccessResponseCategories,
+        SuccessResponseCategorizedMenu,
+        SuccessResponseStockLevel,
+        SuccessResponseStockLevels,
+        SuccessResponseIngredient,
+        SuccessResponseIngredients,
+        SuccessResponseLowStockReport,
         SuccessResponseTables,
+        SuccessResponseTableStatuses,
+        SuccessResponseReservation,
+        SuccessResponseReservations,
         ErrorResponse
     )),
     tags(
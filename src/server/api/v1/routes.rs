@@ -2,12 +2,37 @@
 #![deny(clippy::all)]
 
 use crate::server::api::v1::handlers::add_item;
+use crate::server::api::v1::handlers::cancel_reservation;
+use crate::server::api::v1::handlers::close_table;
+use crate::server::api::v1::handlers::declare_allergies;
+use crate::server::api::v1::handlers::get_bill;
+use crate::server::api::v1::handlers::get_categories;
+use crate::server::api::v1::handlers::get_categorized_menu;
+use crate::server::api::v1::handlers::get_checks;
+use crate::server::api::v1::handlers::get_ingredients;
 use crate::server::api::v1::handlers::get_item;
 use crate::server::api::v1::handlers::get_items;
+use crate::server::api::v1::handlers::get_low_stock_report;
 use crate::server::api::v1::handlers::get_menus;
+use crate::server::api::v1::handlers::get_payments;
+use crate::server::api::v1::handlers::get_reservations;
+use crate::server::api::v1::handlers::get_session;
+use crate::server::api::v1::handlers::get_sessions;
+use crate::server::api::v1::handlers::get_stock;
 use crate::server::api::v1::handlers::get_tables;
+use crate::server::api::v1::handlers::make_reservation;
+use crate::server::api::v1::handlers::open_table;
+use crate::server::api::v1::handlers::pay;
 use crate::server::api::v1::handlers::remove_item;
-use actix_web::web;
+use crate::server::api::v1::handlers::remove_line;
+use crate::server::api::v1::handlers::restock;
+use crate::server::api::v1::handlers::set_category_availability;
+use crate::server::api::v1::handlers::set_stock;
+use crate::server::api::v1::handlers::split_bill;
+use crate::server::api::v1::handlers::tip;
+use crate::server::api::v1::handlers::update_reservation;
+use crate::server::utils::response::error_response;
+use actix_web::{error, web};
 
 /// Configures the API routes for the restaurant application.
 ///
@@ -15,16 +40,53 @@ use actix_web::web;
 ///
 /// - `POST /api/v1/add_item/{table_id}/{item_id}`: Adds a menu item to a table.
 /// - `DELETE /api/v1/remove_item/{table_id}/{item_id}`: Removes a menu item from a table.
+/// - `DELETE /api/v1/remove_line/{table_id}/{line_id}`: Removes a numbered order line from a table.
 /// - `GET /api/v1/get_items/{table_id}`: Retrieves all menu items for a specific table.
 /// - `GET /api/v1/get_item/{table_id}/{item_id}`: Retrieves details of a specific menu item from a table.
-/// - `GET /api/v1/tables`: Retrieves a list of available tables in the restaurant.
-/// - `GET /api/v1/menus`: Retrieves a list of available menu items in the restaurant.
+/// - `GET /api/v1/bill/{table_id}`: Retrieves the itemized bill of a table.
+/// - `POST /api/v1/split/{table_id}`: Splits the bill of a table into checks.
+/// - `GET /api/v1/checks/{table_id}`: Retrieves the checks of a table's split bill.
+/// - `POST /api/v1/pay/{table_id}`: Records a payment towards the bill of a table.
+/// - `POST /api/v1/tip/{table_id}`: Records a tip against the bill of a table.
+/// - `GET /api/v1/payments/{table_id}`: Retrieves the payments and balance of a table's bill.
+/// - `POST /api/v1/open_table/{table_id}`: Opens a table for a party.
+/// - `POST /api/v1/close_table/{table_id}`: Closes a table and archives its session.
+/// - `GET /api/v1/session/{table_id}`: Retrieves the active session of a table.
+/// - `POST /api/v1/allergies/{table_id}`: Declares the allergies of the party at a table.
+/// - `GET /api/v1/sessions`: Retrieves closed sessions, optionally of one table with `?table_id=`.
+/// - `GET /api/v1/tables`: Retrieves a list of available tables in the restaurant, with their
+///   capacity and flagging those reserved right now with `?status=true`.
+/// - `GET /api/v1/menus`: Retrieves a list of available menu items in the restaurant,
+///   optionally filtered with `?exclude_allergens=` and `?diet=`.
+/// - `GET /api/v1/categorized_menu`: Retrieves the menu grouped by category, with the same filters.
+/// - `GET /api/v1/categories`: Retrieves the categories of the menu.
+/// - `POST /api/v1/category_availability/{category_id}`: Marks a category as served or not served.
+/// - `GET /api/v1/stock`: Retrieves the stock of counted or sold out menu items.
+/// - `POST /api/v1/stock/{item_id}`: Sets the stock of a menu item, or marks it as sold out.
+/// - `GET /api/v1/ingredients`: Retrieves the ingredients in the inventory.
+/// - `POST /api/v1/restock/{ingredient_id}`: Adds a delivered quantity of an ingredient.
+/// - `GET /api/v1/low_stock`: Reports ingredients low on stock and menu items that cannot be made.
+/// - `GET /api/v1/reservations`: Retrieves reservations, optionally of one day with `?date=YYYY-MM-DD`.
+/// - `POST /api/v1/reservations`: Reserves a table for a party.
+/// - `PUT /api/v1/reservations/{reservation_id}`: Changes a reservation.
+/// - `DELETE /api/v1/reservations/{reservation_id}`: Cancels a reservation.
+///
+/// Invalid JSON request bodies and query strings are rejected with a `400` error in the
+/// same JSON format as the other errors.
 ///
 /// # Arguments
 ///
 /// * `cfg` - A mutable reference to `web::ServiceConfig` to which the routes are added.
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
-    cfg.route(
+    cfg.app_data(web::JsonConfig::default().error_handler(|err, _| {
+        let response = error_response(400, &format!("Invalid request body: {}", err));
+        error::InternalError::from_response(err, response).into()
+    }))
+    .app_data(web::QueryConfig::default().error_handler(|err, _| {
+        let response = error_response(400, &format!("Invalid query: {}", err));
+        error::InternalError::from_response(err, response).into()
+    }))
+    .route(
         "/api/v1/add_item/{table_id}/{item_id}",
         web::post().to(add_item),
     )
@@ -32,11 +94,56 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         "/api/v1/remove_item/{table_id}/{item_id}",
         web::delete().to(remove_item),
     )
+    .route(
+        "/api/v1/remove_line/{table_id}/{line_id}",
+        web::delete().to(remove_line),
+    )
     .route("/api/v1/get_items/{table_id}", web::get().to(get_items))
     .route(
         "/api/v1/get_item/{table_id}/{item_id}",
         web::get().to(get_item),
     )
+    .route("/api/v1/bill/{table_id}", web::get().to(get_bill))
+    .route("/api/v1/split/{table_id}", web::post().to(split_bill))
+    .route("/api/v1/checks/{table_id}", web::get().to(get_checks))
+    .route("/api/v1/pay/{table_id}", web::post().to(pay))
+    .route("/api/v1/tip/{table_id}", web::post().to(tip))
+    .route("/api/v1/payments/{table_id}", web::get().to(get_payments))
+    .route("/api/v1/open_table/{table_id}", web::post().to(open_table))
+    .route(
+        "/api/v1/close_table/{table_id}",
+        web::post().to(close_table),
+    )
+    .route("/api/v1/session/{table_id}", web::get().to(get_session))
+    .route(
+        "/api/v1/allergies/{table_id}",
+        web::post().to(declare_allergies),
+    )
+    .route("/api/v1/sessions", web::get().to(get_sessions))
     .route("/api/v1/tables", web::get().to(get_tables))
-    .route("/api/v1/menus", web::get().to(get_menus));
+    .route("/api/v1/menus", web::get().to(get_menus))
+    .route(
+        "/api/v1/categorized_menu",
+        web::get().to(get_categorized_menu),
+    )
+    .route("/api/v1/categories", web::get().to(get_categories))
+    .route(
+        "/api/v1/category_availability/{category_id}",
+        web::post().to(set_category_availability),
+    )
+    .route("/api/v1/stock", web::get().to(get_stock))
+    .route("/api/v1/stock/{item_id}", web::post().to(set_stock))
+    .route("/api/v1/ingredients", web::get().to(get_ingredients))
+    .route("/api/v1/restock/{ingredient_id}", web::post().to(restock))
+    .route("/api/v1/low_stock", web::get().to(get_low_stock_report))
+    .route("/api/v1/reservations", web::get().to(get_reservations))
+    .route("/api/v1/reservations", web::post().to(make_reservation))
+    .route(
+        "/api/v1/reservations/{reservation_id}",
+        web::put().to(update_reservation),
+    )
+    .route(
+        "/api/v1/reservations/{reservation_id}",
+        web::delete().to(cancel_reservation),
+    );
 }
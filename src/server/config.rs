@@ -0,0 +1,407 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::money::TaxRate;
+use serde::Deserialize;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Configuration used to start the HTTP server.
+///
+/// This struct groups every setting that `server::main::main` needs, so that
+/// the embedded server started by the client and the standalone server binary
+/// share the same way of configuring the server. It can be loaded from a TOML
+/// file, in which case every field is optional and falls back to its default.
+///
+/// - `host`: The address the server binds to.
+/// - `port`: The port the API is served on (HTTPS when `tls` is set).
+/// - `tls`: Optional TLS settings. When `None`, the API is served over plain HTTP.
+/// - `shutdown_timeout_secs`: How long graceful shutdown waits for in-flight requests to drain.
+/// - `tax_rate`: Tax rate in percent applied to bills, e.g. `"8.25"`.
+/// - `faults`: Latency and failures injected into the stores, for resilience testing.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Address to bind the server to.
+    pub host: String,
+    /// Port number to bind the server to.
+    pub port: u16,
+    /// Optional TLS termination settings.
+    pub tls: Option<TlsConfig>,
+    /// Maximum number of seconds to wait for in-flight requests during graceful shutdown.
+    pub shutdown_timeout_secs: u64,
+    /// Tax rate applied to the subtotal of bills. Written as a string to keep it exact.
+    pub tax_rate: TaxRate,
+    /// Faults injected into the stores. No faults are injected by default.
+    pub faults: FaultsConfig,
+}
+
+impl Default for ServerConfig {
+    /// Provides a default configuration serving plain HTTP on 127.0.0.1:8081 with a 30 second drain timeout and no tax.
+    fn default() -> Self {
+        ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port: 8081,
+            tls: None,
+            shutdown_timeout_secs: 30,
+            tax_rate: TaxRate::default(),
+            faults: FaultsConfig::default(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Loads a configuration from a TOML file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the TOML configuration file.
+    ///
+    /// # Returns
+    /// * `Ok(ServerConfig)` - The parsed configuration, with defaults for omitted fields.
+    /// * `Err(io::Error)` - If the file cannot be read or is not a valid configuration.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let config: ServerConfig = toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid configuration file {}: {}", path.display(), e),
+            )
+        })?;
+        config.faults.validate().map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid configuration file {}: {}", path.display(), e),
+            )
+        })?;
+        Ok(config)
+    }
+
+    /// Returns the `host:port` address of the API listener.
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// TLS termination settings for the server.
+///
+/// - `cert_path`: Path to a PEM file containing the certificate chain.
+/// - `key_path`: Path to a PEM file containing the private key.
+/// - `redirect_http_port`: Optional port on which a plain HTTP listener is started
+///   that redirects every request to the HTTPS port.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// Path to the PEM encoded certificate chain.
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded private key.
+    pub key_path: PathBuf,
+    /// Port for the optional HTTP to HTTPS redirect listener.
+    #[serde(default)]
+    pub redirect_http_port: Option<u16>,
+}
+
+/// Faults injected into the stores of the server, for resilience testing.
+///
+/// Each store whose section is present is wrapped in a
+/// `server::data_store::fault_injection::FaultyStore`, e.g.:
+///
+/// ```toml
+/// [faults.orders]
+/// latency_ms = 50
+/// failure_rate = 0.1
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FaultsConfig {
+    /// Faults injected into the menu store.
+    pub menus: Option<FaultConfig>,
+    /// Faults injected into the order store.
+    pub orders: Option<FaultConfig>,
+    /// Faults injected into the table store.
+    pub tables: Option<FaultConfig>,
+}
+
+impl FaultsConfig {
+    /// Returns `true` if faults are injected into any store.
+    pub fn is_enabled(&self) -> bool {
+        self.menus.is_some() || self.orders.is_some() || self.tables.is_some()
+    }
+
+    /// Checks the settings of every store.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If every failure rate is between 0 and 1.
+    /// * `Err(String)` - A description of the first invalid setting.
+    pub fn validate(&self) -> Result<(), String> {
+        for (store, faults) in [
+            ("menus", &self.menus),
+            ("orders", &self.orders),
+            ("tables", &self.tables),
+        ] {
+            if let Some(faults) = faults {
+                faults
+                    .validate()
+                    .map_err(|e| format!("faults.{}: {}", store, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Latency and failures injected into a single store.
+///
+/// - `latency_ms`: Delay added to every call.
+/// - `jitter_ms`: Maximum random delay added on top of `latency_ms`.
+/// - `failure_rate`: Probability, between 0 and 1, that a call fails.
+/// - `poisoned`: Every call fails as if the store's lock were poisoned.
+/// - `seed`: Seed of the random failures and jitter, to make them reproducible.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FaultConfig {
+    /// Milliseconds added to every call.
+    pub latency_ms: u64,
+    /// Maximum number of random milliseconds added to every call.
+    pub jitter_ms: u64,
+    /// Probability that a call fails.
+    pub failure_rate: f64,
+    /// Whether every call fails as if the store's lock were poisoned.
+    pub poisoned: bool,
+    /// Optional seed of the random number generator.
+    pub seed: Option<u64>,
+}
+
+impl FaultConfig {
+    /// Checks that the failure rate is a probability.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If `failure_rate` is between 0 and 1.
+    /// * `Err(String)` - A description of the invalid setting.
+    pub fn validate(&self) -> Result<(), String> {
+        if (0.0..=1.0).contains(&self.failure_rate) {
+            Ok(())
+        } else {
+            Err(format!(
+                "failure_rate must be between 0 and 1, got {}",
+                self.failure_rate
+            ))
+        }
+    }
+}
+
+/// Command line arguments for configuring the server.
+///
+/// Shared by the standalone server binary and the client's embedded server.
+/// Every argument is optional and, when given, overrides the value from the
+/// configuration file or the defaults.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ServerArgs {
+    /// Address to bind the server to [default: 127.0.0.1]
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Port number of the server [default: 8081]
+    #[arg(short, long)]
+    pub port: Option<u16>,
+
+    /// PEM certificate chain used to serve the API over HTTPS (requires --tls-key)
+    #[arg(long, requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key used to serve the API over HTTPS (requires --tls-cert)
+    #[arg(long, requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// Port for a plain HTTP listener that redirects to HTTPS
+    #[arg(long)]
+    pub https_redirect_port: Option<u16>,
+
+    /// Seconds to wait for in-flight requests to drain when shutting down [default: 30]
+    #[arg(long)]
+    pub shutdown_timeout: Option<u64>,
+
+    /// Tax rate in percent applied to bills, e.g. 8.25 [default: 0]
+    #[arg(long, value_name = "PERCENT")]
+    pub tax_rate: Option<TaxRate>,
+}
+
+impl ServerArgs {
+    /// Applies the command line arguments on top of an existing configuration.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration loaded from a file or the defaults.
+    ///
+    /// # Returns
+    /// * `Ok(ServerConfig)` - The configuration with all given arguments applied.
+    /// * `Err(io::Error)` - If `--https-redirect-port` is given without TLS being configured.
+    pub fn apply_to(&self, mut config: ServerConfig) -> io::Result<ServerConfig> {
+        if let Some(host) = &self.host {
+            config.host = host.clone();
+        }
+        if let Some(port) = self.port {
+            config.port = port;
+        }
+        if let Some(timeout) = self.shutdown_timeout {
+            config.shutdown_timeout_secs = timeout;
+        }
+        if let Some(tax_rate) = self.tax_rate {
+            config.tax_rate = tax_rate;
+        }
+        if let (Some(cert_path), Some(key_path)) = (&self.tls_cert, &self.tls_key) {
+            let redirect_http_port = config.tls.and_then(|tls| tls.redirect_http_port);
+            config.tls = Some(TlsConfig {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                redirect_http_port,
+            });
+        }
+        if let Some(redirect_port) = self.https_redirect_port {
+            match config.tls.as_mut() {
+                Some(tls) => tls.redirect_http_port = Some(redirect_port),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "--https-redirect-port requires TLS to be configured",
+                    ))
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_config() {
+        let config: ServerConfig = toml::from_str(
+            r#"
+            host = "0.0.0.0"
+            port = 9443
+            shutdown_timeout_secs = 5
+            tax_rate = "8.25"
+
+            [tls]
+            cert_path = "cert.pem"
+            key_path = "key.pem"
+            redirect_http_port = 9080
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            ServerConfig {
+                host: "0.0.0.0".to_string(),
+                port: 9443,
+                tls: Some(TlsConfig {
+                    cert_path: PathBuf::from("cert.pem"),
+                    key_path: PathBuf::from("key.pem"),
+                    redirect_http_port: Some(9080),
+                }),
+                shutdown_timeout_secs: 5,
+                tax_rate: TaxRate::from_basis_points(825),
+                faults: FaultsConfig::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_fault_config() {
+        let config: ServerConfig = toml::from_str(
+            r#"
+            [faults.orders]
+            latency_ms = 50
+            failure_rate = 0.25
+            seed = 3
+
+            [faults.tables]
+            poisoned = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.faults.is_enabled());
+        assert_eq!(config.faults.menus, None);
+        assert_eq!(
+            config.faults.orders,
+            Some(FaultConfig {
+                latency_ms: 50,
+                failure_rate: 0.25,
+                seed: Some(3),
+                ..Default::default()
+            })
+        );
+        assert!(config.faults.tables.unwrap().poisoned);
+        assert!(!ServerConfig::default().faults.is_enabled());
+    }
+
+    #[test]
+    fn test_validate_failure_rate() {
+        let faults = FaultsConfig {
+            menus: Some(FaultConfig {
+                failure_rate: 1.5,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            faults.validate(),
+            Err("faults.menus: failure_rate must be between 0 and 1, got 1.5".to_string())
+        );
+        assert_eq!(FaultsConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_empty_config_uses_defaults() {
+        let config: ServerConfig = toml::from_str("").unwrap();
+        assert_eq!(config, ServerConfig::default());
+    }
+
+    #[test]
+    fn test_parse_config_unknown_field() {
+        let result: Result<ServerConfig, _> = toml::from_str("prot = 8081");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_config_invalid_tax_rate() {
+        let result: Result<ServerConfig, _> = toml::from_str("tax_rate = \"101\"");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_file_missing() {
+        let err = ServerConfig::from_file(Path::new("does/not/exist.toml")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_args_override_config() {
+        let args = ServerArgs {
+            port: Some(9000),
+            tls_cert: Some(PathBuf::from("cert.pem")),
+            tls_key: Some(PathBuf::from("key.pem")),
+            https_redirect_port: Some(9001),
+            tax_rate: Some(TaxRate::from_basis_points(700)),
+            ..Default::default()
+        };
+        let config = args.apply_to(ServerConfig::default()).unwrap();
+
+        assert_eq!(config.host, "127.0.0.1");
+        assert_eq!(config.tax_rate, TaxRate::from_basis_points(700));
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.tls.unwrap().redirect_http_port, Some(9001));
+    }
+
+    #[test]
+    fn test_args_redirect_without_tls() {
+        let args = ServerArgs {
+            https_redirect_port: Some(9001),
+            ..Default::default()
+        };
+        let err = args.apply_to(ServerConfig::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,383 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::MenuItem;
+use crate::server::data_model::modifier::SelectedModifier;
+use crate::server::utils::error::RestaurantError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// The allergens a menu item can contain, following the major food allergens guests declare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Allergen {
+    Gluten,
+    Dairy,
+    Eggs,
+    Nuts,
+    Peanuts,
+    Fish,
+    Shellfish,
+    Soy,
+    Sesame,
+}
+
+impl Allergen {
+    /// All allergens, in the order they are listed.
+    pub const ALL: [Allergen; 9] = [
+        Allergen::Gluten,
+        Allergen::Dairy,
+        Allergen::Eggs,
+        Allergen::Nuts,
+        Allergen::Peanuts,
+        Allergen::Fish,
+        Allergen::Shellfish,
+        Allergen::Soy,
+        Allergen::Sesame,
+    ];
+
+    /// Returns the name of the allergen as in JSON, e.g. `nuts`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Allergen::Gluten => "gluten",
+            Allergen::Dairy => "dairy",
+            Allergen::Eggs => "eggs",
+            Allergen::Nuts => "nuts",
+            Allergen::Peanuts => "peanuts",
+            Allergen::Fish => "fish",
+            Allergen::Shellfish => "shellfish",
+            Allergen::Soy => "soy",
+            Allergen::Sesame => "sesame",
+        }
+    }
+}
+
+impl fmt::Display for Allergen {
+    /// Formats the allergen as in JSON, e.g. `nuts`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Allergen {
+    type Err = String;
+
+    /// Parses the name of an allergen as in JSON, ignoring case, e.g. `nuts` or `Nuts`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Allergen::ALL
+            .into_iter()
+            .find(|allergen| allergen.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown allergen '{}'. Expected one of: {}",
+                    s,
+                    join(&Allergen::ALL)
+                )
+            })
+    }
+}
+
+/// The diets a menu item is suitable for.
+///
+/// Tags are not inferred from each other, so a vegan menu item must also be tagged as
+/// vegetarian explicitly to match the vegetarian filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Diet {
+    Vegetarian,
+    Vegan,
+    Halal,
+}
+
+impl Diet {
+    /// All diets, in the order they are listed.
+    pub const ALL: [Diet; 3] = [Diet::Vegetarian, Diet::Vegan, Diet::Halal];
+
+    /// Returns the name of the diet as in JSON, e.g. `vegan`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Diet::Vegetarian => "vegetarian",
+            Diet::Vegan => "vegan",
+            Diet::Halal => "halal",
+        }
+    }
+}
+
+impl fmt::Display for Diet {
+    /// Formats the diet as in JSON, e.g. `vegan`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Diet {
+    type Err = String;
+
+    /// Parses the name of a diet as in JSON, ignoring case, e.g. `vegan` or `Vegan`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Diet::ALL
+            .into_iter()
+            .find(|diet| diet.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown diet '{}'. Expected one of: {}",
+                    s,
+                    join(&Diet::ALL)
+                )
+            })
+    }
+}
+
+/// Joins allergens or diets with commas, e.g. `nuts, dairy`.
+pub fn join<T: fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A filter of the menu by allergens and diets.
+///
+/// - `exclude_allergens`: Menu items containing any of these allergens are left out.
+/// - `diets`: Only menu items suitable for all of these diets are kept.
+///
+/// The default filter keeps every menu item.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MenuFilter {
+    pub exclude_allergens: Vec<Allergen>,
+    pub diets: Vec<Diet>,
+}
+
+impl MenuFilter {
+    /// Parses a filter from comma separated lists, as in `?exclude_allergens=nuts,dairy&diet=vegan`.
+    ///
+    /// # Arguments
+    /// * `exclude_allergens` - The allergens to leave out, if any.
+    /// * `diets` - The diets the menu items must be suitable for, if any.
+    ///
+    /// # Returns
+    /// * `Ok(MenuFilter)` - The parsed filter.
+    /// * `Err(RestaurantError::InvalidMenuFilter)` - If an allergen or diet is unknown.
+    pub fn parse(
+        exclude_allergens: Option<&str>,
+        diets: Option<&str>,
+    ) -> Result<Self, RestaurantError> {
+        Ok(MenuFilter {
+            exclude_allergens: parse_list(exclude_allergens)?,
+            diets: parse_list(diets)?,
+        })
+    }
+
+    /// Returns whether the filter keeps every menu item.
+    pub fn is_empty(&self) -> bool {
+        self.exclude_allergens.is_empty() && self.diets.is_empty()
+    }
+
+    /// Returns whether a menu item passes the filter.
+    pub fn matches(&self, item: &MenuItem) -> bool {
+        !item
+            .allergens
+            .iter()
+            .any(|allergen| self.exclude_allergens.contains(allergen))
+            && self.diets.iter().all(|diet| item.diets.contains(diet))
+    }
+
+    /// Formats the filter as the query string of the menu endpoints, e.g.
+    /// `?exclude_allergens=nuts,dairy&diet=vegan`. The default filter has an empty query.
+    pub fn to_query(&self) -> String {
+        let list = |names: Vec<&str>| names.join(",");
+        let mut params = Vec::new();
+        if !self.exclude_allergens.is_empty() {
+            params.push(format!(
+                "exclude_allergens={}",
+                list(self.exclude_allergens.iter().map(|a| a.name()).collect())
+            ));
+        }
+        if !self.diets.is_empty() {
+            params.push(format!(
+                "diet={}",
+                list(self.diets.iter().map(|d| d.name()).collect())
+            ));
+        }
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", params.join("&"))
+        }
+    }
+
+    /// Keeps the menu items that pass the filter, in their order.
+    pub fn apply(&self, items: Vec<MenuItem>) -> Vec<MenuItem> {
+        items
+            .into_iter()
+            .filter(|item| self.matches(item))
+            .collect()
+    }
+}
+
+/// Parses a comma separated list of allergens or diets, ignoring empty entries.
+fn parse_list<T: FromStr<Err = String>>(list: Option<&str>) -> Result<Vec<T>, RestaurantError> {
+    list.unwrap_or_default()
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| entry.parse().map_err(RestaurantError::InvalidMenuFilter))
+        .collect()
+}
+
+/// Returns the allergens of a menu item and its chosen modifiers that are among the
+/// declared allergies, those of the menu item first and each once.
+///
+/// # Arguments
+/// * `item` - The menu item.
+/// * `modifiers` - The modifiers chosen for the item.
+/// * `allergies` - The allergies declared for a table.
+pub fn conflicting_allergens(
+    item: &MenuItem,
+    modifiers: &[SelectedModifier],
+    allergies: &[Allergen],
+) -> Vec<Allergen> {
+    let chosen = item.modifier_groups.iter().flat_map(|group| {
+        group.modifiers.iter().filter(move |modifier| {
+            modifiers.iter().any(|selected| {
+                selected.group_id == group.id && selected.modifier_id == modifier.id
+            })
+        })
+    });
+    let mut conflicts = Vec::new();
+    for allergen in item
+        .allergens
+        .iter()
+        .chain(chosen.flat_map(|modifier| &modifier.allergens))
+    {
+        if allergies.contains(allergen) && !conflicts.contains(allergen) {
+            conflicts.push(*allergen);
+        }
+    }
+    conflicts
+}
+
+/// A request to declare the allergies of the party seated at a table.
+///
+/// - `allergies`: The allergies of the party. An empty list clears them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AllergiesRequest {
+    /// Allergies of the party.
+    pub allergies: Vec<Allergen>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::modifier::{Modifier, ModifierGroup};
+    use crate::server::data_model::money::{Money, PriceDelta};
+
+    fn item(id: u32, allergens: Vec<Allergen>, diets: Vec<Diet>) -> MenuItem {
+        MenuItem {
+            allergens,
+            diets,
+            ..MenuItem::new(id, &format!("Item {}", id), 5, Money::from_cents(1000))
+        }
+    }
+
+    #[test]
+    fn test_parse_names() {
+        assert_eq!("Nuts".parse(), Ok(Allergen::Nuts));
+        assert_eq!(" vegan".parse(), Ok(Diet::Vegan));
+        assert!("kosher"
+            .parse::<Diet>()
+            .unwrap_err()
+            .contains("vegetarian, vegan, halal"));
+        for allergen in Allergen::ALL {
+            assert_eq!(
+                serde_json::to_value(allergen).unwrap(),
+                serde_json::json!(allergen.name())
+            );
+        }
+    }
+
+    #[test]
+    fn test_menu_filter() {
+        let items = vec![
+            item(1, vec![Allergen::Nuts], vec![Diet::Vegetarian, Diet::Vegan]),
+            item(2, vec![Allergen::Dairy], vec![Diet::Vegetarian]),
+            item(3, Vec::new(), vec![Diet::Halal]),
+        ];
+        let ids = |filter: &MenuFilter| -> Vec<u32> {
+            filter
+                .apply(items.clone())
+                .iter()
+                .map(|item| item.id)
+                .collect()
+        };
+
+        let filter = MenuFilter::parse(None, None).unwrap();
+        assert!(filter.is_empty());
+        assert_eq!(ids(&filter), vec![1, 2, 3]);
+        let filter = MenuFilter::parse(Some("nuts,,DAIRY"), None).unwrap();
+        assert_eq!(ids(&filter), vec![3]);
+        let filter = MenuFilter::parse(None, Some("vegetarian")).unwrap();
+        assert_eq!(ids(&filter), vec![1, 2]);
+        let filter = MenuFilter::parse(Some("nuts"), Some("vegetarian")).unwrap();
+        assert_eq!(ids(&filter), vec![2]);
+        assert_eq!(filter.to_query(), "?exclude_allergens=nuts&diet=vegetarian");
+        assert_eq!(MenuFilter::default().to_query(), "");
+        assert!(matches!(
+            MenuFilter::parse(Some("nuts,pollen"), None),
+            Err(RestaurantError::InvalidMenuFilter(_))
+        ));
+    }
+
+    #[test]
+    fn test_conflicting_allergens() {
+        let item = item(1, vec![Allergen::Gluten, Allergen::Nuts], Vec::new());
+        assert_eq!(
+            conflicting_allergens(&item, &[], &[Allergen::Nuts, Allergen::Dairy]),
+            vec![Allergen::Nuts]
+        );
+        assert!(conflicting_allergens(&item, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_conflicting_allergens_of_chosen_modifiers() {
+        let dressing = |id: u32, name: &str, allergens: Vec<Allergen>| Modifier {
+            id,
+            name: name.to_string(),
+            price_delta: PriceDelta::ZERO,
+            allergens,
+        };
+        let salad = MenuItem {
+            modifier_groups: vec![ModifierGroup {
+                id: 1,
+                name: "Dressing".to_string(),
+                required: false,
+                min_selections: 0,
+                max_selections: 1,
+                modifiers: vec![
+                    dressing(1, "Vinaigrette", Vec::new()),
+                    dressing(2, "Caesar", vec![Allergen::Fish, Allergen::Eggs]),
+                ],
+            }],
+            ..item(1, vec![Allergen::Eggs], Vec::new())
+        };
+        let chosen = |modifier_id: u32| SelectedModifier {
+            group_id: 1,
+            modifier_id,
+            name: String::new(),
+            price_delta: PriceDelta::ZERO,
+        };
+        let allergies = [Allergen::Fish, Allergen::Eggs];
+
+        assert_eq!(
+            conflicting_allergens(&salad, &[chosen(1)], &allergies),
+            vec![Allergen::Eggs]
+        );
+        assert_eq!(
+            conflicting_allergens(&salad, &[chosen(2)], &allergies),
+            vec![Allergen::Eggs, Allergen::Fish]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::MenuItem;
+use crate::server::data_model::stock::MenuListing;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Represents an ingredient kept in the kitchen's inventory.
+///
+/// - `id`: A unique identifier for the ingredient.
+/// - `name`: The name of the ingredient.
+/// - `unit`: The unit quantities are counted in, e.g. `g`, `ml` or `pcs`.
+/// - `on_hand`: The quantity in stock.
+/// - `low_stock_threshold`: The ingredient is reported as low on stock at or below this quantity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Ingredient {
+    /// Unique identifier of the ingredient.
+    pub id: u32,
+    /// Name of the ingredient.
+    pub name: String,
+    /// Unit of the quantities, e.g. `g`.
+    pub unit: String,
+    /// Quantity in stock.
+    pub on_hand: u32,
+    /// Quantity at or below which the ingredient is low on stock.
+    pub low_stock_threshold: u32,
+}
+
+impl Ingredient {
+    /// Returns whether the ingredient is at or below its low stock threshold.
+    pub fn is_low(&self) -> bool {
+        self.on_hand <= self.low_stock_threshold
+    }
+}
+
+/// An ingredient of a menu item's recipe, with the quantity used for one portion.
+///
+/// - `ingredient_id`: The ID of the ingredient.
+/// - `quantity`: The quantity used for one portion, in the unit of the ingredient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RecipeIngredient {
+    /// ID of the ingredient.
+    pub ingredient_id: u32,
+    /// Quantity used for one portion.
+    pub quantity: u32,
+}
+
+/// A request to restock an ingredient.
+///
+/// - `quantity`: The quantity delivered, added to the quantity in stock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RestockRequest {
+    /// Quantity delivered.
+    pub quantity: u32,
+}
+
+/// The ingredients running short and the menu items that can no longer be made.
+///
+/// - `ingredients`: Ingredients at or below their low stock threshold, ordered by ID.
+/// - `unavailable_item_ids`: Menu items lacking an ingredient for one more portion, in menu order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LowStockReport {
+    /// Ingredients low on stock.
+    pub ingredients: Vec<Ingredient>,
+    /// IDs of the menu items that cannot be made.
+    pub unavailable_item_ids: Vec<u32>,
+}
+
+impl LowStockReport {
+    /// Builds the low stock report of the inventory.
+    ///
+    /// # Arguments
+    /// * `ingredients` - All ingredients in the inventory.
+    /// * `items` - All menu items.
+    pub fn new(ingredients: Vec<Ingredient>, items: &[MenuItem]) -> Self {
+        let unavailable_item_ids = items
+            .iter()
+            .filter(|item| !can_make(item, &ingredients))
+            .map(|item| item.id)
+            .collect();
+        LowStockReport {
+            ingredients: ingredients.into_iter().filter(Ingredient::is_low).collect(),
+            unavailable_item_ids,
+        }
+    }
+}
+
+/// Sums the quantities of ingredients that a recipe lists more than once, e.g. butter for
+/// the dough and for the filling, so that each ingredient is checked against the
+/// inventory with the full quantity of a portion.
+///
+/// # Arguments
+/// * `recipe` - The ingredients of one portion.
+///
+/// # Returns
+/// * `Vec<RecipeIngredient>` - Each ingredient once, in the order of its first listing.
+pub fn combine_quantities(recipe: &[RecipeIngredient]) -> Vec<RecipeIngredient> {
+    let mut combined: Vec<RecipeIngredient> = Vec::new();
+    for needed in recipe {
+        match combined
+            .iter_mut()
+            .find(|existing| existing.ingredient_id == needed.ingredient_id)
+        {
+            Some(existing) => existing.quantity = existing.quantity.saturating_add(needed.quantity),
+            None => combined.push(*needed),
+        }
+    }
+    combined
+}
+
+/// Returns whether the inventory holds enough of every ingredient for one portion of a
+/// menu item. Ingredients that are not in the inventory are not counted.
+///
+/// # Arguments
+/// * `item` - The menu item.
+/// * `ingredients` - The ingredients in the inventory.
+pub fn can_make(item: &MenuItem, ingredients: &[Ingredient]) -> bool {
+    combine_quantities(&item.ingredients).iter().all(|needed| {
+        ingredients
+            .iter()
+            .find(|ingredient| ingredient.id == needed.ingredient_id)
+            .is_none_or(|ingredient| ingredient.on_hand >= needed.quantity)
+    })
+}
+
+/// Marks listed menu items as sold out if an ingredient runs short.
+///
+/// # Arguments
+/// * `listings` - The listed menu items.
+/// * `ingredients` - The ingredients in the inventory.
+///
+/// # Returns
+/// * `Vec<MenuListing>` - The listed menu items in their order. Items already marked sold
+///   out stay so.
+pub fn with_inventory(listings: Vec<MenuListing>, ingredients: &[Ingredient]) -> Vec<MenuListing> {
+    listings
+        .into_iter()
+        .map(|mut listing| {
+            listing.sold_out |= !can_make(&listing.item, ingredients);
+            listing
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::Money;
+
+    fn item(id: u32, ingredients: Vec<RecipeIngredient>) -> MenuItem {
+        MenuItem {
+            ingredients,
+            ..MenuItem::new(id, &format!("Item {}", id), 5, Money::from_cents(1000))
+        }
+    }
+
+    fn ingredient(id: u32, on_hand: u32) -> Ingredient {
+        Ingredient {
+            id,
+            name: format!("Ingredient {}", id),
+            unit: "g".to_string(),
+            on_hand,
+            low_stock_threshold: 100,
+        }
+    }
+
+    fn needs(ingredient_id: u32, quantity: u32) -> RecipeIngredient {
+        RecipeIngredient {
+            ingredient_id,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn test_can_make() {
+        let ingredients = vec![ingredient(1, 200), ingredient(2, 50)];
+        assert!(can_make(&item(1, vec![needs(1, 200)]), &ingredients));
+        assert!(!can_make(
+            &item(2, vec![needs(1, 100), needs(2, 60)]),
+            &ingredients
+        ));
+        // Ingredients outside the inventory are not counted
+        assert!(can_make(&item(3, vec![needs(9, 1000)]), &ingredients));
+        assert!(can_make(&item(4, Vec::new()), &[]));
+    }
+
+    #[test]
+    fn test_repeated_ingredients_are_combined() {
+        let recipe = [needs(1, 100), needs(2, 10), needs(1, 60)];
+        assert_eq!(
+            combine_quantities(&recipe),
+            vec![needs(1, 160), needs(2, 10)]
+        );
+        let ingredients = vec![ingredient(1, 150), ingredient(2, 50)];
+        assert!(!can_make(&item(1, recipe.to_vec()), &ingredients));
+    }
+
+    #[test]
+    fn test_low_stock_report() {
+        let items = vec![
+            item(1, vec![needs(1, 150)]),
+            item(2, vec![needs(2, 60)]),
+            item(3, Vec::new()),
+        ];
+        let ingredients = vec![ingredient(1, 500), ingredient(2, 50)];
+        let report = LowStockReport::new(ingredients.clone(), &items);
+        assert_eq!(report.ingredients, vec![ingredient(2, 50)]);
+        assert_eq!(report.unavailable_item_ids, vec![2]);
+
+        let listings = items.into_iter().map(MenuListing::from).collect();
+        let sold_out: Vec<bool> = with_inventory(listings, &ingredients)
+            .iter()
+            .map(|listing| listing.sold_out)
+            .collect();
+        assert_eq!(sold_out, vec![false, true, false]);
+    }
+}
@@ -0,0 +1,225 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::stock::MenuListing;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Represents a category of the menu, e.g. starters or desserts.
+///
+/// - `id`: A unique identifier for the category.
+/// - `name`: The name shown to guests.
+/// - `display_order`: Position of the category on the menu. Lower values come first.
+/// - `available`: Whether the category is currently served, e.g. breakfast only in the morning.
+/// - `item_ids`: The menu items of the category, in the order they are listed. An item may
+///   belong to several categories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Category {
+    /// Unique identifier of the category.
+    pub id: u32,
+    /// Name of the category.
+    pub name: String,
+    /// Position of the category on the menu.
+    pub display_order: u32,
+    /// Whether the category is currently served.
+    #[serde(default = "default_available")]
+    pub available: bool,
+    /// IDs of the menu items of the category.
+    pub item_ids: Vec<u32>,
+}
+
+/// Categories are served unless marked otherwise.
+fn default_available() -> bool {
+    true
+}
+
+/// Returns whether a menu item can be ordered with the categories of the menu.
+///
+/// An item is served if it belongs to no category, or to at least one category that is
+/// served; an item listed only in categories that are not served cannot be ordered.
+///
+/// # Arguments
+/// * `item_id` - The ID of the menu item.
+/// * `categories` - The categories of the menu.
+pub fn is_served(item_id: u32, categories: &[Category]) -> bool {
+    let mut listed = categories
+        .iter()
+        .filter(|category| category.item_ids.contains(&item_id))
+        .peekable();
+    listed.peek().is_none() || listed.any(|category| category.available)
+}
+
+/// A request to change whether a category is served.
+///
+/// - `available`: The new availability of the category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryAvailabilityRequest {
+    /// Whether the category is served.
+    pub available: bool,
+}
+
+/// A category of the menu together with its menu items.
+///
+/// - `category_id`, `name` and `available`: As in `Category`.
+/// - `items`: The menu items of the category, in the order of the category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MenuSection {
+    /// ID of the category.
+    pub category_id: u32,
+    /// Name of the category.
+    pub name: String,
+    /// Whether the category is currently served.
+    pub available: bool,
+    /// Menu items of the category.
+    pub items: Vec<MenuListing>,
+}
+
+/// The menu grouped by category, ready to be presented.
+///
+/// - `sections`: One section per category, in display order.
+/// - `uncategorized`: Menu items that belong to no category, in the order of the menu.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CategorizedMenu {
+    /// Sections of the menu, in display order.
+    pub sections: Vec<MenuSection>,
+    /// Menu items without a category.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uncategorized: Vec<MenuListing>,
+}
+
+impl CategorizedMenu {
+    /// Groups the menu items by category.
+    ///
+    /// Categories are ordered by `display_order`, and by ID for equal positions. Item IDs of a
+    /// category that are not on the menu are skipped.
+    ///
+    /// # Arguments
+    /// * `categories` - The categories of the menu.
+    /// * `items` - All listed menu items.
+    ///
+    /// # Returns
+    /// * `CategorizedMenu` - The grouped menu.
+    pub fn new(mut categories: Vec<Category>, items: &[MenuListing]) -> Self {
+        categories.sort_by_key(|category| (category.display_order, category.id));
+        let sections = categories
+            .into_iter()
+            .map(|category| MenuSection {
+                category_id: category.id,
+                name: category.name,
+                available: category.available,
+                items: category
+                    .item_ids
+                    .iter()
+                    .filter_map(|item_id| items.iter().find(|listing| listing.item.id == *item_id))
+                    .cloned()
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        let uncategorized = items
+            .iter()
+            .filter(|listing| {
+                !sections.iter().any(|section| {
+                    section
+                        .items
+                        .iter()
+                        .any(|listed| listed.item.id == listing.item.id)
+                })
+            })
+            .cloned()
+            .collect();
+        CategorizedMenu {
+            sections,
+            uncategorized,
+        }
+    }
+
+    /// Returns the number of distinct menu items on the menu.
+    pub fn item_count(&self) -> usize {
+        let mut item_ids: Vec<u32> = self
+            .sections
+            .iter()
+            .flat_map(|section| section.items.iter())
+            .chain(self.uncategorized.iter())
+            .map(|listing| listing.item.id)
+            .collect();
+        item_ids.sort_unstable();
+        item_ids.dedup();
+        item_ids.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::models::MenuItem;
+    use crate::server::data_model::money::Money;
+
+    fn item(id: u32, name: &str) -> MenuListing {
+        MenuItem::new(id, name, 5, Money::from_cents(500)).into()
+    }
+
+    fn category(id: u32, name: &str, display_order: u32, item_ids: Vec<u32>) -> Category {
+        Category {
+            id,
+            name: name.to_string(),
+            display_order,
+            available: true,
+            item_ids,
+        }
+    }
+
+    #[test]
+    fn test_categorized_menu_orders_sections_and_items() {
+        let items = [
+            item(1, "Salad"),
+            item(2, "Steak"),
+            item(3, "Fries"),
+            item(4, "Cake"),
+        ];
+        let categories = vec![
+            category(7, "Mains", 2, vec![2, 3]),
+            category(3, "Desserts", 3, vec![4, 99]),
+            category(5, "Starters", 1, vec![3, 1]),
+        ];
+        let menu = CategorizedMenu::new(categories, &items);
+
+        let names: Vec<&str> = menu.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Starters", "Mains", "Desserts"]);
+        assert_eq!(
+            menu.sections[0].items,
+            vec![item(3, "Fries"), item(1, "Salad")]
+        );
+        assert_eq!(menu.sections[2].items, vec![item(4, "Cake")]);
+        assert!(menu.uncategorized.is_empty());
+        assert_eq!(menu.item_count(), 4);
+    }
+
+    #[test]
+    fn test_is_served() {
+        let breakfast = Category {
+            available: false,
+            ..category(1, "Breakfast", 1, vec![1, 2])
+        };
+        let drinks = category(2, "Drinks", 2, vec![2]);
+        let categories = [breakfast, drinks];
+        assert!(!is_served(1, &categories));
+        assert!(is_served(2, &categories));
+        assert!(is_served(3, &categories));
+    }
+
+    #[test]
+    fn test_categorized_menu_collects_uncategorized_items() {
+        let items = [item(1, "Salad"), item(2, "Steak")];
+        let menu = CategorizedMenu::new(vec![category(1, "Starters", 1, vec![1])], &items);
+        assert_eq!(menu.uncategorized, vec![item(2, "Steak")]);
+
+        let json = serde_json::to_value(CategorizedMenu::new(vec![], &[])).unwrap();
+        assert_eq!(json, serde_json::json!({"sections": []}));
+        let parsed: Category = serde_json::from_value(
+            serde_json::json!({"id": 1, "name": "Drinks", "display_order": 4, "item_ids": []}),
+        )
+        .unwrap();
+        assert!(parsed.available);
+    }
+}
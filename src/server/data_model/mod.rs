@@ -1,4 +1,14 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+pub mod dietary;
+pub mod inventory;
+pub mod menu;
 pub mod models;
+pub mod modifier;
+pub mod money;
+pub mod payment;
+pub mod reservation;
+pub mod session;
+pub mod split;
+pub mod stock;
@@ -1,6 +1,18 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+use crate::server::data_model::dietary::{Allergen, Diet, MenuFilter};
+use crate::server::data_model::inventory::{Ingredient, LowStockReport, RecipeIngredient};
+use crate::server::data_model::menu::{CategorizedMenu, Category};
+use crate::server::data_model::modifier::{ItemOptions, ModifierGroup, OrderLine, OrderedItem};
+use crate::server::data_model::money::{Money, TaxRate};
+use crate::server::data_model::payment::{
+    Payment, PaymentMethod, PaymentReceipt, PaymentRequest, TableAccount,
+};
+use crate::server::data_model::reservation::{Reservation, ReservationRequest, TableStatus};
+use crate::server::data_model::session::TableSession;
+use crate::server::data_model::split::{SplitBill, SplitRequest};
+use crate::server::data_model::stock::{MenuListing, StockLevel};
 use crate::server::utils::error::RestaurantError;
 use mockall::automock;
 use serde::{Deserialize, Serialize};
@@ -12,7 +24,13 @@ use utoipa::ToSchema;
 /// - `id`: A unique identifier for the menu item.
 /// - `name`: The name of the menu item.
 /// - `cooking_time_minutes`: The time it takes to prepare the item in minutes.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+/// - `price`: The exact price of the menu item.
+/// - `allergens`: The allergens the menu item contains without modifiers. Modifiers list
+///   the allergens they add themselves.
+/// - `diets`: The diets the menu item is suitable for, e.g. vegan, whichever modifiers are chosen.
+/// - `ingredients`: The ingredients used for one portion, deducted from the inventory when ordered.
+/// - `modifier_groups`: The modifiers guests can choose from when ordering the item.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, ToSchema)]
 pub struct MenuItem {
     /// Unique identifier of the menu item.
     pub id: u32,
@@ -20,16 +38,157 @@ pub struct MenuItem {
     pub name: String,
     /// The cooking time required for this menu item (in minutes).
     pub cooking_time_minutes: u64,
+    /// The price of the menu item, as a decimal string with two fraction digits.
+    /// Defaults to zero when missing, e.g. in responses of older servers.
+    #[serde(default)]
+    #[schema(value_type = String, example = "12.50")]
+    pub price: Money,
+    /// Allergens the menu item contains.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allergens: Vec<Allergen>,
+    /// Diets the menu item is suitable for with any choice of modifiers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diets: Vec<Diet>,
+    /// Ingredients used for one portion of the menu item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingredients: Vec<RecipeIngredient>,
+    /// Groups of modifiers that can be chosen for the item, e.g. its extras.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifier_groups: Vec<ModifierGroup>,
+}
+
+impl MenuItem {
+    /// Creates a menu item without allergens, diets, ingredients or modifiers.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the menu item.
+    /// * `name` - The name of the menu item.
+    /// * `cooking_time_minutes` - The time it takes to prepare the item.
+    /// * `price` - The price of the menu item.
+    pub fn new(id: u32, name: &str, cooking_time_minutes: u64, price: Money) -> Self {
+        MenuItem {
+            id,
+            name: name.to_string(),
+            cooking_time_minutes,
+            price,
+            ..Default::default()
+        }
+    }
+}
+
+/// Represents a line of a table's bill: a menu item with the number of times it was ordered.
+///
+/// - `item_id`: The ID of the menu item.
+/// - `name`: The name of the menu item.
+/// - `modifiers`: The names of the modifiers chosen for the item.
+/// - `quantity`: How many times the item was ordered.
+/// - `unit_price`: The price of a single item, including its modifiers.
+/// - `amount`: The price of all ordered items of the line.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct BillLine {
+    /// ID of the ordered menu item.
+    pub item_id: u32,
+    /// Name of the ordered menu item.
+    pub name: String,
+    /// Names of the modifiers chosen for the item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+    /// Number of times the item was ordered.
+    pub quantity: u32,
+    /// Price of a single item.
+    #[schema(value_type = String, example = "12.50")]
+    pub unit_price: Money,
+    /// `unit_price` multiplied by `quantity`.
+    #[schema(value_type = String, example = "25.00")]
+    pub amount: Money,
+}
+
+/// Represents the itemized bill of a table, built from its current order.
+///
+/// - `table_id`: The ID of the billed table.
+/// - `lines`: One line per distinct menu item and choice of modifiers, in order of first arrival.
+/// - `subtotal`: The sum of the line amounts.
+/// - `tax_rate`: The tax rate in percent.
+/// - `tax`: The tax on the subtotal, rounded half a cent up.
+/// - `total`: The subtotal plus tax.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Bill {
+    /// ID of the billed table.
+    pub table_id: u32,
+    /// Lines of the bill.
+    pub lines: Vec<BillLine>,
+    /// Sum of the line amounts.
+    #[schema(value_type = String, example = "25.00")]
+    pub subtotal: Money,
+    /// Tax rate in percent.
+    #[schema(value_type = String, example = "8.25")]
+    pub tax_rate: TaxRate,
+    /// Tax on the subtotal.
+    #[schema(value_type = String, example = "2.06")]
+    pub tax: Money,
+    /// Amount to pay.
+    #[schema(value_type = String, example = "27.06")]
+    pub total: Money,
+}
+
+impl Bill {
+    /// Builds the bill of a table from its ordered items.
+    ///
+    /// # Arguments
+    /// * `table_id` - The ID of the billed table.
+    /// * `items` - The menu items ordered for the table, in order of arrival.
+    /// * `tax_rate` - The tax rate applied to the subtotal.
+    ///
+    /// # Returns
+    /// * `Bill` - The bill, with menu items ordered repeatedly with the same modifiers
+    ///   grouped into one line.
+    pub fn new(table_id: u32, items: &[OrderedItem], tax_rate: TaxRate) -> Self {
+        let mut lines: Vec<BillLine> = Vec::new();
+        for item in items {
+            let modifiers: Vec<String> = item.modifiers.iter().map(|m| m.name.clone()).collect();
+            match lines.iter_mut().find(|line| {
+                line.item_id == item.id
+                    && line.modifiers == modifiers
+                    && line.unit_price == item.price
+            }) {
+                Some(line) => {
+                    line.quantity += 1;
+                    line.amount = line.amount + item.price;
+                }
+                None => lines.push(BillLine {
+                    item_id: item.id,
+                    name: item.name.clone(),
+                    modifiers,
+                    quantity: 1,
+                    unit_price: item.price,
+                    amount: item.price,
+                }),
+            }
+        }
+        let subtotal: Money = lines.iter().map(|line| line.amount).sum();
+        let tax = tax_rate.tax_on(subtotal);
+        Bill {
+            table_id,
+            lines,
+            subtotal,
+            tax_rate,
+            tax,
+            total: subtotal + tax,
+        }
+    }
 }
 
 /// The `MenuStore` trait defines the behavior of a menu store.
 ///
 /// This trait abstracts the functionality for accessing and managing
-/// the restaurant's menu items. A struct implementing this trait can
-/// retrieve all available menu items.
+/// the restaurant's menu items and their categories. A struct implementing
+/// this trait can retrieve all available menu items and categories.
 ///
 /// # Methods
 /// - `get_all_menus`: Retrieves all menu items in the store.
+/// - `get_categories`: Retrieves all categories of the menu.
+/// - `set_category_availability`: Marks a category as served or not served.
+/// - `shutdown`: Flushes any persistent state before the server exits.
 #[automock]
 pub trait MenuStore: Send + Sync {
     /// Retrieves all menu items from the store.
@@ -39,15 +198,55 @@ pub trait MenuStore: Send + Sync {
     /// - `Ok(Vec<MenuItem>)` with a list of all menu items if successful.
     /// - `Err(RestaurantError)` if there is a failure.
     fn get_all_menus(&self) -> Result<Vec<MenuItem>, RestaurantError>;
+
+    /// Retrieves all categories of the menu.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Category>)` with all categories, in no particular order.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_categories(&self) -> Result<Vec<Category>, RestaurantError>;
+
+    /// Marks a category as served or not served.
+    ///
+    /// # Parameters
+    /// - `category_id`: The ID of the category.
+    /// - `available`: Whether the category is served.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Category)` with the updated category.
+    /// - `Err(RestaurantError::CategoryNotFound)` if the category does not exist.
+    fn set_category_availability(
+        &self,
+        category_id: u32,
+        available: bool,
+    ) -> Result<Category, RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped accepting requests and all
+    /// in-flight requests have drained. The default implementation does nothing,
+    /// which is sufficient for stores that keep their state only in memory.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
 }
 
 /// The `TableStore` trait defines the behavior of a table store.
 ///
 /// This trait provides functionality to retrieve all available tables
-/// in the restaurant.
+/// in the restaurant and the number of guests they seat.
 ///
 /// # Methods
 /// - `get_all_tables`: Retrieves all available table IDs.
+/// - `get_table_capacity`: Retrieves the number of guests a table seats.
+/// - `shutdown`: Flushes any persistent state before the server exits.
 #[automock]
 pub trait TableStore: Send + Sync {
     /// Retrieves all table IDs in the store.
@@ -57,6 +256,31 @@ pub trait TableStore: Send + Sync {
     /// - `Ok(Vec<u32>)` with a list of all table IDs if successful.
     /// - `Err(RestaurantError)` if there is a failure.
     fn get_all_tables(&self) -> Result<Vec<u32>, RestaurantError>;
+
+    /// Retrieves the number of guests a table seats.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(u32)` with the capacity of the table.
+    /// - `Err(RestaurantError::TableNotFound)` if the table does not exist.
+    /// - `Err(RestaurantError)` if there is another failure.
+    fn get_table_capacity(&self, table_id: u32) -> Result<u32, RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
 }
 
 /// The `OrderStore` trait defines the behavior of an order store.
@@ -66,13 +290,33 @@ pub trait TableStore: Send + Sync {
 /// with a table.
 ///
 /// # Methods
-/// - `add_item`: Adds an item to the order for a specific table.
+/// - `add_line`: Adds an item with its modifiers and note to the order for a specific table.
+/// - `add_item`: Adds an item without modifiers or note to the order for a specific table.
 /// - `remove_item`: Removes an item from the order for a specific table.
+/// - `remove_line`: Removes a specific line from the order for a specific table.
+/// - `get_lines`: Retrieves all order lines for a specific table.
 /// - `get_item_ids`: Retrieves all item IDs for a specific table.
 /// - `get_item_id`: Retrieves a specific item ID for a table.
+/// - `shutdown`: Flushes any persistent state before the server exits.
 #[automock]
 pub trait OrderStore: Send + Sync {
-    /// Adds a menu item to a table's order.
+    /// Adds a line with a menu item, its modifiers and its note to a table's order.
+    ///
+    /// The store numbers the lines of each table consecutively, starting at 1, and never
+    /// reuses a number for the same table, so that a line removed by number is never
+    /// confused with a line added later.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table placing the order.
+    /// - `line`: The order line, already validated against the menu.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the line was successfully added.
+    /// - `Err(RestaurantError)` if there was a failure.
+    fn add_line(&self, table_id: u32, line: OrderLine) -> Result<(), RestaurantError>;
+
+    /// Adds a menu item without modifiers or note to a table's order.
     ///
     /// # Parameters
     /// - `table_id`: The ID of the table placing the order.
@@ -82,7 +326,9 @@ pub trait OrderStore: Send + Sync {
     /// A `Result` which is:
     /// - `Ok(())` if the item was successfully added.
     /// - `Err(RestaurantError)` if there was a failure.
-    fn add_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError>;
+    fn add_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError> {
+        self.add_line(table_id, OrderLine::plain(item_id))
+    }
 
     /// Removes a menu item from a table's order.
     ///
@@ -96,6 +342,30 @@ pub trait OrderStore: Send + Sync {
     /// - `Err(RestaurantError)` if there was a failure.
     fn remove_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError>;
 
+    /// Removes a line from a table's order by its number.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table from which the line is being removed.
+    /// - `line_id`: The number of the order line.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(OrderLine)` with the removed line.
+    /// - `Err(RestaurantError::NoMenusForTable)` if the table has no order.
+    /// - `Err(RestaurantError::OrderLineNotFound)` if the order has no line with the number.
+    fn remove_line(&self, table_id: u32, line_id: u32) -> Result<OrderLine, RestaurantError>;
+
+    /// Retrieves all lines of a table's order, in order of arrival.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table whose order lines are being retrieved.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<OrderLine>)` with the lines of the table's order.
+    /// - `Err(RestaurantError::NoMenusForTable)` if the table has no order.
+    fn get_lines(&self, table_id: u32) -> Result<Vec<OrderLine>, RestaurantError>;
+
     /// Retrieves all item IDs ordered by a specific table.
     ///
     /// # Parameters
@@ -118,6 +388,520 @@ pub trait OrderStore: Send + Sync {
     /// - `Ok(u32)` with the item ID if successful.
     /// - `Err(RestaurantError)` if there was a failure.
     fn get_item_id(&self, table_id: u32, item_id: u32) -> Result<u32, RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `CheckStore` trait defines the behavior of a store for split bills.
+///
+/// This trait keeps the checks a table's bill was split into, so that each
+/// check can be retrieved and paid independently.
+///
+/// # Methods
+/// - `save_split`: Stores the split bill of a table, replacing a previous split.
+/// - `get_split`: Retrieves the split bill of a table.
+/// - `remove_split`: Removes the split bill of a table.
+/// - `shutdown`: Flushes any persistent state before the server exits.
+#[automock]
+pub trait CheckStore: Send + Sync {
+    /// Stores the split bill of a table, replacing any previous split of the table.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table whose bill was split.
+    /// - `split`: The bill and its checks.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the split was stored.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn save_split(&self, table_id: u32, split: SplitBill) -> Result<(), RestaurantError>;
+
+    /// Retrieves the split bill of a table.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(SplitBill)` with the bill and its checks.
+    /// - `Err(RestaurantError::NoSplitForTable)` if the bill of the table has not been split.
+    fn get_split(&self, table_id: u32) -> Result<SplitBill, RestaurantError>;
+
+    /// Removes the split bill of a table, e.g. once the bill is paid.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the split was removed, or the bill was not split.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn remove_split(&self, table_id: u32) -> Result<(), RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `PaymentStore` trait defines the behavior of a store for payments.
+///
+/// This trait keeps the payments made towards the current bill of each table.
+///
+/// # Methods
+/// - `record_payment`: Records a payment towards the bill of a table.
+/// - `get_payments`: Retrieves the payments towards the bill of a table.
+/// - `clear_payments`: Removes the payments of a table once its bill is settled.
+/// - `shutdown`: Flushes any persistent state before the server exits.
+#[automock]
+pub trait PaymentStore: Send + Sync {
+    /// Records a payment towards the bill of the payment's table.
+    ///
+    /// # Parameters
+    /// - `payment`: The payment, approved by the payment provider.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the payment was recorded.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn record_payment(&self, payment: Payment) -> Result<(), RestaurantError>;
+
+    /// Retrieves the payments towards the current bill of a table.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Payment>)` with the payments in the order they were made, empty if there are none.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_payments(&self, table_id: u32) -> Result<Vec<Payment>, RestaurantError>;
+
+    /// Removes the payments of a table, so that the next bill of the table starts unpaid.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the payments were removed.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn clear_payments(&self, table_id: u32) -> Result<(), RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `SessionStore` trait defines the behavior of a store for table sessions.
+///
+/// This trait keeps the active session of each table and the history of closed sessions.
+///
+/// # Methods
+/// - `open_session`: Opens a new session for a table.
+/// - `get_active_session`: Retrieves the active session of a table.
+/// - `set_allergies`: Sets the allergies declared by the party at a table.
+/// - `close_session`: Closes the active session of a table and archives it with its payments.
+/// - `record_tip`: Adds a tip to a closed session.
+/// - `get_history`: Retrieves closed sessions.
+/// - `shutdown`: Flushes any persistent state before the server exits.
+#[automock]
+pub trait SessionStore: Send + Sync {
+    /// Opens a new session for a table and assigns it a unique session ID.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    /// - `party_size`: The number of guests, if known.
+    /// - `opened_at`: When the table was opened, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the new session.
+    /// - `Err(RestaurantError::SessionAlreadyOpen)` if the table has an active session.
+    fn open_session(
+        &self,
+        table_id: u32,
+        party_size: Option<u32>,
+        opened_at: u64,
+    ) -> Result<TableSession, RestaurantError>;
+
+    /// Retrieves the active session of a table.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the active session.
+    /// - `Err(RestaurantError::NoActiveSession)` if the table is not open.
+    fn get_active_session(&self, table_id: u32) -> Result<TableSession, RestaurantError>;
+
+    /// Sets the allergies declared by the party at a table, replacing earlier ones.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    /// - `allergies`: The allergies of the party.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the updated active session.
+    /// - `Err(RestaurantError::NoActiveSession)` if the table is not open.
+    fn set_allergies(
+        &self,
+        table_id: u32,
+        allergies: Vec<Allergen>,
+    ) -> Result<TableSession, RestaurantError>;
+
+    /// Closes the active session of a table and moves it to the history.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    /// - `closed_at`: When the table was closed, in seconds since the Unix epoch.
+    /// - `bill`: The final bill of the party.
+    /// - `payments`: The payments and tips of the party.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the closed session.
+    /// - `Err(RestaurantError::NoActiveSession)` if the table is not open.
+    fn close_session(
+        &self,
+        table_id: u32,
+        closed_at: u64,
+        bill: Bill,
+        payments: Vec<Payment>,
+    ) -> Result<TableSession, RestaurantError>;
+
+    /// Adds a tip left after the bill was settled to a closed session.
+    ///
+    /// # Parameters
+    /// - `session_id`: The ID of the closed session.
+    /// - `payment`: The tip, recorded as a payment without an amount.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the updated session.
+    /// - `Err(RestaurantError::InvalidPayment)` if there is no closed session with the ID.
+    fn record_tip(
+        &self,
+        session_id: u64,
+        payment: Payment,
+    ) -> Result<TableSession, RestaurantError>;
+
+    /// Retrieves closed sessions, in the order they were closed.
+    ///
+    /// # Parameters
+    /// - `table_id`: Only sessions of this table if set, of all tables otherwise.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<TableSession>)` with the closed sessions.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_history(&self, table_id: Option<u32>) -> Result<Vec<TableSession>, RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `StockStore` trait defines the behavior of a store for the stock of menu items.
+///
+/// Only menu items whose stock is counted or that are marked sold out have a stock level;
+/// all other menu items can always be ordered.
+///
+/// # Methods
+/// - `get_stock_levels`: Retrieves the stock levels of counted or sold out menu items.
+/// - `set_stock`: Sets the stock of a menu item.
+/// - `take`: Takes one portion of a menu item from stock when it is ordered.
+/// - `restore`: Puts one portion of a menu item back when it is removed from an order.
+/// - `shutdown`: Flushes any persistent state before the server exits.
+#[automock]
+pub trait StockStore: Send + Sync {
+    /// Retrieves the stock levels of all counted or sold out menu items, ordered by item ID.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<StockLevel>)` with the stock levels.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_stock_levels(&self) -> Result<Vec<StockLevel>, RestaurantError>;
+
+    /// Sets the stock of a menu item, replacing the previous count and sold out mark.
+    ///
+    /// # Parameters
+    /// - `item_id`: The ID of the menu item.
+    /// - `remaining`: Portions in stock, or `None` to stop counting.
+    /// - `sold_out`: Whether the menu item is marked as sold out.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(StockLevel)` with the new stock level.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn set_stock(
+        &self,
+        item_id: u32,
+        remaining: Option<u32>,
+        sold_out: bool,
+    ) -> Result<StockLevel, RestaurantError>;
+
+    /// Takes one portion of a menu item from stock. Checking and decrementing the count is
+    /// atomic, so that two orders cannot both take the last portion.
+    ///
+    /// # Parameters
+    /// - `item_id`: The ID of the menu item.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the item is available. Counted stock is decremented.
+    /// - `Err(RestaurantError::ItemUnavailable)` if the item is sold out or out of stock.
+    fn take(&self, item_id: u32) -> Result<(), RestaurantError>;
+
+    /// Puts one portion of a menu item back into stock. Does nothing if the stock of the
+    /// item is not counted.
+    ///
+    /// # Parameters
+    /// - `item_id`: The ID of the menu item.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the portion was put back.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn restore(&self, item_id: u32) -> Result<(), RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `InventoryStore` trait defines the behavior of a store for the kitchen's ingredients.
+///
+/// Ordering a menu item deducts the ingredients of its recipe, and removing it from an
+/// order restores them. Ingredients that are not in the store are not counted.
+///
+/// # Methods
+/// - `get_ingredients`: Retrieves all ingredients with their quantities in stock.
+/// - `restock`: Adds a delivered quantity of an ingredient.
+/// - `deduct`: Deducts the ingredients of one portion of a menu item.
+/// - `restore`: Puts the ingredients of one portion of a menu item back.
+/// - `shutdown`: Flushes any persistent state before the server exits.
+#[automock]
+pub trait InventoryStore: Send + Sync {
+    /// Retrieves all ingredients, ordered by ID.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Ingredient>)` with the ingredients.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_ingredients(&self) -> Result<Vec<Ingredient>, RestaurantError>;
+
+    /// Adds a delivered quantity of an ingredient to its stock.
+    ///
+    /// # Parameters
+    /// - `ingredient_id`: The ID of the ingredient.
+    /// - `quantity`: The quantity delivered.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Ingredient)` with the restocked ingredient.
+    /// - `Err(RestaurantError::IngredientNotFound)` if the ingredient does not exist.
+    fn restock(&self, ingredient_id: u32, quantity: u32) -> Result<Ingredient, RestaurantError>;
+
+    /// Deducts the ingredients of one portion of a menu item. Either all ingredients are
+    /// deducted or, if one runs short, none is.
+    ///
+    /// # Parameters
+    /// - `item_id`: The ID of the menu item, for the error.
+    /// - `recipe`: The ingredients of one portion.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the ingredients were deducted.
+    /// - `Err(RestaurantError::ItemUnavailable)` if an ingredient runs short.
+    fn deduct(&self, item_id: u32, recipe: &[RecipeIngredient]) -> Result<(), RestaurantError>;
+
+    /// Puts the ingredients of one portion of a menu item back into stock.
+    ///
+    /// # Parameters
+    /// - `recipe`: The ingredients of one portion.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the ingredients were put back.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn restore(&self, recipe: &[RecipeIngredient]) -> Result<(), RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `ReservationStore` trait defines the behavior of a store for table reservations.
+///
+/// The store keeps reservations from the moment they are made until they are cancelled,
+/// and refuses reservations overlapping another reservation of the same table.
+///
+/// # Methods
+/// - `get_reservations`: Retrieves all reservations.
+/// - `get`: Retrieves a single reservation.
+/// - `create`: Makes a reservation and assigns it an ID.
+/// - `update`: Replaces the details of a reservation.
+/// - `cancel`: Cancels a reservation.
+/// - `shutdown`: Flushes any persistent state before the server exits.
+#[automock]
+pub trait ReservationStore: Send + Sync {
+    /// Retrieves all reservations, ordered by the start of their slot.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Reservation>)` with the reservations.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_reservations(&self) -> Result<Vec<Reservation>, RestaurantError>;
+
+    /// Retrieves a reservation.
+    ///
+    /// # Parameters
+    /// - `reservation_id`: The ID of the reservation.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the reservation.
+    /// - `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    fn get(&self, reservation_id: u64) -> Result<Reservation, RestaurantError>;
+
+    /// Makes a reservation and assigns it a unique reservation ID.
+    ///
+    /// # Parameters
+    /// - `request`: The table, party and slot, already validated.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the new reservation.
+    /// - `Err(RestaurantError::ReservationConflict)` if the slot overlaps another
+    ///   reservation of the table.
+    fn create(&self, request: &ReservationRequest) -> Result<Reservation, RestaurantError>;
+
+    /// Replaces the table, party and slot of a reservation, keeping its ID.
+    ///
+    /// # Parameters
+    /// - `reservation_id`: The ID of the reservation.
+    /// - `request`: The new table, party and slot, already validated.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the updated reservation.
+    /// - `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    /// - `Err(RestaurantError::ReservationConflict)` if the new slot overlaps another
+    ///   reservation of the table.
+    fn update(
+        &self,
+        reservation_id: u64,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, RestaurantError>;
+
+    /// Cancels a reservation.
+    ///
+    /// # Parameters
+    /// - `reservation_id`: The ID of the reservation.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the cancelled reservation.
+    /// - `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    fn cancel(&self, reservation_id: u64) -> Result<Reservation, RestaurantError>;
+
+    /// Flushes any persistent state held by the store.
+    ///
+    /// Called once after the server has stopped and all in-flight requests have drained.
+    /// The default implementation does nothing.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the state was flushed successfully.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        Ok(())
+    }
+}
+
+/// The `PaymentProvider` trait defines how payments of one method are taken.
+///
+/// A provider either approves a charge and returns a reference for it, or declines it.
+/// The restaurant has one provider per `PaymentMethod`; `server::payment` contains a
+/// cash provider and a mock card provider that decides locally.
+///
+/// # Methods
+/// - `method`: The payment method handled by the provider.
+/// - `charge`: Takes the amount and tip of a payment.
+#[automock]
+pub trait PaymentProvider: Send + Sync {
+    /// Returns the payment method handled by the provider.
+    fn method(&self) -> PaymentMethod;
+
+    /// Takes the amount and the tip of a payment.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table the payment is for.
+    /// - `request`: The payment, already validated against the table's balance.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(String)` with the provider's reference of the approved payment.
+    /// - `Err(RestaurantError::PaymentDeclined)` if the payment was declined.
+    fn charge(&self, table_id: u32, request: &PaymentRequest) -> Result<String, RestaurantError>;
 }
 
 /// The `Restaurant` trait combines `MenuStore`, `OrderStore`, and `TableStore`
@@ -129,11 +913,36 @@ pub trait OrderStore: Send + Sync {
 ///
 /// # Methods
 /// - `get_all_menus`: Retrieves all menu items.
+/// - `get_menus`: Retrieves the menu items passing a filter by allergens and diets, with their stock.
+/// - `get_stock`: Retrieves the stock levels of counted or sold out menu items.
+/// - `set_stock`: Sets the stock of a menu item, or marks it as sold out.
+/// - `get_ingredients`: Retrieves the ingredients in the inventory.
+/// - `restock`: Adds a delivered quantity of an ingredient.
+/// - `get_low_stock_report`: Reports ingredients low on stock and menu items that cannot be made.
 /// - `get_all_tables`: Retrieves all available tables.
+/// - `get_table_statuses`: Retrieves all tables with their capacity and current reservation.
 /// - `add_item`: Adds a menu item to a table's order.
+/// - `add_item_with_options`: Adds a menu item with modifiers and a note to a table's order.
 /// - `remove_item`: Removes a menu item from a table's order.
+/// - `remove_line`: Removes a line from a table's order by its number.
 /// - `get_items`: Retrieves all menu items ordered at a table.
 /// - `get_item`: Retrieves a specific menu item ordered at a table.
+/// - `get_bill`: Builds the itemized bill of a table.
+/// - `split_bill`: Splits the bill of a table into checks.
+/// - `get_checks`: Retrieves the checks of a table's split bill.
+/// - `pay`: Records a payment and tip towards the bill of a table.
+/// - `get_account`: Retrieves the payment state of a table's bill.
+/// - `open_table`: Opens a session for a party at a table.
+/// - `close_table`: Closes the session of a table and frees the table.
+/// - `get_session`: Retrieves the active session of a table.
+/// - `declare_allergies`: Declares the allergies of the party at a table.
+/// - `get_session_history`: Retrieves closed sessions.
+/// - `get_reservations`: Retrieves reservations, optionally of a single day.
+/// - `get_reservation`: Retrieves a single reservation.
+/// - `make_reservation`: Reserves a table for a party.
+/// - `update_reservation`: Changes the table, party or slot of a reservation.
+/// - `cancel_reservation`: Cancels a reservation.
+/// - `shutdown`: Flushes the state of all underlying stores.
 #[automock]
 pub trait Restaurant: Send + Sync {
     /// Retrieves all menu items in the restaurant.
@@ -144,6 +953,107 @@ pub trait Restaurant: Send + Sync {
     /// - `Err(RestaurantError)` if there is a failure.
     fn get_all_menus(&self) -> Result<Vec<MenuItem>, RestaurantError>;
 
+    /// Retrieves the menu items passing a filter by allergens and diets, with their stock.
+    ///
+    /// # Parameters
+    /// - `filter`: The allergens to leave out and the diets to keep.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<MenuListing>)` with the matching menu items, in menu order.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_menus(&self, filter: &MenuFilter) -> Result<Vec<MenuListing>, RestaurantError>;
+
+    /// Retrieves all categories of the menu, in display order.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Category>)` with all categories.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_categories(&self) -> Result<Vec<Category>, RestaurantError>;
+
+    /// Retrieves the menu grouped by category.
+    ///
+    /// # Parameters
+    /// - `filter`: The allergens to leave out and the diets to keep.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(CategorizedMenu)` with one section per category, in display order.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_categorized_menu(&self, filter: &MenuFilter)
+        -> Result<CategorizedMenu, RestaurantError>;
+
+    /// Marks a category of the menu as served or not served.
+    ///
+    /// # Parameters
+    /// - `category_id`: The ID of the category.
+    /// - `available`: Whether the category is served.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Category)` with the updated category.
+    /// - `Err(RestaurantError)` if the category does not exist or there is a failure.
+    fn set_category_availability(
+        &self,
+        category_id: u32,
+        available: bool,
+    ) -> Result<Category, RestaurantError>;
+
+    /// Retrieves the stock levels of all counted or sold out menu items.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<StockLevel>)` with the stock levels, ordered by item ID.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_stock(&self) -> Result<Vec<StockLevel>, RestaurantError>;
+
+    /// Sets the stock of a menu item, or marks it as sold out.
+    ///
+    /// # Parameters
+    /// - `item_id`: The ID of the menu item.
+    /// - `remaining`: Portions in stock, or `None` to stop counting.
+    /// - `sold_out`: Whether the menu item is sold out.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(StockLevel)` with the new stock level.
+    /// - `Err(RestaurantError)` if the menu item does not exist or there is a failure.
+    fn set_stock(
+        &self,
+        item_id: u32,
+        remaining: Option<u32>,
+        sold_out: bool,
+    ) -> Result<StockLevel, RestaurantError>;
+
+    /// Retrieves all ingredients in the inventory with their quantities in stock.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Ingredient>)` with the ingredients, ordered by ID.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_ingredients(&self) -> Result<Vec<Ingredient>, RestaurantError>;
+
+    /// Adds a delivered quantity of an ingredient to the inventory.
+    ///
+    /// # Parameters
+    /// - `ingredient_id`: The ID of the ingredient.
+    /// - `quantity`: The quantity delivered.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Ingredient)` with the restocked ingredient.
+    /// - `Err(RestaurantError)` if the ingredient does not exist or there is a failure.
+    fn restock(&self, ingredient_id: u32, quantity: u32) -> Result<Ingredient, RestaurantError>;
+
+    /// Reports the ingredients low on stock and the menu items that can no longer be made.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(LowStockReport)` with the report.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_low_stock_report(&self) -> Result<LowStockReport, RestaurantError>;
+
     /// Retrieves all table IDs in the restaurant.
     ///
     /// # Returns
@@ -152,6 +1062,15 @@ pub trait Restaurant: Send + Sync {
     /// - `Err(RestaurantError)` if there is a failure.
     fn get_all_tables(&self) -> Result<Vec<u32>, RestaurantError>;
 
+    /// Retrieves all tables with the number of guests they seat, flagging the tables
+    /// whose reservation slot is in progress.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<TableStatus>)` with the status of every table, in the order of `get_all_tables`.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_table_statuses(&self) -> Result<Vec<TableStatus>, RestaurantError>;
+
     /// Adds a menu item to a table's order.
     ///
     /// # Parameters
@@ -164,7 +1083,31 @@ pub trait Restaurant: Send + Sync {
     /// - `Err(RestaurantError)` if there is a failure.
     fn add_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError>;
 
-    /// Removes a menu item from a table's order.
+    /// Adds a menu item with modifiers, a kitchen note and a seat to a table's order.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table placing the order.
+    /// - `item_id`: The ID of the menu item being added to the order.
+    /// - `options`: The chosen modifiers, the note, the seat and whether declared allergies are overridden.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Allergen>)` with the declared allergies the item contains, if they were overridden.
+    /// - `Err(RestaurantError::InvalidModifiers)` if the options do not match the menu item,
+    ///   or the seat does not exist at the table.
+    /// - `Err(RestaurantError::AllergenConflict)` if the item contains declared allergies
+    ///   and they were not overridden.
+    /// - `Err(RestaurantError::ItemUnavailable)` if the item is sold out, an ingredient runs short,
+    ///   or none of its categories is served.
+    /// - `Err(RestaurantError)` if there is another failure.
+    fn add_item_with_options(
+        &self,
+        table_id: u32,
+        item_id: u32,
+        options: &ItemOptions,
+    ) -> Result<Vec<Allergen>, RestaurantError>;
+
+    /// Removes a menu item from a table's order and puts it and its ingredients back into stock.
     ///
     /// # Parameters
     /// - `table_id`: The ID of the table removing the item.
@@ -176,6 +1119,20 @@ pub trait Restaurant: Send + Sync {
     /// - `Err(RestaurantError)` if there is a failure.
     fn remove_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError>;
 
+    /// Removes a line from a table's order by its number and puts its item and ingredients
+    /// back into stock.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table removing the line.
+    /// - `line_id`: The number of the order line, as listed with the table's items.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if the line was successfully removed.
+    /// - `Err(RestaurantError::OrderLineNotFound)` if the order has no line with the number.
+    /// - `Err(RestaurantError)` if there is another failure.
+    fn remove_line(&self, table_id: u32, line_id: u32) -> Result<(), RestaurantError>;
+
     /// Retrieves all menu items ordered by a specific table.
     ///
     /// # Parameters
@@ -183,9 +1140,9 @@ pub trait Restaurant: Send + Sync {
     ///
     /// # Returns
     /// A `Result` which is:
-    /// - `Ok(Vec<MenuItem>)` with the list of items ordered by the table.
+    /// - `Ok(Vec<OrderedItem>)` with the items ordered by the table, with their modifiers and notes.
     /// - `Err(RestaurantError)` if there is a failure.
-    fn get_items(&self, table_id: u32) -> Result<Vec<MenuItem>, RestaurantError>;
+    fn get_items(&self, table_id: u32) -> Result<Vec<OrderedItem>, RestaurantError>;
 
     /// Retrieves a specific menu item ordered by a table.
     ///
@@ -195,7 +1152,232 @@ pub trait Restaurant: Send + Sync {
     ///
     /// # Returns
     /// A `Result` which is:
-    /// - `Ok(MenuItem)` with the requested menu item.
+    /// - `Ok(OrderedItem)` with the first order line of the requested menu item.
     /// - `Err(RestaurantError)` if there is a failure.
-    fn get_item(&self, table_id: u32, item_id: u32) -> Result<MenuItem, RestaurantError>;
+    fn get_item(&self, table_id: u32, item_id: u32) -> Result<OrderedItem, RestaurantError>;
+
+    /// Builds the itemized bill of a table from its current order.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table being billed.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Bill)` with the bill, including subtotal, tax and total.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_bill(&self, table_id: u32) -> Result<Bill, RestaurantError>;
+
+    /// Splits the bill of a table into checks and stores them, replacing a previous split.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table whose bill is split.
+    /// - `request`: How the bill is divided: by items, by seat or evenly.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(SplitBill)` with the checks, which add up exactly to the bill.
+    /// - `Err(RestaurantError)` if the split does not match the order, or there is a failure.
+    fn split_bill(
+        &self,
+        table_id: u32,
+        request: &SplitRequest,
+    ) -> Result<SplitBill, RestaurantError>;
+
+    /// Retrieves the checks of a table's split bill.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(SplitBill)` with the bill and its checks.
+    /// - `Err(RestaurantError::SplitOutdated)` if the order changed after the split.
+    /// - `Err(RestaurantError)` if the bill was not split, or there is a failure.
+    fn get_checks(&self, table_id: u32) -> Result<SplitBill, RestaurantError>;
+
+    /// Records a payment, and its tip, towards the bill of a table.
+    ///
+    /// Once the bill is paid in full, the table is settled: its payments are archived with
+    /// the closed session, and its order, split and open payments are removed, so that the
+    /// table is free for the next guests. A tip without an amount for a settled table is
+    /// added to the closed session until the table is opened again.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the paying table.
+    /// - `request`: The payment.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(PaymentReceipt)` with the recorded payment and the resulting account.
+    /// - `Err(RestaurantError::Overpayment)` if the amount exceeds the balance.
+    /// - `Err(RestaurantError::PaymentDeclined)` if the payment provider declined the payment.
+    /// - `Err(RestaurantError)` if the payment is invalid, or there is a failure.
+    fn pay(
+        &self,
+        table_id: u32,
+        request: &PaymentRequest,
+    ) -> Result<PaymentReceipt, RestaurantError>;
+
+    /// Retrieves the payment state of a table's current bill.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableAccount)` with the total, payments and balance of the bill.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_account(&self, table_id: u32) -> Result<TableAccount, RestaurantError>;
+
+    /// Opens a session for a party at a table.
+    ///
+    /// Tables are also opened implicitly, without a party size, by their first order.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    /// - `party_size`: The number of guests.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the new session.
+    /// - `Err(RestaurantError::SessionAlreadyOpen)` if the table is already open.
+    /// - `Err(RestaurantError)` if the table or party size is invalid, or there is a failure.
+    fn open_table(&self, table_id: u32, party_size: u32) -> Result<TableSession, RestaurantError>;
+
+    /// Closes the session of a table: the bill is archived with the session, and the order,
+    /// split and payments of the table are cleared for the next party.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the closed session and its final bill.
+    /// - `Err(RestaurantError::OutstandingBalance)` if the bill is not paid in full.
+    /// - `Err(RestaurantError)` if the table is not open, or there is a failure.
+    fn close_table(&self, table_id: u32) -> Result<TableSession, RestaurantError>;
+
+    /// Retrieves the active session of a table.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the active session.
+    /// - `Err(RestaurantError)` if the table is not open, or there is a failure.
+    fn get_session(&self, table_id: u32) -> Result<TableSession, RestaurantError>;
+
+    /// Declares the allergies of the party at a table, replacing earlier ones.
+    ///
+    /// A table without an active session is opened implicitly, as by its first order.
+    ///
+    /// # Parameters
+    /// - `table_id`: The ID of the table.
+    /// - `allergies`: The allergies of the party. An empty list clears them.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(TableSession)` with the active session and its allergies.
+    /// - `Err(RestaurantError)` if the table is not found, or there is a failure.
+    fn declare_allergies(
+        &self,
+        table_id: u32,
+        allergies: Vec<Allergen>,
+    ) -> Result<TableSession, RestaurantError>;
+
+    /// Retrieves closed sessions, in the order they were closed.
+    ///
+    /// # Parameters
+    /// - `table_id`: Only sessions of this table if set, of all tables otherwise.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<TableSession>)` with the closed sessions.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_session_history(
+        &self,
+        table_id: Option<u32>,
+    ) -> Result<Vec<TableSession>, RestaurantError>;
+
+    /// Retrieves reservations, ordered by the start of their slot.
+    ///
+    /// # Parameters
+    /// - `day`: Only reservations starting on the day beginning at this time, in seconds
+    ///   since the Unix epoch, if set. All reservations otherwise.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Vec<Reservation>)` with the reservations.
+    /// - `Err(RestaurantError)` if there is a failure.
+    fn get_reservations(&self, day: Option<u64>) -> Result<Vec<Reservation>, RestaurantError>;
+
+    /// Reserves a table for a party.
+    ///
+    /// # Parameters
+    /// - `request`: The table, party size, contact name and slot.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the new reservation.
+    /// - `Err(RestaurantError::InvalidReservation)` if the party does not fit the table, or
+    ///   the contact name or slot is not valid.
+    /// - `Err(RestaurantError::ReservationConflict)` if the table is already reserved during the slot.
+    /// - `Err(RestaurantError)` if the table does not exist or there is another failure.
+    fn make_reservation(
+        &self,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, RestaurantError>;
+
+    /// Retrieves a reservation.
+    ///
+    /// # Parameters
+    /// - `reservation_id`: The ID of the reservation.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the reservation.
+    /// - `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    fn get_reservation(&self, reservation_id: u64) -> Result<Reservation, RestaurantError>;
+
+    /// Changes the table, party size, contact name or slot of a reservation.
+    ///
+    /// The reservation is looked up before the new details are validated, so an unknown
+    /// reservation is reported even if the new details are not valid.
+    ///
+    /// # Parameters
+    /// - `reservation_id`: The ID of the reservation.
+    /// - `request`: The new details of the reservation.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the updated reservation.
+    /// - `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    /// - `Err(RestaurantError)` for the same reasons as `make_reservation`.
+    fn update_reservation(
+        &self,
+        reservation_id: u64,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, RestaurantError>;
+
+    /// Cancels a reservation.
+    ///
+    /// # Parameters
+    /// - `reservation_id`: The ID of the reservation.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(Reservation)` with the cancelled reservation.
+    /// - `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    fn cancel_reservation(&self, reservation_id: u64) -> Result<Reservation, RestaurantError>;
+
+    /// Flushes the persistent state of all underlying stores.
+    ///
+    /// Called once by the server after graceful shutdown has drained all in-flight requests.
+    ///
+    /// # Returns
+    /// A `Result` which is:
+    /// - `Ok(())` if every store was flushed successfully.
+    /// - `Err(RestaurantError)` with the first failure if any store could not be flushed.
+    fn shutdown(&self) -> Result<(), RestaurantError>;
 }
@@ -0,0 +1,450 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::dietary::Allergen;
+use crate::server::data_model::models::MenuItem;
+use crate::server::data_model::money::{Money, PriceDelta};
+use crate::server::utils::error::RestaurantError;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest kitchen note accepted for an order line, in characters.
+pub const MAX_NOTE_LENGTH: usize = 200;
+
+/// Represents a single choice of a modifier group, e.g. `extra cheese` or `no onions`.
+///
+/// - `id`: An identifier of the modifier, unique within its menu item.
+/// - `name`: The name shown to guests and the kitchen.
+/// - `price_delta`: The change of the price of the menu item when the modifier is chosen.
+///   A negative change is a discount, e.g. `-2.00` for a half portion.
+/// - `allergens`: The allergens the modifier adds to the menu item, e.g. fish for a Caesar
+///   dressing. They are checked against the declared allergies when the modifier is chosen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Modifier {
+    /// Identifier of the modifier, unique within its menu item.
+    pub id: u32,
+    /// Name of the modifier.
+    pub name: String,
+    /// Change of the price of the menu item, negative for a discount.
+    #[serde(default)]
+    #[schema(value_type = String, example = "-2.00")]
+    pub price_delta: PriceDelta,
+    /// Allergens the modifier adds to the menu item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allergens: Vec<Allergen>,
+}
+
+/// Represents a group of modifiers of a menu item, e.g. the doneness of a steak or its extras.
+///
+/// - `id`: An identifier of the group, unique within its menu item.
+/// - `name`: The name of the group.
+/// - `required`: Whether a selection must be made from the group.
+/// - `min_selections` and `max_selections`: How many modifiers of the group may be chosen.
+///   An optional group may be left out entirely, but if anything is chosen, at least
+///   `min_selections` modifiers must be.
+/// - `modifiers`: The choices of the group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ModifierGroup {
+    /// Identifier of the group, unique within its menu item.
+    pub id: u32,
+    /// Name of the group.
+    pub name: String,
+    /// Whether a selection must be made from the group.
+    #[serde(default)]
+    pub required: bool,
+    /// Fewest modifiers that may be chosen.
+    #[serde(default)]
+    pub min_selections: u32,
+    /// Most modifiers that may be chosen.
+    pub max_selections: u32,
+    /// Choices of the group.
+    pub modifiers: Vec<Modifier>,
+}
+
+/// A modifier chosen for an ordered menu item.
+///
+/// The name and price delta are copied from the menu when the item is ordered.
+///
+/// - `group_id` and `modifier_id`: Identify the modifier within its menu item.
+/// - `name`: The name of the modifier.
+/// - `price_delta`: The change of the price of the menu item, negative for a discount.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SelectedModifier {
+    /// ID of the modifier group.
+    pub group_id: u32,
+    /// ID of the modifier.
+    pub modifier_id: u32,
+    /// Name of the modifier.
+    pub name: String,
+    /// Change of the price of the menu item, negative for a discount.
+    #[schema(value_type = String, example = "1.00")]
+    pub price_delta: PriceDelta,
+}
+
+/// The modifiers, kitchen note and seat of a menu item being ordered.
+///
+/// - `modifiers`: The IDs of the chosen modifiers.
+/// - `note`: A free-text note for the kitchen, e.g. `allergic to nuts`.
+/// - `seat`: The seat the item is ordered from, numbered from 1, used to split the bill by seat.
+/// - `allergen_override`: Orders the item even if it contains allergies declared for the table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ItemOptions {
+    /// IDs of the chosen modifiers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<u32>,
+    /// Note for the kitchen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Seat the item is ordered from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seat: Option<u32>,
+    /// Whether the item is ordered even if it contains allergies declared for the table.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allergen_override: bool,
+}
+
+/// A line of a table's order: one menu item with its modifiers, kitchen note and seat.
+///
+/// - `line_id`: The number of the line within the table's order, assigned by the order store
+///   when the line is added, starting at 1. Lines that were not added yet have `0`.
+/// - `item_id`: The ID of the ordered menu item.
+/// - `modifiers`: The chosen modifiers, in the order of the menu item's groups.
+/// - `note`: The note for the kitchen, if any.
+/// - `seat`: The seat the item was ordered from, if given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderLine {
+    /// Number of the line within the table's order.
+    #[serde(default)]
+    pub line_id: u32,
+    /// ID of the ordered menu item.
+    pub item_id: u32,
+    /// Chosen modifiers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<SelectedModifier>,
+    /// Note for the kitchen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Seat the item was ordered from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seat: Option<u32>,
+}
+
+impl OrderLine {
+    /// Creates an order line for a menu item without modifiers, note or seat.
+    ///
+    /// # Arguments
+    /// * `item_id` - The ID of the ordered menu item.
+    pub fn plain(item_id: u32) -> Self {
+        OrderLine {
+            line_id: 0,
+            item_id,
+            modifiers: Vec::new(),
+            note: None,
+            seat: None,
+        }
+    }
+
+    /// Creates an order line after validating the options against the menu item.
+    ///
+    /// Every chosen modifier must belong to the menu item and may be chosen once, and the
+    /// selections of each group must respect its `required`, `min_selections` and
+    /// `max_selections`. The note is trimmed; a blank note is dropped. Seats are numbered
+    /// from 1.
+    ///
+    /// # Arguments
+    /// * `item` - The ordered menu item.
+    /// * `options` - The chosen modifiers, note and seat.
+    ///
+    /// # Returns
+    /// * `Ok(OrderLine)` - The line, with the chosen modifiers copied from the menu.
+    /// * `Err(RestaurantError::InvalidModifiers)` - If the options do not match the menu item.
+    pub fn new(item: &MenuItem, options: &ItemOptions) -> Result<Self, RestaurantError> {
+        let invalid = |reason: String| Err(RestaurantError::InvalidModifiers(item.id, reason));
+
+        for (index, modifier_id) in options.modifiers.iter().enumerate() {
+            if options.modifiers[..index].contains(modifier_id) {
+                return invalid(format!("modifier {} is chosen more than once", modifier_id));
+            }
+            if !item
+                .modifier_groups
+                .iter()
+                .any(|group| group.modifiers.iter().any(|m| m.id == *modifier_id))
+            {
+                return invalid(format!("modifier {} does not exist", modifier_id));
+            }
+        }
+
+        let mut modifiers = Vec::new();
+        for group in &item.modifier_groups {
+            let chosen: Vec<&Modifier> = group
+                .modifiers
+                .iter()
+                .filter(|modifier| options.modifiers.contains(&modifier.id))
+                .collect();
+            let count = chosen.len() as u32;
+            let min = if group.required {
+                group.min_selections.max(1)
+            } else if count == 0 {
+                0
+            } else {
+                group.min_selections
+            };
+            if count < min {
+                return invalid(format!(
+                    "{} requires at least {} selection(s)",
+                    group.name, min
+                ));
+            }
+            if count > group.max_selections {
+                return invalid(format!(
+                    "{} allows at most {} selection(s)",
+                    group.name, group.max_selections
+                ));
+            }
+            modifiers.extend(chosen.into_iter().map(|modifier| SelectedModifier {
+                group_id: group.id,
+                modifier_id: modifier.id,
+                name: modifier.name.clone(),
+                price_delta: modifier.price_delta,
+            }));
+        }
+
+        let note = options
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|note| !note.is_empty());
+        if let Some(note) = note {
+            if note.chars().count() > MAX_NOTE_LENGTH {
+                return invalid(format!(
+                    "the note is longer than {} characters",
+                    MAX_NOTE_LENGTH
+                ));
+            }
+        }
+
+        if options.seat == Some(0) {
+            return invalid("seats are numbered from 1".to_string());
+        }
+
+        Ok(OrderLine {
+            line_id: 0,
+            item_id: item.id,
+            modifiers,
+            note: note.map(str::to_string),
+            seat: options.seat,
+        })
+    }
+}
+
+/// Represents a menu item as ordered at a table, with its modifiers, kitchen note and seat.
+///
+/// - `line_id`: The number of the order line, used to remove this very line from the order.
+/// - `id`, `name` and `cooking_time_minutes`: As in `MenuItem`.
+/// - `price`: The price of the item including the price deltas of its modifiers.
+/// - `modifiers`: The chosen modifiers.
+/// - `note`: The note for the kitchen, if any.
+/// - `seat`: The seat the item was ordered from, if given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OrderedItem {
+    /// Number of the order line.
+    #[serde(default)]
+    pub line_id: u32,
+    /// ID of the menu item.
+    pub id: u32,
+    /// Name of the menu item.
+    pub name: String,
+    /// The cooking time required for this menu item (in minutes).
+    pub cooking_time_minutes: u64,
+    /// The price of the item including its modifiers.
+    #[serde(default)]
+    #[schema(value_type = String, example = "13.50")]
+    pub price: Money,
+    /// Chosen modifiers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<SelectedModifier>,
+    /// Note for the kitchen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Seat the item was ordered from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seat: Option<u32>,
+}
+
+impl OrderedItem {
+    /// Combines an order line with its menu item.
+    ///
+    /// # Arguments
+    /// * `item` - The menu item of the line.
+    /// * `line` - The order line.
+    pub fn new(item: &MenuItem, line: OrderLine) -> Self {
+        let price = item
+            .price
+            .saturating_add_delta(line.modifiers.iter().map(|m| m.price_delta).sum());
+        OrderedItem {
+            line_id: line.line_id,
+            id: item.id,
+            name: item.name.clone(),
+            cooking_time_minutes: item.cooking_time_minutes,
+            price,
+            modifiers: line.modifiers,
+            note: line.note,
+            seat: line.seat,
+        }
+    }
+
+    /// Returns the names of the chosen modifiers joined by commas, e.g. `Extra cheese, No onions`.
+    pub fn modifier_names(&self) -> String {
+        self.modifiers
+            .iter()
+            .map(|modifier| modifier.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl From<MenuItem> for OrderedItem {
+    /// Orders a menu item without modifiers, note or seat.
+    fn from(item: MenuItem) -> Self {
+        OrderedItem {
+            line_id: 0,
+            id: item.id,
+            name: item.name,
+            cooking_time_minutes: item.cooking_time_minutes,
+            price: item.price,
+            modifiers: Vec::new(),
+            note: None,
+            seat: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn burger() -> MenuItem {
+        let modifier = |id: u32, name: &str, cents: i64| Modifier {
+            id,
+            name: name.to_string(),
+            price_delta: PriceDelta::from_cents(cents),
+            allergens: Vec::new(),
+        };
+        MenuItem {
+            modifier_groups: vec![
+                ModifierGroup {
+                    id: 1,
+                    name: "Doneness".to_string(),
+                    required: true,
+                    min_selections: 1,
+                    max_selections: 1,
+                    modifiers: vec![modifier(1, "Medium", 0), modifier(2, "Well done", 0)],
+                },
+                ModifierGroup {
+                    id: 2,
+                    name: "Extras".to_string(),
+                    required: false,
+                    min_selections: 0,
+                    max_selections: 2,
+                    modifiers: vec![
+                        modifier(3, "Extra cheese", 100),
+                        modifier(4, "Bacon", 150),
+                        modifier(5, "No onions", 0),
+                        modifier(6, "No patty", -1300),
+                    ],
+                },
+            ],
+            ..MenuItem::new(6, "Burger", 10, Money::from_cents(1250))
+        }
+    }
+
+    fn options(modifiers: Vec<u32>, note: Option<&str>) -> ItemOptions {
+        ItemOptions {
+            modifiers,
+            note: note.map(str::to_string),
+            ..ItemOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_order_line_copies_modifiers_in_menu_order() {
+        let line = OrderLine::new(&burger(), &options(vec![4, 1, 3], Some("  no salt "))).unwrap();
+        let ids: Vec<(u32, u32)> = line
+            .modifiers
+            .iter()
+            .map(|m| (m.group_id, m.modifier_id))
+            .collect();
+        assert_eq!(ids, vec![(1, 1), (2, 3), (2, 4)]);
+        assert_eq!(line.note.as_deref(), Some("no salt"));
+
+        let ordered = OrderedItem::new(&burger(), line);
+        assert_eq!(ordered.price, Money::from_cents(1500));
+        assert_eq!(ordered.modifier_names(), "Medium, Extra cheese, Bacon");
+    }
+
+    #[test]
+    fn test_discount_modifiers_lower_the_price_to_at_most_zero() {
+        let line = OrderLine::new(&burger(), &options(vec![1, 4, 6], None)).unwrap();
+        let ordered = OrderedItem::new(&burger(), line);
+        // 12.50 + 1.50 - 13.00
+        assert_eq!(ordered.price, Money::from_cents(100));
+
+        let line = OrderLine::new(&burger(), &options(vec![1, 6], None)).unwrap();
+        assert_eq!(OrderedItem::new(&burger(), line).price, Money::ZERO);
+    }
+
+    #[test]
+    fn test_order_line_rejects_invalid_selections() {
+        let cases = [
+            (vec![], "Doneness requires at least 1 selection(s)"),
+            (vec![1, 2], "Doneness allows at most 1 selection(s)"),
+            (vec![1, 3, 4, 5], "Extras allows at most 2 selection(s)"),
+            (vec![1, 9], "modifier 9 does not exist"),
+            (vec![1, 1], "modifier 1 is chosen more than once"),
+        ];
+        for (modifiers, reason) in cases {
+            assert_eq!(
+                OrderLine::new(&burger(), &options(modifiers, None)),
+                Err(RestaurantError::InvalidModifiers(6, reason.to_string()))
+            );
+        }
+        let long_note = "x".repeat(MAX_NOTE_LENGTH + 1);
+        assert!(OrderLine::new(&burger(), &options(vec![1], Some(&long_note))).is_err());
+        let seat_zero = ItemOptions {
+            seat: Some(0),
+            ..options(vec![1], None)
+        };
+        assert_eq!(
+            OrderLine::new(&burger(), &seat_zero),
+            Err(RestaurantError::InvalidModifiers(
+                6,
+                "seats are numbered from 1".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_order_line_keeps_seat() {
+        let seated = ItemOptions {
+            seat: Some(2),
+            ..options(vec![1], None)
+        };
+        let line = OrderLine::new(&burger(), &seated).unwrap();
+        assert_eq!(line.seat, Some(2));
+        assert_eq!(OrderedItem::new(&burger(), line).seat, Some(2));
+    }
+
+    #[test]
+    fn test_order_line_drops_blank_note() {
+        let line = OrderLine::new(&burger(), &options(vec![2], Some("   "))).unwrap();
+        assert_eq!(line.note, None);
+        let json = serde_json::to_value(OrderedItem::from(MenuItem {
+            modifier_groups: vec![],
+            ..burger()
+        }))
+        .unwrap();
+        assert!(json.get("modifiers").is_none());
+        assert!(json.get("note").is_none());
+    }
+}
@@ -0,0 +1,370 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+/// An exact amount of money, stored in minor units (cents).
+///
+/// Amounts are never represented as floating point numbers, so that sums and taxes are
+/// exact. They are serialized as decimal strings with two fraction digits, e.g. `"12.50"`,
+/// which keeps them exact in JSON and TOML as well.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(u64);
+
+impl Money {
+    /// A zero amount.
+    pub const ZERO: Money = Money(0);
+
+    /// The largest amount that can be represented, 184467440737095516.15.
+    pub const MAX: Money = Money(u64::MAX);
+
+    /// Creates an amount from minor units, e.g. `Money::from_cents(1250)` for 12.50.
+    pub const fn from_cents(cents: u64) -> Self {
+        Money(cents)
+    }
+
+    /// Returns the amount in minor units.
+    pub const fn cents(self) -> u64 {
+        self.0
+    }
+
+    /// Adds `other`, or returns `None` if the sum exceeds `Money::MAX`.
+    pub const fn checked_add(self, other: Money) -> Option<Money> {
+        match self.0.checked_add(other.0) {
+            Some(cents) => Some(Money(cents)),
+            None => None,
+        }
+    }
+
+    /// Adds up amounts, or returns `None` if the sum exceeds `Money::MAX`.
+    pub fn checked_sum<I: IntoIterator<Item = Money>>(amounts: I) -> Option<Money> {
+        amounts
+            .into_iter()
+            .try_fold(Money::ZERO, Money::checked_add)
+    }
+
+    /// Subtracts `other`, stopping at zero instead of going negative.
+    pub const fn saturating_sub(self, other: Money) -> Money {
+        Money(self.0.saturating_sub(other.0))
+    }
+
+    /// Applies a price change, stopping at zero instead of going negative.
+    pub fn saturating_add_delta(self, delta: PriceDelta) -> Money {
+        let cents = i128::from(self.0) + i128::from(delta.0);
+        Money(cents.clamp(0, i128::from(u64::MAX)) as u64)
+    }
+}
+
+impl fmt::Display for Money {
+    /// Formats the amount with two fraction digits, e.g. `12.50`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}", self.0 / 100, self.0 % 100)
+    }
+}
+
+impl FromStr for Money {
+    type Err = String;
+
+    /// Parses a non-negative decimal amount with at most two fraction digits, e.g. `12`, `12.5` or `12.50`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid amount '{}'. Expected e.g. 12.50", s);
+        let (units, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if units.is_empty()
+            || fraction.len() > 2
+            || !units
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+            || (s.contains('.') && fraction.is_empty())
+        {
+            return Err(invalid());
+        }
+        let units: u64 = units.parse().map_err(|_| invalid())?;
+        let fraction: u64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+        units
+            .checked_mul(100)
+            .and_then(|cents| cents.checked_add(fraction))
+            .map(Money)
+            .ok_or_else(invalid)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    /// Adds amounts, stopping at `Money::MAX` instead of overflowing. Use `checked_add`
+    /// where a larger sum must be refused.
+    fn add(self, other: Money) -> Money {
+        Money(self.0.saturating_add(other.0))
+    }
+}
+
+impl Mul<u32> for Money {
+    type Output = Money;
+
+    /// Multiplies an amount, stopping at `Money::MAX` instead of overflowing.
+    fn mul(self, quantity: u32) -> Money {
+        Money(self.0.saturating_mul(u64::from(quantity)))
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A signed change of a price, stored in minor units (cents).
+///
+/// Modifiers use it to raise the price of a menu item, e.g. `"1.50"` for extra bacon, or
+/// to lower it, e.g. `"-2.00"` for a half portion. Like `Money`, it is serialized as a
+/// decimal string with two fraction digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PriceDelta(i64);
+
+impl PriceDelta {
+    /// No change of the price.
+    pub const ZERO: PriceDelta = PriceDelta(0);
+
+    /// Creates a change from minor units, e.g. `PriceDelta::from_cents(-200)` for -2.00.
+    pub const fn from_cents(cents: i64) -> Self {
+        PriceDelta(cents)
+    }
+
+    /// Returns the change in minor units.
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+}
+
+impl fmt::Display for PriceDelta {
+    /// Formats the change with two fraction digits, e.g. `1.50` or `-2.00`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        write!(f, "{}{}", sign, Money(self.0.unsigned_abs()))
+    }
+}
+
+impl FromStr for PriceDelta {
+    type Err = String;
+
+    /// Parses a decimal amount with an optional leading `-`, e.g. `1.50` or `-2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid price change '{}'. Expected e.g. 1.50 or -2.00", s);
+        let (negative, amount) = match s.strip_prefix('-') {
+            Some(amount) => (true, amount),
+            None => (false, s),
+        };
+        let cents = i64::try_from(amount.parse::<Money>().map_err(|_| invalid())?.cents())
+            .map_err(|_| invalid())?;
+        Ok(PriceDelta(if negative { -cents } else { cents }))
+    }
+}
+
+impl Add for PriceDelta {
+    type Output = PriceDelta;
+
+    fn add(self, other: PriceDelta) -> PriceDelta {
+        PriceDelta(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sum for PriceDelta {
+    fn sum<I: Iterator<Item = PriceDelta>>(iter: I) -> PriceDelta {
+        iter.fold(PriceDelta::ZERO, Add::add)
+    }
+}
+
+impl Serialize for PriceDelta {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PriceDelta {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A tax rate, stored exactly in basis points (hundredths of a percent).
+///
+/// The rate is written as a percentage with at most two fraction digits, e.g. `"8.25"`
+/// for 8.25 %, and serialized the same way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaxRate(u32);
+
+impl TaxRate {
+    /// Creates a rate from basis points, e.g. `TaxRate::from_basis_points(825)` for 8.25 %.
+    pub const fn from_basis_points(basis_points: u32) -> Self {
+        TaxRate(basis_points)
+    }
+
+    /// Returns the rate in basis points.
+    pub const fn basis_points(self) -> u32 {
+        self.0
+    }
+
+    /// Computes the tax on an amount, rounding half a cent up.
+    ///
+    /// # Arguments
+    /// * `amount` - The taxed amount.
+    ///
+    /// # Returns
+    /// * `Money` - The tax, in whole cents.
+    pub fn tax_on(self, amount: Money) -> Money {
+        let tax = (u128::from(amount.cents()) * u128::from(self.0) + 5_000) / 10_000;
+        Money(tax as u64)
+    }
+}
+
+impl fmt::Display for TaxRate {
+    /// Formats the rate as a percentage with two fraction digits, e.g. `8.25`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Money::from_cents(u64::from(self.0)))
+    }
+}
+
+impl FromStr for TaxRate {
+    type Err = String;
+
+    /// Parses a percentage between 0 and 100 with at most two fraction digits, e.g. `8.25` or `8.25%`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid tax rate '{}'. Expected a percentage between 0 and 100, e.g. 8.25",
+                s
+            )
+        };
+        let percent: Money = s.trim_end_matches('%').parse().map_err(|_| invalid())?;
+        if percent > Money::from_cents(10_000) {
+            return Err(invalid());
+        }
+        Ok(TaxRate(percent.cents() as u32))
+    }
+}
+
+impl Serialize for TaxRate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TaxRate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_display_and_parse() {
+        assert_eq!(Money::from_cents(1250).to_string(), "12.50");
+        assert_eq!(Money::from_cents(5).to_string(), "0.05");
+        assert_eq!("12".parse(), Ok(Money::from_cents(1200)));
+        assert_eq!("12.5".parse(), Ok(Money::from_cents(1250)));
+        assert_eq!("0.05".parse(), Ok(Money::from_cents(5)));
+        for invalid in ["", ".5", "12.", "12.505", "-1", "1e3", "12,50"] {
+            assert!(invalid.parse::<Money>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_money_arithmetic() {
+        let prices = [Money::from_cents(1250), Money::from_cents(399) * 3];
+        assert_eq!(prices.into_iter().sum::<Money>(), Money::from_cents(2447));
+        assert_eq!(
+            Money::from_cents(1000).saturating_sub(Money::from_cents(250)),
+            Money::from_cents(750)
+        );
+        assert_eq!(
+            Money::from_cents(250).saturating_sub(Money::from_cents(1000)),
+            Money::ZERO
+        );
+        assert_eq!(Money::MAX.checked_add(Money::ZERO), Some(Money::MAX));
+        assert_eq!(Money::MAX.checked_add(Money::from_cents(1)), None);
+        assert_eq!(Money::MAX + Money::from_cents(1), Money::MAX);
+        assert_eq!(Money::MAX * 2, Money::MAX);
+        assert_eq!(Money::checked_sum(prices), Some(Money::from_cents(2447)));
+        assert_eq!(Money::checked_sum([Money::MAX, Money::from_cents(1)]), None);
+        assert_eq!("184467440737095516.15".parse(), Ok(Money::MAX));
+    }
+
+    #[test]
+    fn test_money_serializes_as_string() {
+        let json = serde_json::to_string(&Money::from_cents(1099)).unwrap();
+        assert_eq!(json, "\"10.99\"");
+        assert_eq!(
+            serde_json::from_str::<Money>(&json).unwrap(),
+            Money::from_cents(1099)
+        );
+        assert!(serde_json::from_str::<Money>("10.99").is_err());
+    }
+
+    #[test]
+    fn test_price_delta_parse_and_apply() {
+        assert_eq!("1.50".parse(), Ok(PriceDelta::from_cents(150)));
+        assert_eq!("-2".parse(), Ok(PriceDelta::from_cents(-200)));
+        assert_eq!(PriceDelta::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(PriceDelta::from_cents(150).to_string(), "1.50");
+        for invalid in ["", "-", "--1", "+1", "1.505", "abc"] {
+            assert!(invalid.parse::<PriceDelta>().is_err(), "{}", invalid);
+        }
+        let json = serde_json::to_string(&PriceDelta::from_cents(-250)).unwrap();
+        assert_eq!(json, "\"-2.50\"");
+        assert_eq!(
+            serde_json::from_str::<PriceDelta>(&json).unwrap(),
+            PriceDelta::from_cents(-250)
+        );
+
+        let deltas = [PriceDelta::from_cents(150), PriceDelta::from_cents(-200)];
+        let total = deltas.into_iter().sum::<PriceDelta>();
+        assert_eq!(total, PriceDelta::from_cents(-50));
+        assert_eq!(
+            Money::from_cents(1000).saturating_add_delta(total),
+            Money::from_cents(950)
+        );
+        assert_eq!(
+            Money::from_cents(300).saturating_add_delta(PriceDelta::from_cents(-500)),
+            Money::ZERO
+        );
+    }
+
+    #[test]
+    fn test_tax_rate_parse_and_tax() {
+        let rate: TaxRate = "8.25".parse().unwrap();
+        assert_eq!(rate, TaxRate::from_basis_points(825));
+        assert_eq!("8.25%".parse(), Ok(rate));
+        assert_eq!(rate.to_string(), "8.25");
+        // 8.25 % of 10.00 is 0.825, rounded half up
+        assert_eq!(rate.tax_on(Money::from_cents(1000)), Money::from_cents(83));
+        assert_eq!(
+            TaxRate::default().tax_on(Money::from_cents(1000)),
+            Money::ZERO
+        );
+        assert!("100.01".parse::<TaxRate>().is_err());
+        assert!("abc".parse::<TaxRate>().is_err());
+    }
+}
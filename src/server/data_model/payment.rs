@@ -0,0 +1,303 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::money::Money;
+use crate::server::utils::error::RestaurantError;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The ways a payment can be made.
+///
+/// - `Cash`: Paid in cash at the table, always accepted.
+/// - `Card`: Charged to a card through the card provider, which may decline it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    Cash,
+    Card,
+}
+
+impl std::fmt::Display for PaymentMethod {
+    /// Formats the method as in JSON, e.g. `cash`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaymentMethod::Cash => write!(f, "cash"),
+            PaymentMethod::Card => write!(f, "card"),
+        }
+    }
+}
+
+/// A request to pay towards the bill of a table, optionally with a tip.
+///
+/// - `method`: How the payment is made.
+/// - `amount`: The amount paid towards the bill. May be zero for a tip on its own.
+/// - `tip`: The tip paid on top of the amount. Tips do not reduce the balance.
+/// - `check_id`: The check of a split bill the payment is for, if any.
+/// - `card_number`: The card charged for card payments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentRequest {
+    /// How the payment is made.
+    pub method: PaymentMethod,
+    /// Amount paid towards the bill.
+    #[serde(default)]
+    #[schema(value_type = String, example = "25.00")]
+    pub amount: Money,
+    /// Tip on top of the amount.
+    #[serde(default)]
+    #[schema(value_type = String, example = "3.00")]
+    pub tip: Money,
+    /// Check of the table's split bill the payment is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_id: Option<u32>,
+    /// Number of the charged card, required for card payments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "4242424242424242")]
+    pub card_number: Option<String>,
+}
+
+/// A request to record a tip on its own, e.g. when a tip is added after the bill was paid by check.
+///
+/// A tip for a table whose bill was settled goes to the closed session of its last party,
+/// until the table is opened again.
+///
+/// - `method`: How the tip is paid.
+/// - `tip`: The tip.
+/// - `check_id`: The check of a split bill the tip is for, if any.
+/// - `card_number`: The card charged for card tips.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TipRequest {
+    /// How the tip is paid.
+    pub method: PaymentMethod,
+    /// The tip.
+    #[schema(value_type = String, example = "3.00")]
+    pub tip: Money,
+    /// Check of the table's split bill the tip is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_id: Option<u32>,
+    /// Number of the charged card, required for card tips.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_number: Option<String>,
+}
+
+impl From<TipRequest> for PaymentRequest {
+    /// Converts a tip into a payment of zero towards the bill.
+    fn from(tip: TipRequest) -> Self {
+        PaymentRequest {
+            method: tip.method,
+            amount: Money::ZERO,
+            tip: tip.tip,
+            check_id: tip.check_id,
+            card_number: tip.card_number,
+        }
+    }
+}
+
+/// A payment recorded against the bill of a table.
+///
+/// - `payment_id`: The number of the payment on the table's current bill, starting at 1.
+/// - `table_id`: The ID of the table.
+/// - `check_id`: The check the payment is for, if any.
+/// - `method`: How the payment was made.
+/// - `amount` and `tip`: The amounts paid towards the bill and as tip.
+/// - `reference`: The reference returned by the payment provider, e.g. `card-4242-1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Payment {
+    /// Number of the payment on the table's current bill.
+    pub payment_id: u32,
+    /// ID of the table.
+    pub table_id: u32,
+    /// Check the payment is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_id: Option<u32>,
+    /// How the payment was made.
+    pub method: PaymentMethod,
+    /// Amount paid towards the bill.
+    #[schema(value_type = String, example = "25.00")]
+    pub amount: Money,
+    /// Tip on top of the amount.
+    #[schema(value_type = String, example = "3.00")]
+    pub tip: Money,
+    /// Reference of the payment provider.
+    pub reference: String,
+}
+
+/// The payment state of a table's current bill.
+///
+/// - `total`: The total of the bill.
+/// - `paid`: The sum of the payments towards the bill.
+/// - `tips`: The sum of the tips.
+/// - `balance`: What is left to pay.
+/// - `settled`: Whether the bill is paid in full. A settled table is freed for the next guests.
+/// - `payments`: The payments towards the bill, in the order they were made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TableAccount {
+    /// ID of the table.
+    pub table_id: u32,
+    /// Total of the bill.
+    #[schema(value_type = String, example = "27.06")]
+    pub total: Money,
+    /// Sum of the payments towards the bill.
+    #[schema(value_type = String, example = "20.00")]
+    pub paid: Money,
+    /// Sum of the tips.
+    #[schema(value_type = String, example = "3.00")]
+    pub tips: Money,
+    /// Amount left to pay.
+    #[schema(value_type = String, example = "7.06")]
+    pub balance: Money,
+    /// Whether the bill is paid in full.
+    pub settled: bool,
+    /// Payments towards the bill.
+    pub payments: Vec<Payment>,
+}
+
+impl TableAccount {
+    /// Builds the account of a table from the total of its bill and the payments made.
+    ///
+    /// # Arguments
+    /// * `table_id` - The ID of the table.
+    /// * `total` - The total of the table's bill.
+    /// * `payments` - The payments recorded against the bill.
+    ///
+    /// # Returns
+    /// * `TableAccount` - The account. The balance never drops below zero. Sums beyond
+    ///   `Money::MAX`, which `check_totals` keeps from being recorded, stop at `Money::MAX`.
+    pub fn new(table_id: u32, total: Money, payments: Vec<Payment>) -> Self {
+        let paid: Money = payments.iter().map(|payment| payment.amount).sum();
+        let tips: Money = payments.iter().map(|payment| payment.tip).sum();
+        let balance = total.saturating_sub(paid);
+        TableAccount {
+            table_id,
+            total,
+            paid,
+            tips,
+            balance,
+            settled: balance == Money::ZERO && total > Money::ZERO,
+            payments,
+        }
+    }
+}
+
+/// Checks that a payment can be recorded along with the earlier payments of a bill, i.e.
+/// that neither the amounts paid nor the tips add up to more than `Money::MAX`.
+///
+/// # Arguments
+/// * `table_id` - The ID of the paying table.
+/// * `payments` - The payments already recorded against the bill.
+/// * `amount` - The amount of the new payment.
+/// * `tip` - The tip of the new payment.
+///
+/// # Returns
+/// * `Ok(())` - If the sums of the amounts and of the tips can be represented.
+/// * `Err(RestaurantError::InvalidPayment)` - Otherwise.
+pub fn check_totals(
+    table_id: u32,
+    payments: &[Payment],
+    amount: Money,
+    tip: Money,
+) -> Result<(), RestaurantError> {
+    let paid = Money::checked_sum(
+        payments
+            .iter()
+            .map(|payment| payment.amount)
+            .chain([amount]),
+    );
+    let tips = Money::checked_sum(payments.iter().map(|payment| payment.tip).chain([tip]));
+    if paid.is_none() || tips.is_none() {
+        return Err(RestaurantError::InvalidPayment(format!(
+            "The payments or tips of table {} would add up to more than {}",
+            table_id,
+            Money::MAX
+        )));
+    }
+    Ok(())
+}
+
+/// The outcome of a recorded payment.
+///
+/// - `payment`: The recorded payment.
+/// - `account`: The account of the table's bill after the payment. When `account.settled`
+///   is set, the bill is paid in full and the table is free again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PaymentReceipt {
+    /// The recorded payment.
+    pub payment: Payment,
+    /// The account of the bill after the payment.
+    pub account: TableAccount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(payment_id: u32, amount: u64, tip: u64) -> Payment {
+        Payment {
+            payment_id,
+            table_id: 1,
+            check_id: None,
+            method: PaymentMethod::Cash,
+            amount: Money::from_cents(amount),
+            tip: Money::from_cents(tip),
+            reference: format!("cash-{}", payment_id),
+        }
+    }
+
+    #[test]
+    fn test_table_account_balance() {
+        let account = TableAccount::new(1, Money::from_cents(3000), vec![payment(1, 1000, 200)]);
+        assert_eq!(account.paid, Money::from_cents(1000));
+        assert_eq!(account.tips, Money::from_cents(200));
+        assert_eq!(account.balance, Money::from_cents(2000));
+        assert!(!account.settled);
+
+        let account = TableAccount::new(
+            1,
+            Money::from_cents(3000),
+            vec![payment(1, 1000, 200), payment(2, 2000, 0)],
+        );
+        assert_eq!(account.balance, Money::ZERO);
+        assert!(account.settled);
+        assert!(!TableAccount::new(1, Money::ZERO, vec![]).settled);
+    }
+
+    #[test]
+    fn test_check_totals() {
+        let payments = vec![payment(1, 1000, u64::MAX - 1)];
+        assert_eq!(
+            check_totals(1, &payments, Money::from_cents(500), Money::from_cents(1)),
+            Ok(())
+        );
+        assert!(matches!(
+            check_totals(1, &payments, Money::ZERO, Money::from_cents(2)),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+        assert!(matches!(
+            check_totals(1, &payments, Money::MAX, Money::ZERO),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+    }
+
+    #[test]
+    fn test_payment_request_json() {
+        let request: PaymentRequest =
+            serde_json::from_str(r#"{"method": "card", "amount": "12.50", "card_number": "4242"}"#)
+                .unwrap();
+        assert_eq!(request.method, PaymentMethod::Card);
+        assert_eq!(request.amount, Money::from_cents(1250));
+        assert_eq!(request.tip, Money::ZERO);
+        assert!(serde_json::from_str::<PaymentRequest>(r#"{"method": "cheque"}"#).is_err());
+
+        let tip: PaymentRequest = TipRequest {
+            method: PaymentMethod::Cash,
+            tip: Money::from_cents(300),
+            check_id: Some(2),
+            card_number: None,
+        }
+        .into();
+        assert_eq!(tip.amount, Money::ZERO);
+        assert_eq!(tip.tip, Money::from_cents(300));
+        assert_eq!(tip.check_id, Some(2));
+    }
+}
@@ -0,0 +1,258 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Length of a reservation if the request does not set one, in minutes.
+pub const DEFAULT_DURATION_MINUTES: u32 = 90;
+
+/// Longest slot a table can be reserved for, in minutes.
+pub const MAX_DURATION_MINUTES: u32 = 6 * 60;
+
+/// Represents a table booked ahead of time for a party.
+///
+/// - `reservation_id`: A unique identifier of the reservation.
+/// - `table_id`: The ID of the reserved table.
+/// - `party_size`: The number of guests, at most the capacity of the table.
+/// - `contact_name`: The name the reservation was made under.
+/// - `starts_at`: The start of the slot, in seconds since the Unix epoch.
+/// - `duration_minutes`: The length of the slot. Reservations of the same table may not overlap.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Reservation {
+    /// Unique identifier of the reservation.
+    pub reservation_id: u64,
+    /// ID of the reserved table.
+    pub table_id: u32,
+    /// Number of guests.
+    pub party_size: u32,
+    /// Name the reservation was made under.
+    pub contact_name: String,
+    /// Start of the slot, in seconds since the Unix epoch.
+    pub starts_at: u64,
+    /// Length of the slot, in minutes.
+    pub duration_minutes: u32,
+}
+
+impl Reservation {
+    /// Returns the end of the slot, in seconds since the Unix epoch, or `None` if it lies
+    /// beyond the latest representable time.
+    pub fn checked_ends_at(&self) -> Option<u64> {
+        self.starts_at
+            .checked_add(u64::from(self.duration_minutes) * 60)
+    }
+
+    /// Returns the end of the slot, in seconds since the Unix epoch. Slots ending beyond
+    /// the latest representable time end at `u64::MAX`; the restaurant refuses them.
+    pub fn ends_at(&self) -> u64 {
+        self.checked_ends_at().unwrap_or(u64::MAX)
+    }
+
+    /// Returns whether the slot overlaps the slot of another reservation of the same table.
+    /// A slot starting exactly when the other one ends does not overlap it.
+    pub fn overlaps(&self, other: &Reservation) -> bool {
+        self.table_id == other.table_id
+            && self.starts_at < other.ends_at()
+            && other.starts_at < self.ends_at()
+    }
+
+    /// Returns whether the table is reserved at the given time.
+    ///
+    /// # Arguments
+    /// * `now` - The time, in seconds since the Unix epoch.
+    pub fn is_active_at(&self, now: u64) -> bool {
+        self.starts_at <= now && now < self.ends_at()
+    }
+}
+
+/// A request to reserve a table, or to change a reservation.
+///
+/// - `table_id`: The ID of the table.
+/// - `party_size`: The number of guests.
+/// - `contact_name`: The name the reservation is made under.
+/// - `starts_at`: The start of the slot, in seconds since the Unix epoch.
+/// - `duration_minutes`: The length of the slot. Defaults to `DEFAULT_DURATION_MINUTES`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReservationRequest {
+    /// ID of the table.
+    pub table_id: u32,
+    /// Number of guests.
+    pub party_size: u32,
+    /// Name the reservation is made under.
+    pub contact_name: String,
+    /// Start of the slot, in seconds since the Unix epoch.
+    pub starts_at: u64,
+    /// Length of the slot, in minutes.
+    #[serde(default = "default_duration_minutes")]
+    pub duration_minutes: u32,
+}
+
+impl ReservationRequest {
+    /// Builds the reservation requested, with the given ID.
+    pub fn to_reservation(&self, reservation_id: u64) -> Reservation {
+        Reservation {
+            reservation_id,
+            table_id: self.table_id,
+            party_size: self.party_size,
+            contact_name: self.contact_name.trim().to_string(),
+            starts_at: self.starts_at,
+            duration_minutes: self.duration_minutes,
+        }
+    }
+}
+
+fn default_duration_minutes() -> u32 {
+    DEFAULT_DURATION_MINUTES
+}
+
+/// A table with the number of guests it seats and its reservation at the moment.
+///
+/// - `table_id`: The ID of the table.
+/// - `capacity`: The most guests the table can be reserved for.
+/// - `reserved`: Whether the table is reserved right now.
+/// - `reservation`: The reservation whose slot is in progress, if any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TableStatus {
+    /// ID of the table.
+    pub table_id: u32,
+    /// Number of guests the table seats.
+    pub capacity: u32,
+    /// Whether the table is reserved right now.
+    pub reserved: bool,
+    /// The reservation in progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reservation: Option<Reservation>,
+}
+
+impl TableStatus {
+    /// Builds the status of a table at the given time.
+    ///
+    /// # Arguments
+    /// * `table_id` - The ID of the table.
+    /// * `capacity` - The number of guests the table seats.
+    /// * `reservations` - The reservations of all tables.
+    /// * `now` - The time, in seconds since the Unix epoch.
+    pub fn at(table_id: u32, capacity: u32, reservations: &[Reservation], now: u64) -> Self {
+        let reservation = reservations
+            .iter()
+            .find(|reservation| reservation.table_id == table_id && reservation.is_active_at(now))
+            .cloned();
+        TableStatus {
+            table_id,
+            capacity,
+            reserved: reservation.is_some(),
+            reservation,
+        }
+    }
+}
+
+/// Parses a day written as `YYYY-MM-DD` into the start of the day in UTC.
+///
+/// # Returns
+/// * `Some(u64)` - Midnight of the day, in seconds since the Unix epoch.
+/// * `None` - If the date is not valid or before 1970.
+pub fn parse_date(date: &str) -> Option<u64> {
+    let mut parts = date.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day): (i64, i64, i64) =
+        (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if day < 1 || day > days_in_month {
+        return None;
+    }
+
+    // Converts a civil date in the proleptic Gregorian calendar to days since 1970-01-01
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    u64::try_from(days).ok().map(|days| days * 86_400)
+}
+
+/// Parses a date and time written as `YYYY-MM-DD HH:MM` or `YYYY-MM-DDTHH:MM` in UTC.
+///
+/// # Returns
+/// * `Some(u64)` - The time, in seconds since the Unix epoch.
+/// * `None` - If the date or the time is not valid.
+pub fn parse_date_time(date_time: &str) -> Option<u64> {
+    let (date, time) = date_time.split_once(['T', ' '])?;
+    let (hours, minutes) = time.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes): (u64, u64) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(parse_date(date)? + hours * 3_600 + minutes * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reservation(reservation_id: u64, table_id: u32, starts_at: u64) -> Reservation {
+        Reservation {
+            reservation_id,
+            table_id,
+            party_size: 2,
+            contact_name: "Ada".to_string(),
+            starts_at,
+            duration_minutes: 90,
+        }
+    }
+
+    #[test]
+    fn test_overlaps_and_status() {
+        let dinner = reservation(1, 5, 72_000);
+        assert_eq!(dinner.ends_at(), 77_400);
+        let endless = reservation(3, 5, u64::MAX - 60);
+        assert_eq!(endless.checked_ends_at(), None);
+        assert_eq!(endless.ends_at(), u64::MAX);
+        assert!(endless.is_active_at(u64::MAX - 1));
+        assert!(dinner.overlaps(&reservation(2, 5, 75_600)));
+        assert!(!dinner.overlaps(&reservation(2, 5, 77_400)));
+        assert!(!dinner.overlaps(&reservation(2, 6, 72_000)));
+
+        let reservations = vec![dinner.clone()];
+        let status = TableStatus::at(5, 2, &reservations, 77_399);
+        assert!(status.reserved);
+        assert_eq!(status.reservation, Some(dinner));
+        assert!(!TableStatus::at(5, 2, &reservations, 77_400).reserved);
+        assert!(!TableStatus::at(6, 2, &reservations, 72_000).reserved);
+        assert_eq!(
+            serde_json::to_value(TableStatus::at(6, 2, &reservations, 0)).unwrap(),
+            serde_json::json!({"table_id": 6, "capacity": 2, "reserved": false})
+        );
+    }
+
+    #[test]
+    fn test_parse_date_and_time() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2026-10-18"), Some(1_792_281_600));
+        assert_eq!(parse_date("2024-02-29"), Some(1_709_164_800));
+        assert_eq!(parse_date("2026-02-29"), None);
+        assert_eq!(parse_date("2026-13-01"), None);
+        assert_eq!(parse_date("1969-12-31"), None);
+        assert_eq!(parse_date("2026-10-18-1"), None);
+        assert_eq!(parse_date("18.10.2026"), None);
+
+        assert_eq!(parse_date_time("2026-10-18 19:05"), Some(1_792_350_300));
+        assert_eq!(parse_date_time("2026-10-18T19:05"), Some(1_792_350_300));
+        assert_eq!(parse_date_time("2026-10-18 24:00"), None);
+        assert_eq!(parse_date_time("2026-10-18"), None);
+    }
+}
@@ -0,0 +1,103 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::dietary::Allergen;
+use crate::server::data_model::models::Bill;
+use crate::server::data_model::payment::Payment;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use utoipa::ToSchema;
+
+/// Largest party that can be seated at a single table.
+pub const MAX_PARTY_SIZE: u32 = 50;
+
+/// Represents the stay of one party at a table, from opening the table until it is closed.
+///
+/// - `session_id`: A unique identifier of the session.
+/// - `table_id`: The ID of the table.
+/// - `party_size`: The number of guests, if known. Sessions opened implicitly by the first
+///   order of a table have no party size.
+/// - `opened_at` and `closed_at`: When the table was opened and closed, in seconds since the Unix epoch.
+/// - `bill`: The final bill of the party, set when the session is closed.
+/// - `payments`: The payments and tips of the party, archived when the session is closed.
+///   Tips left after the bill was settled are added until the table is opened again.
+/// - `allergies`: The allergies declared by the party. Menu items containing them are only
+///   ordered with an allergen override.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TableSession {
+    /// Unique identifier of the session.
+    pub session_id: u64,
+    /// ID of the table.
+    pub table_id: u32,
+    /// Number of guests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub party_size: Option<u32>,
+    /// When the table was opened, in seconds since the Unix epoch.
+    pub opened_at: u64,
+    /// When the table was closed, in seconds since the Unix epoch. Not set while the session is active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<u64>,
+    /// The final bill of the party. Not set while the session is active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bill: Option<Bill>,
+    /// Payments and tips of the party. Empty while the session is active.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payments: Vec<Payment>,
+    /// Allergies declared by the party.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allergies: Vec<Allergen>,
+}
+
+impl TableSession {
+    /// Returns whether the session is still active, i.e. the party is still seated.
+    pub fn is_active(&self) -> bool {
+        self.closed_at.is_none()
+    }
+}
+
+/// A request to open a table for a party.
+///
+/// - `party_size`: The number of guests, between 1 and `MAX_PARTY_SIZE`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OpenTableRequest {
+    /// Number of guests.
+    pub party_size: u32,
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_json_omits_unset_fields() {
+        let session = TableSession {
+            session_id: 3,
+            table_id: 7,
+            party_size: None,
+            opened_at: 1_700_000_000,
+            closed_at: None,
+            bill: None,
+            payments: Vec::new(),
+            allergies: Vec::new(),
+        };
+        assert!(session.is_active());
+        let json = serde_json::to_value(&session).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"session_id": 3, "table_id": 7, "opened_at": 1_700_000_000})
+        );
+        assert_eq!(
+            serde_json::from_value::<TableSession>(json).unwrap(),
+            session
+        );
+        assert!(unix_now() > 1_700_000_000);
+    }
+}
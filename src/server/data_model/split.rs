@@ -0,0 +1,467 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::{Bill, BillLine};
+use crate::server::data_model::modifier::OrderedItem;
+use crate::server::data_model::money::Money;
+use crate::server::utils::error::RestaurantError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Maximum number of checks of an even split.
+pub const MAX_PAYERS: u32 = 100;
+
+/// Describes how the bill of a table is divided into checks.
+///
+/// - `Items`: One check per group of order line IDs, as listed with the table's order.
+///   Every order line must be assigned to exactly one check, e.g.
+///   `{"mode": "items", "checks": [[1, 3], [2]]}`.
+/// - `Seats`: One check per seat, with the items ordered from that seat, e.g. `{"mode": "seats"}`.
+///   Items ordered without a seat go to a shared check.
+/// - `Even`: The total is divided evenly between `payers` checks, e.g. `{"mode": "even", "payers": 3}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SplitRequest {
+    /// One check per group of order line IDs.
+    Items { checks: Vec<Vec<u32>> },
+    /// One check per seat the items were ordered from.
+    Seats {},
+    /// The given number of checks with amounts that differ by at most a cent.
+    Even { payers: u32 },
+}
+
+/// Represents one of the checks a table's bill was split into.
+///
+/// - `check_id`: The number of the check within the table's split, starting at 1.
+/// - `label`: Describes who pays the check, e.g. `Seat 2` or `Share 1 of 3`.
+/// - `lines`: The items on the check. Empty for even splits.
+/// - `subtotal`, `tax` and `total`: The amounts of the check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Check {
+    /// Number of the check, starting at 1.
+    pub check_id: u32,
+    /// Who pays the check.
+    pub label: String,
+    /// Items on the check.
+    pub lines: Vec<BillLine>,
+    /// Sum of the line amounts, or the share of the table's subtotal.
+    #[schema(value_type = String, example = "25.00")]
+    pub subtotal: Money,
+    /// Share of the table's tax.
+    #[schema(value_type = String, example = "2.06")]
+    pub tax: Money,
+    /// Amount to pay for the check.
+    #[schema(value_type = String, example = "27.06")]
+    pub total: Money,
+}
+
+/// Represents the bill of a table split into checks.
+///
+/// The subtotals, taxes and totals of the checks always add up exactly to the
+/// corresponding amounts of `bill`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SplitBill {
+    /// The bill that was split.
+    pub bill: Bill,
+    /// The checks, each payable independently.
+    pub checks: Vec<Check>,
+}
+
+impl SplitBill {
+    /// Splits the bill of a table into checks.
+    ///
+    /// Tax is not computed per check, which could make the checks add up to a cent more or
+    /// less than the bill. Instead, the bill's tax is divided between the checks in proportion
+    /// to their subtotals, and the cents lost by rounding down go to the checks with the
+    /// largest remainders.
+    ///
+    /// # Arguments
+    /// * `bill` - The bill of the table.
+    /// * `items` - The menu items ordered for the table, from which `bill` was built.
+    /// * `request` - How the bill is divided.
+    ///
+    /// # Returns
+    /// * `Ok(SplitBill)` - The checks.
+    /// * `Err(RestaurantError::InvalidSplit)` - If the table has no items, a check is empty,
+    ///   an order line is unknown, assigned twice or not assigned, or no item has a seat.
+    pub fn new(
+        bill: Bill,
+        items: &[OrderedItem],
+        request: &SplitRequest,
+    ) -> Result<Self, RestaurantError> {
+        if items.is_empty() {
+            return Err(RestaurantError::InvalidSplit(format!(
+                "Table {} has no items to split",
+                bill.table_id
+            )));
+        }
+
+        let checks = match request {
+            SplitRequest::Items { checks } => {
+                let groups: Vec<(String, &[u32])> = checks
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ids)| (format!("Check {}", index + 1), ids.as_slice()))
+                    .collect();
+                split_by_items(&bill, items, &groups)?
+            }
+            SplitRequest::Seats {} => split_by_seats(&bill, items)?,
+            SplitRequest::Even { payers } => split_evenly(&bill, *payers)?,
+        };
+        Ok(SplitBill { bill, checks })
+    }
+}
+
+/// Builds one check per group of order line IDs.
+fn split_by_items(
+    bill: &Bill,
+    items: &[OrderedItem],
+    groups: &[(String, &[u32])],
+) -> Result<Vec<Check>, RestaurantError> {
+    if groups.is_empty() {
+        return Err(RestaurantError::InvalidSplit(
+            "At least one check is required".to_string(),
+        ));
+    }
+
+    // Every order line must be assigned exactly once
+    let mut assigned = vec![false; items.len()];
+    let mut item_groups = Vec::with_capacity(groups.len());
+    for (label, line_ids) in groups {
+        if line_ids.is_empty() {
+            return Err(RestaurantError::InvalidSplit(format!(
+                "{} has no items",
+                label
+            )));
+        }
+        let mut group = Vec::with_capacity(line_ids.len());
+        for &line_id in line_ids.iter() {
+            let position = items
+                .iter()
+                .position(|item| item.line_id == line_id)
+                .ok_or_else(|| {
+                    RestaurantError::InvalidSplit(format!(
+                        "Order line {} of {} is not on the bill",
+                        line_id, label
+                    ))
+                })?;
+            if assigned[position] {
+                return Err(RestaurantError::InvalidSplit(format!(
+                    "Order line {} is assigned more than once",
+                    line_id
+                )));
+            }
+            assigned[position] = true;
+            group.push(items[position].clone());
+        }
+        item_groups.push(group);
+    }
+    if let Some((item, _)) = items
+        .iter()
+        .zip(&assigned)
+        .find(|(_, assigned)| !**assigned)
+    {
+        return Err(RestaurantError::InvalidSplit(format!(
+            "Order line {} ({}) is not assigned to a check",
+            item.line_id, item.name
+        )));
+    }
+
+    let labels = groups.iter().map(|(label, _)| label.clone()).collect();
+    Ok(build_checks(bill, labels, item_groups))
+}
+
+/// Builds one check per seat from the seats recorded on the ordered items.
+///
+/// The checks follow the seat numbers; items ordered without a seat go to a shared
+/// check after them.
+fn split_by_seats(bill: &Bill, items: &[OrderedItem]) -> Result<Vec<Check>, RestaurantError> {
+    let mut seats: BTreeMap<u32, Vec<OrderedItem>> = BTreeMap::new();
+    let mut shared = Vec::new();
+    for item in items {
+        match item.seat {
+            Some(seat) => seats.entry(seat).or_default().push(item.clone()),
+            None => shared.push(item.clone()),
+        }
+    }
+    if seats.is_empty() {
+        return Err(RestaurantError::InvalidSplit(format!(
+            "No items of table {} were ordered with a seat",
+            bill.table_id
+        )));
+    }
+
+    let (mut labels, mut item_groups): (Vec<String>, Vec<Vec<OrderedItem>>) = seats
+        .into_iter()
+        .map(|(seat, group)| (format!("Seat {}", seat), group))
+        .unzip();
+    if !shared.is_empty() {
+        labels.push("Shared".to_string());
+        item_groups.push(shared);
+    }
+    Ok(build_checks(bill, labels, item_groups))
+}
+
+/// Builds the checks of groups of ordered items, dividing the bill's tax between them.
+fn build_checks(
+    bill: &Bill,
+    labels: Vec<String>,
+    item_groups: Vec<Vec<OrderedItem>>,
+) -> Vec<Check> {
+    let subtotals: Vec<Money> = item_groups
+        .iter()
+        .map(|group| group.iter().map(|item| item.price).sum())
+        .collect();
+    let taxes = allocate(bill.tax, &subtotals);
+    labels
+        .into_iter()
+        .zip(item_groups)
+        .zip(subtotals.into_iter().zip(taxes))
+        .enumerate()
+        .map(|(index, ((label, group), (subtotal, tax)))| Check {
+            check_id: index as u32 + 1,
+            label,
+            lines: Bill::new(bill.table_id, &group, bill.tax_rate).lines,
+            subtotal,
+            tax,
+            total: subtotal + tax,
+        })
+        .collect()
+}
+
+/// Divides the bill into `payers` checks whose totals differ by at most a cent.
+fn split_evenly(bill: &Bill, payers: u32) -> Result<Vec<Check>, RestaurantError> {
+    if payers == 0 || payers > MAX_PAYERS {
+        return Err(RestaurantError::InvalidSplit(format!(
+            "The number of payers must be between 1 and {}",
+            MAX_PAYERS
+        )));
+    }
+    let shares = vec![Money::from_cents(1); payers as usize];
+    let totals = allocate(bill.total, &shares);
+    let subtotals = allocate(bill.subtotal, &shares);
+    Ok(totals
+        .into_iter()
+        .zip(subtotals)
+        .enumerate()
+        .map(|(index, (total, subtotal))| Check {
+            check_id: index as u32 + 1,
+            label: format!("Share {} of {}", index + 1, payers),
+            lines: Vec::new(),
+            subtotal,
+            // The first checks get the extra cents of both the total and the subtotal,
+            // so the total share is never smaller than the subtotal share
+            tax: Money::from_cents(total.cents() - subtotal.cents()),
+            total,
+        })
+        .collect())
+}
+
+/// Divides an amount in proportion to weights, without losing or creating a cent.
+///
+/// Every part is first rounded down; the remaining cents go one by one to the parts with
+/// the largest remainders, and to the first of them on ties. Zero weights receive nothing
+/// unless all weights are zero, in which case the amount goes to the first part.
+///
+/// # Arguments
+/// * `amount` - The amount to divide.
+/// * `weights` - The weight of each part.
+///
+/// # Returns
+/// * `Vec<Money>` - The parts, which add up exactly to `amount`.
+pub fn allocate(amount: Money, weights: &[Money]) -> Vec<Money> {
+    let total_weight: u128 = weights.iter().map(|w| u128::from(w.cents())).sum();
+    if total_weight == 0 {
+        let mut parts = vec![Money::ZERO; weights.len()];
+        if let Some(first) = parts.first_mut() {
+            *first = amount;
+        }
+        return parts;
+    }
+
+    let amount = u128::from(amount.cents());
+    let mut parts: Vec<u128> = Vec::with_capacity(weights.len());
+    let mut remainders: Vec<(u128, usize)> = Vec::with_capacity(weights.len());
+    for (index, weight) in weights.iter().enumerate() {
+        let share = amount * u128::from(weight.cents());
+        parts.push(share / total_weight);
+        remainders.push((share % total_weight, index));
+    }
+    let left = amount - parts.iter().sum::<u128>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, index) in remainders.into_iter().take(left as usize) {
+        parts[index] += 1;
+    }
+    parts
+        .into_iter()
+        .map(|part| Money::from_cents(part as u64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::models::MenuItem;
+    use crate::server::data_model::money::TaxRate;
+
+    fn item(id: u32, cents: u64) -> OrderedItem {
+        MenuItem::new(id, &format!("Item {}", id), 5, Money::from_cents(cents)).into()
+    }
+
+    fn line(line_id: u32, id: u32, cents: u64) -> OrderedItem {
+        OrderedItem {
+            line_id,
+            ..item(id, cents)
+        }
+    }
+
+    fn assert_sums_to_bill(split: &SplitBill) {
+        let sum = |f: fn(&Check) -> Money| split.checks.iter().map(f).sum::<Money>();
+        assert_eq!(sum(|check| check.subtotal), split.bill.subtotal);
+        assert_eq!(sum(|check| check.tax), split.bill.tax);
+        assert_eq!(sum(|check| check.total), split.bill.total);
+    }
+
+    #[test]
+    fn test_allocate_distributes_remainder() {
+        let cents = |values: &[u64]| values.iter().map(|&c| Money::from_cents(c)).collect();
+        let parts: Vec<Money> = cents(&[34, 33, 33]);
+        assert_eq!(allocate(Money::from_cents(100), &cents(&[1, 1, 1])), parts);
+        assert_eq!(
+            allocate(Money::from_cents(10), &cents(&[0, 5, 5])),
+            cents(&[0, 5, 5])
+        );
+        assert_eq!(
+            allocate(Money::from_cents(7), &cents(&[0, 0])),
+            cents(&[7, 0])
+        );
+    }
+
+    #[test]
+    fn test_split_by_items() {
+        let items = vec![line(1, 1, 999), line(2, 2, 1), line(4, 1, 999)];
+        let bill = Bill::new(3, &items, TaxRate::from_basis_points(825));
+        let request = SplitRequest::Items {
+            checks: vec![vec![4], vec![2, 1]],
+        };
+        let split = SplitBill::new(bill, &items, &request).unwrap();
+
+        assert_eq!(split.checks.len(), 2);
+        assert_eq!(split.checks[0].label, "Check 1");
+        assert_eq!(split.checks[0].subtotal, Money::from_cents(999));
+        assert_eq!(split.checks[1].lines.len(), 2);
+        assert_eq!(split.checks[1].subtotal, Money::from_cents(1000));
+        assert_sums_to_bill(&split);
+    }
+
+    fn seated(id: u32, cents: u64, seat: Option<u32>) -> OrderedItem {
+        OrderedItem {
+            seat,
+            ..item(id, cents)
+        }
+    }
+
+    #[test]
+    fn test_split_by_seats() {
+        let items = vec![
+            seated(1, 333, Some(2)),
+            seated(2, 333, Some(1)),
+            seated(3, 334, None),
+            seated(1, 333, Some(1)),
+        ];
+        let bill = Bill::new(3, &items, TaxRate::from_basis_points(1000));
+        let split = SplitBill::new(bill, &items, &SplitRequest::Seats {}).unwrap();
+
+        let labels: Vec<&str> = split.checks.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["Seat 1", "Seat 2", "Shared"]);
+        assert_eq!(split.checks[0].subtotal, Money::from_cents(666));
+        assert_eq!(split.checks[1].subtotal, Money::from_cents(333));
+        assert_eq!(split.checks[2].subtotal, Money::from_cents(334));
+        assert_sums_to_bill(&split);
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        let items = vec![item(1, 1000)];
+        let bill = Bill::new(3, &items, TaxRate::from_basis_points(825));
+        let split = SplitBill::new(bill, &items, &SplitRequest::Even { payers: 3 }).unwrap();
+
+        let totals: Vec<Money> = split.checks.iter().map(|c| c.total).collect();
+        assert_eq!(
+            totals,
+            vec![
+                Money::from_cents(361),
+                Money::from_cents(361),
+                Money::from_cents(361)
+            ]
+        );
+        assert_eq!(split.checks[2].label, "Share 3 of 3");
+        assert_sums_to_bill(&split);
+    }
+
+    #[test]
+    fn test_split_sums_exactly_for_many_payers() {
+        let items = vec![item(1, 1234), item(2, 5678), item(3, 1)];
+        for payers in 1..=25 {
+            let bill = Bill::new(3, &items, TaxRate::from_basis_points(775));
+            let split = SplitBill::new(bill, &items, &SplitRequest::Even { payers }).unwrap();
+            assert_sums_to_bill(&split);
+            let min = split.checks.iter().map(|c| c.total).min().unwrap();
+            let max = split.checks.iter().map(|c| c.total).max().unwrap();
+            assert!(max.cents() - min.cents() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_invalid_splits() {
+        let items = vec![line(1, 1, 500), line(2, 2, 500)];
+        let bill = Bill::new(3, &items, TaxRate::default());
+        let invalid = [
+            SplitRequest::Items { checks: vec![] },
+            // Line 2 is not assigned
+            SplitRequest::Items {
+                checks: vec![vec![1]],
+            },
+            SplitRequest::Items {
+                checks: vec![vec![1, 2], vec![]],
+            },
+            // Line 1 is assigned twice
+            SplitRequest::Items {
+                checks: vec![vec![1], vec![2, 1]],
+            },
+            // There is no line 3
+            SplitRequest::Items {
+                checks: vec![vec![1, 3], vec![2]],
+            },
+            SplitRequest::Seats {},
+            SplitRequest::Even { payers: 0 },
+            SplitRequest::Even {
+                payers: MAX_PAYERS + 1,
+            },
+        ];
+        for request in invalid {
+            assert!(
+                matches!(
+                    SplitBill::new(bill.clone(), &items, &request),
+                    Err(RestaurantError::InvalidSplit(_))
+                ),
+                "{:?}",
+                request
+            );
+        }
+        let empty = Bill::new(3, &[], TaxRate::default());
+        assert!(SplitBill::new(empty, &[], &SplitRequest::Even { payers: 2 }).is_err());
+    }
+
+    #[test]
+    fn test_split_request_json() {
+        let request: SplitRequest = serde_json::from_str(r#"{"mode": "seats"}"#).unwrap();
+        assert_eq!(request, SplitRequest::Seats {});
+        assert!(
+            serde_json::from_str::<SplitRequest>(r#"{"mode": "seats", "seats": {"1": [6]}}"#)
+                .is_err()
+        );
+        assert!(serde_json::from_str::<SplitRequest>(r#"{"mode": "random"}"#).is_err());
+    }
+}
@@ -0,0 +1,182 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::MenuItem;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Represents the stock of a menu item that is counted or marked sold out.
+///
+/// Menu items without a stock level are not counted and can always be ordered.
+///
+/// - `item_id`: The ID of the menu item.
+/// - `remaining`: Portions left, if counted. Ordering decrements it, and removing the item
+///   from an order restores it.
+/// - `sold_out`: Whether a manager marked the item as sold out ("86'd"), regardless of
+///   the count.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StockLevel {
+    /// ID of the menu item.
+    pub item_id: u32,
+    /// Portions left in stock. Not set if the stock is not counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u32>,
+    /// Whether the menu item is marked as sold out.
+    #[serde(default)]
+    pub sold_out: bool,
+}
+
+impl StockLevel {
+    /// Returns whether the menu item can be ordered: it is not marked sold out and has
+    /// portions left, if counted.
+    pub fn is_available(&self) -> bool {
+        !self.sold_out && self.remaining != Some(0)
+    }
+
+    /// Returns whether the stock level has any effect, i.e. the stock is counted or the
+    /// menu item is marked sold out.
+    pub fn is_tracked(&self) -> bool {
+        self.remaining.is_some() || self.sold_out
+    }
+}
+
+/// A request to set the stock of a menu item.
+///
+/// - `remaining`: Portions in stock. Leaving it out stops counting the stock.
+/// - `sold_out`: Whether the menu item is sold out. Defaults to `false`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StockRequest {
+    /// Portions in stock.
+    #[serde(default)]
+    pub remaining: Option<u32>,
+    /// Whether the menu item is sold out.
+    #[serde(default)]
+    pub sold_out: bool,
+}
+
+/// A menu item as listed on the menu, together with its current stock.
+///
+/// The stock is kept by the stock and inventory stores, not on the menu item; it is only
+/// added to the menu item when the menu is listed. In JSON, the fields of the menu item
+/// and the stock are at the same level.
+///
+/// - `item`: The menu item.
+/// - `sold_out`: Whether the menu item cannot be ordered right now, either marked by a
+///   manager, out of stock or short of an ingredient.
+/// - `remaining`: Portions left in stock, if counted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MenuListing {
+    /// The menu item.
+    #[serde(flatten)]
+    pub item: MenuItem,
+    /// Whether the menu item is sold out.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sold_out: bool,
+    /// Portions left in stock, if counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u32>,
+}
+
+impl From<MenuItem> for MenuListing {
+    /// Lists a menu item that is available and not counted.
+    fn from(item: MenuItem) -> Self {
+        MenuListing {
+            item,
+            sold_out: false,
+            remaining: None,
+        }
+    }
+}
+
+/// Lists menu items with their stock levels.
+///
+/// # Arguments
+/// * `items` - The menu items.
+/// * `levels` - The stock levels of counted or sold out menu items.
+///
+/// # Returns
+/// * `Vec<MenuListing>` - The menu items in their order. Items without a stock level are
+///   available and not counted.
+pub fn with_stock(items: Vec<MenuItem>, levels: &[StockLevel]) -> Vec<MenuListing> {
+    items
+        .into_iter()
+        .map(|item| {
+            let level = levels.iter().find(|level| level.item_id == item.id);
+            MenuListing {
+                sold_out: level.is_some_and(|level| !level.is_available()),
+                remaining: level.and_then(|level| level.remaining),
+                item,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::Money;
+
+    fn item(id: u32) -> MenuItem {
+        MenuItem::new(id, &format!("Item {}", id), 5, Money::from_cents(1000))
+    }
+
+    fn level(item_id: u32, remaining: Option<u32>, sold_out: bool) -> StockLevel {
+        StockLevel {
+            item_id,
+            remaining,
+            sold_out,
+        }
+    }
+
+    #[test]
+    fn test_availability() {
+        assert!(level(1, None, false).is_available());
+        assert!(!level(1, None, false).is_tracked());
+        assert!(level(1, Some(2), false).is_available());
+        assert!(!level(1, Some(0), false).is_available());
+        assert!(!level(1, Some(2), true).is_available());
+        assert!(level(1, None, true).is_tracked());
+    }
+
+    #[test]
+    fn test_with_stock() {
+        let items = with_stock(
+            vec![item(1), item(2), item(3)],
+            &[level(1, Some(0), false), level(3, Some(4), false)],
+        );
+        let stock: Vec<(u32, bool, Option<u32>)> = items
+            .iter()
+            .map(|listing| (listing.item.id, listing.sold_out, listing.remaining))
+            .collect();
+        assert_eq!(
+            stock,
+            vec![(1, true, Some(0)), (2, false, None), (3, false, Some(4))]
+        );
+    }
+
+    #[test]
+    fn test_menu_listing_serializes_stock_with_the_item() {
+        let listing = MenuListing {
+            sold_out: true,
+            remaining: Some(0),
+            ..MenuListing::from(item(1))
+        };
+        let json = serde_json::to_value(&listing).unwrap();
+        assert_eq!(json["id"], 1);
+        assert_eq!(json["sold_out"], true);
+        assert_eq!(json["remaining"], 0);
+        assert_eq!(
+            serde_json::from_value::<MenuListing>(json).unwrap(),
+            listing
+        );
+
+        let json = serde_json::to_value(MenuListing::from(item(2))).unwrap();
+        assert!(json.get("sold_out").is_none());
+        assert!(json.get("remaining").is_none());
+        assert!(serde_json::to_value(item(2))
+            .unwrap()
+            .get("sold_out")
+            .is_none());
+    }
+}
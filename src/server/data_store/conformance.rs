@@ -0,0 +1,1069 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::dietary::Allergen;
+use crate::server::data_model::inventory::{Ingredient, RecipeIngredient};
+use crate::server::data_model::models::{
+    Bill, CheckStore, InventoryStore, MenuItem, MenuStore, OrderStore, PaymentStore,
+    ReservationStore, SessionStore, StockStore, TableStore,
+};
+use crate::server::data_model::modifier::{OrderLine, SelectedModifier};
+use crate::server::data_model::money::{Money, PriceDelta, TaxRate};
+use crate::server::data_model::payment::{Payment, PaymentMethod};
+use crate::server::data_model::reservation::ReservationRequest;
+use crate::server::data_model::split::{SplitBill, SplitRequest};
+use crate::server::data_model::stock::StockLevel;
+use crate::server::utils::error::RestaurantError;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// Number of threads used by the concurrency checks.
+const THREADS: u32 = 8;
+/// Number of operations of each thread in the concurrency checks.
+const ITEMS_PER_THREAD: u32 = 50;
+
+/// Checks that a menu store returns exactly the expected menu items.
+///
+/// Every backend must behave like the in-memory stores, because the server's responses
+/// depend on the exact results and `RestaurantError` variants the stores return.
+///
+/// The contract of `MenuStore`:
+/// - `get_all_menus` returns the menu items in a stable order, the same on every call.
+/// - Menu item IDs are unique.
+/// - Category IDs are unique, and `set_category_availability` returns
+///   `CategoryNotFound` for an unknown category and updates a known one.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `store` - The store to check.
+/// * `expected` - The menu items the store was created with, in the expected order.
+pub fn check_menu_store(store: &dyn MenuStore, expected: &[MenuItem]) {
+    let menus = store
+        .get_all_menus()
+        .unwrap_or_else(|e| panic!("get_all_menus failed: {}", e));
+    assert_eq!(menus, expected, "get_all_menus returned unexpected items");
+    assert_eq!(
+        store.get_all_menus(),
+        Ok(menus.clone()),
+        "get_all_menus must return the same items on every call"
+    );
+    assert_unique(menus.iter().map(|item| item.id), "menu item");
+
+    let categories = store
+        .get_categories()
+        .unwrap_or_else(|e| panic!("get_categories failed: {}", e));
+    assert_unique(categories.iter().map(|category| category.id), "category");
+    let unknown = categories
+        .iter()
+        .map(|category| category.id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    assert_eq!(
+        store.set_category_availability(unknown, false),
+        Err(RestaurantError::CategoryNotFound(unknown)),
+        "an unknown category must be reported as CategoryNotFound"
+    );
+    if let Some(category) = categories.first() {
+        let updated = store
+            .set_category_availability(category.id, !category.available)
+            .unwrap_or_else(|e| panic!("set_category_availability failed: {}", e));
+        assert_eq!(updated.available, !category.available);
+        assert_eq!(
+            store.set_category_availability(category.id, category.available),
+            Ok(category.clone()),
+            "restoring the availability must restore the category"
+        );
+    }
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks that a table store returns exactly the expected table IDs.
+///
+/// The contract of `TableStore`:
+/// - `get_all_tables` returns the table IDs in a stable order, the same on every call.
+/// - Table IDs are unique.
+/// - Every table seats at least one guest, and `get_table_capacity` returns
+///   `TableNotFound` for an unknown table.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `store` - The store to check.
+/// * `expected` - The table IDs the store was created with, in the expected order.
+pub fn check_table_store(store: &dyn TableStore, expected: &[u32]) {
+    let tables = store
+        .get_all_tables()
+        .unwrap_or_else(|e| panic!("get_all_tables failed: {}", e));
+    assert_eq!(tables, expected, "get_all_tables returned unexpected IDs");
+    assert_eq!(
+        store.get_all_tables(),
+        Ok(tables.clone()),
+        "get_all_tables must return the same IDs on every call"
+    );
+    assert_unique(tables.iter().cloned(), "table");
+    for &table_id in &tables {
+        let capacity = store
+            .get_table_capacity(table_id)
+            .unwrap_or_else(|e| panic!("get_table_capacity failed: {}", e));
+        assert!(capacity > 0, "table {} seats no guests", table_id);
+    }
+    let unknown = tables.iter().max().map_or(1, |max| max + 1);
+    assert_eq!(
+        store.get_table_capacity(unknown),
+        Err(RestaurantError::TableNotFound(unknown)),
+        "an unknown table must be reported as TableNotFound"
+    );
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks the full contract of an order store.
+///
+/// Each rule is checked on a new, empty store created by `new_store`:
+/// - A table without orders: `get_item_ids`, `get_lines`, `remove_item` and `remove_line`
+///   fail with `NoMenusForTable(table_id)`, `get_item_id` fails with
+///   `NoMenuForTable(table_id, item_id)`.
+/// - `add_item` keeps the order of arrival and duplicates, separately for each table.
+/// - `add_line` keeps the modifiers, note and seat of a line, which `get_lines` returns
+///   numbered from 1 in order of arrival.
+/// - `remove_line` removes exactly the line with the number and returns it, fails with
+///   `OrderLineNotFound(table_id, line_id)` for an unknown number, and numbers of removed
+///   lines are not given to later lines of the table.
+/// - `get_item_id` returns the item ID if the item is ordered for the table, and fails with
+///   `NoMenuForTable(table_id, item_id)` otherwise.
+/// - `remove_item` removes only the first occurrence of the item, and fails with
+///   `NoMenuForTable(table_id, item_id)` without changing the order if the item is not ordered.
+/// - A table whose items have all been removed has an empty order (`Ok(vec![])`).
+/// - Concurrent adds and removes from several threads are neither lost nor duplicated.
+/// - `shutdown` succeeds.
+///
+/// Stores do not validate table and menu item IDs; that is done by the `Restaurant`.
+/// Like the other checks, this panics with a description of the violated rule, so it is
+/// meant to be called from a test of the backend:
+///
+/// ```
+/// use restaurant_menu_app::server::data_store::conformance;
+/// use restaurant_menu_app::server::data_store::in_memory_order_store::InMemoryOrderStore;
+///
+/// conformance::check_order_store(InMemoryOrderStore::new);
+/// ```
+///
+/// # Arguments
+/// * `new_store` - Creates a new, empty store.
+pub fn check_order_store<S, F>(new_store: F)
+where
+    S: OrderStore,
+    F: Fn() -> S,
+{
+    check_empty_order_store(&new_store());
+    check_add_keeps_order_and_duplicates(&new_store());
+    check_tables_are_independent(&new_store());
+    check_lines_keep_modifiers_note_and_seat(&new_store());
+    check_get_item_id(&new_store());
+    check_remove_first_occurrence(&new_store());
+    check_remove_line(&new_store());
+    check_remove_missing_item(&new_store());
+    check_remove_all_items(&new_store());
+    check_concurrent_updates(&new_store());
+    assert_eq!(new_store().shutdown(), Ok(()), "shutdown failed");
+}
+
+fn check_empty_order_store(store: &dyn OrderStore) {
+    assert_eq!(
+        store.get_item_ids(1),
+        Err(RestaurantError::NoMenusForTable(1)),
+        "get_item_ids for a table without orders"
+    );
+    assert_eq!(
+        store.get_lines(1),
+        Err(RestaurantError::NoMenusForTable(1)),
+        "get_lines for a table without orders"
+    );
+    assert_eq!(
+        store.get_item_id(1, 2),
+        Err(RestaurantError::NoMenuForTable(1, 2)),
+        "get_item_id for a table without orders"
+    );
+    assert_eq!(
+        store.remove_item(1, 2),
+        Err(RestaurantError::NoMenusForTable(1)),
+        "remove_item for a table without orders"
+    );
+    assert_eq!(
+        store.remove_line(1, 1),
+        Err(RestaurantError::NoMenusForTable(1)),
+        "remove_line for a table without orders"
+    );
+}
+
+fn check_add_keeps_order_and_duplicates(store: &dyn OrderStore) {
+    for item_id in [3, 1, 3, 2] {
+        assert_eq!(store.add_item(1, item_id), Ok(()), "add_item failed");
+    }
+    assert_eq!(
+        store.get_item_ids(1),
+        Ok(vec![3, 1, 3, 2]),
+        "get_item_ids must return the items in order of arrival, including duplicates"
+    );
+}
+
+fn check_tables_are_independent(store: &dyn OrderStore) {
+    assert_eq!(store.add_item(1, 5), Ok(()), "add_item failed");
+    assert_eq!(store.add_item(2, 6), Ok(()), "add_item failed");
+    assert_eq!(store.get_item_ids(1), Ok(vec![5]), "orders of table 1");
+    assert_eq!(store.get_item_ids(2), Ok(vec![6]), "orders of table 2");
+    assert_eq!(
+        store.get_item_ids(3),
+        Err(RestaurantError::NoMenusForTable(3)),
+        "adding items to other tables must not create an order for table 3"
+    );
+    assert_eq!(
+        store.get_item_id(2, 5),
+        Err(RestaurantError::NoMenuForTable(2, 5)),
+        "an item of table 1 must not be found for table 2"
+    );
+}
+
+fn check_lines_keep_modifiers_note_and_seat(store: &dyn OrderStore) {
+    let line = OrderLine {
+        line_id: 0,
+        item_id: 6,
+        modifiers: vec![SelectedModifier {
+            group_id: 1,
+            modifier_id: 2,
+            name: "Bacon".to_string(),
+            price_delta: PriceDelta::from_cents(150),
+        }],
+        note: Some("No sesame".to_string()),
+        seat: Some(2),
+    };
+    assert_eq!(store.add_line(1, line.clone()), Ok(()), "add_line failed");
+    store.add_item(1, 6).expect("add_item failed");
+    assert_eq!(
+        store.get_lines(1),
+        Ok(vec![
+            OrderLine { line_id: 1, ..line },
+            OrderLine {
+                line_id: 2,
+                ..OrderLine::plain(6)
+            }
+        ]),
+        "get_lines must return the numbered lines with their modifiers, notes and seats"
+    );
+    assert_eq!(
+        store.get_item_ids(1),
+        Ok(vec![6, 6]),
+        "get_item_ids must include lines with modifiers"
+    );
+}
+
+fn check_get_item_id(store: &dyn OrderStore) {
+    store.add_item(1, 7).expect("add_item failed");
+    store.add_item(1, 7).expect("add_item failed");
+    assert_eq!(
+        store.get_item_id(1, 7),
+        Ok(7),
+        "get_item_id of an ordered item"
+    );
+    assert_eq!(
+        store.get_item_id(1, 8),
+        Err(RestaurantError::NoMenuForTable(1, 8)),
+        "get_item_id of an item that is not ordered"
+    );
+}
+
+fn check_remove_line(store: &dyn OrderStore) {
+    for item_id in [3, 1, 3] {
+        store.add_item(1, item_id).expect("add_item failed");
+    }
+    let removed = store.remove_line(1, 3).expect("remove_line failed");
+    assert_eq!(
+        (removed.line_id, removed.item_id),
+        (3, 3),
+        "remove_line must return the removed line"
+    );
+    store.add_item(1, 4).expect("add_item failed");
+    let lines = store.get_lines(1).expect("get_lines failed");
+    assert_eq!(
+        lines
+            .iter()
+            .map(|line| (line.line_id, line.item_id))
+            .collect::<Vec<_>>(),
+        vec![(1, 3), (2, 1), (4, 4)],
+        "remove_line must remove only the line with the number, which is not reused"
+    );
+    assert_eq!(
+        store.remove_line(1, 3),
+        Err(RestaurantError::OrderLineNotFound(1, 3)),
+        "remove_line of a removed line"
+    );
+}
+
+fn check_remove_first_occurrence(store: &dyn OrderStore) {
+    for item_id in [3, 1, 3] {
+        store.add_item(1, item_id).expect("add_item failed");
+    }
+    assert_eq!(store.remove_item(1, 3), Ok(()), "remove_item failed");
+    assert_eq!(
+        store.get_item_ids(1),
+        Ok(vec![1, 3]),
+        "remove_item must remove only the first occurrence of the item"
+    );
+    assert_eq!(
+        store.get_item_id(1, 3),
+        Ok(3),
+        "the remaining occurrence must still be found"
+    );
+}
+
+fn check_remove_missing_item(store: &dyn OrderStore) {
+    store.add_item(1, 1).expect("add_item failed");
+    assert_eq!(
+        store.remove_item(1, 2),
+        Err(RestaurantError::NoMenuForTable(1, 2)),
+        "remove_item of an item that is not ordered"
+    );
+    assert_eq!(
+        store.get_item_ids(1),
+        Ok(vec![1]),
+        "a failed remove_item must not change the order"
+    );
+}
+
+fn check_remove_all_items(store: &dyn OrderStore) {
+    store.add_item(1, 4).expect("add_item failed");
+    store.remove_item(1, 4).expect("remove_item failed");
+    assert_eq!(
+        store.get_item_ids(1),
+        Ok(vec![]),
+        "a table whose items have all been removed has an empty order"
+    );
+    assert_eq!(
+        store.get_item_id(1, 4),
+        Err(RestaurantError::NoMenuForTable(1, 4)),
+        "get_item_id of a removed item"
+    );
+    assert_eq!(
+        store.remove_item(1, 4),
+        Err(RestaurantError::NoMenuForTable(1, 4)),
+        "remove_item of a removed item"
+    );
+}
+
+fn check_concurrent_updates(store: &dyn OrderStore) {
+    // Every thread adds its own items to a shared table, then removes every other one
+    std::thread::scope(|scope| {
+        for thread in 0..THREADS {
+            scope.spawn(move || {
+                let items = (0..ITEMS_PER_THREAD).map(|i| thread * ITEMS_PER_THREAD + i);
+                for item_id in items.clone() {
+                    store
+                        .add_item(1, item_id)
+                        .expect("concurrent add_item failed");
+                }
+                for item_id in items.step_by(2) {
+                    store
+                        .remove_item(1, item_id)
+                        .expect("concurrent remove_item failed");
+                }
+            });
+        }
+    });
+
+    let mut item_ids = store.get_item_ids(1).expect("get_item_ids failed");
+    item_ids.sort_unstable();
+    let expected: Vec<u32> = (0..THREADS * ITEMS_PER_THREAD)
+        .filter(|item_id| item_id % 2 == 1)
+        .collect();
+    assert_eq!(
+        item_ids, expected,
+        "concurrent adds and removes must be neither lost nor duplicated"
+    );
+}
+
+/// Checks the full contract of a check store.
+///
+/// The contract of `CheckStore`, checked on a new, empty store created by `new_store`:
+/// - `get_split` fails with `NoSplitForTable(table_id)` for a table without a split, and
+///   `remove_split` succeeds for it.
+/// - `save_split` replaces an earlier split of the table, separately for each table.
+/// - `remove_split` removes the split of the table.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `new_store` - Creates a new, empty store.
+pub fn check_check_store<S, F>(new_store: F)
+where
+    S: CheckStore,
+    F: Fn() -> S,
+{
+    let store = new_store();
+    assert_eq!(
+        store.get_split(1),
+        Err(RestaurantError::NoSplitForTable(1)),
+        "get_split for a table without a split"
+    );
+    assert_eq!(
+        store.remove_split(1),
+        Ok(()),
+        "remove_split for a table without a split"
+    );
+
+    store
+        .save_split(1, even_split(1, 2))
+        .expect("save_split failed");
+    store
+        .save_split(1, even_split(1, 3))
+        .expect("save_split failed");
+    assert_eq!(
+        store.get_split(1),
+        Ok(even_split(1, 3)),
+        "save_split must replace the earlier split of the table"
+    );
+    assert_eq!(
+        store.get_split(2),
+        Err(RestaurantError::NoSplitForTable(2)),
+        "a split of table 1 must not be found for table 2"
+    );
+
+    assert_eq!(store.remove_split(1), Ok(()), "remove_split failed");
+    assert_eq!(
+        store.get_split(1),
+        Err(RestaurantError::NoSplitForTable(1)),
+        "remove_split must remove the split of the table"
+    );
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks the full contract of a payment store.
+///
+/// The contract of `PaymentStore`, checked on a new, empty store created by `new_store`:
+/// - `get_payments` returns no payments for a table without payments.
+/// - `record_payment` keeps the payments of a table in order of arrival, separately for
+///   each table.
+/// - `clear_payments` removes the payments of the table only, and succeeds for a table
+///   without payments.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `new_store` - Creates a new, empty store.
+pub fn check_payment_store<S, F>(new_store: F)
+where
+    S: PaymentStore,
+    F: Fn() -> S,
+{
+    let store = new_store();
+    assert_eq!(
+        store.get_payments(1),
+        Ok(vec![]),
+        "get_payments for a table without payments"
+    );
+
+    let payments = [
+        cash(1, 1, 1000, 0),
+        cash(1, 2, 500, 150),
+        cash(2, 1, 700, 0),
+    ];
+    for payment in &payments {
+        assert_eq!(
+            store.record_payment(payment.clone()),
+            Ok(()),
+            "record_payment failed"
+        );
+    }
+    assert_eq!(
+        store.get_payments(1),
+        Ok(payments[..2].to_vec()),
+        "get_payments must return the payments of the table in order of arrival"
+    );
+
+    assert_eq!(store.clear_payments(1), Ok(()), "clear_payments failed");
+    assert_eq!(
+        store.get_payments(1),
+        Ok(vec![]),
+        "clear_payments must remove the payments of the table"
+    );
+    assert_eq!(
+        store.get_payments(2),
+        Ok(payments[2..].to_vec()),
+        "clear_payments must not remove the payments of other tables"
+    );
+    assert_eq!(
+        store.clear_payments(3),
+        Ok(()),
+        "clear_payments for a table without payments"
+    );
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks the full contract of a session store.
+///
+/// The contract of `SessionStore`, checked on a new, empty store created by `new_store`:
+/// - `get_active_session`, `set_allergies` and `close_session` fail with
+///   `NoActiveSession(table_id)` for a table that is not open.
+/// - `open_session` assigns unique session IDs and fails with `SessionAlreadyOpen(table_id)`
+///   for a table that is open.
+/// - `set_allergies` replaces the allergies of the active session.
+/// - `close_session` keeps the bill and payments, and the table can be opened again.
+/// - `record_tip` adds a payment to a closed session, and fails with `InvalidPayment` for an
+///   active or unknown session.
+/// - `get_history` returns the closed sessions in the order they were closed, of one table
+///   or of all tables.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `new_store` - Creates a new, empty store.
+pub fn check_session_store<S, F>(new_store: F)
+where
+    S: SessionStore,
+    F: Fn() -> S,
+{
+    let store = new_store();
+    assert_eq!(
+        store.get_active_session(1),
+        Err(RestaurantError::NoActiveSession(1)),
+        "get_active_session for a table that is not open"
+    );
+    assert_eq!(
+        store.set_allergies(1, vec![Allergen::Nuts]),
+        Err(RestaurantError::NoActiveSession(1)),
+        "set_allergies for a table that is not open"
+    );
+    assert_eq!(
+        store.close_session(1, 20, empty_bill(1), vec![]),
+        Err(RestaurantError::NoActiveSession(1)),
+        "close_session for a table that is not open"
+    );
+
+    let first = store
+        .open_session(1, Some(4), 10)
+        .unwrap_or_else(|e| panic!("open_session failed: {}", e));
+    assert_eq!(
+        (first.table_id, first.party_size, first.opened_at),
+        (1, Some(4), 10),
+        "open_session must keep the table, party size and opening time"
+    );
+    assert_eq!(
+        store.open_session(1, None, 11),
+        Err(RestaurantError::SessionAlreadyOpen(1)),
+        "open_session for a table that is open"
+    );
+    let other = store
+        .open_session(2, None, 12)
+        .unwrap_or_else(|e| panic!("open_session failed: {}", e));
+    assert_ne!(
+        first.session_id, other.session_id,
+        "open_session must assign unique session IDs"
+    );
+
+    let updated = store
+        .set_allergies(1, vec![Allergen::Gluten])
+        .unwrap_or_else(|e| panic!("set_allergies failed: {}", e));
+    assert_eq!(
+        store.get_active_session(1),
+        Ok(updated.clone()),
+        "get_active_session must return the session with its allergies"
+    );
+    assert_eq!(
+        updated.allergies,
+        vec![Allergen::Gluten],
+        "set_allergies must replace the allergies of the session"
+    );
+    assert!(
+        matches!(
+            store.record_tip(first.session_id, cash(1, 1, 0, 100)),
+            Err(RestaurantError::InvalidPayment(_))
+        ),
+        "record_tip for an active session"
+    );
+
+    let payment = cash(2, 1, 1000, 0);
+    let closed_other = store
+        .close_session(2, 30, empty_bill(2), vec![payment.clone()])
+        .unwrap_or_else(|e| panic!("close_session failed: {}", e));
+    assert_eq!(
+        (
+            closed_other.closed_at,
+            closed_other.bill.clone(),
+            closed_other.payments.clone()
+        ),
+        (Some(30), Some(empty_bill(2)), vec![payment]),
+        "close_session must keep the closing time, bill and payments"
+    );
+    let closed_first = store
+        .close_session(1, 40, empty_bill(1), vec![])
+        .unwrap_or_else(|e| panic!("close_session failed: {}", e));
+    assert_eq!(
+        store.get_active_session(1),
+        Err(RestaurantError::NoActiveSession(1)),
+        "close_session must end the active session"
+    );
+
+    let tipped = store
+        .record_tip(closed_first.session_id, cash(1, 1, 0, 100))
+        .unwrap_or_else(|e| panic!("record_tip failed: {}", e));
+    assert_eq!(
+        tipped.payments,
+        vec![cash(1, 1, 0, 100)],
+        "record_tip must add the tip to the closed session"
+    );
+    let unknown = closed_first.session_id.max(closed_other.session_id) + 1;
+    assert!(
+        matches!(
+            store.record_tip(unknown, cash(1, 2, 0, 100)),
+            Err(RestaurantError::InvalidPayment(_))
+        ),
+        "record_tip for an unknown session"
+    );
+
+    assert_eq!(
+        store.get_history(None),
+        Ok(vec![closed_other.clone(), tipped.clone()]),
+        "get_history must return the closed sessions in the order they were closed"
+    );
+    assert_eq!(
+        store.get_history(Some(1)),
+        Ok(vec![tipped]),
+        "get_history of one table"
+    );
+    store
+        .open_session(1, None, 50)
+        .unwrap_or_else(|e| panic!("a closed table must open again: {}", e));
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks the full contract of a stock store.
+///
+/// The contract of `StockStore`, checked on a new, empty store created by `new_store`:
+/// - A new store has no stock levels, and `take` and `restore` succeed for menu items
+///   whose stock is not counted.
+/// - `get_stock_levels` returns the counted or sold out menu items, ordered by item ID.
+///   Setting a menu item neither counted nor sold out removes its stock level.
+/// - `take` takes one portion of a counted menu item, and fails with
+///   `ItemUnavailable(item_id)` if the item is sold out or out of stock.
+/// - `restore` puts one portion back.
+/// - Concurrent takes from several threads never take more portions than in stock.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `new_store` - Creates a new, empty store.
+pub fn check_stock_store<S, F>(new_store: F)
+where
+    S: StockStore,
+    F: Fn() -> S,
+{
+    let store = new_store();
+    assert_eq!(
+        store.get_stock_levels(),
+        Ok(vec![]),
+        "a new store has no stock levels"
+    );
+    assert_eq!(store.take(1), Ok(()), "take of an uncounted item");
+    assert_eq!(store.restore(1), Ok(()), "restore of an uncounted item");
+
+    let counted = StockLevel {
+        item_id: 3,
+        remaining: Some(1),
+        sold_out: false,
+    };
+    let sold_out = StockLevel {
+        item_id: 2,
+        remaining: None,
+        sold_out: true,
+    };
+    assert_eq!(
+        store.set_stock(3, Some(1), false),
+        Ok(counted.clone()),
+        "set_stock failed"
+    );
+    assert_eq!(
+        store.set_stock(2, None, true),
+        Ok(sold_out.clone()),
+        "set_stock failed"
+    );
+    store
+        .set_stock(4, Some(5), false)
+        .expect("set_stock failed");
+    store.set_stock(4, None, false).expect("set_stock failed");
+    assert_eq!(
+        store.get_stock_levels(),
+        Ok(vec![sold_out, counted.clone()]),
+        "get_stock_levels must return the counted or sold out items, ordered by item ID"
+    );
+
+    assert_eq!(
+        store.take(2),
+        Err(RestaurantError::ItemUnavailable(2)),
+        "take of a sold out item"
+    );
+    assert_eq!(store.take(3), Ok(()), "take of an item in stock");
+    assert_eq!(
+        store.take(3),
+        Err(RestaurantError::ItemUnavailable(3)),
+        "take of an item out of stock"
+    );
+    assert_eq!(store.restore(3), Ok(()), "restore failed");
+    assert_eq!(
+        store.get_stock_levels().map(|levels| levels[1].clone()),
+        Ok(counted),
+        "restore must put one portion back"
+    );
+
+    let store = new_store();
+    store
+        .set_stock(1, Some(THREADS * ITEMS_PER_THREAD / 2), false)
+        .expect("set_stock failed");
+    let taken: u32 = std::thread::scope(|scope| {
+        let store = &store;
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                scope.spawn(move || {
+                    (0..ITEMS_PER_THREAD)
+                        .filter(|_| store.take(1).is_ok())
+                        .count() as u32
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .sum()
+    });
+    assert_eq!(
+        taken,
+        THREADS * ITEMS_PER_THREAD / 2,
+        "concurrent takes must take exactly the portions in stock"
+    );
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks the full contract of an inventory store.
+///
+/// The contract of `InventoryStore`, checked on stores created by `new_store` with the
+/// given ingredients:
+/// - `get_ingredients` returns the ingredients ordered by ID.
+/// - `restock` adds the quantity to an ingredient and returns it, and fails with
+///   `IngredientNotFound(ingredient_id)` for an unknown ingredient.
+/// - `deduct` deducts all ingredients of a recipe, or fails with `ItemUnavailable(item_id)`
+///   without deducting any if one runs short. An ingredient listed more than once is
+///   checked against its combined quantity.
+/// - `restore` puts the ingredients of a recipe back.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `new_store` - Creates a new store with the given ingredients.
+pub fn check_inventory_store<S, F>(new_store: F)
+where
+    S: InventoryStore,
+    F: Fn(Vec<Ingredient>) -> S,
+{
+    let lettuce = ingredient(1, "Lettuce", 100);
+    let bread = ingredient(2, "Bread", 2);
+    let store = new_store(vec![bread.clone(), lettuce.clone()]);
+    assert_eq!(
+        store.get_ingredients(),
+        Ok(vec![lettuce.clone(), bread.clone()]),
+        "get_ingredients must return the ingredients ordered by ID"
+    );
+
+    assert_eq!(
+        store.restock(2, 3),
+        Ok(Ingredient {
+            on_hand: 5,
+            ..bread.clone()
+        }),
+        "restock must add the quantity to the ingredient"
+    );
+    assert_eq!(
+        store.restock(3, 1),
+        Err(RestaurantError::IngredientNotFound(3)),
+        "restock of an unknown ingredient"
+    );
+
+    let recipe = [needs(1, 40), needs(2, 2)];
+    assert_eq!(store.deduct(7, &recipe), Ok(()), "deduct failed");
+    assert_eq!(store.deduct(7, &recipe), Ok(()), "deduct failed");
+    assert_eq!(
+        store.deduct(7, &recipe),
+        Err(RestaurantError::ItemUnavailable(7)),
+        "deduct of a recipe whose ingredient runs short"
+    );
+    assert_eq!(
+        on_hand(&store),
+        vec![20, 1],
+        "a failed deduct must not deduct any ingredient"
+    );
+    assert_eq!(
+        store.deduct(8, &[needs(2, 1), needs(2, 1)]),
+        Err(RestaurantError::ItemUnavailable(8)),
+        "deduct must check repeated ingredients against their combined quantity"
+    );
+
+    assert_eq!(store.restore(&recipe), Ok(()), "restore failed");
+    assert_eq!(
+        on_hand(&store),
+        vec![60, 3],
+        "restore must put the ingredients back"
+    );
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Checks the full contract of a reservation store.
+///
+/// The contract of `ReservationStore`, checked on a new, empty store created by `new_store`:
+/// - `get`, `update` and `cancel` fail with `ReservationNotFound(reservation_id)` for an
+///   unknown reservation.
+/// - `create` assigns unique reservation IDs and fails with
+///   `ReservationConflict(table_id, reservation_id)` if the slot overlaps another
+///   reservation of the table. A slot starting when another ends does not overlap it.
+/// - `get_reservations` returns the reservations ordered by the start of their slot.
+/// - `update` keeps the ID, and a reservation does not conflict with itself.
+/// - `cancel` removes the reservation and frees its slot.
+/// - Of concurrent overlapping reservations from several threads, exactly one is made.
+/// - `shutdown` succeeds.
+///
+/// # Arguments
+/// * `new_store` - Creates a new, empty store.
+pub fn check_reservation_store<S, F>(new_store: F)
+where
+    S: ReservationStore,
+    F: Fn() -> S,
+{
+    let store = new_store();
+    assert_eq!(
+        store.get(1),
+        Err(RestaurantError::ReservationNotFound(1)),
+        "get of an unknown reservation"
+    );
+    assert_eq!(
+        store.update(1, &reservation(1, 3600)),
+        Err(RestaurantError::ReservationNotFound(1)),
+        "update of an unknown reservation"
+    );
+    assert_eq!(
+        store.cancel(1),
+        Err(RestaurantError::ReservationNotFound(1)),
+        "cancel of an unknown reservation"
+    );
+
+    let late = store
+        .create(&reservation(1, 7200))
+        .unwrap_or_else(|e| panic!("create failed: {}", e));
+    let early = store
+        .create(&reservation(1, 3600))
+        .unwrap_or_else(|e| panic!("a slot ending when another starts must not conflict: {}", e));
+    let other = store
+        .create(&reservation(2, 7200))
+        .unwrap_or_else(|e| panic!("reservations of other tables must not conflict: {}", e));
+    assert_unique(
+        [
+            late.reservation_id,
+            early.reservation_id,
+            other.reservation_id,
+        ]
+        .into_iter(),
+        "reservation",
+    );
+    assert_eq!(
+        store.create(&reservation(1, 3000)),
+        Err(RestaurantError::ReservationConflict(
+            1,
+            early.reservation_id
+        )),
+        "create of an overlapping slot"
+    );
+    assert_eq!(
+        store
+            .get_reservations()
+            .map(|reservations| reservations.len()),
+        Ok(3),
+        "create of a conflicting slot must not make a reservation"
+    );
+    let starts: Vec<u64> = store
+        .get_reservations()
+        .expect("get_reservations failed")
+        .iter()
+        .map(|reservation| reservation.starts_at)
+        .collect();
+    assert_eq!(
+        starts,
+        vec![3600, 7200, 7200],
+        "get_reservations must order the reservations by the start of their slot"
+    );
+    assert_eq!(
+        store.get(late.reservation_id),
+        Ok(late.clone()),
+        "get failed"
+    );
+
+    let moved = store
+        .update(late.reservation_id, &reservation(1, 8000))
+        .unwrap_or_else(|e| panic!("a reservation must not conflict with itself: {}", e));
+    assert_eq!(
+        moved.reservation_id, late.reservation_id,
+        "update must keep the reservation ID"
+    );
+    assert_eq!(
+        store.get(late.reservation_id),
+        Ok(moved.clone()),
+        "update must replace the reservation"
+    );
+    assert_eq!(
+        store.update(moved.reservation_id, &reservation(1, 4000)),
+        Err(RestaurantError::ReservationConflict(
+            1,
+            early.reservation_id
+        )),
+        "update to an overlapping slot"
+    );
+
+    assert_eq!(
+        store.cancel(early.reservation_id),
+        Ok(early.clone()),
+        "cancel failed"
+    );
+    assert_eq!(
+        store.get(early.reservation_id),
+        Err(RestaurantError::ReservationNotFound(early.reservation_id)),
+        "cancel must remove the reservation"
+    );
+    store
+        .create(&reservation(1, 3600))
+        .unwrap_or_else(|e| panic!("cancel must free the slot: {}", e));
+
+    let store = new_store();
+    let made = std::thread::scope(|scope| {
+        let store = &store;
+        let handles: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                scope.spawn(move || {
+                    store
+                        .create(&reservation(1, 3600 + u64::from(thread) * 60))
+                        .is_ok()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|made| *made)
+            .count()
+    });
+    assert_eq!(
+        made, 1,
+        "exactly one of concurrent overlapping reservations must be made"
+    );
+    assert_eq!(store.shutdown(), Ok(()), "shutdown failed");
+}
+
+/// Returns the bill of one burger at a table, split evenly between payers.
+fn even_split(table_id: u32, payers: u32) -> SplitBill {
+    let items = vec![MenuItem::new(1, "Burger", 10, Money::from_cents(1250)).into()];
+    let bill = Bill::new(table_id, &items, TaxRate::default());
+    SplitBill::new(bill, &items, &SplitRequest::Even { payers })
+        .unwrap_or_else(|e| panic!("the split of a bill failed: {}", e))
+}
+
+/// Returns the bill of a table without items.
+fn empty_bill(table_id: u32) -> Bill {
+    Bill::new(table_id, &[], TaxRate::default())
+}
+
+/// Returns a cash payment towards the bill of a table.
+fn cash(table_id: u32, payment_id: u32, amount: u64, tip: u64) -> Payment {
+    Payment {
+        payment_id,
+        table_id,
+        check_id: None,
+        method: PaymentMethod::Cash,
+        amount: Money::from_cents(amount),
+        tip: Money::from_cents(tip),
+        reference: format!("cash-{}-{}", table_id, payment_id),
+    }
+}
+
+/// Returns an ingredient without a low stock threshold.
+fn ingredient(id: u32, name: &str, on_hand: u32) -> Ingredient {
+    Ingredient {
+        id,
+        name: name.to_string(),
+        unit: "pcs".to_string(),
+        on_hand,
+        low_stock_threshold: 0,
+    }
+}
+
+/// Returns a quantity of an ingredient used by a recipe.
+fn needs(ingredient_id: u32, quantity: u32) -> RecipeIngredient {
+    RecipeIngredient {
+        ingredient_id,
+        quantity,
+    }
+}
+
+/// Returns the quantities in stock, ordered by ingredient ID.
+fn on_hand(store: &dyn InventoryStore) -> Vec<u32> {
+    store
+        .get_ingredients()
+        .expect("get_ingredients failed")
+        .iter()
+        .map(|ingredient| ingredient.on_hand)
+        .collect()
+}
+
+/// Returns a request to reserve a table for an hour for two guests.
+fn reservation(table_id: u32, starts_at: u64) -> ReservationRequest {
+    ReservationRequest {
+        table_id,
+        party_size: 2,
+        contact_name: "Kim".to_string(),
+        starts_at,
+        duration_minutes: 60,
+    }
+}
+
+/// Asserts that no ID occurs twice.
+fn assert_unique<T: Copy + Eq + Hash + Display>(ids: impl Iterator<Item = T>, kind: &str) {
+    let mut seen = HashSet::new();
+    for id in ids {
+        assert!(seen.insert(id), "duplicate {} ID {}", kind, id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::models::{MockInventoryStore, MockOrderStore, MockTableStore};
+
+    #[test]
+    #[should_panic(expected = "get_item_ids for a table without orders")]
+    fn test_check_order_store_reports_violations() {
+        check_order_store(|| {
+            let mut store = MockOrderStore::new();
+            store.expect_get_item_ids().returning(|_| Ok(vec![]));
+            store
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "get_ingredients must return the ingredients ordered by ID")]
+    fn test_check_inventory_store_reports_violations() {
+        check_inventory_store(|ingredients| {
+            let mut store = MockInventoryStore::new();
+            store
+                .expect_get_ingredients()
+                .returning(move || Ok(ingredients.clone()));
+            store
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate table ID 2")]
+    fn test_check_table_store_rejects_duplicates() {
+        let mut store = MockTableStore::new();
+        store
+            .expect_get_all_tables()
+            .returning(|| Ok(vec![1, 2, 2]));
+        check_table_store(&store, &[1, 2, 2]);
+    }
+}
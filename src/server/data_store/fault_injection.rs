@@ -0,0 +1,397 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::config::FaultConfig;
+use crate::server::data_model::menu::Category;
+use crate::server::data_model::models::{MenuItem, MenuStore, OrderStore, TableStore};
+use crate::server::data_model::modifier::OrderLine;
+use crate::server::utils::error::RestaurantError;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Message of the `LockError` returned by a poisoned order store, as for a poisoned `Mutex`.
+const POISONED_MESSAGE: &str = "poisoned lock: another task failed inside";
+/// Message of the `LockError` returned by an order store for a randomly injected failure.
+const INJECTED_MESSAGE: &str = "injected fault";
+
+/// A fault decided for a single store call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The call fails as if the store's lock were poisoned.
+    Poisoned,
+    /// The call fails randomly.
+    Injected,
+}
+
+/// Decides which store calls are delayed and which fail, according to a `FaultConfig`.
+pub struct FaultInjector {
+    config: FaultConfig,
+    rng: Mutex<StdRng>,
+}
+
+impl FaultInjector {
+    /// Creates an injector. Its random decisions are reproducible if the config has a seed.
+    ///
+    /// # Arguments
+    /// * `config` - The latency and failures to inject.
+    ///
+    /// # Returns
+    /// * `Ok(FaultInjector)` - The injector.
+    /// * `Err(String)` - If the failure rate is not between 0 and 1, as for
+    ///   `FaultConfig::validate`.
+    pub fn new(config: FaultConfig) -> Result<Self, String> {
+        config.validate()?;
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Ok(FaultInjector {
+            config,
+            rng: Mutex::new(rng),
+        })
+    }
+
+    /// Delays the calling thread by the configured latency and decides whether the call fails.
+    ///
+    /// The delay blocks the thread, like a store waiting for a slow disk or database would.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the call should be passed to the wrapped store.
+    /// * `Err(Fault)` - If the call should fail.
+    pub fn before_call(&self) -> Result<(), Fault> {
+        let (latency, fails) = match self.rng.lock() {
+            Ok(mut rng) => {
+                let jitter = if self.config.jitter_ms > 0 {
+                    rng.gen_range(0..=self.config.jitter_ms)
+                } else {
+                    0
+                };
+                (
+                    self.config.latency_ms + jitter,
+                    rng.gen_bool(self.config.failure_rate),
+                )
+            }
+            Err(_) => (self.config.latency_ms, true),
+        };
+        if latency > 0 {
+            std::thread::sleep(Duration::from_millis(latency));
+        }
+
+        if self.config.poisoned {
+            Err(Fault::Poisoned)
+        } else if fails {
+            Err(Fault::Injected)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Decorator that injects latency and failures into any menu, order or table store.
+///
+/// Failures use the error the in-memory stores return when their lock is poisoned:
+/// `MenusRetrieveError` for menu stores, `TablesRetrieveError` for table stores and
+/// `LockError` for order stores. `shutdown` is always passed to the wrapped store.
+///
+/// # Example
+/// ```
+/// use restaurant_menu_app::server::config::FaultConfig;
+/// use restaurant_menu_app::server::data_model::models::OrderStore;
+/// use restaurant_menu_app::server::data_store::fault_injection::FaultyStore;
+/// use restaurant_menu_app::server::data_store::in_memory_order_store::InMemoryOrderStore;
+///
+/// let config = FaultConfig { poisoned: true, ..Default::default() };
+/// let store = FaultyStore::new(InMemoryOrderStore::new(), config).unwrap();
+/// assert!(store.add_item(1, 2).is_err());
+///
+/// let invalid = FaultConfig { failure_rate: 1.5, ..Default::default() };
+/// assert!(FaultyStore::new(InMemoryOrderStore::new(), invalid).is_err());
+/// ```
+pub struct FaultyStore<S> {
+    inner: S,
+    injector: FaultInjector,
+}
+
+impl<S> FaultyStore<S> {
+    /// Wraps a store.
+    ///
+    /// # Arguments
+    /// * `inner` - The store whose calls are passed through when no failure is injected.
+    /// * `config` - The latency and failures to inject.
+    ///
+    /// # Returns
+    /// * `Ok(FaultyStore)` - The wrapped store.
+    /// * `Err(String)` - If the failure rate is not between 0 and 1.
+    pub fn new(inner: S, config: FaultConfig) -> Result<Self, String> {
+        Ok(FaultyStore {
+            inner,
+            injector: FaultInjector::new(config)?,
+        })
+    }
+
+    /// Returns the wrapped store.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Runs the fault injection for a call that reports failures as lock errors, e.g. of the order store.
+    fn check_lock(&self) -> Result<(), RestaurantError> {
+        self.injector.before_call().map_err(|fault| {
+            RestaurantError::LockError(
+                match fault {
+                    Fault::Poisoned => POISONED_MESSAGE,
+                    Fault::Injected => INJECTED_MESSAGE,
+                }
+                .to_string(),
+            )
+        })
+    }
+}
+
+impl<S: MenuStore> MenuStore for FaultyStore<S> {
+    fn get_all_menus(&self) -> Result<Vec<MenuItem>, RestaurantError> {
+        self.injector
+            .before_call()
+            .map_err(|_| RestaurantError::MenusRetrieveError)?;
+        self.inner.get_all_menus()
+    }
+
+    fn get_categories(&self) -> Result<Vec<Category>, RestaurantError> {
+        self.injector
+            .before_call()
+            .map_err(|_| RestaurantError::MenusRetrieveError)?;
+        self.inner.get_categories()
+    }
+
+    fn set_category_availability(
+        &self,
+        category_id: u32,
+        available: bool,
+    ) -> Result<Category, RestaurantError> {
+        self.check_lock()?;
+        self.inner.set_category_availability(category_id, available)
+    }
+
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        self.inner.shutdown()
+    }
+}
+
+impl<S: TableStore> TableStore for FaultyStore<S> {
+    fn get_all_tables(&self) -> Result<Vec<u32>, RestaurantError> {
+        self.injector
+            .before_call()
+            .map_err(|_| RestaurantError::TablesRetrieveError)?;
+        self.inner.get_all_tables()
+    }
+
+    fn get_table_capacity(&self, table_id: u32) -> Result<u32, RestaurantError> {
+        self.injector
+            .before_call()
+            .map_err(|_| RestaurantError::TablesRetrieveError)?;
+        self.inner.get_table_capacity(table_id)
+    }
+
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        self.inner.shutdown()
+    }
+}
+
+impl<S: OrderStore> OrderStore for FaultyStore<S> {
+    fn add_line(&self, table_id: u32, line: OrderLine) -> Result<(), RestaurantError> {
+        self.check_lock()?;
+        self.inner.add_line(table_id, line)
+    }
+
+    fn add_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError> {
+        self.check_lock()?;
+        self.inner.add_item(table_id, item_id)
+    }
+
+    fn remove_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError> {
+        self.check_lock()?;
+        self.inner.remove_item(table_id, item_id)
+    }
+
+    fn remove_line(&self, table_id: u32, line_id: u32) -> Result<OrderLine, RestaurantError> {
+        self.check_lock()?;
+        self.inner.remove_line(table_id, line_id)
+    }
+
+    fn get_lines(&self, table_id: u32) -> Result<Vec<OrderLine>, RestaurantError> {
+        self.check_lock()?;
+        self.inner.get_lines(table_id)
+    }
+
+    fn get_item_ids(&self, table_id: u32) -> Result<Vec<u32>, RestaurantError> {
+        self.check_lock()?;
+        self.inner.get_item_ids(table_id)
+    }
+
+    fn get_item_id(&self, table_id: u32, item_id: u32) -> Result<u32, RestaurantError> {
+        self.check_lock()?;
+        self.inner.get_item_id(table_id, item_id)
+    }
+
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        self.inner.shutdown()
+    }
+}
+
+/// Boxes a menu store, wrapped in a `FaultyStore` if faults are configured for it.
+/// Fails if the configured failure rate is not between 0 and 1.
+pub fn menu_store<S: MenuStore + 'static>(
+    store: S,
+    faults: Option<&FaultConfig>,
+) -> Result<Box<dyn MenuStore>, String> {
+    Ok(match faults {
+        Some(config) => Box::new(FaultyStore::new(store, config.clone())?),
+        None => Box::new(store),
+    })
+}
+
+/// Boxes an order store, wrapped in a `FaultyStore` if faults are configured for it.
+/// Fails if the configured failure rate is not between 0 and 1.
+pub fn order_store<S: OrderStore + 'static>(
+    store: S,
+    faults: Option<&FaultConfig>,
+) -> Result<Box<dyn OrderStore>, String> {
+    Ok(match faults {
+        Some(config) => Box::new(FaultyStore::new(store, config.clone())?),
+        None => Box::new(store),
+    })
+}
+
+/// Boxes a table store, wrapped in a `FaultyStore` if faults are configured for it.
+/// Fails if the configured failure rate is not between 0 and 1.
+pub fn table_store<S: TableStore + 'static>(
+    store: S,
+    faults: Option<&FaultConfig>,
+) -> Result<Box<dyn TableStore>, String> {
+    Ok(match faults {
+        Some(config) => Box::new(FaultyStore::new(store, config.clone())?),
+        None => Box::new(store),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_store::conformance;
+    use crate::server::data_store::in_memory_menu_store::InMemoryMenuStore;
+    use crate::server::data_store::in_memory_order_store::InMemoryOrderStore;
+    use crate::server::data_store::in_memory_table_store::InMemoryTableStore;
+    use std::time::Instant;
+
+    #[test]
+    fn test_without_faults_store_is_unchanged() {
+        conformance::check_order_store(|| {
+            FaultyStore::new(InMemoryOrderStore::new(), FaultConfig::default()).unwrap()
+        });
+        let tables: Vec<u32> = (1..=100).collect();
+        conformance::check_table_store(
+            &FaultyStore::new(InMemoryTableStore::new(), FaultConfig::default()).unwrap(),
+            &tables,
+        );
+    }
+
+    #[test]
+    fn test_poisoned_store_returns_lock_errors() {
+        let config = FaultConfig {
+            poisoned: true,
+            ..Default::default()
+        };
+        let orders = FaultyStore::new(InMemoryOrderStore::new(), config.clone()).unwrap();
+        assert_eq!(
+            orders.add_item(1, 1),
+            Err(RestaurantError::LockError(POISONED_MESSAGE.to_string()))
+        );
+        assert_eq!(
+            orders.inner().get_item_ids(1),
+            Err(RestaurantError::NoMenusForTable(1))
+        );
+
+        let menus = FaultyStore::new(InMemoryMenuStore::default(), config.clone()).unwrap();
+        assert_eq!(
+            menus.get_all_menus(),
+            Err(RestaurantError::MenusRetrieveError)
+        );
+        let tables = FaultyStore::new(InMemoryTableStore::new(), config).unwrap();
+        assert_eq!(
+            tables.get_all_tables(),
+            Err(RestaurantError::TablesRetrieveError)
+        );
+        assert_eq!(tables.shutdown(), Ok(()));
+    }
+
+    #[test]
+    fn test_failure_rate_is_reproducible() {
+        let config = FaultConfig {
+            failure_rate: 0.3,
+            seed: Some(7),
+            ..Default::default()
+        };
+        let run = || {
+            let store = FaultyStore::new(InMemoryOrderStore::new(), config.clone()).unwrap();
+            (0..200)
+                .map(|i| store.add_item(1, i).is_err())
+                .collect::<Vec<bool>>()
+        };
+        let failures = run();
+        assert_eq!(failures, run());
+        let count = failures.iter().filter(|&&failed| failed).count();
+        assert!((30..90).contains(&count), "{} failures", count);
+    }
+
+    #[test]
+    fn test_invalid_failure_rate_is_rejected() {
+        for failure_rate in [-0.1, 1.5, f64::NAN] {
+            let config = FaultConfig {
+                failure_rate,
+                ..Default::default()
+            };
+            assert!(FaultyStore::new(InMemoryOrderStore::new(), config.clone()).is_err());
+            assert!(menu_store(InMemoryMenuStore::default(), Some(&config)).is_err());
+            assert!(table_store(InMemoryTableStore::new(), Some(&config)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_latency_is_injected() {
+        let config = FaultConfig {
+            latency_ms: 20,
+            jitter_ms: 5,
+            ..Default::default()
+        };
+        let store = FaultyStore::new(InMemoryTableStore::new(), config).unwrap();
+        let start = Instant::now();
+        assert!(store.get_all_tables().is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_store_helpers_wrap_only_configured_stores() {
+        let poisoned = FaultConfig {
+            poisoned: true,
+            ..Default::default()
+        };
+        assert!(menu_store(InMemoryMenuStore::default(), None)
+            .unwrap()
+            .get_all_menus()
+            .is_ok());
+        assert!(menu_store(InMemoryMenuStore::default(), Some(&poisoned))
+            .unwrap()
+            .get_all_menus()
+            .is_err());
+        assert!(order_store(InMemoryOrderStore::new(), Some(&poisoned))
+            .unwrap()
+            .add_item(1, 1)
+            .is_err());
+        assert!(table_store(InMemoryTableStore::new(), None)
+            .unwrap()
+            .get_all_tables()
+            .is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::CheckStore;
+use crate::server::data_model::split::SplitBill;
+use crate::server::utils::error::RestaurantError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// In-memory implementation of the `CheckStore` trait.
+///
+/// This store keeps the latest split bill of each table. The store is thread-safe,
+/// using a `Mutex` to protect access to the underlying data.
+pub struct InMemoryCheckStore {
+    splits: Arc<Mutex<HashMap<u32, SplitBill>>>, // Stores table_id -> SplitBill
+}
+
+impl InMemoryCheckStore {
+    /// Creates a new instance of `InMemoryCheckStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryCheckStore` without any split bills.
+    pub fn new() -> Self {
+        InMemoryCheckStore {
+            splits: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryCheckStore {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CheckStore for InMemoryCheckStore {
+    /// Stores the split bill of a table, replacing a previous split.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table whose bill was split.
+    /// * `split` - The bill and its checks.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the split was stored.
+    /// * `Err(RestaurantError)` if there was an error accessing the check store.
+    fn save_split(&self, table_id: u32, split: SplitBill) -> Result<(), RestaurantError> {
+        let mut splits = self
+            .splits
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        splits.insert(table_id, split);
+        Ok(())
+    }
+
+    /// Retrieves the split bill of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SplitBill)` with the bill and its checks.
+    /// * `Err(RestaurantError::NoSplitForTable)` if the bill of the table has not been split.
+    fn get_split(&self, table_id: u32) -> Result<SplitBill, RestaurantError> {
+        let splits = self
+            .splits
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        splits
+            .get(&table_id)
+            .cloned()
+            .ok_or(RestaurantError::NoSplitForTable(table_id))
+    }
+
+    /// Removes the split bill of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the split was removed, or the bill was not split.
+    /// * `Err(RestaurantError)` if there was an error accessing the check store.
+    fn remove_split(&self, table_id: u32) -> Result<(), RestaurantError> {
+        let mut splits = self
+            .splits
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        splits.remove(&table_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::models::{Bill, MenuItem};
+    use crate::server::data_model::money::{Money, TaxRate};
+    use crate::server::data_model::split::SplitRequest;
+
+    fn split(table_id: u32, payers: u32) -> SplitBill {
+        let items = vec![MenuItem::new(1, "Burger", 10, Money::from_cents(1250)).into()];
+        let bill = Bill::new(table_id, &items, TaxRate::default());
+        SplitBill::new(bill, &items, &SplitRequest::Even { payers }).unwrap()
+    }
+
+    #[test]
+    fn test_save_and_get_split() {
+        let store = InMemoryCheckStore::new();
+        assert_eq!(store.get_split(1), Err(RestaurantError::NoSplitForTable(1)));
+
+        store.save_split(1, split(1, 2)).unwrap();
+        store.save_split(1, split(1, 3)).unwrap();
+        assert_eq!(store.get_split(1).unwrap().checks.len(), 3);
+        assert_eq!(store.get_split(2), Err(RestaurantError::NoSplitForTable(2)));
+
+        store.remove_split(1).unwrap();
+        store.remove_split(1).unwrap();
+        assert_eq!(store.get_split(1), Err(RestaurantError::NoSplitForTable(1)));
+    }
+}
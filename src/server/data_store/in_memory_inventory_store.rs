@@ -0,0 +1,266 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::inventory::{combine_quantities, Ingredient, RecipeIngredient};
+use crate::server::data_model::models::InventoryStore;
+use crate::server::utils::error::RestaurantError;
+use std::sync::{Arc, Mutex};
+
+/// In-memory implementation of the `InventoryStore` trait.
+///
+/// This store keeps the ingredients of the kitchen, ordered by ID, with their quantities
+/// in stock. The store is thread-safe, using a `Mutex` to protect access to the underlying data,
+/// so that deducting the ingredients of a portion is atomic.
+pub struct InMemoryInventoryStore {
+    ingredients: Arc<Mutex<Vec<Ingredient>>>,
+}
+
+impl InMemoryInventoryStore {
+    /// Creates a new instance of `InMemoryInventoryStore` with the provided ingredients.
+    ///
+    /// # Arguments
+    ///
+    /// * `ingredients` - The ingredients in stock.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryInventoryStore`.
+    pub fn new(mut ingredients: Vec<Ingredient>) -> Self {
+        ingredients.sort_by_key(|ingredient| ingredient.id);
+        InMemoryInventoryStore {
+            ingredients: Arc::new(Mutex::new(ingredients)),
+        }
+    }
+
+    /// Creates a new instance of `InMemoryInventoryStore` with the ingredients of the
+    /// predefined recipes of `InMemoryMenuStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryInventoryStore` containing 15 ingredients, stocked for about
+    /// 1000 portions of every menu item, so that simulations and load tests do not run out.
+    pub fn with_predefined_ingredients() -> Self {
+        let ingredient = |id: u32, name: &str, unit: &str, on_hand: u32| Ingredient {
+            id,
+            name: name.to_string(),
+            unit: unit.to_string(),
+            on_hand,
+            low_stock_threshold: on_hand / 5,
+        };
+        Self::new(vec![
+            ingredient(1, "Lettuce", "g", 230000),
+            ingredient(2, "Tomatoes", "pcs", 6000),
+            ingredient(3, "Bread", "pcs", 4000),
+            ingredient(4, "Cheese", "g", 290000),
+            ingredient(5, "Eggs", "pcs", 11000),
+            ingredient(6, "Flour", "g", 600000),
+            ingredient(7, "Beef", "g", 720000),
+            ingredient(8, "Potatoes", "g", 850000),
+            ingredient(9, "Rice", "g", 950000),
+            ingredient(10, "Salmon", "g", 300000),
+            ingredient(11, "Noodles", "g", 350000),
+            ingredient(12, "Tortillas", "pcs", 4000),
+            ingredient(13, "Vegetables", "g", 1080000),
+            ingredient(14, "Milk", "ml", 600000),
+            ingredient(15, "White fish", "g", 200000),
+        ])
+    }
+}
+
+impl Default for InMemoryInventoryStore {
+    /// Provides a default implementation that initializes the store with predefined ingredients.
+    fn default() -> Self {
+        Self::with_predefined_ingredients()
+    }
+}
+
+impl InventoryStore for InMemoryInventoryStore {
+    /// Retrieves all ingredients in the store.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Ingredient>)` with the ingredients, ordered by ID.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn get_ingredients(&self) -> Result<Vec<Ingredient>, RestaurantError> {
+        let ingredients = self
+            .ingredients
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        Ok(ingredients.clone())
+    }
+
+    /// Adds a delivered quantity of an ingredient to its stock.
+    ///
+    /// # Arguments
+    ///
+    /// * `ingredient_id` - The ID of the ingredient.
+    /// * `quantity` - The quantity delivered.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Ingredient)` with the restocked ingredient.
+    /// * `Err(RestaurantError::IngredientNotFound)` if the ingredient does not exist.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn restock(&self, ingredient_id: u32, quantity: u32) -> Result<Ingredient, RestaurantError> {
+        let mut ingredients = self
+            .ingredients
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let ingredient = ingredients
+            .iter_mut()
+            .find(|ingredient| ingredient.id == ingredient_id)
+            .ok_or(RestaurantError::IngredientNotFound(ingredient_id))?;
+        ingredient.on_hand = ingredient.on_hand.saturating_add(quantity);
+        Ok(ingredient.clone())
+    }
+
+    /// Deducts the ingredients of one portion of a menu item, all or none. An ingredient
+    /// listed more than once is checked against its combined quantity.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_id` - The ID of the menu item.
+    /// * `recipe` - The ingredients of one portion.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the ingredients were deducted.
+    /// * `Err(RestaurantError::ItemUnavailable)` if an ingredient runs short.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn deduct(&self, item_id: u32, recipe: &[RecipeIngredient]) -> Result<(), RestaurantError> {
+        let mut ingredients = self
+            .ingredients
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let recipe = combine_quantities(recipe);
+        let short = recipe.iter().any(|needed| {
+            ingredients
+                .iter()
+                .find(|ingredient| ingredient.id == needed.ingredient_id)
+                .is_some_and(|ingredient| ingredient.on_hand < needed.quantity)
+        });
+        if short {
+            return Err(RestaurantError::ItemUnavailable(item_id));
+        }
+        for needed in &recipe {
+            if let Some(ingredient) = ingredients
+                .iter_mut()
+                .find(|ingredient| ingredient.id == needed.ingredient_id)
+            {
+                ingredient.on_hand -= needed.quantity;
+            }
+        }
+        Ok(())
+    }
+
+    /// Puts the ingredients of one portion of a menu item back into stock.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipe` - The ingredients of one portion.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the ingredients were put back.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn restore(&self, recipe: &[RecipeIngredient]) -> Result<(), RestaurantError> {
+        let mut ingredients = self
+            .ingredients
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        for needed in recipe {
+            if let Some(ingredient) = ingredients
+                .iter_mut()
+                .find(|ingredient| ingredient.id == needed.ingredient_id)
+            {
+                ingredient.on_hand = ingredient.on_hand.saturating_add(needed.quantity);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ingredient(id: u32, on_hand: u32) -> Ingredient {
+        Ingredient {
+            id,
+            name: format!("Ingredient {}", id),
+            unit: "g".to_string(),
+            on_hand,
+            low_stock_threshold: 10,
+        }
+    }
+
+    fn needs(ingredient_id: u32, quantity: u32) -> RecipeIngredient {
+        RecipeIngredient {
+            ingredient_id,
+            quantity,
+        }
+    }
+
+    fn on_hand(store: &InMemoryInventoryStore) -> Vec<u32> {
+        store
+            .get_ingredients()
+            .unwrap()
+            .iter()
+            .map(|ingredient| ingredient.on_hand)
+            .collect()
+    }
+
+    #[test]
+    fn test_deduct_all_or_nothing() {
+        let store = InMemoryInventoryStore::new(vec![ingredient(2, 50), ingredient(1, 100)]);
+        let recipe = [needs(1, 60), needs(2, 20), needs(9, 1)];
+        assert_eq!(store.deduct(7, &recipe), Ok(()));
+        assert_eq!(on_hand(&store), vec![40, 30]);
+
+        // Not enough of ingredient 1 for a second portion: nothing is deducted
+        assert_eq!(
+            store.deduct(7, &recipe),
+            Err(RestaurantError::ItemUnavailable(7))
+        );
+        assert_eq!(on_hand(&store), vec![40, 30]);
+
+        store.restore(&recipe).unwrap();
+        assert_eq!(on_hand(&store), vec![100, 50]);
+    }
+
+    #[test]
+    fn test_deduct_combines_repeated_ingredients() {
+        let store = InMemoryInventoryStore::new(vec![ingredient(1, 100)]);
+        let recipe = [needs(1, 60), needs(1, 60)];
+        assert_eq!(
+            store.deduct(7, &recipe),
+            Err(RestaurantError::ItemUnavailable(7))
+        );
+        assert_eq!(on_hand(&store), vec![100]);
+
+        store.restock(1, 20).unwrap();
+        assert_eq!(store.deduct(7, &recipe), Ok(()));
+        assert_eq!(on_hand(&store), vec![0]);
+        store.restore(&recipe).unwrap();
+        assert_eq!(on_hand(&store), vec![120]);
+    }
+
+    #[test]
+    fn test_restock() {
+        let store = InMemoryInventoryStore::new(vec![ingredient(1, 5)]);
+        let restocked = store.restock(1, 45).unwrap();
+        assert_eq!(restocked.on_hand, 50);
+        assert!(!restocked.is_low());
+        assert_eq!(
+            store.restock(2, 10),
+            Err(RestaurantError::IngredientNotFound(2))
+        );
+    }
+
+    #[test]
+    fn test_predefined_ingredients() {
+        let ingredients = InMemoryInventoryStore::default().get_ingredients().unwrap();
+        assert_eq!(ingredients.len(), 15);
+        assert!(ingredients.iter().all(|ingredient| !ingredient.is_low()));
+    }
+}
@@ -1,16 +1,22 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+use crate::server::data_model::dietary::{Allergen, Diet};
+use crate::server::data_model::inventory::RecipeIngredient;
+use crate::server::data_model::menu::Category;
 use crate::server::data_model::models::{MenuItem, MenuStore};
+use crate::server::data_model::modifier::{Modifier, ModifierGroup};
+use crate::server::data_model::money::{Money, PriceDelta};
 use crate::server::utils::error::RestaurantError;
 use std::sync::{Arc, Mutex};
 
 /// In-memory implementation of the `MenuStore` trait.
 ///
-/// This store maintains a list of menu items that can be accessed concurrently.
-/// The store is thread-safe, using a `Mutex` to protect access to the underlying data.
+/// This store maintains a list of menu items and their categories that can be accessed
+/// concurrently. The store is thread-safe, using a `Mutex` to protect access to the underlying data.
 pub struct InMemoryMenuStore {
     menus: Arc<Mutex<Vec<MenuItem>>>,
+    categories: Arc<Mutex<Vec<Category>>>,
 }
 
 impl InMemoryMenuStore {
@@ -22,10 +28,27 @@ impl InMemoryMenuStore {
     ///
     /// # Returns
     ///
-    /// A new instance of `InMemoryMenuStore`.
+    /// A new instance of `InMemoryMenuStore` without categories.
     pub fn new(menus: Vec<MenuItem>) -> Self {
         InMemoryMenuStore {
             menus: Arc::new(Mutex::new(menus)),
+            categories: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Sets the categories of the menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `categories` - The categories, referring to menu items by ID.
+    ///
+    /// # Returns
+    ///
+    /// The `InMemoryMenuStore` with the categories applied.
+    pub fn with_categories(self, categories: Vec<Category>) -> Self {
+        InMemoryMenuStore {
+            categories: Arc::new(Mutex::new(categories)),
+            ..self
         }
     }
 
@@ -33,111 +56,327 @@ impl InMemoryMenuStore {
     ///
     /// # Returns
     ///
-    /// A new instance of `InMemoryMenuStore` containing 20 predefined recipes with cooking times ranging from 1 to 15 minutes.
+    /// A new instance of `InMemoryMenuStore` containing 20 predefined recipes with cooking times ranging from 1 to 15 minutes,
+    /// grouped into starters, mains, breakfast and desserts and tagged with their allergens and diets.
+    /// Salad, steak, burger and pizza come with optional modifiers, tagged with the allergens
+    /// they add. An item is only tagged with a diet that every choice of modifiers keeps.
+    /// Every recipe lists the ingredients used for one portion.
     pub fn with_predefined_recipes() -> Self {
+        let modifier = |id: u32, name: &str, cents: i64| Modifier {
+            id,
+            name: name.to_string(),
+            price_delta: PriceDelta::from_cents(cents),
+            allergens: Vec::new(),
+        };
+        let group =
+            |id: u32, name: &str, max_selections: u32, modifiers: Vec<Modifier>| ModifierGroup {
+                id,
+                name: name.to_string(),
+                required: false,
+                min_selections: 0,
+                max_selections,
+                modifiers,
+            };
+        // Ingredient IDs refer to `InMemoryInventoryStore::with_predefined_ingredients`
+        let recipe = |lines: &[(u32, u32)]| -> Vec<RecipeIngredient> {
+            lines
+                .iter()
+                .map(|&(ingredient_id, quantity)| RecipeIngredient {
+                    ingredient_id,
+                    quantity,
+                })
+                .collect()
+        };
         let predefined_menus = vec![
             MenuItem {
                 id: 1,
                 name: "Salad".to_string(),
                 cooking_time_minutes: 1,
+                price: Money::from_cents(850),
+                // Not vegetarian: the Caesar dressing has anchovies
+                diets: vec![Diet::Halal],
+                ingredients: recipe(&[(1, 150), (2, 1)]),
+                modifier_groups: vec![group(
+                    1,
+                    "Dressing",
+                    1,
+                    vec![
+                        modifier(1, "Vinaigrette", 0),
+                        Modifier {
+                            allergens: vec![Allergen::Eggs, Allergen::Dairy, Allergen::Fish],
+                            ..modifier(2, "Caesar", 0)
+                        },
+                        modifier(3, "Dressing on the side", 0),
+                    ],
+                )],
+                ..Default::default()
             },
             MenuItem {
                 id: 2,
                 name: "Soup".to_string(),
                 cooking_time_minutes: 5,
+                price: Money::from_cents(650),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy],
+                diets: vec![Diet::Vegetarian],
+                ingredients: recipe(&[(13, 250), (14, 100), (3, 1)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 3,
                 name: "Sandwich".to_string(),
                 cooking_time_minutes: 7,
+                price: Money::from_cents(900),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy, Allergen::Eggs],
+                ingredients: recipe(&[(3, 2), (4, 40), (5, 1), (1, 30)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 4,
                 name: "Pasta".to_string(),
                 cooking_time_minutes: 12,
+                price: Money::from_cents(1400),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy, Allergen::Eggs],
+                diets: vec![Diet::Vegetarian],
+                ingredients: recipe(&[(11, 150), (4, 30), (2, 2)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 5,
                 name: "Steak".to_string(),
                 cooking_time_minutes: 15,
+                price: Money::from_cents(2450),
+                ingredients: recipe(&[(7, 300), (8, 200)]),
+                modifier_groups: vec![
+                    group(
+                        1,
+                        "Doneness",
+                        1,
+                        vec![
+                            modifier(1, "Rare", 0),
+                            modifier(2, "Medium rare", 0),
+                            modifier(3, "Medium", 0),
+                            modifier(4, "Well done", 0),
+                        ],
+                    ),
+                    group(
+                        2,
+                        "Sides",
+                        2,
+                        vec![
+                            Modifier {
+                                allergens: vec![Allergen::Dairy],
+                                ..modifier(5, "Pepper sauce", 250)
+                            },
+                            modifier(6, "Grilled mushrooms", 300),
+                        ],
+                    ),
+                ],
+                ..Default::default()
             },
             MenuItem {
                 id: 6,
                 name: "Burger".to_string(),
                 cooking_time_minutes: 10,
+                price: Money::from_cents(1250),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy, Allergen::Sesame],
+                ingredients: recipe(&[(3, 1), (7, 180), (4, 30), (1, 20), (2, 1)]),
+                modifier_groups: vec![
+                    group(
+                        1,
+                        "Extras",
+                        3,
+                        vec![
+                            modifier(1, "Extra cheese", 100),
+                            modifier(2, "Bacon", 150),
+                            modifier(3, "Avocado", 200),
+                        ],
+                    ),
+                    group(
+                        2,
+                        "Hold",
+                        3,
+                        vec![
+                            modifier(4, "No onions", 0),
+                            modifier(5, "No pickles", 0),
+                            modifier(6, "No tomato", 0),
+                        ],
+                    ),
+                ],
+                ..Default::default()
             },
             MenuItem {
                 id: 7,
                 name: "Pizza".to_string(),
                 cooking_time_minutes: 14,
+                price: Money::from_cents(1350),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy],
+                // Not vegetarian: pepperoni is one of the toppings
+                diets: Vec::new(),
+                ingredients: recipe(&[(6, 250), (4, 120), (2, 2)]),
+                modifier_groups: vec![group(
+                    1,
+                    "Toppings",
+                    4,
+                    vec![
+                        modifier(1, "Extra cheese", 150),
+                        modifier(2, "Mushrooms", 100),
+                        modifier(3, "Olives", 100),
+                        modifier(4, "Pepperoni", 200),
+                    ],
+                )],
             },
             MenuItem {
                 id: 8,
                 name: "Tacos".to_string(),
                 cooking_time_minutes: 8,
+                price: Money::from_cents(1100),
+                allergens: vec![Allergen::Gluten],
+                diets: vec![Diet::Halal],
+                ingredients: recipe(&[(12, 3), (7, 120), (1, 30)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 9,
                 name: "Fries".to_string(),
                 cooking_time_minutes: 3,
+                price: Money::from_cents(450),
+                diets: vec![Diet::Vegetarian, Diet::Vegan, Diet::Halal],
+                ingredients: recipe(&[(8, 250)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 10,
                 name: "Stir Fry".to_string(),
                 cooking_time_minutes: 10,
+                price: Money::from_cents(1300),
+                allergens: vec![Allergen::Peanuts, Allergen::Soy, Allergen::Sesame],
+                diets: vec![Diet::Vegetarian, Diet::Vegan, Diet::Halal],
+                ingredients: recipe(&[(13, 300), (9, 150)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 11,
                 name: "Omelette".to_string(),
                 cooking_time_minutes: 4,
+                price: Money::from_cents(800),
+                allergens: vec![Allergen::Dairy, Allergen::Eggs],
+                diets: vec![Diet::Vegetarian, Diet::Halal],
+                ingredients: recipe(&[(5, 3), (4, 30), (14, 50)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 12,
                 name: "Pancakes".to_string(),
                 cooking_time_minutes: 6,
+                price: Money::from_cents(750),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy, Allergen::Eggs],
+                diets: vec![Diet::Vegetarian],
+                ingredients: recipe(&[(6, 150), (5, 2), (14, 200)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 13,
                 name: "Sushi".to_string(),
                 cooking_time_minutes: 12,
+                price: Money::from_cents(1800),
+                allergens: vec![Allergen::Fish, Allergen::Soy, Allergen::Sesame],
+                diets: vec![Diet::Halal],
+                ingredients: recipe(&[(9, 200), (10, 100)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 14,
                 name: "Curry".to_string(),
                 cooking_time_minutes: 15,
+                price: Money::from_cents(1450),
+                allergens: vec![Allergen::Dairy, Allergen::Nuts],
+                diets: vec![Diet::Vegetarian, Diet::Halal],
+                ingredients: recipe(&[(13, 250), (9, 200), (14, 100)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 15,
                 name: "Fish & Chips".to_string(),
                 cooking_time_minutes: 13,
+                price: Money::from_cents(1550),
+                allergens: vec![Allergen::Gluten, Allergen::Fish],
+                diets: vec![Diet::Halal],
+                ingredients: recipe(&[(15, 200), (8, 250), (6, 50)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 16,
                 name: "Fried Rice".to_string(),
                 cooking_time_minutes: 9,
+                price: Money::from_cents(1050),
+                allergens: vec![Allergen::Eggs, Allergen::Soy],
+                diets: vec![Diet::Vegetarian],
+                ingredients: recipe(&[(9, 250), (5, 2), (13, 100)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 17,
                 name: "Ramen".to_string(),
                 cooking_time_minutes: 14,
+                price: Money::from_cents(1400),
+                allergens: vec![Allergen::Gluten, Allergen::Eggs, Allergen::Soy],
+                ingredients: recipe(&[(11, 200), (5, 1), (13, 80)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 18,
                 name: "Burrito".to_string(),
                 cooking_time_minutes: 8,
+                price: Money::from_cents(1150),
+                allergens: vec![Allergen::Gluten, Allergen::Dairy],
+                diets: vec![Diet::Halal],
+                ingredients: recipe(&[(12, 1), (9, 150), (7, 120), (4, 40)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 19,
                 name: "Waffles".to_string(),
                 cooking_time_minutes: 5,
+                price: Money::from_cents(700),
+                allergens: vec![
+                    Allergen::Gluten,
+                    Allergen::Dairy,
+                    Allergen::Eggs,
+                    Allergen::Nuts,
+                ],
+                diets: vec![Diet::Vegetarian],
+                ingredients: recipe(&[(6, 150), (5, 2), (14, 150)]),
+                ..Default::default()
             },
             MenuItem {
                 id: 20,
                 name: "Salmon".to_string(),
                 cooking_time_minutes: 13,
+                price: Money::from_cents(2100),
+                allergens: vec![Allergen::Fish],
+                diets: vec![Diet::Halal],
+                ingredients: recipe(&[(10, 200), (8, 150), (13, 100)]),
+                ..Default::default()
             },
         ];
-        Self::new(predefined_menus)
+        let category = |id: u32, name: &str, item_ids: Vec<u32>| Category {
+            id,
+            name: name.to_string(),
+            display_order: id,
+            available: true,
+            item_ids,
+        };
+        let predefined_categories = vec![
+            category(1, "Starters", vec![1, 2, 9]),
+            category(
+                2,
+                "Mains",
+                vec![3, 4, 5, 6, 7, 8, 10, 13, 14, 15, 16, 17, 18, 20],
+            ),
+            category(3, "Breakfast", vec![11, 12, 19]),
+            category(4, "Desserts", vec![12, 19]),
+        ];
+        Self::new(predefined_menus).with_categories(predefined_categories)
     }
 }
 
@@ -161,6 +400,47 @@ impl MenuStore for InMemoryMenuStore {
             .map_err(|_| RestaurantError::MenusRetrieveError)?;
         Ok(menus.clone())
     }
+
+    /// Retrieves all categories stored in the `InMemoryMenuStore`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of `Category`s if successful, or a `RestaurantError` if an error occurs.
+    fn get_categories(&self) -> Result<Vec<Category>, RestaurantError> {
+        let categories = self
+            .categories
+            .lock()
+            .map_err(|_| RestaurantError::MenusRetrieveError)?;
+        Ok(categories.clone())
+    }
+
+    /// Marks a category as served or not served.
+    ///
+    /// # Arguments
+    ///
+    /// * `category_id` - The ID of the category.
+    /// * `available` - Whether the category is served.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the updated `Category` if successful,
+    /// or `RestaurantError::CategoryNotFound` if the category does not exist.
+    fn set_category_availability(
+        &self,
+        category_id: u32,
+        available: bool,
+    ) -> Result<Category, RestaurantError> {
+        let mut categories = self
+            .categories
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let category = categories
+            .iter_mut()
+            .find(|category| category.id == category_id)
+            .ok_or(RestaurantError::CategoryNotFound(category_id))?;
+        category.available = available;
+        Ok(category.clone())
+    }
 }
 
 #[cfg(test)]
@@ -178,19 +458,49 @@ mod tests {
         assert!(menus.iter().any(|item| item.name == "Sushi"));
     }
 
+    #[test]
+    fn test_predefined_categories_cover_all_menus() {
+        let store = InMemoryMenuStore::with_predefined_recipes();
+        let menus = store.get_all_menus().unwrap();
+        let categories = store.get_categories().unwrap();
+
+        assert_eq!(categories.len(), 4);
+        for item in &menus {
+            assert!(
+                categories.iter().any(|c| c.item_ids.contains(&item.id)),
+                "{} has no category",
+                item.name
+            );
+        }
+        for category in &categories {
+            for item_id in &category.item_ids {
+                assert!(menus.iter().any(|item| item.id == *item_id));
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_category_availability() {
+        let store = InMemoryMenuStore::with_predefined_recipes();
+        let category = store.set_category_availability(3, false).unwrap();
+        assert_eq!(category.name, "Breakfast");
+        assert!(!category.available);
+        assert!(!store.get_categories().unwrap()[2].available);
+        assert_eq!(
+            store.set_category_availability(99, false),
+            Err(RestaurantError::CategoryNotFound(99))
+        );
+        assert!(InMemoryMenuStore::new(vec![])
+            .get_categories()
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_get_all_menus_custom_items_success() {
         let custom_items = vec![
-            MenuItem {
-                id: 1,
-                name: "Custom Item 1".to_string(),
-                cooking_time_minutes: 5,
-            },
-            MenuItem {
-                id: 2,
-                name: "Custom Item 2".to_string(),
-                cooking_time_minutes: 10,
-            },
+            MenuItem::new(1, "Custom Item 1", 5, Money::from_cents(1000)),
+            MenuItem::new(2, "Custom Item 2", 10, Money::from_cents(1000)),
         ];
         let store = InMemoryMenuStore::new(custom_items.clone());
         let menus = store.get_all_menus().unwrap();
@@ -202,9 +512,7 @@ mod tests {
     #[test]
     fn test_get_all_menus_error() {
         // Create a store with an empty list.
-        let store = InMemoryMenuStore {
-            menus: Arc::new(Mutex::new(vec![])),
-        };
+        let store = InMemoryMenuStore::new(vec![]);
 
         // Simulate a panic that causes the mutex to be poisoned.
         let result = std::panic::catch_unwind(|| {
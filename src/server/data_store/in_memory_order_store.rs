@@ -2,6 +2,7 @@
 #![deny(clippy::all)]
 
 use crate::server::data_model::models::OrderStore;
+use crate::server::data_model::modifier::OrderLine;
 use crate::server::utils::error::RestaurantError;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -9,10 +10,18 @@ use std::sync::{Arc, Mutex};
 /// In-memory implementation of the `OrderStore` trait.
 ///
 /// This store maintains orders for tables in the restaurant. Each order is represented
-/// as a mapping from table IDs to a list of menu item IDs. The store is thread-safe, using
-/// a `Mutex` to protect access to the underlying data.
+/// as a mapping from table IDs to a list of order lines, each holding a menu item ID with
+/// its modifiers and note. The store is thread-safe, using a `Mutex` to protect access to
+/// the underlying data.
 pub struct InMemoryOrderStore {
-    orders: Arc<Mutex<HashMap<u32, Vec<u32>>>>, // Stores table_id -> Vec<item_id>
+    orders: Arc<Mutex<HashMap<u32, TableOrder>>>, // Stores table_id -> TableOrder
+}
+
+/// The order of a table, with the number of the last line ever added to it.
+#[derive(Default)]
+struct TableOrder {
+    last_line_id: u32,
+    lines: Vec<OrderLine>,
 }
 
 impl InMemoryOrderStore {
@@ -36,27 +45,33 @@ impl Default for InMemoryOrderStore {
 }
 
 impl OrderStore for InMemoryOrderStore {
-    /// Adds a single item to the specified table's order by its menu item ID.
+    /// Adds a single order line to the specified table's order, numbering it after the
+    /// last line ever added to the table.
     ///
     /// # Arguments
     ///
-    /// * `table_id` - The ID of the table to which the item should be added.
-    /// * `item_id` - The ID of the menu item to add to the order.
+    /// * `table_id` - The ID of the table to which the line should be added.
+    /// * `line` - The menu item with its modifiers and note.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the item was successfully added.
+    /// * `Ok(())` if the line was successfully added.
     /// * `Err(RestaurantError)` if there was an error accessing the order store.
-    fn add_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError> {
+    fn add_line(&self, table_id: u32, line: OrderLine) -> Result<(), RestaurantError> {
         let mut orders = self
             .orders
             .lock()
             .map_err(|e| RestaurantError::LockError(e.to_string()))?;
-        orders.entry(table_id).or_default().push(item_id);
+        let order = orders.entry(table_id).or_default();
+        order.last_line_id += 1;
+        order.lines.push(OrderLine {
+            line_id: order.last_line_id,
+            ..line
+        });
         Ok(())
     }
 
-    /// Removes a specific item from the specified table's order by its menu item ID.
+    /// Removes the first order line of a menu item from the specified table's order.
     ///
     /// # Arguments
     ///
@@ -72,9 +87,9 @@ impl OrderStore for InMemoryOrderStore {
             .orders
             .lock()
             .map_err(|e| RestaurantError::LockError(e.to_string()))?;
-        if let Some(items) = orders.get_mut(&table_id) {
-            if let Some(pos) = items.iter().position(|&id| id == item_id) {
-                items.remove(pos);
+        if let Some(order) = orders.get_mut(&table_id) {
+            if let Some(pos) = order.lines.iter().position(|line| line.item_id == item_id) {
+                order.lines.remove(pos);
                 Ok(())
             } else {
                 Err(RestaurantError::NoMenuForTable(table_id, item_id))
@@ -84,27 +99,69 @@ impl OrderStore for InMemoryOrderStore {
         }
     }
 
-    /// Retrieves all item IDs from the specified table's order.
+    /// Removes the order line with the given number from the specified table's order.
     ///
     /// # Arguments
     ///
-    /// * `table_id` - The ID of the table whose items should be retrieved.
+    /// * `table_id` - The ID of the table from which the line should be removed.
+    /// * `line_id` - The number of the line to remove.
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<u32>)` containing all item IDs if the table is found.
+    /// * `Ok(OrderLine)` with the removed line.
+    /// * `Err(RestaurantError)` if the table or line was not found, or if there was an error accessing the order store.
+    fn remove_line(&self, table_id: u32, line_id: u32) -> Result<OrderLine, RestaurantError> {
+        let mut orders = self
+            .orders
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let order = orders
+            .get_mut(&table_id)
+            .ok_or(RestaurantError::NoMenusForTable(table_id))?;
+        let pos = order
+            .lines
+            .iter()
+            .position(|line| line.line_id == line_id)
+            .ok_or(RestaurantError::OrderLineNotFound(table_id, line_id))?;
+        Ok(order.lines.remove(pos))
+    }
+
+    /// Retrieves all order lines from the specified table's order.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table whose lines should be retrieved.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<OrderLine>)` containing all lines in order of arrival if the table is found.
     /// * `Err(RestaurantError)` if the table is not found or if there was an error accessing the order store.
-    fn get_item_ids(&self, table_id: u32) -> Result<Vec<u32>, RestaurantError> {
+    fn get_lines(&self, table_id: u32) -> Result<Vec<OrderLine>, RestaurantError> {
         let orders = self
             .orders
             .lock()
             .map_err(|e| RestaurantError::LockError(e.to_string()))?;
         orders
             .get(&table_id)
-            .cloned()
+            .map(|order| order.lines.clone())
             .ok_or(RestaurantError::NoMenusForTable(table_id))
     }
 
+    /// Retrieves all item IDs from the specified table's order.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table whose items should be retrieved.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u32>)` containing all item IDs if the table is found.
+    /// * `Err(RestaurantError)` if the table is not found or if there was an error accessing the order store.
+    fn get_item_ids(&self, table_id: u32) -> Result<Vec<u32>, RestaurantError> {
+        let lines = self.get_lines(table_id)?;
+        Ok(lines.into_iter().map(|line| line.item_id).collect())
+    }
+
     /// Retrieves a specific item ID from the specified table's order.
     ///
     /// # Arguments
@@ -123,8 +180,8 @@ impl OrderStore for InMemoryOrderStore {
             .map_err(|e| RestaurantError::LockError(e.to_string()))?;
         orders
             .get(&table_id)
-            .and_then(|items| {
-                if items.contains(&item_id) {
+            .and_then(|order| {
+                if order.lines.iter().any(|line| line.item_id == item_id) {
                     Some(item_id)
                 } else {
                     None
@@ -151,6 +208,30 @@ mod tests {
         assert_eq!(item_ids[0], item_id);
     }
 
+    #[test]
+    fn test_add_line_keeps_modifiers_and_note() {
+        let store = InMemoryOrderStore::new();
+        let line = OrderLine {
+            note: Some("no salt".to_string()),
+            ..OrderLine::plain(42)
+        };
+
+        store.add_line(1, line.clone()).unwrap();
+        store.add_item(1, 42).unwrap();
+        assert_eq!(
+            store.get_lines(1),
+            Ok(vec![
+                OrderLine { line_id: 1, ..line },
+                OrderLine {
+                    line_id: 2,
+                    ..OrderLine::plain(42)
+                }
+            ])
+        );
+        assert_eq!(store.get_item_ids(1), Ok(vec![42, 42]));
+        assert_eq!(store.get_lines(2), Err(RestaurantError::NoMenusForTable(2)));
+    }
+
     #[test]
     fn test_remove_item_success() {
         let store = InMemoryOrderStore::new();
@@ -185,6 +266,78 @@ mod tests {
         assert!(matches!(result, Err(RestaurantError::NoMenusForTable(99))));
     }
 
+    #[test]
+    fn test_remove_item_from_emptied_table() {
+        let store = InMemoryOrderStore::new();
+        store.add_item(1, 42).unwrap();
+        store.remove_item(1, 42).unwrap();
+
+        let result = store.remove_item(1, 42);
+        assert!(matches!(
+            result,
+            Err(RestaurantError::NoMenuForTable(1, 42))
+        ));
+        assert_eq!(store.get_item_ids(1).unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_remove_line_removes_that_line() {
+        let store = InMemoryOrderStore::new();
+        store.add_item(1, 42).unwrap();
+        store
+            .add_line(
+                1,
+                OrderLine {
+                    note: Some("no salt".to_string()),
+                    ..OrderLine::plain(42)
+                },
+            )
+            .unwrap();
+
+        let removed = store.remove_line(1, 2).unwrap();
+        assert_eq!(removed.note.as_deref(), Some("no salt"));
+        let lines = store.get_lines(1).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!((lines[0].line_id, lines[0].note.as_deref()), (1, None));
+    }
+
+    #[test]
+    fn test_remove_line_not_found() {
+        let store = InMemoryOrderStore::new();
+        assert_eq!(
+            store.remove_line(1, 1),
+            Err(RestaurantError::NoMenusForTable(1))
+        );
+
+        store.add_item(1, 42).unwrap();
+        store.remove_line(1, 1).unwrap();
+        assert_eq!(
+            store.remove_line(1, 1),
+            Err(RestaurantError::OrderLineNotFound(1, 1))
+        );
+    }
+
+    #[test]
+    fn test_line_ids_are_not_reused() {
+        let store = InMemoryOrderStore::new();
+        store.add_item(1, 42).unwrap();
+        store.add_item(1, 43).unwrap();
+        store.remove_line(1, 2).unwrap();
+        store.add_item(1, 44).unwrap();
+        store.add_item(2, 42).unwrap();
+
+        let line_ids = |table_id| {
+            store
+                .get_lines(table_id)
+                .unwrap()
+                .iter()
+                .map(|line| line.line_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(line_ids(1), vec![1, 3]);
+        assert_eq!(line_ids(2), vec![1]);
+    }
+
     #[test]
     fn test_get_item_ids_success() {
         let store = InMemoryOrderStore::new();
@@ -0,0 +1,131 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::PaymentStore;
+use crate::server::data_model::payment::Payment;
+use crate::server::utils::error::RestaurantError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// In-memory implementation of the `PaymentStore` trait.
+///
+/// This store keeps the payments towards the current bill of each table. The store is
+/// thread-safe, using a `Mutex` to protect access to the underlying data.
+pub struct InMemoryPaymentStore {
+    payments: Arc<Mutex<HashMap<u32, Vec<Payment>>>>, // Stores table_id -> Vec<Payment>
+}
+
+impl InMemoryPaymentStore {
+    /// Creates a new instance of `InMemoryPaymentStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryPaymentStore` without any payments.
+    pub fn new() -> Self {
+        InMemoryPaymentStore {
+            payments: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryPaymentStore {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaymentStore for InMemoryPaymentStore {
+    /// Records a payment towards the bill of the payment's table.
+    ///
+    /// # Arguments
+    ///
+    /// * `payment` - The approved payment.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the payment was recorded.
+    /// * `Err(RestaurantError)` if there was an error accessing the payment store.
+    fn record_payment(&self, payment: Payment) -> Result<(), RestaurantError> {
+        let mut payments = self
+            .payments
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        payments.entry(payment.table_id).or_default().push(payment);
+        Ok(())
+    }
+
+    /// Retrieves the payments towards the current bill of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Payment>)` with the payments, empty if the table has none.
+    /// * `Err(RestaurantError)` if there was an error accessing the payment store.
+    fn get_payments(&self, table_id: u32) -> Result<Vec<Payment>, RestaurantError> {
+        let payments = self
+            .payments
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        Ok(payments.get(&table_id).cloned().unwrap_or_default())
+    }
+
+    /// Removes the payments of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the payments were removed.
+    /// * `Err(RestaurantError)` if there was an error accessing the payment store.
+    fn clear_payments(&self, table_id: u32) -> Result<(), RestaurantError> {
+        let mut payments = self
+            .payments
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        payments.remove(&table_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::Money;
+    use crate::server::data_model::payment::PaymentMethod;
+
+    fn payment(table_id: u32, payment_id: u32) -> Payment {
+        Payment {
+            payment_id,
+            table_id,
+            check_id: None,
+            method: PaymentMethod::Cash,
+            amount: Money::from_cents(1000),
+            tip: Money::ZERO,
+            reference: format!("cash-{}", payment_id),
+        }
+    }
+
+    #[test]
+    fn test_record_get_and_clear_payments() {
+        let store = InMemoryPaymentStore::new();
+        assert_eq!(store.get_payments(1), Ok(vec![]));
+
+        store.record_payment(payment(1, 1)).unwrap();
+        store.record_payment(payment(1, 2)).unwrap();
+        store.record_payment(payment(2, 1)).unwrap();
+        assert_eq!(
+            store.get_payments(1),
+            Ok(vec![payment(1, 1), payment(1, 2)])
+        );
+
+        store.clear_payments(1).unwrap();
+        assert_eq!(store.get_payments(1), Ok(vec![]));
+        assert_eq!(store.get_payments(2), Ok(vec![payment(2, 1)]));
+    }
+}
@@ -0,0 +1,262 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::ReservationStore;
+use crate::server::data_model::reservation::{Reservation, ReservationRequest};
+use crate::server::utils::error::RestaurantError;
+use std::sync::{Arc, Mutex};
+
+/// The reservations guarded by the store's lock.
+struct Reservations {
+    next_reservation_id: u64,
+    booked: Vec<Reservation>, // Ordered by the start of the slot, then by ID
+}
+
+impl Reservations {
+    /// Returns an error if the reservation overlaps another reservation of its table.
+    fn check_overlap(&self, reservation: &Reservation) -> Result<(), RestaurantError> {
+        match self.booked.iter().find(|booked| {
+            booked.reservation_id != reservation.reservation_id && booked.overlaps(reservation)
+        }) {
+            Some(booked) => Err(RestaurantError::ReservationConflict(
+                reservation.table_id,
+                booked.reservation_id,
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds a reservation, keeping the reservations ordered by the start of their slot.
+    fn insert(&mut self, reservation: Reservation) {
+        let index = self.booked.partition_point(|booked| {
+            (booked.starts_at, booked.reservation_id)
+                < (reservation.starts_at, reservation.reservation_id)
+        });
+        self.booked.insert(index, reservation);
+    }
+}
+
+/// In-memory implementation of the `ReservationStore` trait.
+///
+/// This store keeps all reservations that have not been cancelled. Reservation IDs are
+/// assigned consecutively, starting at 1. The store is thread-safe, using a `Mutex` to
+/// protect access to the underlying data, so that two overlapping reservations of the same
+/// table cannot both be made.
+pub struct InMemoryReservationStore {
+    reservations: Arc<Mutex<Reservations>>,
+}
+
+impl InMemoryReservationStore {
+    /// Creates a new instance of `InMemoryReservationStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryReservationStore` without any reservations.
+    pub fn new() -> Self {
+        InMemoryReservationStore {
+            reservations: Arc::new(Mutex::new(Reservations {
+                next_reservation_id: 1,
+                booked: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl Default for InMemoryReservationStore {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReservationStore for InMemoryReservationStore {
+    /// Retrieves all reservations.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Reservation>)` with the reservations, ordered by the start of their slot.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn get_reservations(&self) -> Result<Vec<Reservation>, RestaurantError> {
+        let reservations = self
+            .reservations
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        Ok(reservations.booked.clone())
+    }
+
+    /// Retrieves a reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_id` - The ID of the reservation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Reservation)` with the reservation.
+    /// * `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn get(&self, reservation_id: u64) -> Result<Reservation, RestaurantError> {
+        let reservations = self
+            .reservations
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        reservations
+            .booked
+            .iter()
+            .find(|booked| booked.reservation_id == reservation_id)
+            .cloned()
+            .ok_or(RestaurantError::ReservationNotFound(reservation_id))
+    }
+
+    /// Makes a reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The table, party and slot.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Reservation)` with the new reservation.
+    /// * `Err(RestaurantError::ReservationConflict)` if the slot overlaps another reservation.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn create(&self, request: &ReservationRequest) -> Result<Reservation, RestaurantError> {
+        let mut reservations = self
+            .reservations
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let reservation = request.to_reservation(reservations.next_reservation_id);
+        reservations.check_overlap(&reservation)?;
+        reservations.next_reservation_id += 1;
+        reservations.insert(reservation.clone());
+        Ok(reservation)
+    }
+
+    /// Replaces the details of a reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_id` - The ID of the reservation.
+    /// * `request` - The new table, party and slot.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Reservation)` with the updated reservation.
+    /// * `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    /// * `Err(RestaurantError::ReservationConflict)` if the new slot overlaps another reservation.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn update(
+        &self,
+        reservation_id: u64,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, RestaurantError> {
+        let mut reservations = self
+            .reservations
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let index = reservations
+            .booked
+            .iter()
+            .position(|booked| booked.reservation_id == reservation_id)
+            .ok_or(RestaurantError::ReservationNotFound(reservation_id))?;
+        let reservation = request.to_reservation(reservation_id);
+        reservations.check_overlap(&reservation)?;
+        reservations.booked.remove(index);
+        reservations.insert(reservation.clone());
+        Ok(reservation)
+    }
+
+    /// Cancels a reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_id` - The ID of the reservation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Reservation)` with the cancelled reservation.
+    /// * `Err(RestaurantError::ReservationNotFound)` if the reservation does not exist.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn cancel(&self, reservation_id: u64) -> Result<Reservation, RestaurantError> {
+        let mut reservations = self
+            .reservations
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let index = reservations
+            .booked
+            .iter()
+            .position(|booked| booked.reservation_id == reservation_id)
+            .ok_or(RestaurantError::ReservationNotFound(reservation_id))?;
+        Ok(reservations.booked.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(table_id: u32, starts_at: u64) -> ReservationRequest {
+        ReservationRequest {
+            table_id,
+            party_size: 2,
+            contact_name: " Ada ".to_string(),
+            starts_at,
+            duration_minutes: 60,
+        }
+    }
+
+    fn ids(store: &InMemoryReservationStore) -> Vec<u64> {
+        store
+            .get_reservations()
+            .unwrap()
+            .iter()
+            .map(|reservation| reservation.reservation_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_create_refuses_overlapping_slots() {
+        let store = InMemoryReservationStore::new();
+        let dinner = store.create(&request(5, 7_200)).unwrap();
+        assert_eq!(dinner.reservation_id, 1);
+        assert_eq!(dinner.contact_name, "Ada");
+
+        assert_eq!(
+            store.create(&request(5, 9_000)),
+            Err(RestaurantError::ReservationConflict(5, 1))
+        );
+        // Back to back on the same table, and the same slot on another table
+        store.create(&request(5, 10_800)).unwrap();
+        store.create(&request(6, 3_600)).unwrap();
+        assert_eq!(ids(&store), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_update_and_cancel() {
+        let store = InMemoryReservationStore::new();
+        store.create(&request(5, 7_200)).unwrap();
+        store.create(&request(5, 10_800)).unwrap();
+
+        // Moving a reservation within its own slot does not conflict with itself
+        let moved = store.update(1, &request(5, 6_600)).unwrap();
+        assert_eq!(moved.starts_at, 6_600);
+        assert_eq!(
+            store.update(1, &request(5, 10_000)),
+            Err(RestaurantError::ReservationConflict(5, 2))
+        );
+        store.update(2, &request(5, 1_800)).unwrap();
+        assert_eq!(ids(&store), vec![2, 1]);
+
+        assert_eq!(store.get(2).unwrap().starts_at, 1_800);
+        assert_eq!(store.cancel(2).unwrap().starts_at, 1_800);
+        assert_eq!(
+            store.cancel(2),
+            Err(RestaurantError::ReservationNotFound(2))
+        );
+        assert_eq!(
+            store.update(2, &request(5, 1_800)),
+            Err(RestaurantError::ReservationNotFound(2))
+        );
+        assert_eq!(store.get(2), Err(RestaurantError::ReservationNotFound(2)));
+        assert_eq!(ids(&store), vec![1]);
+    }
+}
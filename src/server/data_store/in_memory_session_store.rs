@@ -0,0 +1,330 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::dietary::Allergen;
+use crate::server::data_model::models::{Bill, SessionStore};
+use crate::server::data_model::payment::Payment;
+use crate::server::data_model::session::TableSession;
+use crate::server::utils::error::RestaurantError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The sessions guarded by the store's lock.
+struct Sessions {
+    next_session_id: u64,
+    active: HashMap<u32, TableSession>, // Stores table_id -> active TableSession
+    history: Vec<TableSession>,         // Closed sessions, in the order they were closed
+}
+
+/// In-memory implementation of the `SessionStore` trait.
+///
+/// This store keeps the active session of each table and every closed session. Session IDs
+/// are assigned consecutively, starting at 1. The store is thread-safe, using a `Mutex` to
+/// protect access to the underlying data.
+pub struct InMemorySessionStore {
+    sessions: Arc<Mutex<Sessions>>,
+}
+
+impl InMemorySessionStore {
+    /// Creates a new instance of `InMemorySessionStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemorySessionStore` without any sessions.
+    pub fn new() -> Self {
+        InMemorySessionStore {
+            sessions: Arc::new(Mutex::new(Sessions {
+                next_session_id: 1,
+                active: HashMap::new(),
+                history: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl Default for InMemorySessionStore {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    /// Opens a new session for a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    /// * `party_size` - The number of guests, if known.
+    /// * `opened_at` - When the table was opened, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TableSession)` with the new session.
+    /// * `Err(RestaurantError::SessionAlreadyOpen)` if the table has an active session.
+    fn open_session(
+        &self,
+        table_id: u32,
+        party_size: Option<u32>,
+        opened_at: u64,
+    ) -> Result<TableSession, RestaurantError> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        if sessions.active.contains_key(&table_id) {
+            return Err(RestaurantError::SessionAlreadyOpen(table_id));
+        }
+        let session = TableSession {
+            session_id: sessions.next_session_id,
+            table_id,
+            party_size,
+            opened_at,
+            closed_at: None,
+            bill: None,
+            payments: Vec::new(),
+            allergies: Vec::new(),
+        };
+        sessions.next_session_id += 1;
+        sessions.active.insert(table_id, session.clone());
+        Ok(session)
+    }
+
+    /// Retrieves the active session of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TableSession)` with the active session.
+    /// * `Err(RestaurantError::NoActiveSession)` if the table is not open.
+    fn get_active_session(&self, table_id: u32) -> Result<TableSession, RestaurantError> {
+        let sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        sessions
+            .active
+            .get(&table_id)
+            .cloned()
+            .ok_or(RestaurantError::NoActiveSession(table_id))
+    }
+
+    /// Sets the allergies declared by the party at a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    /// * `allergies` - The allergies of the party, replacing earlier ones.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TableSession)` with the updated active session.
+    /// * `Err(RestaurantError::NoActiveSession)` if the table is not open.
+    fn set_allergies(
+        &self,
+        table_id: u32,
+        allergies: Vec<Allergen>,
+    ) -> Result<TableSession, RestaurantError> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let session = sessions
+            .active
+            .get_mut(&table_id)
+            .ok_or(RestaurantError::NoActiveSession(table_id))?;
+        session.allergies = allergies;
+        Ok(session.clone())
+    }
+
+    /// Closes the active session of a table and moves it to the history.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    /// * `closed_at` - When the table was closed, in seconds since the Unix epoch.
+    /// * `bill` - The final bill of the party.
+    /// * `payments` - The payments and tips of the party.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TableSession)` with the closed session.
+    /// * `Err(RestaurantError::NoActiveSession)` if the table is not open.
+    fn close_session(
+        &self,
+        table_id: u32,
+        closed_at: u64,
+        bill: Bill,
+        payments: Vec<Payment>,
+    ) -> Result<TableSession, RestaurantError> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let mut session = sessions
+            .active
+            .remove(&table_id)
+            .ok_or(RestaurantError::NoActiveSession(table_id))?;
+        session.closed_at = Some(closed_at);
+        session.bill = Some(bill);
+        session.payments = payments;
+        sessions.history.push(session.clone());
+        Ok(session)
+    }
+
+    /// Adds a tip left after the bill was settled to a closed session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The ID of the closed session.
+    /// * `payment` - The tip, recorded as a payment without an amount.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TableSession)` with the updated session.
+    /// * `Err(RestaurantError::InvalidPayment)` if there is no closed session with the ID.
+    fn record_tip(
+        &self,
+        session_id: u64,
+        payment: Payment,
+    ) -> Result<TableSession, RestaurantError> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let session = sessions
+            .history
+            .iter_mut()
+            .find(|session| session.session_id == session_id)
+            .ok_or_else(|| {
+                RestaurantError::InvalidPayment(format!("Session {} is not closed", session_id))
+            })?;
+        session.payments.push(payment);
+        Ok(session.clone())
+    }
+
+    /// Retrieves closed sessions, in the order they were closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Only sessions of this table if set, of all tables otherwise.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<TableSession>)` with the closed sessions.
+    /// * `Err(RestaurantError)` if there was an error accessing the session store.
+    fn get_history(&self, table_id: Option<u32>) -> Result<Vec<TableSession>, RestaurantError> {
+        let sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        Ok(sessions
+            .history
+            .iter()
+            .filter(|session| table_id.is_none_or(|id| session.table_id == id))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::{Money, TaxRate};
+    use crate::server::data_model::payment::PaymentMethod;
+
+    fn cash(payment_id: u32, amount: u64, tip: u64) -> Payment {
+        Payment {
+            payment_id,
+            table_id: 7,
+            check_id: None,
+            method: PaymentMethod::Cash,
+            amount: Money::from_cents(amount),
+            tip: Money::from_cents(tip),
+            reference: format!("cash-{}", payment_id),
+        }
+    }
+
+    #[test]
+    fn test_open_close_and_history() {
+        let store = InMemorySessionStore::new();
+        assert_eq!(
+            store.get_active_session(7),
+            Err(RestaurantError::NoActiveSession(7))
+        );
+
+        let first = store.open_session(7, Some(4), 100).unwrap();
+        assert_eq!(first.session_id, 1);
+        assert_eq!(
+            store.open_session(7, None, 110),
+            Err(RestaurantError::SessionAlreadyOpen(7))
+        );
+        store.open_session(8, None, 120).unwrap();
+        assert_eq!(store.get_active_session(7), Ok(first));
+
+        let bill = Bill::new(7, &[], TaxRate::default());
+        let closed = store
+            .close_session(7, 200, bill.clone(), Vec::new())
+            .unwrap();
+        assert_eq!(closed.closed_at, Some(200));
+        assert_eq!(closed.bill, Some(bill.clone()));
+        assert_eq!(
+            store.close_session(7, 210, bill, Vec::new()),
+            Err(RestaurantError::NoActiveSession(7))
+        );
+
+        let second = store.open_session(7, Some(2), 300).unwrap();
+        assert_eq!(second.session_id, 3);
+        assert_eq!(store.get_history(Some(7)), Ok(vec![closed.clone()]));
+        assert_eq!(store.get_history(None), Ok(vec![closed]));
+        assert_eq!(store.get_history(Some(8)), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_set_allergies() {
+        let store = InMemorySessionStore::new();
+        assert_eq!(
+            store.set_allergies(7, vec![Allergen::Nuts]),
+            Err(RestaurantError::NoActiveSession(7))
+        );
+        store.open_session(7, Some(2), 100).unwrap();
+        let session = store.set_allergies(7, vec![Allergen::Nuts]).unwrap();
+        assert_eq!(session.allergies, vec![Allergen::Nuts]);
+        assert_eq!(store.get_active_session(7), Ok(session));
+
+        // The next party starts without allergies
+        let bill = Bill::new(7, &[], TaxRate::default());
+        store.close_session(7, 200, bill, Vec::new()).unwrap();
+        assert!(store
+            .open_session(7, None, 300)
+            .unwrap()
+            .allergies
+            .is_empty());
+    }
+
+    #[test]
+    fn test_closed_sessions_keep_payments_and_tips() {
+        let store = InMemorySessionStore::new();
+        let session = store.open_session(7, Some(2), 100).unwrap();
+        assert_eq!(
+            store.record_tip(session.session_id, cash(1, 0, 300)),
+            Err(RestaurantError::InvalidPayment(
+                "Session 1 is not closed".to_string()
+            ))
+        );
+
+        let bill = Bill::new(7, &[], TaxRate::default());
+        let closed = store
+            .close_session(7, 200, bill, vec![cash(1, 1000, 0)])
+            .unwrap();
+        assert_eq!(closed.payments, vec![cash(1, 1000, 0)]);
+        let tipped = store
+            .record_tip(closed.session_id, cash(2, 0, 300))
+            .unwrap();
+        assert_eq!(tipped.payments, vec![cash(1, 1000, 0), cash(2, 0, 300)]);
+        assert_eq!(store.get_history(Some(7)), Ok(vec![tipped]));
+    }
+}
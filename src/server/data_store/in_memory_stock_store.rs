@@ -0,0 +1,216 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::StockStore;
+use crate::server::data_model::stock::StockLevel;
+use crate::server::utils::error::RestaurantError;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// In-memory implementation of the `StockStore` trait.
+///
+/// This store keeps a stock level for each menu item whose stock is counted or that is
+/// marked sold out. Setting a stock level that is neither counted nor sold out removes it.
+/// The store is thread-safe, using a `Mutex` to protect access to the underlying data.
+pub struct InMemoryStockStore {
+    levels: Arc<Mutex<BTreeMap<u32, StockLevel>>>, // Stores item_id -> StockLevel
+}
+
+impl InMemoryStockStore {
+    /// Creates a new instance of `InMemoryStockStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryStockStore` in which every menu item is available.
+    pub fn new() -> Self {
+        InMemoryStockStore {
+            levels: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryStockStore {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StockStore for InMemoryStockStore {
+    /// Retrieves the stock levels of all counted or sold out menu items.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<StockLevel>)` with the stock levels, ordered by item ID.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn get_stock_levels(&self) -> Result<Vec<StockLevel>, RestaurantError> {
+        let levels = self
+            .levels
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        Ok(levels.values().cloned().collect())
+    }
+
+    /// Sets the stock of a menu item.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_id` - The ID of the menu item.
+    /// * `remaining` - Portions in stock, or `None` to stop counting.
+    /// * `sold_out` - Whether the menu item is marked as sold out.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StockLevel)` with the new stock level.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn set_stock(
+        &self,
+        item_id: u32,
+        remaining: Option<u32>,
+        sold_out: bool,
+    ) -> Result<StockLevel, RestaurantError> {
+        let mut levels = self
+            .levels
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        let level = StockLevel {
+            item_id,
+            remaining,
+            sold_out,
+        };
+        if level.is_tracked() {
+            levels.insert(item_id, level.clone());
+        } else {
+            levels.remove(&item_id);
+        }
+        Ok(level)
+    }
+
+    /// Takes one portion of a menu item from stock.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_id` - The ID of the menu item.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the item is available.
+    /// * `Err(RestaurantError::ItemUnavailable)` if the item is sold out or out of stock.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn take(&self, item_id: u32) -> Result<(), RestaurantError> {
+        let mut levels = self
+            .levels
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        if let Some(level) = levels.get_mut(&item_id) {
+            if !level.is_available() {
+                return Err(RestaurantError::ItemUnavailable(item_id));
+            }
+            if let Some(remaining) = level.remaining.as_mut() {
+                *remaining -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Puts one portion of a menu item back into stock, if its stock is counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_id` - The ID of the menu item.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the portion was put back or the stock is not counted.
+    /// * `Err(RestaurantError::LockError)` if the lock cannot be acquired.
+    fn restore(&self, item_id: u32) -> Result<(), RestaurantError> {
+        let mut levels = self
+            .levels
+            .lock()
+            .map_err(|e| RestaurantError::LockError(e.to_string()))?;
+        if let Some(remaining) = levels
+            .get_mut(&item_id)
+            .and_then(|level| level.remaining.as_mut())
+        {
+            *remaining = remaining.saturating_add(1);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_take_and_restore() {
+        let store = InMemoryStockStore::new();
+        assert_eq!(store.take(1), Ok(()));
+        assert_eq!(store.restore(1), Ok(()));
+        assert_eq!(store.get_stock_levels(), Ok(vec![]));
+
+        store.set_stock(20, Some(2), false).unwrap();
+        assert_eq!(store.take(20), Ok(()));
+        assert_eq!(store.take(20), Ok(()));
+        assert_eq!(store.take(20), Err(RestaurantError::ItemUnavailable(20)));
+        store.restore(20).unwrap();
+        assert_eq!(
+            store.get_stock_levels(),
+            Ok(vec![StockLevel {
+                item_id: 20,
+                remaining: Some(1),
+                sold_out: false,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_restore_stops_at_the_largest_count() {
+        let store = InMemoryStockStore::new();
+        store.set_stock(20, Some(u32::MAX), false).unwrap();
+        assert_eq!(store.restore(20), Ok(()));
+        assert_eq!(
+            store.get_stock_levels().unwrap()[0].remaining,
+            Some(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_sold_out_and_untracked() {
+        let store = InMemoryStockStore::new();
+        store.set_stock(5, None, true).unwrap();
+        store.set_stock(3, Some(4), false).unwrap();
+        assert_eq!(store.take(5), Err(RestaurantError::ItemUnavailable(5)));
+        let ids: Vec<u32> = store
+            .get_stock_levels()
+            .unwrap()
+            .iter()
+            .map(|level| level.item_id)
+            .collect();
+        assert_eq!(ids, vec![3, 5]);
+
+        store.set_stock(5, None, false).unwrap();
+        assert_eq!(store.take(5), Ok(()));
+        assert_eq!(store.get_stock_levels().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_takes_never_oversell() {
+        let store = Arc::new(InMemoryStockStore::new());
+        store.set_stock(20, Some(5), false).unwrap();
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                thread::spawn(move || store.take(20).is_ok())
+            })
+            .collect();
+        let taken = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|ok| *ok)
+            .count();
+        assert_eq!(taken, 5);
+        assert_eq!(store.get_stock_levels().unwrap()[0].remaining, Some(0));
+    }
+}
@@ -7,10 +7,11 @@ use std::sync::{Arc, Mutex};
 
 /// In-memory implementation of the `TableStore` trait.
 ///
-/// This store maintains a list of tables that can be accessed concurrently.
-/// The store is thread-safe, using a `Mutex` to protect access to the underlying data.
+/// This store maintains a list of tables with the number of guests they seat, which can be
+/// accessed concurrently. The store is thread-safe, using a `Mutex` to protect access to
+/// the underlying data.
 pub struct InMemoryTableStore {
-    tables: Arc<Mutex<Vec<u32>>>, // Stores a list of table IDs
+    tables: Arc<Mutex<Vec<(u32, u32)>>>, // Stores a list of (table ID, capacity)
 }
 
 impl InMemoryTableStore {
@@ -18,11 +19,35 @@ impl InMemoryTableStore {
     ///
     /// # Returns
     ///
-    /// A new instance of `InMemoryTableStore` with 100 predefined table IDs.
+    /// A new instance of `InMemoryTableStore` with the floor plan of 100 predefined tables:
+    /// tables 1 to 40 seat 2 guests, 41 to 80 seat 4, 81 to 95 seat 6 and 96 to 100 seat 10.
     pub fn new() -> Self {
-        let predefined_tables = (1..=100).collect();
+        let predefined_tables = (1..=100)
+            .map(|table_id| {
+                let capacity = match table_id {
+                    1..=40 => 2,
+                    41..=80 => 4,
+                    81..=95 => 6,
+                    _ => 10,
+                };
+                (table_id, capacity)
+            })
+            .collect();
+        Self::with_tables(predefined_tables)
+    }
+
+    /// Creates a new instance of `InMemoryTableStore` with the given floor plan.
+    ///
+    /// # Arguments
+    ///
+    /// * `tables` - The table IDs with the number of guests each table seats.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InMemoryTableStore` with the given tables, in their order.
+    pub fn with_tables(tables: Vec<(u32, u32)>) -> Self {
         InMemoryTableStore {
-            tables: Arc::new(Mutex::new(predefined_tables)),
+            tables: Arc::new(Mutex::new(tables)),
         }
     }
 }
@@ -45,7 +70,30 @@ impl TableStore for InMemoryTableStore {
             .tables
             .lock()
             .map_err(|_| RestaurantError::TablesRetrieveError)?;
-        Ok(tables.clone())
+        Ok(tables.iter().map(|&(table_id, _)| table_id).collect())
+    }
+
+    /// Retrieves the number of guests a table seats.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - The ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u32)` with the capacity of the table.
+    /// * `Err(RestaurantError::TableNotFound)` if the table does not exist.
+    /// * `Err(RestaurantError::TablesRetrieveError)` if the lock cannot be acquired.
+    fn get_table_capacity(&self, table_id: u32) -> Result<u32, RestaurantError> {
+        let tables = self
+            .tables
+            .lock()
+            .map_err(|_| RestaurantError::TablesRetrieveError)?;
+        tables
+            .iter()
+            .find(|&&(id, _)| id == table_id)
+            .map(|&(_, capacity)| capacity)
+            .ok_or(RestaurantError::TableNotFound(table_id))
     }
 }
 
@@ -63,11 +111,26 @@ mod tests {
         assert_eq!(tables[99], 100);
     }
 
+    #[test]
+    fn test_get_table_capacity() {
+        let store = InMemoryTableStore::new();
+        assert_eq!(store.get_table_capacity(1), Ok(2));
+        assert_eq!(store.get_table_capacity(41), Ok(4));
+        assert_eq!(store.get_table_capacity(95), Ok(6));
+        assert_eq!(store.get_table_capacity(100), Ok(10));
+        assert_eq!(
+            store.get_table_capacity(101),
+            Err(RestaurantError::TableNotFound(101))
+        );
+
+        let store = InMemoryTableStore::with_tables(vec![(7, 3), (2, 8)]);
+        assert_eq!(store.get_all_tables(), Ok(vec![7, 2]));
+        assert_eq!(store.get_table_capacity(2), Ok(8));
+    }
+
     #[test]
     fn test_get_all_tables_error() {
-        let store = InMemoryTableStore {
-            tables: Arc::new(Mutex::new(vec![])),
-        };
+        let store = InMemoryTableStore::with_tables(vec![]);
 
         // Simulate a panic that causes the mutex to be poisoned.
         let result = std::panic::catch_unwind(|| {
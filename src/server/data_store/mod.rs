@@ -1,6 +1,14 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+pub mod conformance;
+pub mod fault_injection;
+pub mod in_memory_check_store;
+pub mod in_memory_inventory_store;
 pub mod in_memory_menu_store;
 pub mod in_memory_order_store;
+pub mod in_memory_payment_store;
+pub mod in_memory_reservation_store;
+pub mod in_memory_session_store;
+pub mod in_memory_stock_store;
 pub mod in_memory_table_store;
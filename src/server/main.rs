@@ -3,48 +3,189 @@
 
 use crate::server::api::v1;
 use crate::server::api::v1::handlers;
+use crate::server::config::ServerConfig;
 use crate::server::data_model::models::Restaurant;
+use crate::server::data_store::fault_injection;
+use crate::server::data_store::in_memory_inventory_store::InMemoryInventoryStore;
 use crate::server::data_store::in_memory_menu_store::InMemoryMenuStore;
 use crate::server::data_store::in_memory_order_store::InMemoryOrderStore;
 use crate::server::data_store::in_memory_table_store::InMemoryTableStore;
 use crate::server::main::v1::openapi;
 use crate::server::restaurant::SimpleRestaurant;
-use actix_web::{web, App, HttpServer};
+use crate::server::tls;
+use actix_web::dev::ServerHandle;
+use actix_web::{web, App, HttpRequest, HttpServer};
 use std::sync::Arc;
 
+/// Handle used to gracefully stop a running server.
+///
+/// The handle covers every listener started for a configuration, i.e. the API
+/// listener and, if enabled, the HTTP to HTTPS redirect listener.
+#[derive(Clone)]
+pub struct ServerShutdownHandle {
+    handles: Vec<ServerHandle>,
+}
+
+impl ServerShutdownHandle {
+    /// Gracefully stops all listeners.
+    ///
+    /// New connections are refused immediately, while in-flight requests are given
+    /// up to `ServerConfig::shutdown_timeout_secs` to complete. The returned future
+    /// resolves once every listener has stopped. Calling this on an already stopped
+    /// server is a no-op.
+    pub async fn stop(&self) {
+        for handle in &self.handles {
+            handle.stop(true).await;
+        }
+    }
+}
+
 /// Main entry point for starting the HTTP server.
 ///
 /// This function sets up the server, configures routes, and serves the OpenAPI documentation via Swagger UI.
+/// When `config.tls` is set, the API is served over HTTPS using rustls, and an optional plain HTTP
+/// listener is started that redirects every request to the HTTPS port.
+///
+/// The server shuts down gracefully on `SIGINT` or `SIGTERM`.
 ///
 /// # Arguments
-/// * `port` - Optional port number to bind the server to. If not provided, defaults to port 8081.
+/// * `config` - The server configuration, including the bind address and optional TLS settings.
 ///
 /// # Returns
 /// This function returns a `Result` that either contains `Ok` with an empty value indicating success or an `Err` in case of an I/O error.
-pub async fn main(port: Option<u16>) -> std::io::Result<()> {
-    // Default to port 8081 if no port is provided
-    let port = port.unwrap_or(8081);
+pub async fn main(config: ServerConfig) -> std::io::Result<()> {
+    run(config, |_| {}).await
+}
 
-    // Create the restaurant instance using the SimpleRestaurant implementation
-    let restaurant = Arc::new(SimpleRestaurant::new(
-        Box::new(InMemoryMenuStore::default()),  // Using Default trait
-        Box::new(InMemoryOrderStore::default()), // Using Default trait
-        Box::new(InMemoryTableStore::default()), // Using Default trait
-    ));
+/// Starts the HTTP server and hands a shutdown handle to the caller once it is listening.
+///
+/// This behaves like `main`, but calls `on_start` with a `ServerShutdownHandle` after all
+/// listeners are bound, so that an embedding application can stop the server itself.
+/// After the server has stopped and in-flight requests have drained, the restaurant's
+/// stores are flushed via `Restaurant::shutdown` before this function returns.
+///
+/// # Arguments
+/// * `config` - The server configuration, including the bind address and optional TLS settings.
+/// * `on_start` - Callback receiving the handle used to stop the server.
+///
+/// # Returns
+/// This function returns a `Result` that either contains `Ok` with an empty value indicating success or an `Err` in case of an I/O error,
+/// a fault configuration with a failure rate outside 0 to 1, or a failure while flushing the stores.
+pub async fn run<F>(config: ServerConfig, on_start: F) -> std::io::Result<()>
+where
+    F: FnOnce(ServerShutdownHandle),
+{
+    let port = config.port;
+
+    // Create the restaurant instance using the SimpleRestaurant implementation,
+    // wrapping the stores that have faults configured
+    let faults = &config.faults;
+    if faults.is_enabled() {
+        eprintln!("Warning: injecting faults into the stores: {:?}", faults);
+    }
+    let invalid_faults = |e: String| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid fault configuration: {}", e),
+        )
+    };
+    let restaurant = Arc::new(
+        SimpleRestaurant::new(
+            fault_injection::menu_store(InMemoryMenuStore::default(), faults.menus.as_ref())
+                .map_err(|e| invalid_faults(format!("faults.menus: {}", e)))?,
+            fault_injection::order_store(InMemoryOrderStore::default(), faults.orders.as_ref())
+                .map_err(|e| invalid_faults(format!("faults.orders: {}", e)))?,
+            fault_injection::table_store(InMemoryTableStore::default(), faults.tables.as_ref())
+                .map_err(|e| invalid_faults(format!("faults.tables: {}", e)))?,
+        )
+        .with_inventory_store(Box::new(InMemoryInventoryStore::default()))
+        .with_tax_rate(config.tax_rate),
+    ) as Arc<dyn Restaurant + Send + Sync>; // Coerce the type to the trait object
 
     // Set up the shared application state
     let app_state = handlers::AppState {
-        restaurant: restaurant as Arc<dyn Restaurant + Send + Sync>, // Coerce the type to the trait object
+        restaurant: Arc::clone(&restaurant),
     };
 
-    // Start the HTTP server
-    HttpServer::new(move || {
+    // Configure the HTTP server. Signals are handled below so that every listener stops gracefully.
+    let server = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(app_state.clone())) // Share the state with the handlers
             .configure(v1::routes::configure_routes) // Register routes
             .service(openapi::configure_openapi_ui()) // Serve OpenAPI docs via Swagger UI
     })
-    .bind(format!("127.0.0.1:{}", port))?
-    .run()
-    .await
+    .shutdown_timeout(config.shutdown_timeout_secs)
+    .disable_signals();
+
+    let mut servers = Vec::new();
+    match &config.tls {
+        None => servers.push(server.bind(config.bind_address())?.run()),
+        Some(tls_config) => {
+            let rustls_config = tls::load_rustls_config(tls_config)?;
+            servers.push(
+                server
+                    .bind_rustls_0_23(config.bind_address(), rustls_config)?
+                    .run(),
+            );
+
+            if let Some(http_port) = tls_config.redirect_http_port {
+                // Redirect all plain HTTP traffic to the HTTPS port
+                let redirect_server = HttpServer::new(move || {
+                    App::new().default_service(web::to(move |req: HttpRequest| async move {
+                        tls::redirect_to_https(&req, port)
+                    }))
+                })
+                .shutdown_timeout(config.shutdown_timeout_secs)
+                .disable_signals()
+                .bind(format!("{}:{}", config.host, http_port))?
+                .run();
+                servers.push(redirect_server);
+            }
+        }
+    }
+
+    let shutdown_handle = ServerShutdownHandle {
+        handles: servers.iter().map(|server| server.handle()).collect(),
+    };
+
+    // Stop gracefully when the process receives SIGINT or SIGTERM
+    let signal_handle = shutdown_handle.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        signal_handle.stop().await;
+    });
+
+    on_start(shutdown_handle);
+    futures::future::try_join_all(servers).await?;
+
+    // All in-flight requests have drained, flush the stores
+    restaurant.shutdown().map_err(std::io::Error::other)
+}
+
+/// Resolves when the process receives `SIGINT` (Ctrl+C) or, on Unix, `SIGTERM`.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        // If the handler cannot be installed, only SIGTERM can trigger a shutdown
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
@@ -2,8 +2,11 @@
 #![deny(clippy::all)]
 
 pub mod api;
+pub mod config;
 pub mod data_model;
 pub mod data_store;
 pub mod main;
+pub mod payment;
 pub mod restaurant;
+pub mod tls;
 pub mod utils;
@@ -0,0 +1,78 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::PaymentProvider;
+use crate::server::data_model::payment::{PaymentMethod, PaymentRequest};
+use crate::server::utils::error::RestaurantError;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Payment provider for cash payments.
+///
+/// Cash is taken at the table, so every payment is approved. The references are
+/// numbered consecutively, e.g. `cash-1`, `cash-2`.
+pub struct CashProvider {
+    next_reference: AtomicU64,
+}
+
+impl CashProvider {
+    /// Creates a new instance of `CashProvider`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `CashProvider` whose first reference is `cash-1`.
+    pub fn new() -> Self {
+        CashProvider {
+            next_reference: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Default for CashProvider {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaymentProvider for CashProvider {
+    /// Returns `PaymentMethod::Cash`.
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Cash
+    }
+
+    /// Approves the cash payment.
+    ///
+    /// # Arguments
+    ///
+    /// * `_table_id` - The ID of the paying table.
+    /// * `_request` - The payment.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the reference of the payment.
+    fn charge(&self, _table_id: u32, _request: &PaymentRequest) -> Result<String, RestaurantError> {
+        let reference = self.next_reference.fetch_add(1, Ordering::Relaxed);
+        Ok(format!("cash-{}", reference))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::Money;
+
+    #[test]
+    fn test_cash_is_always_approved() {
+        let provider = CashProvider::new();
+        let request = PaymentRequest {
+            method: PaymentMethod::Cash,
+            amount: Money::from_cents(1250),
+            tip: Money::ZERO,
+            check_id: None,
+            card_number: None,
+        };
+        assert_eq!(provider.method(), PaymentMethod::Cash);
+        assert_eq!(provider.charge(1, &request), Ok("cash-1".to_string()));
+        assert_eq!(provider.charge(2, &request), Ok("cash-2".to_string()));
+    }
+}
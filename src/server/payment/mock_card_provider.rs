@@ -0,0 +1,177 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::data_model::models::PaymentProvider;
+use crate::server::data_model::payment::{PaymentMethod, PaymentRequest};
+use crate::server::utils::error::RestaurantError;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Test card number that is always declined.
+pub const DECLINED_CARD: &str = "4000000000000002";
+
+/// Test card number that is declined for insufficient funds.
+pub const INSUFFICIENT_FUNDS_CARD: &str = "4000000000009995";
+
+/// Payment provider that simulates a card terminal without contacting a payment network.
+///
+/// Card numbers may contain spaces. A charge is
+/// - declined as `invalid card number` if the number is not 12 to 19 digits with a valid
+///   Luhn check digit,
+/// - declined as `card declined` for `DECLINED_CARD`,
+/// - declined as `insufficient funds` for `INSUFFICIENT_FUNDS_CARD`,
+/// - approved otherwise, e.g. for `4242 4242 4242 4242`.
+///
+/// References name the last four digits of the card, e.g. `card-4242-1`.
+pub struct MockCardProvider {
+    next_reference: AtomicU64,
+}
+
+impl MockCardProvider {
+    /// Creates a new instance of `MockCardProvider`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `MockCardProvider` whose first reference ends in `-1`.
+    pub fn new() -> Self {
+        MockCardProvider {
+            next_reference: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Default for MockCardProvider {
+    /// Provides a default implementation using the `new` method.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaymentProvider for MockCardProvider {
+    /// Returns `PaymentMethod::Card`.
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Card
+    }
+
+    /// Approves or declines the card payment, depending on the card number.
+    ///
+    /// # Arguments
+    ///
+    /// * `_table_id` - The ID of the paying table.
+    /// * `request` - The payment, with the card number.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the reference of the approved payment.
+    /// * `Err(RestaurantError::InvalidPayment)` if the request has no card number.
+    /// * `Err(RestaurantError::PaymentDeclined)` if the card is declined.
+    fn charge(&self, _table_id: u32, request: &PaymentRequest) -> Result<String, RestaurantError> {
+        let card_number: String = request
+            .card_number
+            .as_deref()
+            .ok_or_else(|| {
+                RestaurantError::InvalidPayment("Card payments require a card number".to_string())
+            })?
+            .chars()
+            .filter(|c| *c != ' ')
+            .collect();
+
+        if !is_valid_card_number(&card_number) {
+            return Err(RestaurantError::PaymentDeclined(
+                "invalid card number".to_string(),
+            ));
+        }
+        match card_number.as_str() {
+            DECLINED_CARD => Err(RestaurantError::PaymentDeclined(
+                "card declined".to_string(),
+            )),
+            INSUFFICIENT_FUNDS_CARD => Err(RestaurantError::PaymentDeclined(
+                "insufficient funds".to_string(),
+            )),
+            _ => {
+                let reference = self.next_reference.fetch_add(1, Ordering::Relaxed);
+                Ok(format!(
+                    "card-{}-{}",
+                    &card_number[card_number.len() - 4..],
+                    reference
+                ))
+            }
+        }
+    }
+}
+
+/// Checks the length and the Luhn check digit of a card number.
+fn is_valid_card_number(card_number: &str) -> bool {
+    if !(12..=19).contains(&card_number.len()) || !card_number.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    let sum: u32 = card_number
+        .bytes()
+        .rev()
+        .map(|b| u32::from(b - b'0'))
+        .enumerate()
+        .map(|(index, digit)| match (index % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::money::Money;
+
+    fn card_payment(card_number: Option<&str>) -> PaymentRequest {
+        PaymentRequest {
+            method: PaymentMethod::Card,
+            amount: Money::from_cents(1250),
+            tip: Money::from_cents(200),
+            check_id: None,
+            card_number: card_number.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_card_number() {
+        assert!(is_valid_card_number("4242424242424242"));
+        assert!(is_valid_card_number(DECLINED_CARD));
+        assert!(is_valid_card_number(INSUFFICIENT_FUNDS_CARD));
+        assert!(!is_valid_card_number("4242424242424241"));
+        assert!(!is_valid_card_number("4242"));
+        assert!(!is_valid_card_number("4242-4242-4242-4242"));
+    }
+
+    #[test]
+    fn test_charge() {
+        let provider = MockCardProvider::new();
+        assert_eq!(provider.method(), PaymentMethod::Card);
+        assert_eq!(
+            provider.charge(1, &card_payment(Some("4242 4242 4242 4242"))),
+            Ok("card-4242-1".to_string())
+        );
+        assert_eq!(
+            provider.charge(1, &card_payment(Some(DECLINED_CARD))),
+            Err(RestaurantError::PaymentDeclined(
+                "card declined".to_string()
+            ))
+        );
+        assert_eq!(
+            provider.charge(1, &card_payment(Some(INSUFFICIENT_FUNDS_CARD))),
+            Err(RestaurantError::PaymentDeclined(
+                "insufficient funds".to_string()
+            ))
+        );
+        assert_eq!(
+            provider.charge(1, &card_payment(Some("1234"))),
+            Err(RestaurantError::PaymentDeclined(
+                "invalid card number".to_string()
+            ))
+        );
+        assert!(matches!(
+            provider.charge(1, &card_payment(None)),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+pub mod cash_provider;
+pub mod mock_card_provider;
@@ -1,17 +1,64 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
-use crate::server::data_model::models::{MenuItem, MenuStore, OrderStore, Restaurant, TableStore};
+use crate::server::data_model::dietary::{conflicting_allergens, Allergen, MenuFilter};
+use crate::server::data_model::inventory::{with_inventory, Ingredient, LowStockReport};
+use crate::server::data_model::menu::{is_served, CategorizedMenu, Category};
+use crate::server::data_model::models::{
+    Bill, CheckStore, InventoryStore, MenuItem, MenuStore, OrderStore, PaymentProvider,
+    PaymentStore, ReservationStore, Restaurant, SessionStore, StockStore, TableStore,
+};
+use crate::server::data_model::modifier::{ItemOptions, OrderLine, OrderedItem};
+use crate::server::data_model::money::{Money, TaxRate};
+use crate::server::data_model::payment::{
+    check_totals, Payment, PaymentReceipt, PaymentRequest, TableAccount,
+};
+use crate::server::data_model::reservation::{
+    Reservation, ReservationRequest, TableStatus, MAX_DURATION_MINUTES,
+};
+use crate::server::data_model::session::{unix_now, TableSession, MAX_PARTY_SIZE};
+use crate::server::data_model::split::{SplitBill, SplitRequest};
+use crate::server::data_model::stock::{with_stock, MenuListing, StockLevel};
+use crate::server::data_store::in_memory_check_store::InMemoryCheckStore;
+use crate::server::data_store::in_memory_inventory_store::InMemoryInventoryStore;
+use crate::server::data_store::in_memory_payment_store::InMemoryPaymentStore;
+use crate::server::data_store::in_memory_reservation_store::InMemoryReservationStore;
+use crate::server::data_store::in_memory_session_store::InMemorySessionStore;
+use crate::server::data_store::in_memory_stock_store::InMemoryStockStore;
+use crate::server::payment::cash_provider::CashProvider;
+use crate::server::payment::mock_card_provider::MockCardProvider;
 use crate::server::utils::error::RestaurantError;
+use std::sync::{Mutex, PoisonError};
 
 /// `SimpleRestaurant` is an implementation of the `Restaurant` trait.
 /// It interacts with `MenuStore`, `OrderStore`, and `TableStore` to manage
 /// restaurant operations such as adding/removing menu items, retrieving
-/// available tables, and fetching order information.
+/// available tables, fetching order information, billing tables and taking payments.
 pub struct SimpleRestaurant {
     pub menu_store: Box<dyn MenuStore>,
     pub order_store: Box<dyn OrderStore>,
     pub table_store: Box<dyn TableStore>,
+    /// Store for the checks of split bills.
+    pub check_store: Box<dyn CheckStore>,
+    /// Store for the payments towards the bills of tables.
+    pub payment_store: Box<dyn PaymentStore>,
+    /// Store for the active and closed sessions of tables.
+    pub session_store: Box<dyn SessionStore>,
+    /// Store for the stock of menu items.
+    pub stock_store: Box<dyn StockStore>,
+    /// Store for the ingredients deducted by the recipes of menu items.
+    pub inventory_store: Box<dyn InventoryStore>,
+    /// Store for the reservations of tables.
+    pub reservation_store: Box<dyn ReservationStore>,
+    /// One provider per accepted payment method.
+    pub payment_providers: Vec<Box<dyn PaymentProvider>>,
+    /// Tax rate applied to the subtotal of bills.
+    pub tax_rate: TaxRate,
+    /// Serializes payments, closing tables and changes to orders, so that two payments cannot
+    /// both pay the same balance and no item is added to or removed from a table being freed.
+    /// The lock guards no data, so it is taken over after a panic instead of failing every
+    /// later call.
+    payment_lock: Mutex<()>,
 }
 
 impl SimpleRestaurant {
@@ -25,7 +72,9 @@ impl SimpleRestaurant {
     ///
     /// # Returns
     ///
-    /// A new instance of `SimpleRestaurant`.
+    /// A new instance of `SimpleRestaurant` that bills without tax, keeps split bills,
+    /// payments, sessions, stock and reservations in memory, counts no ingredients, and
+    /// accepts cash and mock card payments.
     pub fn new(
         menu_store: Box<dyn MenuStore>,
         order_store: Box<dyn OrderStore>,
@@ -35,8 +84,319 @@ impl SimpleRestaurant {
             menu_store,
             order_store,
             table_store,
+            check_store: Box::new(InMemoryCheckStore::new()),
+            payment_store: Box::new(InMemoryPaymentStore::new()),
+            session_store: Box::new(InMemorySessionStore::new()),
+            stock_store: Box::new(InMemoryStockStore::new()),
+            inventory_store: Box::new(InMemoryInventoryStore::new(Vec::new())),
+            reservation_store: Box::new(InMemoryReservationStore::new()),
+            payment_providers: vec![
+                Box::new(CashProvider::new()),
+                Box::new(MockCardProvider::new()),
+            ],
+            tax_rate: TaxRate::default(),
+            payment_lock: Mutex::new(()),
         }
     }
+
+    /// Sets the store used for the checks of split bills.
+    ///
+    /// # Arguments
+    ///
+    /// * `check_store` - A boxed implementation of `CheckStore`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` using the given check store.
+    pub fn with_check_store(mut self, check_store: Box<dyn CheckStore>) -> Self {
+        self.check_store = check_store;
+        self
+    }
+
+    /// Sets the store used for payments.
+    ///
+    /// # Arguments
+    ///
+    /// * `payment_store` - A boxed implementation of `PaymentStore`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` using the given payment store.
+    pub fn with_payment_store(mut self, payment_store: Box<dyn PaymentStore>) -> Self {
+        self.payment_store = payment_store;
+        self
+    }
+
+    /// Sets the store used for table sessions.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_store` - A boxed implementation of `SessionStore`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` using the given session store.
+    pub fn with_session_store(mut self, session_store: Box<dyn SessionStore>) -> Self {
+        self.session_store = session_store;
+        self
+    }
+
+    /// Sets the store used for the stock of menu items.
+    ///
+    /// # Arguments
+    ///
+    /// * `stock_store` - A boxed implementation of `StockStore`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` using the given stock store.
+    pub fn with_stock_store(mut self, stock_store: Box<dyn StockStore>) -> Self {
+        self.stock_store = stock_store;
+        self
+    }
+
+    /// Sets the store used for the ingredients of menu items.
+    ///
+    /// # Arguments
+    ///
+    /// * `inventory_store` - A boxed implementation of `InventoryStore`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` deducting ingredients from the given inventory store.
+    pub fn with_inventory_store(mut self, inventory_store: Box<dyn InventoryStore>) -> Self {
+        self.inventory_store = inventory_store;
+        self
+    }
+
+    /// Sets the store used for reservations.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_store` - A boxed implementation of `ReservationStore`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` using the given reservation store.
+    pub fn with_reservation_store(mut self, reservation_store: Box<dyn ReservationStore>) -> Self {
+        self.reservation_store = reservation_store;
+        self
+    }
+
+    /// Sets the provider for a payment method, replacing the provider of the same method.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - A boxed implementation of `PaymentProvider`.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` taking payments of the provider's method through `provider`.
+    pub fn with_payment_provider(mut self, provider: Box<dyn PaymentProvider>) -> Self {
+        self.payment_providers
+            .retain(|existing| existing.method() != provider.method());
+        self.payment_providers.push(provider);
+        self
+    }
+
+    /// Frees a table for the next party: archives its session with the final bill and the
+    /// payments, and removes its order, split and open payments.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `bill` - The final bill of the party.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(TableSession))` with the closed session.
+    /// * `Ok(None)` if the table had no active session.
+    /// * `Err(RestaurantError)` if a store failed.
+    fn free_table(
+        &self,
+        table_id: u32,
+        bill: Bill,
+    ) -> Result<Option<TableSession>, RestaurantError> {
+        for item_id in self.ordered_item_ids(table_id)? {
+            self.order_store.remove_item(table_id, item_id)?;
+        }
+        self.check_store.remove_split(table_id)?;
+        let payments = self.payment_store.get_payments(table_id)?;
+        let session = match self
+            .session_store
+            .close_session(table_id, unix_now(), bill, payments)
+        {
+            Ok(session) => Some(session),
+            Err(RestaurantError::NoActiveSession(_)) => None,
+            Err(e) => return Err(e),
+        };
+        self.payment_store.clear_payments(table_id)?;
+        Ok(session)
+    }
+
+    /// Retrieves the closed session of the last party of a table while the table is free.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(TableSession))` with the last closed session.
+    /// * `Ok(None)` if the table is open or was never closed.
+    /// * `Err(RestaurantError)` if a store failed.
+    fn settled_session(&self, table_id: u32) -> Result<Option<TableSession>, RestaurantError> {
+        match self.session_store.get_active_session(table_id) {
+            Err(RestaurantError::NoActiveSession(_)) => {
+                Ok(self.session_store.get_history(Some(table_id))?.pop())
+            }
+            Ok(_) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Records a tip for the last party of a table after its bill was settled and the table
+    /// was freed, adding it to the party's closed session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The closed session of the party.
+    /// * `request` - The tip, without an amount towards the bill.
+    /// * `provider` - The payment provider of the tip's method.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PaymentReceipt)` with the tip and the account of the settled bill.
+    /// * `Err(RestaurantError::InvalidPayment)` if no payment of the party was for the check.
+    /// * `Err(RestaurantError)` if the provider declined the tip or a store failed.
+    fn tip_settled_session(
+        &self,
+        session: TableSession,
+        request: &PaymentRequest,
+        provider: &dyn PaymentProvider,
+    ) -> Result<PaymentReceipt, RestaurantError> {
+        let table_id = session.table_id;
+        if let Some(check_id) = request.check_id {
+            if !session
+                .payments
+                .iter()
+                .any(|payment| payment.check_id == Some(check_id))
+            {
+                return Err(RestaurantError::InvalidPayment(format!(
+                    "Table {} has no check {}",
+                    table_id, check_id
+                )));
+            }
+        }
+
+        check_totals(table_id, &session.payments, Money::ZERO, request.tip)?;
+        let reference = provider.charge(table_id, request)?;
+        let payment = Payment {
+            payment_id: session.payments.len() as u32 + 1,
+            table_id,
+            check_id: request.check_id,
+            method: request.method,
+            amount: Money::ZERO,
+            tip: request.tip,
+            reference,
+        };
+        let session = self
+            .session_store
+            .record_tip(session.session_id, payment.clone())?;
+        let total = session.bill.map_or(Money::ZERO, |bill| bill.total);
+        Ok(PaymentReceipt {
+            payment,
+            account: TableAccount::new(table_id, total, session.payments),
+        })
+    }
+
+    /// Retrieves the item IDs ordered at a table, treating a table without orders as empty.
+    fn ordered_item_ids(&self, table_id: u32) -> Result<Vec<u32>, RestaurantError> {
+        match self.order_store.get_item_ids(table_id) {
+            Err(RestaurantError::NoMenusForTable(_)) => Ok(Vec::new()),
+            result => result,
+        }
+    }
+
+    /// Opens a session without a party size for a table that has none, e.g. on its first order.
+    fn ensure_session(&self, table_id: u32) -> Result<(), RestaurantError> {
+        match self.session_store.get_active_session(table_id) {
+            Err(RestaurantError::NoActiveSession(_)) => {
+                match self.session_store.open_session(table_id, None, unix_now()) {
+                    Ok(_) | Err(RestaurantError::SessionAlreadyOpen(_)) => Ok(()),
+                    Err(e) => Err(e),
+                }
+            }
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// Takes one portion of a menu item from its stock and deducts its ingredients. Neither
+    /// is changed if the item is unavailable.
+    fn take_portion(&self, item: &MenuItem) -> Result<(), RestaurantError> {
+        self.stock_store.take(item.id)?;
+        if let Err(e) = self.inventory_store.deduct(item.id, &item.ingredients) {
+            self.stock_store.restore(item.id)?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Puts one portion of a menu item and its ingredients back into stock.
+    fn restore_portion(&self, item: &MenuItem) -> Result<(), RestaurantError> {
+        self.stock_store.restore(item.id)?;
+        self.inventory_store.restore(&item.ingredients)
+    }
+
+    /// Checks that a reservation can be made: the table exists and seats the party, the
+    /// contact name is set and the slot is not over yet.
+    fn validate_reservation(&self, request: &ReservationRequest) -> Result<(), RestaurantError> {
+        let capacity = self.table_store.get_table_capacity(request.table_id)?;
+        if request.party_size == 0 {
+            return Err(RestaurantError::InvalidPartySize(request.party_size));
+        }
+        if request.party_size > capacity {
+            return Err(RestaurantError::InvalidReservation(format!(
+                "table {} seats {} guests, not {}",
+                request.table_id, capacity, request.party_size
+            )));
+        }
+        if request.contact_name.trim().is_empty() {
+            return Err(RestaurantError::InvalidReservation(
+                "the contact name is empty".to_string(),
+            ));
+        }
+        if request.duration_minutes == 0 || request.duration_minutes > MAX_DURATION_MINUTES {
+            return Err(RestaurantError::InvalidReservation(format!(
+                "the duration must be 1 to {} minutes",
+                MAX_DURATION_MINUTES
+            )));
+        }
+        match request.to_reservation(0).checked_ends_at() {
+            None => {
+                return Err(RestaurantError::InvalidReservation(
+                    "the slot ends too far in the future".to_string(),
+                ))
+            }
+            Some(ends_at) if ends_at <= unix_now() => {
+                return Err(RestaurantError::InvalidReservation(
+                    "the slot is already over".to_string(),
+                ))
+            }
+            Some(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Sets the tax rate applied to bills.
+    ///
+    /// # Arguments
+    ///
+    /// * `tax_rate` - The tax rate, e.g. 8.25 %.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleRestaurant` with the tax rate applied.
+    pub fn with_tax_rate(mut self, tax_rate: TaxRate) -> Self {
+        self.tax_rate = tax_rate;
+        self
+    }
 }
 
 impl Restaurant for SimpleRestaurant {
@@ -50,6 +410,153 @@ impl Restaurant for SimpleRestaurant {
         self.menu_store.get_all_menus()
     }
 
+    /// Retrieves the menu items passing a filter by allergens and diets, with their stock.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The allergens to leave out and the diets to keep.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the matching `MenuListing`s in menu order, including sold out ones,
+    /// if successful, or `RestaurantError` in case of failure.
+    fn get_menus(&self, filter: &MenuFilter) -> Result<Vec<MenuListing>, RestaurantError> {
+        let items = filter.apply(self.menu_store.get_all_menus()?);
+        let items = with_stock(items, &self.stock_store.get_stock_levels()?);
+        Ok(with_inventory(
+            items,
+            &self.inventory_store.get_ingredients()?,
+        ))
+    }
+
+    /// Retrieves all categories of the menu, ordered by their display order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of `Category` if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_categories(&self) -> Result<Vec<Category>, RestaurantError> {
+        let mut categories = self.menu_store.get_categories()?;
+        categories.sort_by_key(|category| (category.display_order, category.id));
+        Ok(categories)
+    }
+
+    /// Retrieves the menu grouped by category. Menu items that do not pass the filter are left
+    /// out of every category.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The allergens to leave out and the diets to keep.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `CategorizedMenu` if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_categorized_menu(
+        &self,
+        filter: &MenuFilter,
+    ) -> Result<CategorizedMenu, RestaurantError> {
+        let items = self.get_menus(filter)?;
+        let categories = self.menu_store.get_categories()?;
+        Ok(CategorizedMenu::new(categories, &items))
+    }
+
+    /// Marks a category of the menu as served or not served.
+    ///
+    /// # Arguments
+    ///
+    /// * `category_id` - ID of the category.
+    /// * `available` - Whether the category is served.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the updated `Category` if successful,
+    /// `RestaurantError::CategoryNotFound` if the category does not exist,
+    /// or `RestaurantError` in case of failure.
+    fn set_category_availability(
+        &self,
+        category_id: u32,
+        available: bool,
+    ) -> Result<Category, RestaurantError> {
+        self.menu_store
+            .set_category_availability(category_id, available)
+    }
+
+    /// Retrieves the stock levels of all counted or sold out menu items.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `StockLevel`s ordered by item ID if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_stock(&self) -> Result<Vec<StockLevel>, RestaurantError> {
+        self.stock_store.get_stock_levels()
+    }
+
+    /// Sets the stock of a menu item, or marks it as sold out. Orders already placed are
+    /// not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_id` - ID of the menu item.
+    /// * `remaining` - Portions in stock, or `None` to stop counting.
+    /// * `sold_out` - Whether the menu item is sold out.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new `StockLevel` if successful,
+    /// `RestaurantError::MenuNotFound` if the menu item does not exist,
+    /// or `RestaurantError` in case of failure.
+    fn set_stock(
+        &self,
+        item_id: u32,
+        remaining: Option<u32>,
+        sold_out: bool,
+    ) -> Result<StockLevel, RestaurantError> {
+        if !self.get_all_menus()?.iter().any(|item| item.id == item_id) {
+            return Err(RestaurantError::MenuNotFound(item_id));
+        }
+        self.stock_store.set_stock(item_id, remaining, sold_out)
+    }
+
+    /// Retrieves all ingredients in the inventory.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Ingredient`s ordered by ID if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_ingredients(&self) -> Result<Vec<Ingredient>, RestaurantError> {
+        self.inventory_store.get_ingredients()
+    }
+
+    /// Adds a delivered quantity of an ingredient to the inventory. Menu items that were
+    /// short of the ingredient become available again once there is enough for a portion.
+    ///
+    /// # Arguments
+    ///
+    /// * `ingredient_id` - ID of the ingredient.
+    /// * `quantity` - The quantity delivered.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the restocked `Ingredient` if successful,
+    /// `RestaurantError::IngredientNotFound` if the ingredient does not exist,
+    /// or `RestaurantError` in case of failure.
+    fn restock(&self, ingredient_id: u32, quantity: u32) -> Result<Ingredient, RestaurantError> {
+        self.inventory_store.restock(ingredient_id, quantity)
+    }
+
+    /// Reports the ingredients at or below their low stock threshold and the menu items
+    /// lacking an ingredient for one more portion.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `LowStockReport` if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_low_stock_report(&self) -> Result<LowStockReport, RestaurantError> {
+        let ingredients = self.inventory_store.get_ingredients()?;
+        Ok(LowStockReport::new(ingredients, &self.get_all_menus()?))
+    }
+
     /// Retrieves all available tables in the restaurant.
     ///
     /// # Returns
@@ -60,7 +567,26 @@ impl Restaurant for SimpleRestaurant {
         self.table_store.get_all_tables()
     }
 
-    /// Adds an item to a table's order. Checks if the table exists before adding.
+    /// Retrieves all tables with their capacity, flagging the tables reserved right now.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the status of every table if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_table_statuses(&self) -> Result<Vec<TableStatus>, RestaurantError> {
+        let tables = self.get_all_tables()?;
+        let reservations = self.reservation_store.get_reservations()?;
+        let now = unix_now();
+        tables
+            .into_iter()
+            .map(|table_id| {
+                let capacity = self.table_store.get_table_capacity(table_id)?;
+                Ok(TableStatus::at(table_id, capacity, &reservations, now))
+            })
+            .collect()
+    }
+
+    /// Adds an item without modifiers or note to a table's order.
     ///
     /// # Arguments
     ///
@@ -70,22 +596,99 @@ impl Restaurant for SimpleRestaurant {
     /// # Returns
     ///
     /// * `Ok(())` if the item is successfully added.
-    /// * `Err(RestaurantError)` if the table or menu item is not found.
+    /// * `Err(RestaurantError)` if the table or menu item is not found, or the item
+    ///   cannot be ordered without choosing modifiers.
     fn add_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError> {
+        self.add_item_with_options(table_id, item_id, &ItemOptions::default())
+            .map(|_| ())
+    }
+
+    /// Adds an item with modifiers and a kitchen note to a table's order. Checks if the
+    /// table exists and validates the options against the menu item before adding.
+    ///
+    /// The order belongs to the table's active session; a table without one is opened
+    /// implicitly, without a party size. Items listed only in categories that are not served
+    /// cannot be ordered. The seat may not exceed the party size, if known.
+    /// Menu items containing allergies declared for the session are only added with an
+    /// allergen override. Adding the item takes a portion
+    /// from its stock and deducts the ingredients of its recipe.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `item_id` - ID of the menu item to be added.
+    /// * `options` - The chosen modifiers, the note, the seat and the allergen override.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Allergen>)` with the overridden allergies if the item is successfully added.
+    /// * `Err(RestaurantError::InvalidModifiers)` if the options do not match the menu item,
+    ///   or the seat does not exist at the table.
+    /// * `Err(RestaurantError::AllergenConflict)` if the item contains declared allergies
+    ///   that were not overridden.
+    /// * `Err(RestaurantError::ItemUnavailable)` if the item is sold out, an ingredient
+    ///   runs short, or none of its categories is served.
+    /// * `Err(RestaurantError)` if the table or menu item is not found.
+    fn add_item_with_options(
+        &self,
+        table_id: u32,
+        item_id: u32,
+        options: &ItemOptions,
+    ) -> Result<Vec<Allergen>, RestaurantError> {
+        let _guard = self
+            .payment_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         let tables = self.get_all_tables()?;
         if !tables.contains(&table_id) {
             return Err(RestaurantError::TableNotFound(table_id));
         }
 
         let all_menus = self.get_all_menus()?;
-        if !all_menus.iter().any(|item| item.id == item_id) {
-            return Err(RestaurantError::MenuNotFound(item_id));
+        let item = all_menus
+            .iter()
+            .find(|item| item.id == item_id)
+            .ok_or(RestaurantError::MenuNotFound(item_id))?;
+        if !is_served(item_id, &self.menu_store.get_categories()?) {
+            return Err(RestaurantError::ItemUnavailable(item_id));
+        }
+        let line = OrderLine::new(item, options)?;
+
+        let session = match self.session_store.get_active_session(table_id) {
+            Ok(session) => Some(session),
+            Err(RestaurantError::NoActiveSession(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let party_size = session.as_ref().and_then(|session| session.party_size);
+        if let (Some(seat), Some(party_size)) = (line.seat, party_size) {
+            if seat > party_size {
+                return Err(RestaurantError::InvalidModifiers(
+                    item_id,
+                    format!("seat {} is beyond the party of {}", seat, party_size),
+                ));
+            }
+        }
+        let allergies = session.map(|session| session.allergies).unwrap_or_default();
+        let conflicts = conflicting_allergens(item, &line.modifiers, &allergies);
+        if !conflicts.is_empty() && !options.allergen_override {
+            return Err(RestaurantError::AllergenConflict(
+                table_id, item_id, conflicts,
+            ));
         }
 
-        self.order_store.add_item(table_id, item_id)
+        self.take_portion(item)?;
+        let added = self
+            .ensure_session(table_id)
+            .and_then(|_| self.order_store.add_line(table_id, line));
+        if let Err(e) = added {
+            self.restore_portion(item)?;
+            return Err(e);
+        }
+        Ok(conflicts)
     }
 
-    /// Removes an item from a table's order. Checks if the table exists before removing.
+    /// Removes an item from a table's order and puts it and its ingredients back into stock.
+    /// Checks if the table exists before removing.
     ///
     /// # Arguments
     ///
@@ -97,227 +700,1424 @@ impl Restaurant for SimpleRestaurant {
     /// * `Ok(())` if the item is successfully removed.
     /// * `Err(RestaurantError)` if the table or item is not found.
     fn remove_item(&self, table_id: u32, item_id: u32) -> Result<(), RestaurantError> {
+        let _guard = self
+            .payment_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         let tables = self.get_all_tables()?;
         if !tables.contains(&table_id) {
             return Err(RestaurantError::TableNotFound(table_id));
         }
 
-        self.order_store.remove_item(table_id, item_id)
+        self.order_store.remove_item(table_id, item_id)?;
+        match self.get_all_menus()?.iter().find(|item| item.id == item_id) {
+            Some(item) => self.restore_portion(item),
+            None => self.stock_store.restore(item_id),
+        }
     }
 
-    /// Retrieves all items ordered at a specific table.
+    /// Removes a line from a table's order by its number and restores its stock.
     ///
     /// # Arguments
     ///
     /// * `table_id` - ID of the table.
+    /// * `line_id` - Number of the order line to be removed.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a vector of `MenuItem` if successful,
+    /// * `Ok(())` if the line is successfully removed.
+    /// * `Err(RestaurantError)` if the table or line is not found.
+    fn remove_line(&self, table_id: u32, line_id: u32) -> Result<(), RestaurantError> {
+        let _guard = self
+            .payment_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let tables = self.get_all_tables()?;
+        if !tables.contains(&table_id) {
+            return Err(RestaurantError::TableNotFound(table_id));
+        }
+
+        let line = self.order_store.remove_line(table_id, line_id)?;
+        match self
+            .get_all_menus()?
+            .iter()
+            .find(|item| item.id == line.item_id)
+        {
+            Some(item) => self.restore_portion(item),
+            None => self.stock_store.restore(line.item_id),
+        }
+    }
+
+    /// Retrieves all items ordered at a specific table, with their modifiers and notes.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of `OrderedItem` if successful,
     /// or `RestaurantError` in case of failure.
-    fn get_items(&self, table_id: u32) -> Result<Vec<MenuItem>, RestaurantError> {
+    fn get_items(&self, table_id: u32) -> Result<Vec<OrderedItem>, RestaurantError> {
         let tables = self.get_all_tables()?;
         if !tables.contains(&table_id) {
             return Err(RestaurantError::TableNotFound(table_id));
         }
 
-        let item_ids = self.order_store.get_item_ids(table_id)?;
+        let lines = self.order_store.get_lines(table_id)?;
         let all_menus = self.get_all_menus()?;
 
-        let items = item_ids
+        let items = lines
             .into_iter()
-            .filter_map(|id| all_menus.iter().find(|&item| item.id == id).cloned())
+            .filter_map(|line| {
+                all_menus
+                    .iter()
+                    .find(|&item| item.id == line.item_id)
+                    .map(|item| OrderedItem::new(item, line))
+            })
             .collect();
 
-        Ok(items)
+        Ok(items)
+    }
+
+    /// Retrieves a specific item ordered at a specific table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `item_id` - ID of the item to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the first `OrderedItem` of the menu item if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_item(&self, table_id: u32, item_id: u32) -> Result<OrderedItem, RestaurantError> {
+        let tables = self.get_all_tables()?;
+        if !tables.contains(&table_id) {
+            return Err(RestaurantError::TableNotFound(table_id));
+        }
+
+        self.order_store.get_item_id(table_id, item_id)?;
+        self.get_items(table_id)?
+            .into_iter()
+            .find(|item| item.id == item_id)
+            .ok_or(RestaurantError::MenuNotFound(item_id))
+    }
+
+    /// Builds the bill of a table from the items currently ordered at it.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Bill` with the restaurant's tax rate applied,
+    /// or `RestaurantError` in the same cases as `get_items`.
+    fn get_bill(&self, table_id: u32) -> Result<Bill, RestaurantError> {
+        let items = self.get_items(table_id)?;
+        Ok(Bill::new(table_id, &items, self.tax_rate))
+    }
+
+    /// Splits the bill of a table into checks and stores them.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `request` - How the bill is divided.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SplitBill` if successful, `RestaurantError::InvalidSplit`
+    /// if the request does not match the order, or `RestaurantError` in the same cases as `get_bill`.
+    fn split_bill(
+        &self,
+        table_id: u32,
+        request: &SplitRequest,
+    ) -> Result<SplitBill, RestaurantError> {
+        let items = self.get_items(table_id)?;
+        let bill = Bill::new(table_id, &items, self.tax_rate);
+        let split = SplitBill::new(bill, &items, request)?;
+        self.check_store.save_split(table_id, split.clone())?;
+        Ok(split)
+    }
+
+    /// Retrieves the checks of a table's split bill, as long as they still match its order.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SplitBill` if successful, `RestaurantError::SplitOutdated`
+    /// if the table's bill changed after the split, or `RestaurantError` in case of failure.
+    fn get_checks(&self, table_id: u32) -> Result<SplitBill, RestaurantError> {
+        let bill = self.get_bill(table_id)?;
+        let split = self.check_store.get_split(table_id)?;
+        if split.bill != bill {
+            return Err(RestaurantError::SplitOutdated(table_id));
+        }
+        Ok(split)
+    }
+
+    /// Records a payment towards the bill of a table and frees the table once the bill is paid.
+    ///
+    /// Payments are validated and recorded one at a time: the amount must not exceed the
+    /// balance of the table, nor that of the check when `check_id` is given, before the
+    /// payment provider is asked to take it. A tip without an amount for a table that was settled
+    /// and freed goes to the closed session of its last party, until the table is opened again.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `request` - The payment.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `PaymentReceipt` if successful, `RestaurantError::Overpayment`
+    /// if the amount exceeds the balance, `RestaurantError::PaymentDeclined` if the provider
+    /// declined the payment, `RestaurantError::InvalidPayment` if the payment is empty, the
+    /// method is not accepted, the check does not exist or the payments or tips of the table
+    /// would add up to more than `Money::MAX`, or `RestaurantError` in the same cases as
+    /// `get_bill`.
+    fn pay(
+        &self,
+        table_id: u32,
+        request: &PaymentRequest,
+    ) -> Result<PaymentReceipt, RestaurantError> {
+        if request.amount == Money::ZERO && request.tip == Money::ZERO {
+            return Err(RestaurantError::InvalidPayment(
+                "A payment needs an amount or a tip".to_string(),
+            ));
+        }
+        let provider = self
+            .payment_providers
+            .iter()
+            .find(|provider| provider.method() == request.method)
+            .ok_or_else(|| {
+                RestaurantError::InvalidPayment(format!(
+                    "{} payments are not accepted",
+                    request.method
+                ))
+            })?;
+
+        let _guard = self
+            .payment_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let bill = match self.get_bill(table_id) {
+            Ok(bill) if bill.total > Money::ZERO => bill,
+            result => {
+                if request.amount == Money::ZERO {
+                    if let Some(session) = self.settled_session(table_id)? {
+                        return self.tip_settled_session(session, request, provider.as_ref());
+                    }
+                }
+                result?;
+                return Err(RestaurantError::InvalidPayment(format!(
+                    "Table {} has nothing to pay",
+                    table_id
+                )));
+            }
+        };
+        let mut payments = self.payment_store.get_payments(table_id)?;
+        let account = TableAccount::new(table_id, bill.total, payments.clone());
+        let balance = match request.check_id {
+            None => account.balance,
+            Some(check_id) => {
+                let split = self.get_checks(table_id)?;
+                let check = split
+                    .checks
+                    .iter()
+                    .find(|check| check.check_id == check_id)
+                    .ok_or_else(|| {
+                        RestaurantError::InvalidPayment(format!(
+                            "Table {} has no check {}",
+                            table_id, check_id
+                        ))
+                    })?;
+                let paid: Money = payments
+                    .iter()
+                    .filter(|payment| payment.check_id == Some(check_id))
+                    .map(|payment| payment.amount)
+                    .sum();
+                // Payments to the whole table also count against the checks
+                check.total.saturating_sub(paid).min(account.balance)
+            }
+        };
+        if request.amount > balance {
+            return Err(RestaurantError::Overpayment(
+                table_id,
+                request.amount,
+                balance,
+            ));
+        }
+
+        check_totals(table_id, &payments, request.amount, request.tip)?;
+        let reference = provider.charge(table_id, request)?;
+        let payment = Payment {
+            payment_id: payments.len() as u32 + 1,
+            table_id,
+            check_id: request.check_id,
+            method: request.method,
+            amount: request.amount,
+            tip: request.tip,
+            reference,
+        };
+        self.payment_store.record_payment(payment.clone())?;
+        payments.push(payment.clone());
+
+        let account = TableAccount::new(table_id, bill.total, payments);
+        if account.settled {
+            self.free_table(table_id, bill)?;
+        }
+        Ok(PaymentReceipt { payment, account })
+    }
+
+    /// Retrieves the payment state of a table's current bill.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TableAccount` if successful,
+    /// or `RestaurantError` in the same cases as `get_bill`.
+    fn get_account(&self, table_id: u32) -> Result<TableAccount, RestaurantError> {
+        let bill = self.get_bill(table_id)?;
+        let payments = self.payment_store.get_payments(table_id)?;
+        Ok(TableAccount::new(table_id, bill.total, payments))
+    }
+
+    /// Opens a session for a party at a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `party_size` - Number of guests, from 1 to `MAX_PARTY_SIZE`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new `TableSession` if successful,
+    /// `RestaurantError::InvalidPartySize` if the party cannot be seated,
+    /// `RestaurantError::SessionAlreadyOpen` if the table is already open,
+    /// or `RestaurantError` if the table is not found or a store failed.
+    fn open_table(&self, table_id: u32, party_size: u32) -> Result<TableSession, RestaurantError> {
+        if party_size == 0 || party_size > MAX_PARTY_SIZE {
+            return Err(RestaurantError::InvalidPartySize(party_size));
+        }
+        let tables = self.get_all_tables()?;
+        if !tables.contains(&table_id) {
+            return Err(RestaurantError::TableNotFound(table_id));
+        }
+        self.session_store
+            .open_session(table_id, Some(party_size), unix_now())
+    }
+
+    /// Closes the session of a table once its bill is paid, and frees the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the closed `TableSession` with its final bill if successful,
+    /// `RestaurantError::OutstandingBalance` if the bill is not paid in full,
+    /// `RestaurantError::NoActiveSession` if the table is not open,
+    /// or `RestaurantError` if the table is not found or a store failed.
+    fn close_table(&self, table_id: u32) -> Result<TableSession, RestaurantError> {
+        let _guard = self
+            .payment_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.get_session(table_id)?;
+
+        let bill = match self.get_bill(table_id) {
+            Err(RestaurantError::NoMenusForTable(_)) => Bill::new(table_id, &[], self.tax_rate),
+            result => result?,
+        };
+        let payments = self.payment_store.get_payments(table_id)?;
+        let account = TableAccount::new(table_id, bill.total, payments);
+        if account.balance > Money::ZERO {
+            return Err(RestaurantError::OutstandingBalance(
+                table_id,
+                account.balance,
+            ));
+        }
+        self.free_table(table_id, bill)?
+            .ok_or(RestaurantError::NoActiveSession(table_id))
+    }
+
+    /// Retrieves the active session of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the active `TableSession` if successful,
+    /// `RestaurantError::NoActiveSession` if the table is not open,
+    /// or `RestaurantError` if the table is not found or a store failed.
+    fn get_session(&self, table_id: u32) -> Result<TableSession, RestaurantError> {
+        let tables = self.get_all_tables()?;
+        if !tables.contains(&table_id) {
+            return Err(RestaurantError::TableNotFound(table_id));
+        }
+        self.session_store.get_active_session(table_id)
+    }
+
+    /// Declares the allergies of the party at a table, opening the table without a party
+    /// size if it is not open yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - ID of the table.
+    /// * `allergies` - The allergies of the party, replacing earlier ones.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the active `TableSession` with its allergies if successful,
+    /// or `RestaurantError` if the table is not found or a store failed.
+    fn declare_allergies(
+        &self,
+        table_id: u32,
+        allergies: Vec<Allergen>,
+    ) -> Result<TableSession, RestaurantError> {
+        let tables = self.get_all_tables()?;
+        if !tables.contains(&table_id) {
+            return Err(RestaurantError::TableNotFound(table_id));
+        }
+        let mut unique = Vec::new();
+        for allergen in allergies {
+            if !unique.contains(&allergen) {
+                unique.push(allergen);
+            }
+        }
+        self.ensure_session(table_id)?;
+        self.session_store.set_allergies(table_id, unique)
+    }
+
+    /// Retrieves closed sessions, in the order they were closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Only sessions of this table if set, of all tables otherwise.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the closed sessions if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_session_history(
+        &self,
+        table_id: Option<u32>,
+    ) -> Result<Vec<TableSession>, RestaurantError> {
+        self.session_store.get_history(table_id)
+    }
+
+    /// Retrieves reservations, optionally only those starting on one day.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - Midnight of the day, in seconds since the Unix epoch, or `None` for all reservations.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the reservations ordered by the start of their slot if successful,
+    /// or `RestaurantError` in case of failure.
+    fn get_reservations(&self, day: Option<u64>) -> Result<Vec<Reservation>, RestaurantError> {
+        let mut reservations = self.reservation_store.get_reservations()?;
+        if let Some(day) = day {
+            reservations.retain(|reservation| (day..day + 86_400).contains(&reservation.starts_at));
+        }
+        Ok(reservations)
+    }
+
+    /// Reserves a table for a party.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The table, party size, contact name and slot.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new `Reservation` if successful,
+    /// `RestaurantError::InvalidReservation` or `RestaurantError::InvalidPartySize` if the
+    /// party does not fit the table or the slot is not valid,
+    /// `RestaurantError::ReservationConflict` if the table is already reserved during the slot,
+    /// or `RestaurantError` if the table is not found or a store failed.
+    fn make_reservation(
+        &self,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, RestaurantError> {
+        self.validate_reservation(request)?;
+        self.reservation_store.create(request)
+    }
+
+    /// Retrieves a reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_id` - ID of the reservation.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Reservation` if successful,
+    /// or `RestaurantError::ReservationNotFound` if the reservation does not exist.
+    fn get_reservation(&self, reservation_id: u64) -> Result<Reservation, RestaurantError> {
+        self.reservation_store.get(reservation_id)
+    }
+
+    /// Changes the details of a reservation, validating them like a new reservation once
+    /// the reservation is found.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_id` - ID of the reservation.
+    /// * `request` - The new table, party size, contact name and slot.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the updated `Reservation` if successful,
+    /// `RestaurantError::ReservationNotFound` if the reservation does not exist,
+    /// or `RestaurantError` for the same reasons as `make_reservation`.
+    fn update_reservation(
+        &self,
+        reservation_id: u64,
+        request: &ReservationRequest,
+    ) -> Result<Reservation, RestaurantError> {
+        self.reservation_store.get(reservation_id)?;
+        self.validate_reservation(request)?;
+        self.reservation_store.update(reservation_id, request)
+    }
+
+    /// Cancels a reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_id` - ID of the reservation.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the cancelled `Reservation` if successful,
+    /// or `RestaurantError::ReservationNotFound` if the reservation does not exist.
+    fn cancel_reservation(&self, reservation_id: u64) -> Result<Reservation, RestaurantError> {
+        self.reservation_store.cancel(reservation_id)
+    }
+
+    /// Flushes the state of the menu, order, table, check, payment, session, stock,
+    /// inventory and reservation stores.
+    ///
+    /// Every store is asked to flush even if an earlier one fails.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all stores were flushed successfully.
+    /// * `Err(RestaurantError)` with the first error reported by a store.
+    fn shutdown(&self) -> Result<(), RestaurantError> {
+        let results = [
+            self.menu_store.shutdown(),
+            self.order_store.shutdown(),
+            self.table_store.shutdown(),
+            self.check_store.shutdown(),
+            self.payment_store.shutdown(),
+            self.session_store.shutdown(),
+            self.stock_store.shutdown(),
+            self.inventory_store.shutdown(),
+            self.reservation_store.shutdown(),
+        ];
+        results.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::data_model::models::MockPaymentProvider;
+    use crate::server::data_model::models::{MockMenuStore, MockOrderStore, MockTableStore};
+    use crate::server::data_model::money::Money;
+    use crate::server::data_model::payment::PaymentMethod;
+    use crate::server::data_store::in_memory_menu_store::InMemoryMenuStore;
+    use crate::server::data_store::in_memory_order_store::InMemoryOrderStore;
+    use crate::server::data_store::in_memory_table_store::InMemoryTableStore;
+    use mockall::predicate::*;
+    use std::sync::{mpsc, Arc, Mutex, OnceLock, Weak};
+    use std::time::Duration;
+
+    #[test]
+    fn test_add_item_success() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        let table_id = 1;
+        let item_id = 1;
+
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(move || Ok(vec![table_id]));
+
+        mock_menu_store.expect_get_all_menus().returning(move || {
+            Ok(vec![MenuItem::new(
+                item_id,
+                "Burger",
+                10,
+                Money::from_cents(1250),
+            )])
+        });
+
+        mock_menu_store
+            .expect_get_categories()
+            .returning(|| Ok(Vec::new()));
+
+        mock_order_store
+            .expect_add_line()
+            .with(eq(table_id), eq(OrderLine::plain(item_id)))
+            .returning(|_, _| Ok(()));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        );
+
+        let result = restaurant.add_item(table_id, item_id);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_add_item_failure() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        let table_id = 1;
+        let item_id = 1;
+
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(move || Ok(vec![table_id]));
+
+        mock_menu_store
+            .expect_get_all_menus()
+            .returning(move || Ok(vec![])); // Menu item not found
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(MockOrderStore::new()),
+            Box::new(mock_table_store),
+        );
+
+        let result = restaurant.add_item(table_id, item_id);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), RestaurantError::MenuNotFound(item_id));
+    }
+
+    #[test]
+    fn test_remove_item_success() {
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        let table_id = 1;
+        let item_id = 1;
+
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(move || Ok(vec![table_id]));
+
+        mock_order_store
+            .expect_remove_item()
+            .with(eq(table_id), eq(item_id))
+            .returning(move |_, _| Ok(()));
+
+        // The removed item is looked up to restore its ingredients
+        let mut mock_menu_store = MockMenuStore::new();
+        mock_menu_store
+            .expect_get_all_menus()
+            .returning(|| Ok(Vec::new()));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        );
+
+        let result = restaurant.remove_item(table_id, item_id);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_remove_item_failure() {
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        let table_id = 1;
+        let item_id = 1;
+
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(move || Ok(vec![table_id]));
+
+        mock_order_store
+            .expect_remove_item()
+            .with(eq(table_id), eq(item_id))
+            .returning(move |_, _| Err(RestaurantError::MenuNotFound(item_id)));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(MockMenuStore::new()),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        );
+
+        let result = restaurant.remove_item(table_id, item_id);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), RestaurantError::MenuNotFound(item_id));
+    }
+
+    #[test]
+    fn test_get_items_success() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        let table_id = 1;
+        let item_id = 1;
+        let menu_item = MenuItem::new(item_id, "Burger", 10, Money::from_cents(1250));
+
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(move || Ok(vec![table_id]));
+
+        mock_order_store
+            .expect_get_lines()
+            .with(eq(table_id))
+            .returning(move |_| Ok(vec![OrderLine::plain(item_id)]));
+
+        mock_menu_store
+            .expect_get_all_menus()
+            .returning(move || Ok(vec![menu_item.clone()]));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        );
+
+        let result = restaurant.get_items(table_id).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "Burger");
+    }
+
+    #[test]
+    fn test_get_bill_applies_tax_rate() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(|| Ok(vec![1]));
+        mock_order_store
+            .expect_get_lines()
+            .with(eq(1))
+            .returning(|_| Ok([1, 2, 1].map(OrderLine::plain).to_vec()));
+        mock_menu_store.expect_get_all_menus().returning(|| {
+            Ok(vec![
+                MenuItem::new(1, "Burger", 10, Money::from_cents(1250)),
+                MenuItem::new(2, "Fries", 3, Money::from_cents(450)),
+            ])
+        });
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        )
+        .with_tax_rate(TaxRate::from_basis_points(1000));
+
+        let bill = restaurant.get_bill(1).unwrap();
+        let lines: Vec<(u32, u32, Money)> = bill
+            .lines
+            .iter()
+            .map(|line| (line.item_id, line.quantity, line.amount))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (1, 2, Money::from_cents(2500)),
+                (2, 1, Money::from_cents(450))
+            ]
+        );
+        assert_eq!(bill.subtotal, Money::from_cents(2950));
+        assert_eq!(bill.tax, Money::from_cents(295));
+        assert_eq!(bill.total, Money::from_cents(3245));
+    }
+
+    #[test]
+    fn test_get_bill_table_not_found() {
+        let mut mock_table_store = MockTableStore::new();
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(|| Ok(vec![1]));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(MockMenuStore::new()),
+            Box::new(MockOrderStore::new()),
+            Box::new(mock_table_store),
+        );
+        assert_eq!(
+            restaurant.get_bill(2),
+            Err(RestaurantError::TableNotFound(2))
+        );
+    }
+
+    #[test]
+    fn test_split_bill_and_get_checks() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        let orders = Arc::new(Mutex::new(vec![1, 2, 1]));
+        let table_orders = Arc::clone(&orders);
+        mock_table_store
+            .expect_get_all_tables()
+            .returning(|| Ok(vec![1]));
+        mock_order_store
+            .expect_get_lines()
+            .with(eq(1))
+            .returning(move |_| {
+                Ok(table_orders
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .copied()
+                    .map(OrderLine::plain)
+                    .collect())
+            });
+        mock_menu_store.expect_get_all_menus().returning(|| {
+            Ok(vec![
+                MenuItem::new(1, "Burger", 10, Money::from_cents(1250)),
+                MenuItem::new(2, "Fries", 3, Money::from_cents(450)),
+            ])
+        });
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        )
+        .with_tax_rate(TaxRate::from_basis_points(825));
+
+        assert_eq!(
+            restaurant.get_checks(1),
+            Err(RestaurantError::NoSplitForTable(1))
+        );
+        let request = SplitRequest::Items {
+            checks: vec![vec![1, 2], vec![1, 3]],
+        };
+        assert!(matches!(
+            restaurant.split_bill(1, &request),
+            Err(RestaurantError::InvalidSplit(_))
+        ));
+
+        let split = restaurant
+            .split_bill(1, &SplitRequest::Even { payers: 3 })
+            .unwrap();
+        let totals: Money = split.checks.iter().map(|check| check.total).sum();
+        assert_eq!(totals, split.bill.total);
+        assert_eq!(restaurant.get_checks(1), Ok(split));
+
+        orders.lock().unwrap().push(2);
+        assert_eq!(
+            restaurant.get_checks(1),
+            Err(RestaurantError::SplitOutdated(1))
+        );
+    }
+
+    fn paying_restaurant() -> SimpleRestaurant {
+        let menu_store = InMemoryMenuStore::new(vec![
+            MenuItem::new(1, "Burger", 10, Money::from_cents(1250)),
+            MenuItem::new(2, "Fries", 3, Money::from_cents(450)),
+        ]);
+        let restaurant = SimpleRestaurant::new(
+            Box::new(menu_store),
+            Box::new(InMemoryOrderStore::new()),
+            Box::new(InMemoryTableStore::new()),
+        );
+        for item_id in [1, 2, 1] {
+            restaurant.add_item(1, item_id).unwrap();
+        }
+        restaurant
+    }
+
+    fn cash(amount: u64, tip: u64) -> PaymentRequest {
+        PaymentRequest {
+            method: PaymentMethod::Cash,
+            amount: Money::from_cents(amount),
+            tip: Money::from_cents(tip),
+            check_id: None,
+            card_number: None,
+        }
+    }
+
+    #[test]
+    fn test_pay_until_settled_frees_table() {
+        let restaurant = paying_restaurant();
+
+        let receipt = restaurant.pay(1, &cash(1000, 200)).unwrap();
+        assert_eq!(receipt.payment.payment_id, 1);
+        assert_eq!(receipt.payment.reference, "cash-1");
+        assert_eq!(receipt.account.balance, Money::from_cents(1950));
+        assert!(!receipt.account.settled);
+
+        assert_eq!(
+            restaurant.pay(1, &cash(2000, 0)),
+            Err(RestaurantError::Overpayment(
+                1,
+                Money::from_cents(2000),
+                Money::from_cents(1950)
+            ))
+        );
+        assert!(matches!(
+            restaurant.pay(1, &cash(0, 0)),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+
+        let receipt = restaurant.pay(1, &cash(1950, 0)).unwrap();
+        assert!(receipt.account.settled);
+        assert_eq!(receipt.account.paid, Money::from_cents(2950));
+        assert_eq!(receipt.account.tips, Money::from_cents(200));
+        assert_eq!(receipt.account.payments.len(), 2);
+
+        // The table is free: no order and no open payments left
+        assert_eq!(restaurant.get_items(1), Ok(vec![]));
+        let account = restaurant.get_account(1).unwrap();
+        assert_eq!(account.total, Money::ZERO);
+        assert!(account.payments.is_empty());
+        assert!(matches!(
+            restaurant.pay(1, &cash(100, 0)),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+
+        // The payments are archived with the closed session, which still takes tips
+        let history = restaurant.get_session_history(Some(1)).unwrap();
+        assert_eq!(history[0].payments, receipt.account.payments);
+        let tip = restaurant.pay(1, &cash(0, 100)).unwrap();
+        assert_eq!(tip.payment.payment_id, 3);
+        assert!(tip.account.settled);
+        assert_eq!(tip.account.tips, Money::from_cents(300));
+        let history = restaurant.get_session_history(Some(1)).unwrap();
+        assert_eq!(history[0].payments.len(), 3);
+
+        // Tips go to the next party once the table is opened again
+        restaurant.open_table(1, 2).unwrap();
+        assert!(matches!(
+            restaurant.pay(1, &cash(0, 100)),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+    }
+
+    #[test]
+    fn test_tips_of_settled_table_cannot_exceed_largest_amount() {
+        let restaurant = paying_restaurant();
+        restaurant.pay(1, &cash(2950, 0)).unwrap();
+        restaurant.pay(1, &cash(0, u64::MAX)).unwrap();
+        assert!(matches!(
+            restaurant.pay(1, &cash(0, 1)),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+        let history = restaurant.get_session_history(Some(1)).unwrap();
+        assert_eq!(history[0].payments.len(), 2);
+    }
+
+    #[test]
+    fn test_pay_checks_of_split_bill() {
+        let restaurant = paying_restaurant();
+        let split = restaurant
+            .split_bill(
+                1,
+                &SplitRequest::Items {
+                    checks: vec![vec![1, 2], vec![3]],
+                },
+            )
+            .unwrap();
+
+        let mut request = cash(1250, 0);
+        request.check_id = Some(2);
+        restaurant.pay(1, &request).unwrap();
+        assert_eq!(
+            restaurant.pay(1, &request),
+            Err(RestaurantError::Overpayment(
+                1,
+                Money::from_cents(1250),
+                Money::ZERO
+            ))
+        );
+        request.check_id = Some(3);
+        assert!(matches!(
+            restaurant.pay(1, &request),
+            Err(RestaurantError::InvalidPayment(_))
+        ));
+
+        request.check_id = Some(1);
+        request.amount = split.checks[0].total;
+        let receipt = restaurant.pay(1, &request).unwrap();
+        assert!(receipt.account.settled);
+        assert_eq!(
+            restaurant.get_checks(1),
+            Err(RestaurantError::NoSplitForTable(1))
+        );
+    }
+
+    #[test]
+    fn test_check_payments_cannot_exceed_table_balance() {
+        let restaurant = paying_restaurant();
+        restaurant
+            .split_bill(
+                1,
+                &SplitRequest::Items {
+                    checks: vec![vec![1, 2], vec![3]],
+                },
+            )
+            .unwrap();
+        restaurant.pay(1, &cash(2000, 0)).unwrap();
+
+        // Check 1 is still unpaid, but only 9.50 are left on the table
+        let mut request = cash(1000, 0);
+        request.check_id = Some(1);
+        assert_eq!(
+            restaurant.pay(1, &request),
+            Err(RestaurantError::Overpayment(
+                1,
+                Money::from_cents(1000),
+                Money::from_cents(950)
+            ))
+        );
+        request.amount = Money::from_cents(950);
+        let receipt = restaurant.pay(1, &request).unwrap();
+        assert!(receipt.account.settled);
+        assert_eq!(receipt.account.paid, Money::from_cents(2950));
     }
 
-    /// Retrieves a specific item ordered at a specific table.
-    ///
-    /// # Arguments
-    ///
-    /// * `table_id` - ID of the table.
-    /// * `item_id` - ID of the item to retrieve.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing the `MenuItem` if successful,
-    /// or `RestaurantError` in case of failure.
-    fn get_item(&self, table_id: u32, item_id: u32) -> Result<MenuItem, RestaurantError> {
-        let tables = self.get_all_tables()?;
-        if !tables.contains(&table_id) {
-            return Err(RestaurantError::TableNotFound(table_id));
-        }
+    #[test]
+    fn test_declined_payment_is_not_recorded() {
+        let mut mock_provider = MockPaymentProvider::new();
+        mock_provider
+            .expect_method()
+            .return_const(PaymentMethod::Card);
+        mock_provider.expect_charge().returning(|_, _| {
+            Err(RestaurantError::PaymentDeclined(
+                "card declined".to_string(),
+            ))
+        });
+        let restaurant = paying_restaurant().with_payment_provider(Box::new(mock_provider));
 
-        self.order_store.get_item_id(table_id, item_id)?;
-        let all_menus = self.get_all_menus()?;
-        all_menus
-            .into_iter()
-            .find(|item| item.id == item_id)
-            .ok_or(RestaurantError::MenuNotFound(item_id))
+        let request = PaymentRequest {
+            method: PaymentMethod::Card,
+            card_number: Some("4242424242424242".to_string()),
+            ..cash(1000, 0)
+        };
+        assert_eq!(
+            restaurant.pay(1, &request),
+            Err(RestaurantError::PaymentDeclined(
+                "card declined".to_string()
+            ))
+        );
+        assert!(restaurant.get_account(1).unwrap().payments.is_empty());
+        assert_eq!(restaurant.payment_providers.len(), 2);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::server::data_model::models::{MockMenuStore, MockOrderStore, MockTableStore};
-    use mockall::predicate::*;
 
     #[test]
-    fn test_add_item_success() {
-        let mut mock_menu_store = MockMenuStore::new();
-        let mut mock_order_store = MockOrderStore::new();
-        let mut mock_table_store = MockTableStore::new();
+    fn test_open_and_close_table() {
+        let restaurant = paying_restaurant();
+        assert_eq!(
+            restaurant.open_table(2, 0),
+            Err(RestaurantError::InvalidPartySize(0))
+        );
+        assert_eq!(
+            restaurant.open_table(2, MAX_PARTY_SIZE + 1),
+            Err(RestaurantError::InvalidPartySize(MAX_PARTY_SIZE + 1))
+        );
+        assert_eq!(
+            restaurant.open_table(101, 2),
+            Err(RestaurantError::TableNotFound(101))
+        );
+        assert_eq!(
+            restaurant.close_table(2),
+            Err(RestaurantError::NoActiveSession(2))
+        );
 
-        let table_id = 1;
-        let item_id = 1;
+        let opened = restaurant.open_table(2, 4).unwrap();
+        assert_eq!(opened.party_size, Some(4));
+        assert!(opened.is_active());
+        assert_eq!(
+            restaurant.open_table(2, 4),
+            Err(RestaurantError::SessionAlreadyOpen(2))
+        );
+        assert_eq!(restaurant.get_session(2), Ok(opened.clone()));
 
-        mock_table_store
-            .expect_get_all_tables()
-            .returning(move || Ok(vec![table_id]));
+        restaurant.add_item(2, 2).unwrap();
+        assert_eq!(
+            restaurant.close_table(2),
+            Err(RestaurantError::OutstandingBalance(
+                2,
+                Money::from_cents(450)
+            ))
+        );
+        restaurant.remove_item(2, 2).unwrap();
 
-        mock_menu_store.expect_get_all_menus().returning(move || {
-            Ok(vec![MenuItem {
-                id: item_id,
-                name: "Burger".to_string(),
-                cooking_time_minutes: 10,
-            }])
+        // A table whose order was cancelled can be closed without payment
+        let closed = restaurant.close_table(2).unwrap();
+        assert_eq!(closed.session_id, opened.session_id);
+        assert!(!closed.is_active());
+        assert_eq!(closed.bill.unwrap().total, Money::ZERO);
+        assert_eq!(
+            restaurant.get_session(2),
+            Err(RestaurantError::NoActiveSession(2))
+        );
+        assert_eq!(restaurant.get_session_history(Some(2)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_item_added_during_settlement_is_not_lost() {
+        // The card provider orders fries at the table while the settling payment is charged
+        let cell: Arc<OnceLock<Weak<SimpleRestaurant>>> = Arc::new(OnceLock::new());
+        let (adder, added) = mpsc::channel();
+        let mut mock_provider = MockPaymentProvider::new();
+        mock_provider
+            .expect_method()
+            .return_const(PaymentMethod::Card);
+        let cell_clone = Arc::clone(&cell);
+        mock_provider.expect_charge().returning(move |_, _| {
+            let restaurant = cell_clone.get().unwrap().upgrade().unwrap();
+            let handle = std::thread::spawn(move || restaurant.add_item(1, 2));
+            std::thread::sleep(Duration::from_millis(50));
+            adder.send(handle).unwrap();
+            Ok("card-1".to_string())
         });
+        let restaurant =
+            Arc::new(paying_restaurant().with_payment_provider(Box::new(mock_provider)));
+        cell.set(Arc::downgrade(&restaurant)).unwrap();
+
+        let request = PaymentRequest {
+            method: PaymentMethod::Card,
+            card_number: Some("4242424242424242".to_string()),
+            ..cash(2950, 0)
+        };
+        assert!(restaurant.pay(1, &request).unwrap().account.settled);
+        assert_eq!(added.recv().unwrap().join().unwrap(), Ok(()));
 
-        mock_order_store
-            .expect_add_item()
-            .with(eq(table_id), eq(item_id))
-            .returning(|_, _| Ok(()));
+        // The fries were ordered after the settlement, by the next party
+        let history = restaurant.get_session_history(Some(1)).unwrap();
+        assert_eq!(
+            history[0].bill.as_ref().unwrap().total,
+            Money::from_cents(2950)
+        );
+        let items = restaurant.get_items(1).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Fries");
+    }
 
-        let restaurant = SimpleRestaurant::new(
-            Box::new(mock_menu_store),
-            Box::new(mock_order_store),
-            Box::new(mock_table_store),
+    #[test]
+    fn test_first_order_opens_session_and_settlement_closes_it() {
+        let restaurant = paying_restaurant();
+        let session = restaurant.get_session(1).unwrap();
+        assert_eq!(session.party_size, None);
+
+        restaurant.pay(1, &cash(2950, 0)).unwrap();
+        assert_eq!(
+            restaurant.get_session(1),
+            Err(RestaurantError::NoActiveSession(1))
         );
+        let history = restaurant.get_session_history(None).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].session_id, session.session_id);
+        assert_eq!(
+            history[0].bill.as_ref().unwrap().total,
+            Money::from_cents(2950)
+        );
+        assert_eq!(restaurant.get_session_history(Some(2)), Ok(vec![]));
 
-        let result = restaurant.add_item(table_id, item_id);
-        assert!(result.is_ok());
+        // The next order starts a new session
+        restaurant.add_item(1, 2).unwrap();
+        assert_ne!(
+            restaurant.get_session(1).unwrap().session_id,
+            session.session_id
+        );
     }
 
     #[test]
-    fn test_add_item_failure() {
-        let mut mock_menu_store = MockMenuStore::new();
-        let mut mock_table_store = MockTableStore::new();
-
-        let table_id = 1;
-        let item_id = 1;
+    fn test_declared_allergies_require_override() {
+        let restaurant = SimpleRestaurant::new(
+            Box::new(InMemoryMenuStore::with_predefined_recipes()),
+            Box::new(InMemoryOrderStore::new()),
+            Box::new(InMemoryTableStore::new()),
+        );
+        assert_eq!(
+            restaurant.declare_allergies(101, vec![Allergen::Nuts]),
+            Err(RestaurantError::TableNotFound(101))
+        );
+        let session = restaurant
+            .declare_allergies(2, vec![Allergen::Nuts, Allergen::Dairy, Allergen::Nuts])
+            .unwrap();
+        assert_eq!(session.allergies, vec![Allergen::Nuts, Allergen::Dairy]);
 
-        mock_table_store
-            .expect_get_all_tables()
-            .returning(move || Ok(vec![table_id]));
+        // Curry contains dairy and nuts, fries contain neither
+        assert_eq!(
+            restaurant.add_item(2, 14),
+            Err(RestaurantError::AllergenConflict(
+                2,
+                14,
+                vec![Allergen::Dairy, Allergen::Nuts]
+            ))
+        );
+        restaurant.add_item(2, 9).unwrap();
+        let options = ItemOptions {
+            allergen_override: true,
+            ..ItemOptions::default()
+        };
+        assert_eq!(
+            restaurant.add_item_with_options(2, 14, &options),
+            Ok(vec![Allergen::Dairy, Allergen::Nuts])
+        );
+        let ids: Vec<u32> = restaurant
+            .get_items(2)
+            .unwrap()
+            .iter()
+            .map(|item| item.id)
+            .collect();
+        assert_eq!(ids, vec![9, 14]);
 
-        mock_menu_store
-            .expect_get_all_menus()
-            .returning(move || Ok(vec![])); // Menu item not found
+        let filter = MenuFilter::parse(Some("nuts,dairy"), Some("vegan")).unwrap();
+        let names: Vec<String> = restaurant
+            .get_menus(&filter)
+            .unwrap()
+            .into_iter()
+            .map(|listing| listing.item.name)
+            .collect();
+        assert_eq!(names, vec!["Fries", "Stir Fry"]);
+        let menu = restaurant.get_categorized_menu(&filter).unwrap();
+        assert_eq!(menu.item_count(), 2);
+    }
 
+    #[test]
+    fn test_allergens_of_chosen_modifiers_require_override() {
         let restaurant = SimpleRestaurant::new(
-            Box::new(mock_menu_store),
-            Box::new(MockOrderStore::new()),
-            Box::new(mock_table_store),
+            Box::new(InMemoryMenuStore::with_predefined_recipes()),
+            Box::new(InMemoryOrderStore::new()),
+            Box::new(InMemoryTableStore::new()),
         );
+        restaurant
+            .declare_allergies(3, vec![Allergen::Fish])
+            .unwrap();
+        let dressing = |modifier_id: u32| ItemOptions {
+            modifiers: vec![modifier_id],
+            ..ItemOptions::default()
+        };
 
-        let result = restaurant.add_item(table_id, item_id);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), RestaurantError::MenuNotFound(item_id));
+        // The Caesar dressing has anchovies, the vinaigrette does not
+        assert_eq!(
+            restaurant.add_item_with_options(3, 1, &dressing(2)),
+            Err(RestaurantError::AllergenConflict(
+                3,
+                1,
+                vec![Allergen::Fish]
+            ))
+        );
+        assert_eq!(
+            restaurant.add_item_with_options(3, 1, &dressing(1)),
+            Ok(Vec::new())
+        );
     }
 
     #[test]
-    fn test_remove_item_success() {
-        let mut mock_order_store = MockOrderStore::new();
-        let mut mock_table_store = MockTableStore::new();
-
-        let table_id = 1;
-        let item_id = 1;
+    fn test_stock_is_taken_and_restored() {
+        let restaurant = SimpleRestaurant::new(
+            Box::new(InMemoryMenuStore::with_predefined_recipes()),
+            Box::new(InMemoryOrderStore::new()),
+            Box::new(InMemoryTableStore::new()),
+        );
+        assert_eq!(
+            restaurant.set_stock(21, Some(1), false),
+            Err(RestaurantError::MenuNotFound(21))
+        );
+        restaurant.set_stock(20, Some(1), false).unwrap();
+        restaurant.set_stock(5, None, true).unwrap();
 
-        mock_table_store
-            .expect_get_all_tables()
-            .returning(move || Ok(vec![table_id]));
+        restaurant.add_item(1, 20).unwrap();
+        assert_eq!(
+            restaurant.add_item(2, 20),
+            Err(RestaurantError::ItemUnavailable(20))
+        );
+        assert_eq!(
+            restaurant.add_item(2, 5),
+            Err(RestaurantError::ItemUnavailable(5))
+        );
+        let stock: Vec<(u32, bool, Option<u32>)> = restaurant
+            .get_menus(&MenuFilter::default())
+            .unwrap()
+            .iter()
+            .filter(|listing| listing.sold_out || listing.remaining.is_some())
+            .map(|listing| (listing.item.id, listing.sold_out, listing.remaining))
+            .collect();
+        assert_eq!(stock, vec![(5, true, None), (20, true, Some(0))]);
 
-        mock_order_store
-            .expect_remove_item()
-            .with(eq(table_id), eq(item_id))
-            .returning(move |_, _| Ok(()));
+        restaurant.remove_item(1, 20).unwrap();
+        restaurant.add_item(2, 20).unwrap();
+        assert_eq!(
+            restaurant.get_stock().unwrap()[1],
+            StockLevel {
+                item_id: 20,
+                remaining: Some(0),
+                sold_out: false,
+            }
+        );
+    }
 
+    #[test]
+    fn test_removed_line_restores_stock() {
         let restaurant = SimpleRestaurant::new(
-            Box::new(MockMenuStore::new()),
-            Box::new(mock_order_store),
-            Box::new(mock_table_store),
+            Box::new(InMemoryMenuStore::with_predefined_recipes()),
+            Box::new(InMemoryOrderStore::new()),
+            Box::new(InMemoryTableStore::new()),
         );
+        restaurant.set_stock(20, Some(1), false).unwrap();
+        restaurant.add_item(1, 5).unwrap();
+        restaurant.add_item(1, 20).unwrap();
 
-        let result = restaurant.remove_item(table_id, item_id);
-        assert!(result.is_ok());
+        assert_eq!(
+            restaurant.remove_line(1, 3),
+            Err(RestaurantError::OrderLineNotFound(1, 3))
+        );
+        restaurant.remove_line(1, 2).unwrap();
+        assert_eq!(restaurant.order_store.get_item_ids(1), Ok(vec![5]));
+        restaurant.add_item(2, 20).unwrap();
     }
 
     #[test]
-    fn test_remove_item_failure() {
+    fn test_failed_order_restores_stock() {
         let mut mock_order_store = MockOrderStore::new();
-        let mut mock_table_store = MockTableStore::new();
-
-        let table_id = 1;
-        let item_id = 1;
-
-        mock_table_store
-            .expect_get_all_tables()
-            .returning(move || Ok(vec![table_id]));
-
         mock_order_store
-            .expect_remove_item()
-            .with(eq(table_id), eq(item_id))
-            .returning(move |_, _| Err(RestaurantError::MenuNotFound(item_id)));
-
+            .expect_add_line()
+            .returning(|_, _| Err(RestaurantError::LockError("poisoned".to_string())));
         let restaurant = SimpleRestaurant::new(
-            Box::new(MockMenuStore::new()),
+            Box::new(InMemoryMenuStore::with_predefined_recipes()),
             Box::new(mock_order_store),
-            Box::new(mock_table_store),
+            Box::new(InMemoryTableStore::new()),
         );
-
-        let result = restaurant.remove_item(table_id, item_id);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), RestaurantError::MenuNotFound(item_id));
+        restaurant.set_stock(20, Some(1), false).unwrap();
+        assert!(restaurant.add_item(1, 20).is_err());
+        assert_eq!(restaurant.get_stock().unwrap()[0].remaining, Some(1));
     }
 
     #[test]
-    fn test_get_items_success() {
-        let mut mock_menu_store = MockMenuStore::new();
+    fn test_panicking_store_does_not_block_later_orders() {
         let mut mock_order_store = MockOrderStore::new();
-        let mut mock_table_store = MockTableStore::new();
+        mock_order_store
+            .expect_add_line()
+            .with(eq(1), always())
+            .returning(|_, _| panic!("store bug"));
+        mock_order_store
+            .expect_add_line()
+            .with(eq(2), always())
+            .returning(|_, _| Ok(()));
+        let restaurant = SimpleRestaurant::new(
+            Box::new(InMemoryMenuStore::with_predefined_recipes()),
+            Box::new(mock_order_store),
+            Box::new(InMemoryTableStore::new()),
+        );
+        let panicked =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| restaurant.add_item(1, 20)));
+        assert!(panicked.is_err());
+        assert_eq!(restaurant.add_item(2, 20), Ok(()));
+    }
 
-        let table_id = 1;
-        let item_id = 1;
-        let menu_item = MenuItem {
-            id: item_id,
-            name: "Burger".to_string(),
-            cooking_time_minutes: 10,
+    #[test]
+    fn test_reservations_are_validated_and_flag_tables() {
+        let restaurant = paying_restaurant();
+        let now = unix_now();
+        let request = |table_id: u32, party_size: u32, starts_at: u64| ReservationRequest {
+            table_id,
+            party_size,
+            contact_name: "Ada".to_string(),
+            starts_at,
+            duration_minutes: 90,
         };
 
-        mock_table_store
-            .expect_get_all_tables()
-            .returning(move || Ok(vec![table_id]));
-
-        mock_order_store
-            .expect_get_item_ids()
-            .with(eq(table_id))
-            .returning(move |_| Ok(vec![item_id]));
+        assert_eq!(
+            restaurant.make_reservation(&request(101, 2, now)),
+            Err(RestaurantError::TableNotFound(101))
+        );
+        assert_eq!(
+            restaurant.make_reservation(&request(2, 0, now)),
+            Err(RestaurantError::InvalidPartySize(0))
+        );
+        assert_eq!(
+            restaurant.make_reservation(&request(2, 3, now)),
+            Err(RestaurantError::InvalidReservation(
+                "table 2 seats 2 guests, not 3".to_string()
+            ))
+        );
+        assert!(matches!(
+            restaurant.make_reservation(&request(2, 2, now - 5_400)),
+            Err(RestaurantError::InvalidReservation(_))
+        ));
+        assert!(matches!(
+            restaurant.make_reservation(&ReservationRequest {
+                contact_name: " ".to_string(),
+                ..request(2, 2, now)
+            }),
+            Err(RestaurantError::InvalidReservation(_))
+        ));
+        assert_eq!(
+            restaurant.make_reservation(&request(2, 2, u64::MAX - 60)),
+            Err(RestaurantError::InvalidReservation(
+                "the slot ends too far in the future".to_string()
+            ))
+        );
 
-        mock_menu_store
-            .expect_get_all_menus()
-            .returning(move || Ok(vec![menu_item.clone()]));
+        // A slot in progress flags the table, a later one does not
+        let current = restaurant
+            .make_reservation(&request(2, 2, now - 600))
+            .unwrap();
+        let later = restaurant
+            .make_reservation(&request(3, 2, now + 86_400))
+            .unwrap();
+        let statuses = restaurant.get_table_statuses().unwrap();
+        assert_eq!(statuses.len(), 100);
+        assert_eq!(statuses[1].reservation, Some(current.clone()));
+        assert!(!statuses[2].reserved);
 
-        let restaurant = SimpleRestaurant::new(
-            Box::new(mock_menu_store),
-            Box::new(mock_order_store),
-            Box::new(mock_table_store),
+        assert_eq!(
+            restaurant.update_reservation(later.reservation_id, &request(2, 2, now)),
+            Err(RestaurantError::ReservationConflict(
+                2,
+                current.reservation_id
+            ))
+        );
+        let tomorrow = (now / 86_400 + 1) * 86_400;
+        assert_eq!(
+            restaurant.get_reservations(Some(tomorrow)),
+            Ok(vec![later.clone()])
         );
+        assert_eq!(
+            restaurant.get_reservation(current.reservation_id),
+            Ok(current.clone())
+        );
+        assert_eq!(
+            restaurant.cancel_reservation(current.reservation_id),
+            Ok(current.clone())
+        );
+        assert!(!restaurant.get_table_statuses().unwrap()[1].reserved);
+        assert_eq!(restaurant.get_reservations(None), Ok(vec![later]));
 
-        let result = restaurant.get_items(table_id).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "Burger");
+        // An unknown reservation is reported before the new details are validated
+        assert_eq!(
+            restaurant.update_reservation(current.reservation_id, &request(1, 9, now)),
+            Err(RestaurantError::ReservationNotFound(current.reservation_id))
+        );
     }
 
     #[test]
@@ -332,7 +2132,7 @@ mod tests {
             .returning(move || Ok(vec![table_id]));
 
         mock_order_store
-            .expect_get_item_ids()
+            .expect_get_lines()
             .with(eq(table_id))
             .returning(move |_| Err(RestaurantError::TableNotFound(table_id)));
 
@@ -358,11 +2158,7 @@ mod tests {
 
         let table_id = 1;
         let item_id = 1;
-        let menu_item = MenuItem {
-            id: item_id,
-            name: "Burger".to_string(),
-            cooking_time_minutes: 10,
-        };
+        let menu_item = MenuItem::new(item_id, "Burger", 10, Money::from_cents(1250));
 
         mock_table_store
             .expect_get_all_tables()
@@ -372,6 +2168,10 @@ mod tests {
             .expect_get_item_id()
             .with(eq(table_id), eq(item_id))
             .returning(move |_, _| Ok(item_id));
+        mock_order_store
+            .expect_get_lines()
+            .with(eq(table_id))
+            .returning(move |_| Ok(vec![OrderLine::plain(item_id)]));
 
         mock_menu_store
             .expect_get_all_menus()
@@ -414,4 +2214,63 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), RestaurantError::MenuNotFound(item_id));
     }
+
+    #[test]
+    fn test_shutdown_flushes_all_stores() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        mock_menu_store
+            .expect_shutdown()
+            .times(1)
+            .returning(|| Ok(()));
+        mock_order_store
+            .expect_shutdown()
+            .times(1)
+            .returning(|| Ok(()));
+        mock_table_store
+            .expect_shutdown()
+            .times(1)
+            .returning(|| Ok(()));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        );
+
+        assert!(restaurant.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_shutdown_failure_still_flushes_remaining_stores() {
+        let mut mock_menu_store = MockMenuStore::new();
+        let mut mock_order_store = MockOrderStore::new();
+        let mut mock_table_store = MockTableStore::new();
+
+        mock_menu_store
+            .expect_shutdown()
+            .times(1)
+            .returning(|| Ok(()));
+        mock_order_store
+            .expect_shutdown()
+            .times(1)
+            .returning(|| Err(RestaurantError::LockError("poisoned".to_string())));
+        mock_table_store
+            .expect_shutdown()
+            .times(1)
+            .returning(|| Ok(()));
+
+        let restaurant = SimpleRestaurant::new(
+            Box::new(mock_menu_store),
+            Box::new(mock_order_store),
+            Box::new(mock_table_store),
+        );
+
+        assert_eq!(
+            restaurant.shutdown().unwrap_err(),
+            RestaurantError::LockError("poisoned".to_string())
+        );
+    }
 }
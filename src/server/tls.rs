@@ -0,0 +1,167 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use crate::server::config::TlsConfig;
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Builds a rustls server configuration from the certificate and key files in `TlsConfig`.
+///
+/// # Arguments
+/// * `tls` - The TLS settings containing the certificate and private key paths.
+///
+/// # Returns
+/// * `Ok(rustls::ServerConfig)` - If the certificate chain and private key were loaded successfully.
+/// * `Err(io::Error)` - If a file could not be read, contains no usable PEM entries, or the key does not match the certificate.
+pub fn load_rustls_config(tls: &TlsConfig) -> io::Result<rustls::ServerConfig> {
+    let cert_chain = load_certificates(&tls.cert_path)?;
+    let private_key = load_private_key(&tls.key_path)?;
+
+    rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, private_key)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Reads all PEM encoded certificates from the given file.
+fn load_certificates(path: &Path) -> io::Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let certs = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("No certificates found in {}", path.display()),
+        ));
+    }
+    Ok(certs)
+}
+
+/// Reads the first PEM encoded private key (PKCS#1, PKCS#8 or SEC1) from the given file.
+fn load_private_key(path: &Path) -> io::Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("No private key found in {}", path.display()),
+        )
+    })
+}
+
+/// Redirects a plain HTTP request to the same path on the HTTPS port.
+///
+/// A `308 Permanent Redirect` is used so that clients repeat `POST` and `DELETE`
+/// requests with the same method against the HTTPS endpoint.
+///
+/// # Arguments
+/// * `req` - The incoming plain HTTP request.
+/// * `https_port` - The port the HTTPS server is listening on.
+///
+/// # Returns
+/// A `HttpResponse` with status 308 and a `Location` header pointing at the HTTPS URL.
+pub fn redirect_to_https(req: &HttpRequest, https_port: u16) -> HttpResponse {
+    let connection_info = req.connection_info();
+    let host = strip_port(connection_info.host());
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+
+    HttpResponse::PermanentRedirect()
+        .insert_header((
+            header::LOCATION,
+            format!("https://{}:{}{}", host, https_port, path),
+        ))
+        .finish()
+}
+
+/// Removes an optional `:port` suffix from a host header value, keeping IPv6 literals intact.
+fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        return match host.find(']') {
+            Some(end) => &host[..=end],
+            None => host,
+        };
+    }
+    match host.rsplit_once(':') {
+        Some((name, _)) => name,
+        None => host,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    #[test]
+    fn test_load_rustls_config_success() {
+        let tls = TlsConfig {
+            cert_path: fixture("cert.pem"),
+            key_path: fixture("key.pem"),
+            redirect_http_port: None,
+        };
+        assert!(load_rustls_config(&tls).is_ok());
+    }
+
+    #[test]
+    fn test_load_rustls_config_missing_cert() {
+        let tls = TlsConfig {
+            cert_path: fixture("missing.pem"),
+            key_path: fixture("key.pem"),
+            redirect_http_port: None,
+        };
+        let err = load_rustls_config(&tls).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_load_rustls_config_no_key_in_file() {
+        // The certificate file contains no private key entries.
+        let tls = TlsConfig {
+            cert_path: fixture("cert.pem"),
+            key_path: fixture("cert.pem"),
+            redirect_http_port: None,
+        };
+        let err = load_rustls_config(&tls).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_redirect_to_https() {
+        let req = TestRequest::post()
+            .uri("/api/v1/add_item/1/2?x=1")
+            .insert_header((header::HOST, "localhost:8080"))
+            .to_http_request();
+        let resp = redirect_to_https(&req, 8443);
+
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::PERMANENT_REDIRECT
+        );
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "https://localhost:8443/api/v1/add_item/1/2?x=1"
+        );
+    }
+
+    #[test]
+    fn test_strip_port() {
+        assert_eq!(strip_port("localhost:8080"), "localhost");
+        assert_eq!(strip_port("localhost"), "localhost");
+        assert_eq!(strip_port("[::1]:8080"), "[::1]");
+    }
+}
@@ -1,6 +1,9 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+use crate::server::data_model::dietary::{self, Allergen};
+use crate::server::data_model::money::Money;
+use crate::server::data_model::session::MAX_PARTY_SIZE;
 use std::fmt;
 
 /// Enum representing errors that can occur in the Restaurant system.
@@ -17,6 +20,26 @@ use std::fmt;
 /// - `NoMenusForTable(u32)`: Represents an error when no menu items are found for a given table.
 /// - `MenusRetrieveError`: Represents an error that occurs when retrieving menus from the store.
 /// - `TablesRetrieveError`: Represents an error that occurs when retrieving tables from the store.
+/// - `InvalidSplit(String)`: Represents a request to split a bill that does not match the table's order.
+/// - `NoSplitForTable(u32)`: Represents an error when the bill of a table has not been split.
+/// - `SplitOutdated(u32)`: Represents an error when the order of a table changed after its bill was split.
+/// - `InvalidPayment(String)`: Represents a payment request that cannot be applied to the bill of a table.
+/// - `Overpayment(u32, Money, Money)`: Represents a payment of more than the balance of a table or check.
+/// - `PaymentDeclined(String)`: Represents a payment that was declined by the payment provider.
+/// - `NoActiveSession(u32)`: Represents an error when a table has no active session.
+/// - `SessionAlreadyOpen(u32)`: Represents an error when a table is opened while a party is still seated.
+/// - `InvalidPartySize(u32)`: Represents a party size that cannot be seated.
+/// - `OutstandingBalance(u32, Money)`: Represents an error when a table is closed before its bill is paid.
+/// - `CategoryNotFound(u32)`: Represents an error when a menu category with a given ID is not found.
+/// - `InvalidModifiers(u32, String)`: Represents modifiers, a note or a seat that do not match the ordered menu item.
+/// - `InvalidMenuFilter(String)`: Represents an unknown allergen or diet in a filter of the menu.
+/// - `AllergenConflict(u32, u32, Vec<Allergen>)`: Represents an order of a menu item containing allergies declared for the table.
+/// - `ItemUnavailable(u32)`: Represents an order of a menu item that is sold out or not served.
+/// - `IngredientNotFound(u32)`: Represents an error when an ingredient with a given ID is not found.
+/// - `InvalidReservation(String)`: Represents a reservation that cannot be made, e.g. for more guests than the table seats.
+/// - `ReservationConflict(u32, u64)`: Represents a reservation overlapping another reservation of the same table.
+/// - `ReservationNotFound(u64)`: Represents an error when a reservation with a given ID is not found.
+/// - `OrderLineNotFound(u32, u32)`: Represents an error when a line of a table's order is not found.
 #[derive(Debug, PartialEq)]
 pub enum RestaurantError {
     /// Represents an error when a lock could not be acquired.
@@ -50,6 +73,119 @@ pub enum RestaurantError {
 
     /// Represents an error that occurs when trying to retrieve tables from the store.
     TablesRetrieveError,
+
+    /// Represents a split request that cannot be applied to the bill of a table.
+    ///
+    /// The string describes why the split is invalid.
+    InvalidSplit(String),
+
+    /// Represents an error when the bill of the specified table has not been split.
+    ///
+    /// - `table_id`: The ID of the table.
+    NoSplitForTable(u32),
+
+    /// Represents an error when the order of a table changed after its bill was split,
+    /// so that the checks no longer add up to the bill.
+    ///
+    /// - `table_id`: The ID of the table.
+    SplitOutdated(u32),
+
+    /// Represents a payment request that cannot be applied to the bill of a table,
+    /// e.g. a payment of zero or a card payment without a card.
+    ///
+    /// The string describes why the payment is invalid.
+    InvalidPayment(String),
+
+    /// Represents a payment of more than is left to pay.
+    ///
+    /// - `table_id`: The ID of the table.
+    /// - `amount`: The amount of the payment.
+    /// - `balance`: The balance of the table, or of the paid check.
+    Overpayment(u32, Money, Money),
+
+    /// Represents a payment that was declined by the payment provider.
+    ///
+    /// The string provides the reason given by the provider.
+    PaymentDeclined(String),
+
+    /// Represents an error when the specified table has no active session.
+    ///
+    /// - `table_id`: The ID of the table.
+    NoActiveSession(u32),
+
+    /// Represents an error when a table is opened while its previous party is still seated.
+    ///
+    /// - `table_id`: The ID of the table.
+    SessionAlreadyOpen(u32),
+
+    /// Represents a party size of zero or larger than the restaurant can seat at a table.
+    ///
+    /// - `party_size`: The requested party size.
+    InvalidPartySize(u32),
+
+    /// Represents an error when a table is closed before its bill is paid in full.
+    ///
+    /// - `table_id`: The ID of the table.
+    /// - `balance`: The amount left to pay.
+    OutstandingBalance(u32, Money),
+
+    /// Represents an error when a menu category with the specified `category_id` is not found.
+    ///
+    /// - `category_id`: The ID of the category that was not found.
+    CategoryNotFound(u32),
+
+    /// Represents modifiers, a kitchen note or a seat that cannot be ordered with a menu item.
+    ///
+    /// - `menu_id`: The ID of the ordered menu item.
+    /// - `reason`: Why the options were rejected.
+    InvalidModifiers(u32, String),
+
+    /// Represents a filter of the menu with an unknown allergen or diet.
+    ///
+    /// The string describes the unknown value.
+    InvalidMenuFilter(String),
+
+    /// Represents an order of a menu item that contains allergies declared for the table,
+    /// without an allergen override.
+    ///
+    /// - `table_id`: The ID of the table.
+    /// - `menu_id`: The ID of the ordered menu item.
+    /// - `allergens`: The declared allergies the menu item contains.
+    AllergenConflict(u32, u32, Vec<Allergen>),
+
+    /// Represents an order of a menu item that is marked sold out, has no portions left, or is
+    /// listed only in categories that are not served.
+    ///
+    /// The `u32` is the ID of the menu item.
+    ItemUnavailable(u32),
+
+    /// Represents an error when an ingredient with the specified `ingredient_id` is not found.
+    ///
+    /// The `u32` is the ID of the ingredient.
+    IngredientNotFound(u32),
+
+    /// Represents a reservation that cannot be made, e.g. for more guests than the table
+    /// seats or for a slot that is already over.
+    ///
+    /// The string describes why the reservation is invalid.
+    InvalidReservation(String),
+
+    /// Represents a reservation whose slot overlaps another reservation of the same table.
+    ///
+    /// - `table_id`: The ID of the table.
+    /// - `reservation_id`: The ID of the overlapping reservation.
+    ReservationConflict(u32, u64),
+
+    /// Represents an error when a reservation with the specified `reservation_id` is not found.
+    ///
+    /// The `u64` is the ID of the reservation.
+    ReservationNotFound(u64),
+
+    /// Represents an error when a line of a table's order is not found.
+    ///
+    /// - `table_id`: The ID of the table.
+    /// - `line_id`: The number of the order line that was not found.
+    OrderLineNotFound(u32, u32),
 }
 
 impl fmt::Display for RestaurantError {
@@ -78,6 +214,86 @@ impl fmt::Display for RestaurantError {
                 "No Menu items added for table with table id:{}",
                 table_id
             ),
+            RestaurantError::InvalidSplit(reason) => write!(f, "Invalid split: {}", reason),
+            RestaurantError::NoSplitForTable(table_id) => {
+                write!(
+                    f,
+                    "The bill of table with table id:{} is not split",
+                    table_id
+                )
+            }
+            RestaurantError::SplitOutdated(table_id) => write!(
+                f,
+                "The order of table with table id:{} changed after its bill was split",
+                table_id
+            ),
+            RestaurantError::InvalidPayment(reason) => write!(f, "Invalid payment: {}", reason),
+            RestaurantError::Overpayment(table_id, amount, balance) => write!(
+                f,
+                "Payment of {} exceeds the balance of {} for table with table id:{}",
+                amount, balance, table_id
+            ),
+            RestaurantError::PaymentDeclined(reason) => {
+                write!(f, "Payment declined: {}", reason)
+            }
+            RestaurantError::NoActiveSession(table_id) => {
+                write!(f, "Table with table id:{} is not open", table_id)
+            }
+            RestaurantError::SessionAlreadyOpen(table_id) => {
+                write!(f, "Table with table id:{} is already open", table_id)
+            }
+            RestaurantError::InvalidPartySize(party_size) => write!(
+                f,
+                "Invalid party size {}. Expected 1 to {} guests",
+                party_size, MAX_PARTY_SIZE
+            ),
+            RestaurantError::OutstandingBalance(table_id, balance) => write!(
+                f,
+                "Table with table id:{} cannot be closed with a balance of {}",
+                table_id, balance
+            ),
+            RestaurantError::CategoryNotFound(category_id) => {
+                write!(f, "Category with category id:{} not found", category_id)
+            }
+            RestaurantError::InvalidModifiers(menu_id, reason) => write!(
+                f,
+                "Invalid modifiers for menu item with menu id:{}: {}",
+                menu_id, reason
+            ),
+            RestaurantError::InvalidMenuFilter(reason) => {
+                write!(f, "Invalid menu filter: {}", reason)
+            }
+            RestaurantError::AllergenConflict(table_id, menu_id, allergens) => write!(
+                f,
+                "Menu item with menu id:{} contains {}, declared as allergies of table with table id:{}. Set allergen_override to order it anyway",
+                menu_id,
+                dietary::join(allergens),
+                table_id
+            ),
+            RestaurantError::ItemUnavailable(menu_id) => {
+                write!(f, "Menu item with menu id:{} is not available", menu_id)
+            }
+            RestaurantError::IngredientNotFound(ingredient_id) => {
+                write!(f, "Ingredient with ingredient id:{} not found", ingredient_id)
+            }
+            RestaurantError::InvalidReservation(reason) => {
+                write!(f, "Invalid reservation: {}", reason)
+            }
+            RestaurantError::ReservationConflict(table_id, reservation_id) => write!(
+                f,
+                "Table with table id:{} is already reserved at that time by reservation id:{}",
+                table_id, reservation_id
+            ),
+            RestaurantError::ReservationNotFound(reservation_id) => write!(
+                f,
+                "Reservation with reservation id:{} not found",
+                reservation_id
+            ),
+            RestaurantError::OrderLineNotFound(table_id, line_id) => write!(
+                f,
+                "Order line {} not found for table with table id:{}",
+                line_id, table_id
+            ),
         }
     }
 }
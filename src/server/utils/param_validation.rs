@@ -1,6 +1,8 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+use std::str::FromStr;
+
 /// Validates that the given parameter string is a valid positive integer.
 ///
 /// # Arguments
@@ -11,7 +13,25 @@
 /// * `Ok(u32)` - If the parameter is successfully parsed as a positive integer.
 /// * `Err(String)` - If the parameter cannot be parsed as a valid positive integer, with an error message specifying the parameter name.
 pub fn parse_path_param(param: &str, param_name: &str) -> Result<u32, String> {
-    match param.parse::<u32>() {
+    parse_integer(param, param_name)
+}
+
+/// Validates that the given parameter string is a valid positive 64-bit integer, such as a
+/// reservation ID.
+///
+/// # Arguments
+/// * `param` - The string representation of the parameter to be validated.
+/// * `param_name` - The name of the parameter (used for error messages).
+///
+/// # Returns
+/// * `Ok(u64)` - If the parameter is successfully parsed as a positive integer.
+/// * `Err(String)` - If the parameter cannot be parsed as a valid positive integer, with an error message specifying the parameter name.
+pub fn parse_u64_path_param(param: &str, param_name: &str) -> Result<u64, String> {
+    parse_integer(param, param_name)
+}
+
+fn parse_integer<T: FromStr>(param: &str, param_name: &str) -> Result<T, String> {
+    match param.parse::<T>() {
         Ok(id) => Ok(id),
         Err(_) => Err(format!(
             "Invalid {}. Must be a valid positive integer.",
@@ -56,4 +76,16 @@ mod tests {
             Err("Invalid table_id. Must be a valid positive integer.".to_string())
         );
     }
+
+    #[test]
+    fn test_parse_u64_path_param_beyond_u32() {
+        assert_eq!(
+            parse_u64_path_param("4294967296", "reservation ID"),
+            Ok(4_294_967_296)
+        );
+        assert_eq!(
+            parse_u64_path_param("-1", "reservation ID"),
+            Err("Invalid reservation ID. Must be a valid positive integer.".to_string())
+        );
+    }
 }
@@ -81,12 +81,34 @@ pub fn restaurant_error_to_response(err: RestaurantError) -> HttpResponse {
             404,
             &format!("No Menu items added for table with table id:{}", table_id),
         ),
+        err @ RestaurantError::InvalidSplit(_) => error_response(400, &err.to_string()),
+        err @ RestaurantError::NoSplitForTable(_) => error_response(404, &err.to_string()),
+        err @ RestaurantError::SplitOutdated(_) => error_response(409, &err.to_string()),
+        err @ RestaurantError::InvalidPayment(_) => error_response(400, &err.to_string()),
+        err @ RestaurantError::Overpayment(..) => error_response(409, &err.to_string()),
+        err @ RestaurantError::PaymentDeclined(_) => error_response(402, &err.to_string()),
+        err @ RestaurantError::NoActiveSession(_) => error_response(404, &err.to_string()),
+        err @ RestaurantError::SessionAlreadyOpen(_) => error_response(409, &err.to_string()),
+        err @ RestaurantError::InvalidPartySize(_) => error_response(400, &err.to_string()),
+        err @ RestaurantError::OutstandingBalance(..) => error_response(409, &err.to_string()),
+        err @ RestaurantError::CategoryNotFound(_) => error_response(404, &err.to_string()),
+        err @ RestaurantError::InvalidModifiers(..) => error_response(400, &err.to_string()),
+        err @ RestaurantError::InvalidMenuFilter(_) => error_response(400, &err.to_string()),
+        err @ RestaurantError::AllergenConflict(..) => error_response(409, &err.to_string()),
+        err @ RestaurantError::ItemUnavailable(_) => error_response(409, &err.to_string()),
+        err @ RestaurantError::IngredientNotFound(_) => error_response(404, &err.to_string()),
+        err @ RestaurantError::InvalidReservation(_) => error_response(400, &err.to_string()),
+        err @ RestaurantError::ReservationConflict(..) => error_response(409, &err.to_string()),
+        err @ RestaurantError::ReservationNotFound(_) => error_response(404, &err.to_string()),
+        err @ RestaurantError::OrderLineNotFound(..) => error_response(404, &err.to_string()),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::server::data_model::dietary::Allergen;
+    use crate::server::data_model::money::Money;
     use actix_web::test;
     use serde_json::json;
 
@@ -203,4 +225,68 @@ mod tests {
             "No Menu items added for table with table id:1"
         );
     }
+
+    #[actix_rt::test]
+    async fn test_restaurant_error_to_response_split_payment_and_session_errors() {
+        let cases = [
+            (
+                RestaurantError::InvalidSplit("item 5 is not on the bill".to_string()),
+                400,
+            ),
+            (RestaurantError::NoSplitForTable(1), 404),
+            (RestaurantError::SplitOutdated(1), 409),
+            (
+                RestaurantError::InvalidPayment("nothing to pay".to_string()),
+                400,
+            ),
+            (
+                RestaurantError::Overpayment(1, Money::from_cents(2000), Money::from_cents(1250)),
+                409,
+            ),
+            (
+                RestaurantError::PaymentDeclined("card declined".to_string()),
+                402,
+            ),
+            (RestaurantError::NoActiveSession(7), 404),
+            (RestaurantError::SessionAlreadyOpen(7), 409),
+            (RestaurantError::InvalidPartySize(0), 400),
+            (
+                RestaurantError::OutstandingBalance(7, Money::from_cents(1250)),
+                409,
+            ),
+            (RestaurantError::CategoryNotFound(4), 404),
+            (
+                RestaurantError::InvalidModifiers(6, "modifier 9 does not exist".to_string()),
+                400,
+            ),
+            (
+                RestaurantError::InvalidMenuFilter("Unknown diet 'kosher'".to_string()),
+                400,
+            ),
+            (
+                RestaurantError::AllergenConflict(3, 14, vec![Allergen::Nuts]),
+                409,
+            ),
+            (RestaurantError::ItemUnavailable(20), 409),
+            (RestaurantError::IngredientNotFound(17), 404),
+            (
+                RestaurantError::InvalidReservation("table 5 seats 2 guests".to_string()),
+                400,
+            ),
+            (RestaurantError::ReservationConflict(5, 3), 409),
+            (RestaurantError::ReservationNotFound(3), 404),
+            (RestaurantError::OrderLineNotFound(1, 4), 404),
+        ];
+        for (err, status) in cases {
+            let message = err.to_string();
+            let resp = restaurant_error_to_response(err);
+            assert_eq!(resp.status().as_u16(), status);
+
+            let service_resp = test::TestRequest::default().to_srv_response(resp);
+            let body = test::read_body(service_resp).await;
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["status"], "error");
+            assert_eq!(body["message"], message);
+        }
+    }
 }
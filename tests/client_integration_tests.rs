@@ -0,0 +1,580 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use actix_web::{web, App, HttpResponse, HttpServer};
+use restaurant_menu_app::client::error::ClientError;
+use restaurant_menu_app::client::load_test::{run_load_test, Endpoint, LoadTest, Mix};
+use restaurant_menu_app::client::output::format_timestamp;
+use restaurant_menu_app::client::restaurant_client::RestaurantClient;
+use restaurant_menu_app::client::session::{
+    load_session, replay_session, ReplayOptions, SessionRecorder,
+};
+use restaurant_menu_app::client::simulation::{run_simulation, Scenario};
+use restaurant_menu_app::client::tui::fetch_floor;
+use restaurant_menu_app::server::api::v1::handlers::AppState;
+use restaurant_menu_app::server::api::v1::routes::configure_routes;
+use restaurant_menu_app::server::data_model::dietary::{Allergen, Diet, MenuFilter};
+use restaurant_menu_app::server::data_model::models::{MenuItem, Restaurant};
+use restaurant_menu_app::server::data_model::modifier::ItemOptions;
+use restaurant_menu_app::server::data_model::money::Money;
+use restaurant_menu_app::server::data_model::payment::{PaymentMethod, PaymentRequest};
+use restaurant_menu_app::server::data_model::reservation::ReservationRequest;
+use restaurant_menu_app::server::data_model::session::unix_now;
+use restaurant_menu_app::server::data_model::split::SplitRequest;
+use restaurant_menu_app::server::data_store::in_memory_menu_store::InMemoryMenuStore;
+use restaurant_menu_app::server::data_store::in_memory_order_store::InMemoryOrderStore;
+use restaurant_menu_app::server::data_store::in_memory_table_store::InMemoryTableStore;
+use restaurant_menu_app::server::restaurant::SimpleRestaurant;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Starts the API with a single menu item on a random local port and returns its base URL.
+fn start_server() -> String {
+    let restaurant = Arc::new(SimpleRestaurant::new(
+        Box::new(InMemoryMenuStore::new(vec![MenuItem::new(
+            1,
+            "Burger",
+            10,
+            Money::from_cents(1250),
+        )])),
+        Box::new(InMemoryOrderStore::new()),
+        Box::new(InMemoryTableStore::new()),
+    )) as Arc<dyn Restaurant + Send + Sync>;
+    let app_state = AppState { restaurant };
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(app_state.clone()))
+            .configure(configure_routes)
+    })
+    .workers(1)
+    .bind("127.0.0.1:0")
+    .unwrap();
+    let base_url = format!("http://{}", server.addrs()[0]);
+    actix_rt::spawn(server.run());
+    base_url
+}
+
+/// Starts a server whose endpoints fail with `500` for the first `failures` requests.
+fn start_flaky_server(failures: usize) -> (String, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let server_calls = Arc::clone(&calls);
+
+    let server = HttpServer::new(move || {
+        let calls = Arc::clone(&server_calls);
+        App::new().default_service(web::to(move || {
+            let calls = Arc::clone(&calls);
+            async move {
+                if calls.fetch_add(1, Ordering::SeqCst) < failures {
+                    HttpResponse::InternalServerError().json(
+                        serde_json::json!({"status": "error", "message": "Store unavailable"}),
+                    )
+                } else {
+                    HttpResponse::Ok().json(serde_json::json!({"status": "ok", "data": [1, 2]}))
+                }
+            }
+        }))
+    })
+    .workers(1)
+    .bind("127.0.0.1:0")
+    .unwrap();
+    let base_url = format!("http://{}", server.addrs()[0]);
+    actix_rt::spawn(server.run());
+    (base_url, calls)
+}
+
+#[actix_rt::test]
+async fn test_get_menus_and_tables() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    let menus = client.get_menus().await.unwrap();
+    assert_eq!(menus.len(), 1);
+    assert_eq!(menus[0].item.name, "Burger");
+
+    let tables = client.get_tables().await.unwrap();
+    assert_eq!(tables.len(), 100);
+    assert_eq!(tables[99], 100);
+
+    let tables = client.get_table_statuses().await.unwrap();
+    assert_eq!(tables.len(), 100);
+    assert_eq!(tables[99].capacity, 10);
+    assert!(tables.iter().all(|table| !table.reserved));
+}
+
+#[actix_rt::test]
+async fn test_get_categorized_menu() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    // The menu of the test server has no categories
+    let menu = client.get_categorized_menu().await.unwrap();
+    assert!(menu.sections.is_empty());
+    assert_eq!(menu.uncategorized.len(), 1);
+    assert!(client.get_categories().await.unwrap().is_empty());
+    let err = client
+        .set_category_availability(1, false)
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_add_get_and_remove_item() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    let message = client.add_item(3, 1).await.unwrap();
+    assert!(!message.is_empty());
+
+    let items = client.get_items(3).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].id, 1);
+
+    let item = client.get_item(3, 1).await.unwrap();
+    assert_eq!(item.cooking_time_minutes, 10);
+
+    client.remove_item(3, 1).await.unwrap();
+    let err = client.get_item(3, 1).await.unwrap_err();
+    assert!(err.is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_add_item_with_options() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    let options = ItemOptions {
+        note: Some("Well done".to_string()),
+        ..ItemOptions::default()
+    };
+    client.add_item_with_options(4, 1, &options).await.unwrap();
+    let item = client.get_item(4, 1).await.unwrap();
+    assert_eq!(item.note.as_deref(), Some("Well done"));
+
+    // The burger of the test server has no modifiers
+    let options = ItemOptions {
+        modifiers: vec![1],
+        ..ItemOptions::default()
+    };
+    let err = client
+        .add_item_with_options(4, 1, &options)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(400));
+    assert_eq!(client.get_items(4).await.unwrap().len(), 1);
+}
+
+#[actix_rt::test]
+async fn test_filtered_menus_and_allergies() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    // The burger of the test server is not tagged with allergens or diets
+    let filter = MenuFilter {
+        exclude_allergens: vec![Allergen::Nuts],
+        diets: Vec::new(),
+    };
+    assert_eq!(client.get_filtered_menus(&filter).await.unwrap().len(), 1);
+    let filter = MenuFilter {
+        exclude_allergens: Vec::new(),
+        diets: vec![Diet::Vegan],
+    };
+    assert!(client.get_filtered_menus(&filter).await.unwrap().is_empty());
+    let menu = client.get_filtered_categorized_menu(&filter).await.unwrap();
+    assert_eq!(menu.item_count(), 0);
+
+    let session = client
+        .declare_allergies(6, &[Allergen::Sesame])
+        .await
+        .unwrap();
+    assert_eq!(session.allergies, vec![Allergen::Sesame]);
+    assert!(client
+        .declare_allergies(999, &[])
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_stock_and_sold_out_items() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    let level = client.set_stock(1, Some(1), false).await.unwrap();
+    assert_eq!(level.remaining, Some(1));
+    client.add_item(3, 1).await.unwrap();
+    let err = client.add_item(4, 1).await.unwrap_err();
+    assert_eq!(err.status(), Some(409));
+    let menus = client.get_menus().await.unwrap();
+    assert!(menus[0].sold_out);
+    assert_eq!(menus[0].remaining, Some(0));
+
+    client.remove_item(3, 1).await.unwrap();
+    assert_eq!(client.get_stock().await.unwrap()[0].remaining, Some(1));
+    client.set_stock(1, None, false).await.unwrap();
+    assert!(client.get_stock().await.unwrap().is_empty());
+    assert!(client
+        .set_stock(99, None, true)
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_ingredients_without_inventory() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    assert!(client.get_ingredients().await.unwrap().is_empty());
+    let report = client.get_low_stock_report().await.unwrap();
+    assert!(report.ingredients.is_empty());
+    assert!(report.unavailable_item_ids.is_empty());
+    assert!(client.restock(1, 5).await.unwrap_err().is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_make_change_and_cancel_reservations() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+    let now = unix_now();
+    let request = |table_id: u32, party_size: u32, starts_at: u64| ReservationRequest {
+        table_id,
+        party_size,
+        contact_name: "Ada".to_string(),
+        starts_at,
+        duration_minutes: 90,
+    };
+
+    let current = client
+        .make_reservation(&request(41, 4, now - 600))
+        .await
+        .unwrap();
+    let tables = client.get_table_statuses().await.unwrap();
+    assert!(tables[40].reserved);
+    assert_eq!(tables[40].reservation, Some(current.clone()));
+
+    let err = client
+        .make_reservation(&request(41, 2, now))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(409));
+    let err = client
+        .make_reservation(&request(1, 3, now))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(400));
+
+    // Moving the party to tomorrow frees the table now
+    let tomorrow = now + 86_400;
+    let moved = client
+        .update_reservation(current.reservation_id, &request(41, 4, tomorrow))
+        .await
+        .unwrap();
+    assert_eq!(moved.reservation_id, current.reservation_id);
+    assert!(!client.get_table_statuses().await.unwrap()[40].reserved);
+    let date = format_timestamp(tomorrow)[..10].to_string();
+    assert_eq!(
+        client.get_reservations(Some(&date)).await.unwrap(),
+        vec![moved.clone()]
+    );
+
+    assert_eq!(
+        client
+            .cancel_reservation(moved.reservation_id)
+            .await
+            .unwrap(),
+        moved
+    );
+    assert!(client.get_reservations(None).await.unwrap().is_empty());
+    assert!(client
+        .cancel_reservation(moved.reservation_id)
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_get_bill() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    client.add_item(5, 1).await.unwrap();
+    client.add_item(5, 1).await.unwrap();
+    let bill = client.get_bill(5).await.unwrap();
+    assert_eq!(bill.table_id, 5);
+    assert_eq!(bill.lines.len(), 1);
+    assert_eq!(bill.lines[0].quantity, 2);
+    assert_eq!(bill.subtotal, Money::from_cents(2500));
+    assert_eq!(bill.total, Money::from_cents(2500));
+
+    let err = client.get_bill(101).await.unwrap_err();
+    assert!(err.is_not_found());
+}
+
+#[actix_rt::test]
+async fn test_split_bill_evenly() {
+    let path = std::env::temp_dir().join(format!("split_{}.jsonl", std::process::id()));
+    let client = RestaurantClient::builder(&start_server())
+        .recorder(SessionRecorder::create(&path).unwrap())
+        .build()
+        .unwrap();
+
+    client.add_item(6, 1).await.unwrap();
+    client.add_item(6, 1).await.unwrap();
+    let split = client
+        .split_bill(6, &SplitRequest::Even { payers: 3 })
+        .await
+        .unwrap();
+    let totals: Vec<Money> = split.checks.iter().map(|check| check.total).collect();
+    assert_eq!(
+        totals,
+        [834, 833, 833].map(Money::from_cents).to_vec(),
+        "the checks add up to the bill's 25.00"
+    );
+    assert_eq!(client.get_checks(6).await.unwrap(), split);
+
+    let err = client
+        .split_bill(6, &SplitRequest::Even { payers: 0 })
+        .await
+        .unwrap_err();
+    assert!(err.is_client_error());
+
+    // The request body is recorded, so the split can be replayed
+    let calls = load_session(&path).unwrap();
+    assert!(calls[2].request_body.is_some());
+    let replay_client = RestaurantClient::new(&start_server()).unwrap();
+    let report = replay_session(&replay_client, &calls, &ReplayOptions::default()).await;
+    assert!(report.is_consistent());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_pay_split_checks() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    client.add_item(7, 1).await.unwrap();
+    client.add_item(7, 1).await.unwrap();
+    let split = client
+        .split_bill(7, &SplitRequest::Even { payers: 2 })
+        .await
+        .unwrap();
+
+    let card = |check_id: u32, card_number: &str| PaymentRequest {
+        method: PaymentMethod::Card,
+        amount: split.checks[check_id as usize - 1].total,
+        tip: Money::from_cents(100),
+        check_id: Some(check_id),
+        card_number: Some(card_number.to_string()),
+    };
+    let err = client
+        .pay(7, &card(1, "4000000000009995"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClientError::Api { status: 402, .. }));
+
+    let receipt = client.pay(7, &card(1, "4242424242424242")).await.unwrap();
+    assert_eq!(receipt.account.balance, split.checks[1].total);
+    let err = client
+        .pay(7, &card(1, "4242424242424242"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClientError::Api { status: 409, .. }));
+
+    let receipt = client.pay(7, &card(2, "5555555555554444")).await.unwrap();
+    assert!(receipt.account.settled);
+    assert_eq!(receipt.account.tips, Money::from_cents(200));
+    assert!(client.get_items(7).await.unwrap().is_empty());
+    assert_eq!(client.get_payments(7).await.unwrap().total, Money::ZERO);
+}
+
+#[actix_rt::test]
+async fn test_open_and_close_table() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    let opened = client.open_table(5, 2).await.unwrap();
+    assert_eq!(opened.party_size, Some(2));
+    assert_eq!(client.get_session(5).await.unwrap(), opened);
+    let err = client.open_table(5, 2).await.unwrap_err();
+    assert!(matches!(err, ClientError::Api { status: 409, .. }));
+
+    let closed = client.close_table(5).await.unwrap();
+    assert_eq!(closed.session_id, opened.session_id);
+    assert_eq!(closed.bill.unwrap().total, Money::ZERO);
+    assert!(client.get_session(5).await.unwrap_err().is_not_found());
+
+    let history = client.get_sessions(Some(5)).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(client.get_sessions(None).await.unwrap().len(), 1);
+    assert!(client.get_sessions(Some(6)).await.unwrap().is_empty());
+}
+
+#[actix_rt::test]
+async fn test_error_response_is_mapped() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+
+    let err = client.add_item(3, 99).await.unwrap_err();
+    match err {
+        ClientError::Api { status, message } => {
+            assert_eq!(status, 404);
+            assert!(message.contains("99"));
+        }
+        other => panic!("Unexpected error: {}", other),
+    }
+
+    let err = client.get_items(101).await.unwrap_err();
+    assert!(err.is_not_found());
+    assert!(err.is_client_error());
+}
+
+#[actix_rt::test]
+async fn test_get_is_retried_on_server_error() {
+    let (base_url, calls) = start_flaky_server(2);
+    let client = RestaurantClient::builder(&base_url)
+        .retries(2)
+        .retry_backoff(Duration::from_millis(1))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.get_tables().await.unwrap(), vec![1, 2]);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[actix_rt::test]
+async fn test_post_is_not_retried() {
+    let (base_url, calls) = start_flaky_server(1);
+    let client = RestaurantClient::builder(&base_url)
+        .retries(2)
+        .retry_backoff(Duration::from_millis(1))
+        .build()
+        .unwrap();
+
+    let err = client.add_item(1, 1).await.unwrap_err();
+    assert!(err.is_server_error());
+    assert_eq!(err.to_string(), "Error (500): Store unavailable");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[actix_rt::test]
+async fn test_unreachable_server() {
+    // Bind and drop a listener to get a port nothing listens on
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let client = RestaurantClient::builder(&format!("http://127.0.0.1:{}", port))
+        .retries(1)
+        .retry_backoff(Duration::from_millis(1))
+        .timeout(Duration::from_secs(2))
+        .build()
+        .unwrap();
+
+    let err = client.get_menus().await.unwrap_err();
+    assert!(matches!(err, ClientError::Request(_)));
+    assert_eq!(err.status(), None);
+}
+
+#[test]
+fn test_missing_ca_certificate() {
+    let result = RestaurantClient::builder("https://localhost:8443")
+        .ca_certificate("does/not/exist.pem")
+        .build();
+    assert!(matches!(result, Err(ClientError::Config(_))));
+}
+
+#[actix_rt::test]
+async fn test_fetch_floor() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+    client.add_item(2, 1).await.unwrap();
+    client.add_item(2, 1).await.unwrap();
+
+    let floor = fetch_floor(&client).await.unwrap();
+    assert_eq!(floor.len(), 100);
+    assert_eq!(floor[1].table_id, 2);
+    assert_eq!(floor[1].item_count(), 2);
+    assert_eq!(floor[1].longest_cooking_time(), Some(10));
+    // Tables without orders are reported as empty
+    assert_eq!(floor[0].item_count(), 0);
+}
+
+#[actix_rt::test]
+async fn test_simulation_verifies_orders() {
+    let client = RestaurantClient::new(&start_server()).unwrap();
+    // Existing orders are part of the expected orders
+    client.add_item(7, 1).await.unwrap();
+
+    let scenario = Scenario {
+        seed: Some(3),
+        tables: 100,
+        items_per_table: 4,
+        concurrency: Some(8),
+        ..Default::default()
+    };
+    let report = run_simulation(&client, &scenario).await;
+
+    assert_eq!(report.seed, 3);
+    assert_eq!(report.failures, 0);
+    assert!(report.unverified.is_empty());
+    assert_eq!(report.checks.len(), 100);
+    assert!(report.is_consistent());
+    let table_7 = report.checks.iter().find(|c| c.table_id == 7).unwrap();
+    assert_eq!(table_7.actual, vec![1, 1, 1]);
+}
+
+#[actix_rt::test]
+async fn test_load_test_reports_each_endpoint() {
+    let client = RestaurantClient::builder(&start_server())
+        .retries(0)
+        .build()
+        .unwrap();
+    let load_test = LoadTest {
+        duration: Duration::from_secs(1),
+        concurrency: 4,
+        rate: Some(200.0),
+        mix: Mix::from([
+            (Endpoint::Add, 2),
+            (Endpoint::Remove, 1),
+            (Endpoint::Item, 1),
+        ]),
+        tables: Some(5),
+        seed: Some(1),
+    };
+    let report = run_load_test(&client, &load_test).await.unwrap();
+
+    assert!(report.total.requests > 50 && report.total.requests <= 200);
+    assert_eq!(report.total.errors, 0);
+    let endpoints: Vec<&str> = report
+        .endpoints
+        .iter()
+        .map(|e| e.endpoint.as_str())
+        .collect();
+    assert_eq!(endpoints, vec!["add", "remove", "item"]);
+    assert!(report.total.p50_ms <= report.total.p99_ms);
+}
+
+#[actix_rt::test]
+async fn test_record_and_replay_session() {
+    let path = std::env::temp_dir().join(format!("replay_{}.jsonl", std::process::id()));
+    let client = RestaurantClient::builder(&start_server())
+        .recorder(SessionRecorder::create(&path).unwrap())
+        .build()
+        .unwrap();
+    client.add_item(2, 1).await.unwrap();
+    client.add_item(2, 1).await.unwrap();
+    client.remove_item(2, 1).await.unwrap();
+    assert!(client.get_item(2, 99).await.is_err());
+    assert_eq!(client.get_items(2).await.unwrap().len(), 1);
+
+    let calls = load_session(&path).unwrap();
+    assert_eq!(calls.len(), 5);
+    assert_eq!(calls[3].status, Some(404));
+
+    // A fresh server answers exactly like the recorded one
+    let replay_client = RestaurantClient::new(&start_server()).unwrap();
+    let options = ReplayOptions {
+        speed: Some(100.0),
+        ..Default::default()
+    };
+    let report = replay_session(&replay_client, &calls, &options).await;
+    assert_eq!(report.calls, 5);
+    assert!(report.is_consistent());
+
+    // Replaying on top of the replayed orders leaves two items on the table
+    let report = replay_session(&replay_client, &calls, &options).await;
+    assert_eq!(report.mismatches.len(), 1);
+    assert_eq!(report.mismatches[0].index, 5);
+    std::fs::remove_file(&path).unwrap();
+}
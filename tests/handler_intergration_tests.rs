@@ -5,22 +5,79 @@ use actix_web::{http::StatusCode, test, web, App};
 use futures::future::join_all;
 use restaurant_menu_app::server::api::v1::handlers::AppState;
 use restaurant_menu_app::server::api::v1::routes::configure_routes;
+use restaurant_menu_app::server::config::FaultConfig;
+use restaurant_menu_app::server::data_model::inventory::Ingredient;
 use restaurant_menu_app::server::data_model::models::{MenuItem, Restaurant};
+use restaurant_menu_app::server::data_model::modifier::ItemOptions;
+use restaurant_menu_app::server::data_model::money::Money;
+use restaurant_menu_app::server::data_model::session::unix_now;
+use restaurant_menu_app::server::data_store::fault_injection::FaultyStore;
+use restaurant_menu_app::server::data_store::in_memory_inventory_store::InMemoryInventoryStore;
 use restaurant_menu_app::server::data_store::in_memory_menu_store::InMemoryMenuStore;
 use restaurant_menu_app::server::data_store::in_memory_order_store::InMemoryOrderStore;
 use restaurant_menu_app::server::data_store::in_memory_table_store::InMemoryTableStore;
 use restaurant_menu_app::server::restaurant::SimpleRestaurant;
+use restaurant_menu_app::server::utils::error::RestaurantError;
 use serde_json::Value;
+use std::rc::Rc;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+type SharedRestaurant = Arc<dyn Restaurant + Send + Sync>;
+
+/// Creates a restaurant serving a burger and fries at the 100 predefined tables.
+fn burger_restaurant() -> SimpleRestaurant {
+    SimpleRestaurant::new(
+        Box::new(InMemoryMenuStore::new(vec![
+            MenuItem::new(1, "Burger", 10, Money::from_cents(1250)),
+            MenuItem::new(2, "Fries", 3, Money::from_cents(450)),
+        ])),
+        Box::new(InMemoryOrderStore::new()),
+        Box::new(InMemoryTableStore::new()),
+    )
+}
+
+/// Creates a restaurant serving the predefined recipes at the 100 predefined tables.
+fn predefined_restaurant() -> SimpleRestaurant {
+    SimpleRestaurant::new(
+        Box::new(InMemoryMenuStore::with_predefined_recipes()),
+        Box::new(InMemoryOrderStore::new()),
+        Box::new(InMemoryTableStore::new()),
+    )
+}
+
+/// Sends a request to the API of a restaurant.
+///
+/// # Returns
+/// The status of the response and its JSON body, or `Value::Null` if the body is not JSON.
+async fn send(restaurant: &SharedRestaurant, req: test::TestRequest) -> (StatusCode, Value) {
+    let app_state = AppState {
+        restaurant: Arc::clone(restaurant),
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .configure(configure_routes),
+    )
+    .await;
+    let resp = test::call_service(&app, req.to_request()).await;
+    let status = resp.status();
+    let body = test::read_body(resp).await;
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn post_json(uri: &str, body: Value) -> test::TestRequest {
+    test::TestRequest::post().uri(uri).set_json(body)
+}
+
 #[actix_rt::test]
 async fn test_add_item() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -32,7 +89,7 @@ async fn test_add_item() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
@@ -42,18 +99,19 @@ async fn test_add_item() {
     let req = test::TestRequest::post()
         .uri("/api/v1/add_item/1/1")
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
 #[actix_rt::test]
 async fn test_add_item_to_nonexistent_table() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -65,7 +123,7 @@ async fn test_add_item_to_nonexistent_table() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
@@ -75,18 +133,19 @@ async fn test_add_item_to_nonexistent_table() {
     let req = test::TestRequest::post()
         .uri("/api/v1/add_item/999/1") // Nonexistent table ID
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
 #[actix_rt::test]
 async fn test_remove_item() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -98,35 +157,32 @@ async fn test_remove_item() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
     )
     .await;
 
-    let item = MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    };
+    let item = MenuItem::new(1, "Burger", 10, Money::from_cents(1250));
     restaurant.add_item(1, item.id).unwrap();
 
     let req = test::TestRequest::delete()
         .uri("/api/v1/remove_item/1/1") // Table ID 1, Item ID 1
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
 #[actix_rt::test]
 async fn test_remove_nonexistent_item() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -138,7 +194,7 @@ async fn test_remove_nonexistent_item() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
@@ -148,18 +204,19 @@ async fn test_remove_nonexistent_item() {
     let req = test::TestRequest::delete()
         .uri("/api/v1/remove_item/1/999") // Nonexistent item ID
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
 #[actix_rt::test]
 async fn test_get_items() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -171,24 +228,20 @@ async fn test_get_items() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
     )
     .await;
 
-    let item = MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    };
+    let item = MenuItem::new(1, "Burger", 10, Money::from_cents(1250));
     restaurant.add_item(1, item.id).unwrap();
 
     let req = test::TestRequest::get()
         .uri("/api/v1/get_items/1") // Table ID 1
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::OK);
 
@@ -200,11 +253,12 @@ async fn test_get_items() {
 
 #[actix_rt::test]
 async fn test_get_items_for_nonexistent_table() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -216,7 +270,7 @@ async fn test_get_items_for_nonexistent_table() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
@@ -226,18 +280,19 @@ async fn test_get_items_for_nonexistent_table() {
     let req = test::TestRequest::get()
         .uri("/api/v1/get_items/999") // Nonexistent table ID
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
 #[actix_rt::test]
 async fn test_get_item() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -249,24 +304,20 @@ async fn test_get_item() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
     )
     .await;
 
-    let item = MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    };
+    let item = MenuItem::new(1, "Burger", 10, Money::from_cents(1250));
     restaurant.add_item(1, item.id).unwrap();
 
     let req = test::TestRequest::get()
         .uri("/api/v1/get_item/1/1") // Table ID 1, Item ID 1
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::OK);
 
@@ -277,11 +328,12 @@ async fn test_get_item() {
 
 #[actix_rt::test]
 async fn test_get_item_from_non_existent_table() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -293,7 +345,7 @@ async fn test_get_item_from_non_existent_table() {
     let app_state = AppState {
         restaurant: Arc::clone(&restaurant),
     };
-    let mut app = test::init_service(
+    let app = test::init_service(
         App::new()
             .app_data(web::Data::new(app_state))
             .configure(configure_routes),
@@ -303,18 +355,19 @@ async fn test_get_item_from_non_existent_table() {
     let req = test::TestRequest::get()
         .uri("/api/v1/get_item/999/1") // Table ID 999, Item ID 1 (non-existent table)
         .to_request();
-    let resp = test::call_service(&mut app, req).await;
+    let resp = test::call_service(&app, req).await;
 
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
 #[actix_rt::test]
 async fn test_concurrent_add_remove_items() {
-    let menu_store = InMemoryMenuStore::new(vec![MenuItem {
-        id: 1,
-        name: "Burger".to_string(),
-        cooking_time_minutes: 10,
-    }]);
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
     let order_store = InMemoryOrderStore::new();
     let table_store = InMemoryTableStore::new();
     let restaurant = Arc::new(SimpleRestaurant::new(
@@ -327,8 +380,8 @@ async fn test_concurrent_add_remove_items() {
         restaurant: Arc::clone(&restaurant),
     };
 
-    // Initialize Actix app inside an Arc<Mutex<_>> to allow shared mutable access
-    let app = Arc::new(Mutex::new(
+    // Initialize Actix app inside an Rc<Mutex<_>> to allow shared access from the futures
+    let app = Rc::new(Mutex::new(
         test::init_service(
             App::new()
                 .app_data(web::Data::new(app_state))
@@ -345,12 +398,12 @@ async fn test_concurrent_add_remove_items() {
     let add_futures: Vec<_> = (0..10)
         .map(|_| {
             let table_items_clone = Arc::clone(&table_items);
-            let app_clone = Arc::clone(&app); // Clone app wrapped in Arc<Mutex<_>>
+            let app_clone = Rc::clone(&app); // Clone app wrapped in Rc<Mutex<_>>
             async move {
-                let mut app_ref = app_clone.lock().await; // Lock the app to access it
+                let app_ref = app_clone.lock().await; // Lock the app to access it
                 let url_add = format!("/api/v1/add_item/{}/{}", table_id, menu_item_id);
                 let response = test::TestRequest::post().uri(&url_add).to_request();
-                let resp = test::call_service(&mut *app_ref, response).await;
+                let resp = test::call_service(&*app_ref, response).await;
 
                 if resp.status() == StatusCode::OK {
                     let mut table_items_lock = table_items_clone.lock().await;
@@ -367,12 +420,12 @@ async fn test_concurrent_add_remove_items() {
     let remove_futures: Vec<_> = (0..5)
         .map(|_| {
             let table_items_clone = Arc::clone(&table_items);
-            let app_clone = Arc::clone(&app); // Clone app wrapped in Arc<Mutex<_>>
+            let app_clone = Rc::clone(&app); // Clone app wrapped in Rc<Mutex<_>>
             async move {
-                let mut app_ref = app_clone.lock().await; // Lock the app to access it
+                let app_ref = app_clone.lock().await; // Lock the app to access it
                 let url_remove = format!("/api/v1/remove_item/{}/{}", table_id, menu_item_id);
                 let response = test::TestRequest::delete().uri(&url_remove).to_request();
-                let resp = test::call_service(&mut *app_ref, response).await;
+                let resp = test::call_service(&*app_ref, response).await;
 
                 if resp.status() == StatusCode::OK {
                     let mut table_items_lock = table_items_clone.lock().await;
@@ -391,8 +444,8 @@ async fn test_concurrent_add_remove_items() {
     let req = test::TestRequest::get()
         .uri(&format!("/api/v1/get_items/{}", table_id))
         .to_request();
-    let mut app_ref = app.lock().await; // Lock the app to access it for final check
-    let resp = test::call_service(&mut *app_ref, req).await;
+    let app_ref = app.lock().await; // Lock the app to access it for final check
+    let resp = test::call_service(&*app_ref, req).await;
 
     assert_eq!(resp.status(), StatusCode::OK);
 
@@ -402,3 +455,1288 @@ async fn test_concurrent_add_remove_items() {
     // After adding 10 times and removing 5 times, there should be 5 items remaining
     assert_eq!(items.len(), 5);
 }
+
+#[actix_rt::test]
+async fn test_get_bill() {
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
+    let restaurant = Arc::new(
+        SimpleRestaurant::new(
+            Box::new(menu_store),
+            Box::new(InMemoryOrderStore::new()),
+            Box::new(InMemoryTableStore::new()),
+        )
+        .with_tax_rate("8.25".parse().unwrap()),
+    ) as Arc<dyn Restaurant + Send + Sync>;
+    restaurant.add_item(1, 1).unwrap();
+
+    let app_state = AppState {
+        restaurant: Arc::clone(&restaurant),
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .configure(configure_routes),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/v1/bill/1").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["data"]["lines"][0]["unit_price"], "12.50");
+    assert_eq!(body["data"]["subtotal"], "12.50");
+    assert_eq!(body["data"]["tax_rate"], "8.25");
+    assert_eq!(body["data"]["tax"], "1.03");
+    assert_eq!(body["data"]["total"], "13.53");
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/bill/abc")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_checks_are_not_found_before_split() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/checks/1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_split_bill_checks_sum_to_total() {
+    let restaurant: SharedRestaurant =
+        Arc::new(burger_restaurant().with_tax_rate("8.25".parse().unwrap()));
+    for (item_id, seat) in [(1, 2), (2, 1), (1, 1)] {
+        let (status, _) = send(
+            &restaurant,
+            post_json(
+                &format!("/api/v1/add_item/1/{}", item_id),
+                serde_json::json!({ "seat": seat }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = send(
+        &restaurant,
+        post_json("/api/v1/split/1", serde_json::json!({"mode": "seats"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["bill"]["total"], "31.93");
+    assert_eq!(body["data"]["checks"][0]["label"], "Seat 1");
+    assert_eq!(body["data"]["checks"][0]["total"], "18.40");
+    assert_eq!(body["data"]["checks"][1]["label"], "Seat 2");
+    assert_eq!(body["data"]["checks"][1]["total"], "13.53");
+
+    let (status, checks) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/checks/1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(checks["data"], body["data"]);
+}
+
+#[actix_rt::test]
+async fn test_split_bill_rejects_invalid_requests() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    for item_id in [1, 2, 1] {
+        restaurant.add_item(1, item_id).unwrap();
+    }
+
+    // A line left out of every check, an unknown line, a line assigned twice, a split by
+    // seat without seats, and an unknown mode
+    for body in [
+        serde_json::json!({"mode": "items", "checks": [[1, 2]]}),
+        serde_json::json!({"mode": "items", "checks": [[1, 2], [3, 4]]}),
+        serde_json::json!({"mode": "items", "checks": [[1, 2], [3, 1]]}),
+        serde_json::json!({"mode": "seats"}),
+        serde_json::json!({"mode": "thirds"}),
+    ] {
+        let (status, response) = send(&restaurant, post_json("/api/v1/split/1", body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["status"], "error");
+    }
+}
+
+#[actix_rt::test]
+async fn test_checks_are_outdated_after_order_changes() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/split/1",
+            serde_json::json!({"mode": "even", "payers": 2}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    restaurant.add_item(1, 2).unwrap();
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/checks/1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_declined_card_payment() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/pay/1",
+            serde_json::json!({"method": "card", "amount": "10.00", "card_number": "4000000000000002"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYMENT_REQUIRED);
+    assert!(restaurant.get_account(1).unwrap().payments.is_empty());
+}
+
+#[actix_rt::test]
+async fn test_overpayment_is_refused() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/pay/1",
+            serde_json::json!({"method": "cash", "amount": "30.00"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["status"], "error");
+}
+
+#[actix_rt::test]
+async fn test_payments_and_tips_are_listed() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+    restaurant.add_item(1, 1).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/pay/1",
+            serde_json::json!({"method": "card", "amount": "10.00", "tip": "1.50", "card_number": "4242 4242 4242 4242"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["payment"]["reference"], "card-4242-1");
+    assert_eq!(body["data"]["account"]["balance"], "15.00");
+
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/tip/1",
+            serde_json::json!({"method": "cash", "tip": "2.00"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/payments/1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["paid"], "10.00");
+    assert_eq!(body["data"]["tips"], "3.50");
+    assert_eq!(body["data"]["payments"].as_array().unwrap().len(), 2);
+}
+
+#[actix_rt::test]
+async fn test_tips_adding_up_past_the_largest_amount_are_refused() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+    let pay = |tip: &str| {
+        post_json(
+            "/api/v1/pay/1",
+            serde_json::json!({"method": "cash", "amount": "1.00", "tip": tip}),
+        )
+    };
+
+    let (status, _) = send(&restaurant, pay("184467440737095516.15")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&restaurant, pay("0.01")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["status"], "error");
+
+    // The refused payment was not recorded, and the table still takes payments
+    assert_eq!(restaurant.get_account(1).unwrap().payments.len(), 1);
+    let (status, body) = send(&restaurant, pay("0.00")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["account"]["paid"], "2.00");
+}
+
+#[actix_rt::test]
+async fn test_settling_the_bill_frees_the_table() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.add_item(1, 1).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/pay/1",
+            serde_json::json!({"method": "cash", "amount": "12.50"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["account"]["settled"], true);
+    assert_eq!(restaurant.get_items(1), Ok(vec![]));
+
+    // A tip after settlement is kept with the payments of the closed session
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/tip/1",
+            serde_json::json!({"method": "cash", "tip": "2.00"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["account"]["tips"], "2.00");
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/sessions?table_id=1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let payments = body["data"][0]["payments"].as_array().unwrap();
+    assert_eq!(payments.len(), 2);
+    assert_eq!(payments[0]["amount"], "12.50");
+    assert_eq!(payments[1]["tip"], "2.00");
+}
+
+#[actix_rt::test]
+async fn test_open_table() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    let open = || post_json("/api/v1/open_table/4", serde_json::json!({"party_size": 3}));
+
+    let (status, body) = send(&restaurant, open()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["table_id"], 4);
+    assert_eq!(body["data"]["party_size"], 3);
+    let (status, _) = send(&restaurant, open()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, session) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/session/4"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(session["data"], body["data"]);
+}
+
+#[actix_rt::test]
+async fn test_open_table_rejects_invalid_party() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    for body in [
+        serde_json::json!({"party_size": 0}),
+        serde_json::json!({"guests": 3}),
+    ] {
+        let (status, _) = send(&restaurant, post_json("/api/v1/open_table/4", body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[actix_rt::test]
+async fn test_close_table_requires_paid_bill() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.open_table(4, 3).unwrap();
+    restaurant.add_item(4, 1).unwrap();
+    let close = || test::TestRequest::post().uri("/api/v1/close_table/4");
+
+    let (status, _) = send(&restaurant, close()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    restaurant.remove_item(4, 1).unwrap();
+    let (status, body) = send(&restaurant, close()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["closed_at"].is_u64());
+    let (status, _) = send(&restaurant, close()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_session_history() {
+    let restaurant: SharedRestaurant = Arc::new(burger_restaurant());
+    restaurant.open_table(4, 3).unwrap();
+    restaurant.add_item(4, 1).unwrap();
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/pay/4",
+            serde_json::json!({"method": "cash", "amount": "12.50"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Settling the bill closed the session
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/sessions?table_id=4"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let sessions = body["data"].as_array().unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["party_size"], 3);
+    assert_eq!(sessions[0]["bill"]["total"], "12.50");
+
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/sessions?table_id=four"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_categorized_menu() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/categorized_menu"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let sections = body["data"]["sections"].as_array().unwrap();
+    let names: Vec<&str> = sections
+        .iter()
+        .map(|section| section["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Starters", "Mains", "Breakfast", "Desserts"]);
+    assert_eq!(sections[0]["items"][0]["name"], "Salad");
+    assert!(body["data"].get("uncategorized").is_none());
+}
+
+#[actix_rt::test]
+async fn test_set_category_availability() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/category_availability/3",
+            serde_json::json!({"available": false}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["name"], "Breakfast");
+    assert_eq!(body["data"]["available"], false);
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/categories"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let categories = body["data"].as_array().unwrap();
+    assert_eq!(categories.len(), 4);
+    assert_eq!(categories[2]["available"], false);
+}
+
+#[actix_rt::test]
+async fn test_items_of_unavailable_categories_are_refused() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    restaurant.set_category_availability(3, false).unwrap();
+
+    // Item 11 is only served for breakfast, item 12 is also a dessert
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::post().uri("/api/v1/add_item/1/11"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("is not available"));
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::post().uri("/api/v1/add_item/1/12"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    restaurant.set_category_availability(3, true).unwrap();
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::post().uri("/api/v1/add_item/1/11"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<u32> = restaurant
+        .get_items(1)
+        .unwrap()
+        .iter()
+        .map(|item| item.id)
+        .collect();
+    assert_eq!(ids, vec![12, 11]);
+}
+
+#[actix_rt::test]
+async fn test_set_category_availability_rejects_invalid_requests() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    for (uri, body, status) in [
+        (
+            "/api/v1/category_availability/99",
+            serde_json::json!({"available": false}),
+            StatusCode::NOT_FOUND,
+        ),
+        (
+            "/api/v1/category_availability/mains",
+            serde_json::json!({"available": false}),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            "/api/v1/category_availability/2",
+            serde_json::json!({"available": "no"}),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let (actual, _) = send(&restaurant, post_json(uri, body)).await;
+        assert_eq!(actual, status, "{}", uri);
+    }
+}
+
+#[actix_rt::test]
+async fn test_add_item_with_modifiers_and_note() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+
+    // Burger with bacon and avocado, without onions, and a plain burger
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/add_item/1/6",
+            serde_json::json!({"modifiers": [2, 3, 4], "note": " Nut allergy "}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::post().uri("/api/v1/add_item/1/6"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/get_items/1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["price"], "16.00");
+    assert_eq!(items[0]["note"], "Nut allergy");
+    let modifiers: Vec<&str> = items[0]["modifiers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|modifier| modifier["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(modifiers, vec!["Bacon", "Avocado", "No onions"]);
+    assert_eq!(items[1]["price"], "12.50");
+    assert!(items[1].get("modifiers").is_none());
+}
+
+#[actix_rt::test]
+async fn test_bill_lines_are_grouped_by_modifiers() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let bacon = ItemOptions {
+        modifiers: vec![2],
+        ..ItemOptions::default()
+    };
+    restaurant.add_item_with_options(1, 6, &bacon).unwrap();
+    restaurant.add_item(1, 6).unwrap();
+    restaurant.add_item_with_options(1, 6, &bacon).unwrap();
+
+    let (status, body) = send(&restaurant, test::TestRequest::get().uri("/api/v1/bill/1")).await;
+    assert_eq!(status, StatusCode::OK);
+    let lines = body["data"]["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["quantity"], 2);
+    assert_eq!(lines[0]["unit_price"], "14.00");
+    assert_eq!(body["data"]["subtotal"], "40.50");
+}
+
+#[actix_rt::test]
+async fn test_remove_line_removes_that_line() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let bacon = ItemOptions {
+        modifiers: vec![2],
+        ..ItemOptions::default()
+    };
+    restaurant.add_item(1, 6).unwrap();
+    restaurant.add_item_with_options(1, 6, &bacon).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/get_items/1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][1]["line_id"], 2);
+
+    // Removes the burger with bacon, not the first burger
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::delete().uri("/api/v1/remove_line/1/2"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/get_items/1"),
+    )
+    .await;
+    let items = body["data"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["line_id"], 1);
+    assert!(items[0].get("modifiers").is_none());
+
+    for (uri, expected) in [
+        ("/api/v1/remove_line/1/2", StatusCode::NOT_FOUND),
+        ("/api/v1/remove_line/2/1", StatusCode::NOT_FOUND),
+        ("/api/v1/remove_line/999/1", StatusCode::NOT_FOUND),
+        ("/api/v1/remove_line/1/first", StatusCode::BAD_REQUEST),
+    ] {
+        let (status, _) = send(&restaurant, test::TestRequest::delete().uri(uri)).await;
+        assert_eq!(status, expected, "{}", uri);
+    }
+}
+
+#[actix_rt::test]
+async fn test_add_item_rejects_invalid_options() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    for (uri, body) in [
+        // Modifier of another item
+        ("/api/v1/add_item/1/6", r#"{"modifiers": [9]}"#),
+        // More extras than allowed
+        ("/api/v1/add_item/1/6", r#"{"modifiers": [1, 2, 3, 1]}"#),
+        // Modifiers of an item without modifiers
+        ("/api/v1/add_item/1/4", r#"{"modifiers": [1]}"#),
+        ("/api/v1/add_item/1/6", r#"{"modifier": [1]}"#),
+        ("/api/v1/add_item/1/6", "not json"),
+        // Seats are numbered from 1
+        ("/api/v1/add_item/1/4", r#"{"seat": 0}"#),
+    ] {
+        let req = test::TestRequest::post()
+            .uri(uri)
+            .insert_header(("content-type", "application/json"))
+            .set_payload(body);
+        let (status, _) = send(&restaurant, req).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+    assert_eq!(
+        restaurant.get_items(1),
+        Err(RestaurantError::NoMenusForTable(1))
+    );
+
+    // A seat beyond the party seated at the table
+    restaurant.open_table(2, 2).unwrap();
+    let (status, body) = send(
+        &restaurant,
+        post_json("/api/v1/add_item/2/4", serde_json::json!({"seat": 3})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("seat 3 is beyond the party of 2"));
+}
+
+#[actix_rt::test]
+async fn test_menus_filtered_by_allergens_and_diet() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get()
+            .uri("/api/v1/menus?exclude_allergens=gluten,dairy&diet=vegetarian"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Fries", "Stir Fry", "Fried Rice"]);
+    assert_eq!(body["data"][0]["diets"][1], "vegan");
+}
+
+#[actix_rt::test]
+async fn test_categorized_menu_filtered_by_diet() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/categorized_menu?diet=vegan"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    // Categories without vegan menu items are kept, but empty
+    let sections = body["data"]["sections"].as_array().unwrap();
+    let counts: Vec<usize> = sections
+        .iter()
+        .map(|section| section["items"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(counts, vec![1, 1, 0, 0]);
+    assert_eq!(sections[0]["items"][0]["name"], "Fries");
+}
+
+#[actix_rt::test]
+async fn test_menu_filters_reject_unknown_tags() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    for uri in [
+        "/api/v1/menus?exclude_allergens=pollen",
+        "/api/v1/categorized_menu?diet=kosher",
+    ] {
+        let (status, _) = send(&restaurant, test::TestRequest::get().uri(uri)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
+
+#[actix_rt::test]
+async fn test_declared_allergies_require_override() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/allergies/3",
+            serde_json::json!({"allergies": ["nuts"]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["allergies"], serde_json::json!(["nuts"]));
+
+    // Waffles contain nuts
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::post().uri("/api/v1/add_item/3/19"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["message"].as_str().unwrap().contains("nuts"));
+
+    let (status, body) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/add_item/3/19",
+            serde_json::json!({"allergen_override": true}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["message"].as_str().unwrap().contains("Warning"));
+    assert_eq!(restaurant.get_items(3).unwrap().len(), 1);
+}
+
+#[actix_rt::test]
+async fn test_declare_allergies_rejects_invalid_requests() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    for (uri, body, status) in [
+        (
+            "/api/v1/allergies/3",
+            serde_json::json!({"allergies": ["pollen"]}),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            "/api/v1/allergies/999",
+            serde_json::json!({"allergies": []}),
+            StatusCode::NOT_FOUND,
+        ),
+    ] {
+        let (actual, _) = send(&restaurant, post_json(uri, body)).await;
+        assert_eq!(actual, status, "{}", uri);
+    }
+}
+
+#[actix_rt::test]
+async fn test_handlers_return_server_errors_for_store_faults() {
+    let poisoned = FaultConfig {
+        poisoned: true,
+        ..Default::default()
+    };
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
+    let restaurant = Arc::new(SimpleRestaurant::new(
+        Box::new(menu_store),
+        Box::new(FaultyStore::new(InMemoryOrderStore::new(), poisoned.clone()).unwrap()),
+        Box::new(FaultyStore::new(InMemoryTableStore::new(), poisoned).unwrap()),
+    )) as Arc<dyn Restaurant + Send + Sync>;
+
+    let app_state = AppState {
+        restaurant: Arc::clone(&restaurant),
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .configure(configure_routes),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/v1/menus").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    for req in [
+        test::TestRequest::get().uri("/api/v1/tables"),
+        test::TestRequest::post().uri("/api/v1/add_item/1/1"),
+        test::TestRequest::get().uri("/api/v1/get_items/1"),
+    ] {
+        let resp = test::call_service(&app, req.to_request()).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
+
+#[actix_rt::test]
+async fn test_handlers_survive_random_order_store_faults() {
+    let faults = FaultConfig {
+        failure_rate: 0.5,
+        seed: Some(11),
+        ..Default::default()
+    };
+    let menu_store = InMemoryMenuStore::new(vec![MenuItem::new(
+        1,
+        "Burger",
+        10,
+        Money::from_cents(1250),
+    )]);
+    let restaurant = Arc::new(SimpleRestaurant::new(
+        Box::new(menu_store),
+        Box::new(FaultyStore::new(InMemoryOrderStore::new(), faults).unwrap()),
+        Box::new(InMemoryTableStore::new()),
+    )) as Arc<dyn Restaurant + Send + Sync>;
+
+    let app_state = AppState {
+        restaurant: Arc::clone(&restaurant),
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .configure(configure_routes),
+    )
+    .await;
+
+    // Every request either succeeds or fails with a server error, and only the
+    // successful adds end up in the order
+    let mut added = 0;
+    for _ in 0..20 {
+        let req = test::TestRequest::post()
+            .uri("/api/v1/add_item/1/1")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        match resp.status() {
+            StatusCode::OK => added += 1,
+            status => assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+    assert!(added > 0 && added < 20, "{} successful adds", added);
+
+    let items = loop {
+        if let Ok(items) = restaurant.get_items(1) {
+            break items;
+        }
+    };
+    assert_eq!(items.len(), added);
+}
+
+#[actix_rt::test]
+async fn test_set_stock() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+
+    let (status, body) = send(
+        &restaurant,
+        post_json("/api/v1/stock/20", serde_json::json!({"remaining": 1})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        serde_json::json!({"item_id": 20, "remaining": 1, "sold_out": false})
+    );
+    let (status, _) = send(
+        &restaurant,
+        post_json("/api/v1/stock/5", serde_json::json!({"sold_out": true})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&restaurant, test::TestRequest::get().uri("/api/v1/stock")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        serde_json::json!([
+            {"item_id": 5, "sold_out": true},
+            {"item_id": 20, "remaining": 1, "sold_out": false}
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn test_sold_out_items_are_refused() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    restaurant.set_stock(20, Some(1), false).unwrap();
+    restaurant.set_stock(5, None, true).unwrap();
+
+    // The last salmon goes to table 1, after that it is sold out like the steak
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::post().uri("/api/v1/add_item/1/20"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    for uri in ["/api/v1/add_item/2/20", "/api/v1/add_item/2/5"] {
+        let (status, body) = send(&restaurant, test::TestRequest::post().uri(uri)).await;
+        assert_eq!(status, StatusCode::CONFLICT, "{}", uri);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .ends_with("is not available"));
+    }
+}
+
+#[actix_rt::test]
+async fn test_menus_show_stock() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    restaurant.set_stock(20, Some(0), false).unwrap();
+    restaurant.set_stock(5, None, true).unwrap();
+
+    let (status, body) = send(&restaurant, test::TestRequest::get().uri("/api/v1/menus")).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["data"].as_array().unwrap();
+    assert_eq!(items[4]["sold_out"], true);
+    assert_eq!(items[19]["sold_out"], true);
+    assert_eq!(items[19]["remaining"], 0);
+    assert!(items[0].get("sold_out").is_none());
+}
+
+#[actix_rt::test]
+async fn test_removed_item_returns_to_stock() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    restaurant.set_stock(20, Some(1), false).unwrap();
+    restaurant.add_item(1, 20).unwrap();
+
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::delete().uri("/api/v1/remove_item/1/20"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(restaurant.get_stock().unwrap()[0].remaining, Some(1));
+}
+
+#[actix_rt::test]
+async fn test_set_stock_rejects_invalid_requests() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    for (uri, body, status) in [
+        (
+            "/api/v1/stock/21",
+            serde_json::json!({"sold_out": true}),
+            StatusCode::NOT_FOUND,
+        ),
+        (
+            "/api/v1/stock/abc",
+            serde_json::json!({"sold_out": true}),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            "/api/v1/stock/20",
+            serde_json::json!({"remaining": -1}),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let (actual, _) = send(&restaurant, post_json(uri, body)).await;
+        assert_eq!(actual, status, "{}", uri);
+    }
+}
+
+/// Creates a restaurant serving the predefined recipes with 10 kg of potatoes and 250 g of
+/// salmon in stock, enough for one salmon.
+fn salmon_restaurant() -> SharedRestaurant {
+    let ingredient = |id: u32, name: &str, on_hand: u32, low_stock_threshold: u32| Ingredient {
+        id,
+        name: name.to_string(),
+        unit: "g".to_string(),
+        on_hand,
+        low_stock_threshold,
+    };
+    Arc::new(
+        predefined_restaurant().with_inventory_store(Box::new(InMemoryInventoryStore::new(vec![
+            ingredient(8, "Potatoes", 10000, 1000),
+            ingredient(10, "Salmon", 250, 100),
+        ]))),
+    )
+}
+
+#[actix_rt::test]
+async fn test_ordering_deducts_ingredients() {
+    let restaurant = salmon_restaurant();
+    restaurant.add_item(1, 20).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/ingredients"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["on_hand"], 9850);
+    assert_eq!(body["data"][1]["on_hand"], 50);
+}
+
+#[actix_rt::test]
+async fn test_items_short_of_ingredients_are_sold_out() {
+    let restaurant = salmon_restaurant();
+    restaurant.add_item(1, 20).unwrap();
+
+    // One salmon uses 200 g, leaving too little for another salmon or the sushi
+    for uri in ["/api/v1/add_item/2/20", "/api/v1/add_item/2/13"] {
+        let (status, body) = send(&restaurant, test::TestRequest::post().uri(uri)).await;
+        assert_eq!(status, StatusCode::CONFLICT, "{}", uri);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .ends_with("is not available"));
+    }
+
+    let (_, body) = send(&restaurant, test::TestRequest::get().uri("/api/v1/menus")).await;
+    let items = body["data"].as_array().unwrap();
+    assert_eq!(items[12]["sold_out"], true);
+    assert_eq!(items[19]["sold_out"], true);
+    assert_eq!(
+        items[19]["ingredients"][0],
+        serde_json::json!({"ingredient_id": 10, "quantity": 200})
+    );
+    assert!(items[4].get("sold_out").is_none());
+}
+
+#[actix_rt::test]
+async fn test_low_stock_report() {
+    let restaurant = salmon_restaurant();
+    restaurant.add_item(1, 20).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/low_stock"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["ingredients"][0]["id"], 10);
+    assert_eq!(
+        body["data"]["unavailable_item_ids"],
+        serde_json::json!([13, 20])
+    );
+}
+
+#[actix_rt::test]
+async fn test_restock() {
+    let restaurant = salmon_restaurant();
+    restaurant.add_item(1, 20).unwrap();
+
+    let (status, body) = send(
+        &restaurant,
+        post_json("/api/v1/restock/10", serde_json::json!({"quantity": 500})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["on_hand"], 550);
+    restaurant.add_item(2, 20).unwrap();
+}
+
+#[actix_rt::test]
+async fn test_removed_item_returns_its_ingredients() {
+    let restaurant = salmon_restaurant();
+    restaurant.add_item(1, 20).unwrap();
+
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::delete().uri("/api/v1/remove_item/1/20"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/low_stock"),
+    )
+    .await;
+    assert_eq!(
+        body["data"],
+        serde_json::json!({"ingredients": [], "unavailable_item_ids": []})
+    );
+}
+
+#[actix_rt::test]
+async fn test_restock_rejects_invalid_requests() {
+    let restaurant = salmon_restaurant();
+    for (uri, status) in [
+        ("/api/v1/restock/99", StatusCode::NOT_FOUND),
+        ("/api/v1/restock/abc", StatusCode::BAD_REQUEST),
+    ] {
+        let (actual, _) = send(
+            &restaurant,
+            post_json(uri, serde_json::json!({"quantity": 1})),
+        )
+        .await;
+        assert_eq!(actual, status, "{}", uri);
+    }
+}
+
+fn reservation(table_id: u32, party_size: u32, starts_at: u64) -> Value {
+    serde_json::json!({
+        "table_id": table_id,
+        "party_size": party_size,
+        "contact_name": "Grace",
+        "starts_at": starts_at
+    })
+}
+
+#[actix_rt::test]
+async fn test_make_reservation() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let starts_at = unix_now() + 86_400;
+
+    // Table 96 seats 10
+    let (status, body) = send(
+        &restaurant,
+        post_json("/api/v1/reservations", reservation(96, 8, starts_at)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["reservation_id"], 1);
+    assert_eq!(body["data"]["starts_at"], starts_at);
+    assert_eq!(body["data"]["duration_minutes"], 90);
+}
+
+#[actix_rt::test]
+async fn test_make_reservation_rejects_invalid_requests() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let now = unix_now();
+    let (status, _) = send(
+        &restaurant,
+        post_json("/api/v1/reservations", reservation(96, 8, now)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    for (body, status) in [
+        // Overlaps the first reservation
+        (reservation(96, 2, now + 600), StatusCode::CONFLICT),
+        // More guests than the table seats
+        (reservation(1, 3, now), StatusCode::BAD_REQUEST),
+        (reservation(101, 2, now), StatusCode::NOT_FOUND),
+        // Ends beyond the latest representable time
+        (reservation(1, 2, u64::MAX - 60), StatusCode::BAD_REQUEST),
+        (
+            serde_json::json!({"table_id": 1, "party_size": 2, "starts_at": now}),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let (actual, _) = send(&restaurant, post_json("/api/v1/reservations", body.clone())).await;
+        assert_eq!(actual, status, "{}", body);
+    }
+}
+
+#[actix_rt::test]
+async fn test_reserved_tables_are_flagged() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let now = unix_now();
+    let (status, _) = send(
+        &restaurant,
+        post_json("/api/v1/reservations", reservation(96, 8, now - 600)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/tables?status=true"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let tables = body["data"].as_array().unwrap();
+    assert_eq!(tables.len(), 100);
+    assert_eq!(tables[95]["reserved"], true);
+    assert_eq!(tables[95]["reservation"]["reservation_id"], 1);
+    assert_eq!(
+        tables[0],
+        serde_json::json!({"table_id": 1, "capacity": 2, "reserved": false})
+    );
+
+    // Without the flag, the table list keeps returning plain IDs
+    for uri in ["/api/v1/tables", "/api/v1/tables?status=false"] {
+        let (status, body) = send(&restaurant, test::TestRequest::get().uri(uri)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"][95], 96);
+    }
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/tables?status=yes"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_update_reservation() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let starts_at = unix_now() + 86_400;
+    let (status, _) = send(
+        &restaurant,
+        post_json("/api/v1/reservations", reservation(96, 8, starts_at)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Moving the party to a smaller table
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::put()
+            .uri("/api/v1/reservations/1")
+            .set_json(reservation(41, 4, starts_at)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["reservation_id"], 1);
+    assert_eq!(body["data"]["table_id"], 41);
+
+    // An unknown reservation is reported before a bad body or party
+    for (uri, body) in [
+        (
+            "/api/v1/reservations/2",
+            serde_json::json!({"table_id": 41}),
+        ),
+        ("/api/v1/reservations/2", reservation(1, 9, starts_at)),
+        (
+            "/api/v1/reservations/4294967296",
+            reservation(41, 4, starts_at),
+        ),
+    ] {
+        let (status, _) = send(
+            &restaurant,
+            test::TestRequest::put().uri(uri).set_json(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::put()
+            .uri("/api/v1/reservations/1")
+            .set_json(serde_json::json!({"table_id": 41})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_cancel_reservation() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/reservations",
+            reservation(96, 8, unix_now() + 86_400),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let cancel = || test::TestRequest::delete().uri("/api/v1/reservations/1");
+    let (status, _) = send(&restaurant, cancel()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(restaurant.get_reservations(None), Ok(vec![]));
+    let (status, _) = send(&restaurant, cancel()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::delete().uri("/api/v1/reservations/4294967296"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::delete().uri("/api/v1/reservations/abc"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_reservations_by_date() {
+    let restaurant: SharedRestaurant = Arc::new(predefined_restaurant());
+    let (status, _) = send(
+        &restaurant,
+        post_json(
+            "/api/v1/reservations",
+            reservation(96, 8, unix_now() + 86_400),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/reservations"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    // Nothing is booked on the first of January 2000
+    let (status, body) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/reservations?date=2000-01-01"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], serde_json::json!([]));
+
+    let (status, _) = send(
+        &restaurant,
+        test::TestRequest::get().uri("/api/v1/reservations?date=01.01.2000"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,455 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use proptest::prelude::*;
+use restaurant_menu_app::server::data_model::models::{MenuItem, OrderStore, Restaurant};
+use restaurant_menu_app::server::data_model::money::Money;
+use restaurant_menu_app::server::data_model::payment::{PaymentMethod, PaymentRequest};
+use restaurant_menu_app::server::data_store::in_memory_menu_store::InMemoryMenuStore;
+use restaurant_menu_app::server::data_store::in_memory_order_store::InMemoryOrderStore;
+use restaurant_menu_app::server::data_store::in_memory_table_store::InMemoryTableStore;
+use restaurant_menu_app::server::restaurant::SimpleRestaurant;
+use restaurant_menu_app::server::utils::error::RestaurantError;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Barrier;
+
+/// Table IDs used by the generated operations. `0` and `101` are invalid tables
+/// for the restaurant, whose table store has the tables 1 to 100.
+const TABLES: [u32; 5] = [0, 1, 2, 3, 101];
+/// Highest menu item ID used by the generated operations; the menu has the items 1 to 3.
+const MAX_ITEM_ID: u32 = 4;
+/// Price of every menu item, in cents. The restaurant bills without tax.
+const PRICE_CENTS: u64 = 1000;
+/// Amounts of the generated payments, in cents: less than, exactly and more than one item.
+const PAYMENT_CENTS: [u64; 3] = [500, 1000, 2500];
+
+/// An operation of the order store, or of the restaurant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Add(u32, u32),
+    Remove(u32, u32),
+    GetItemIds(u32),
+    GetItemId(u32, u32),
+}
+
+/// An operation of the restaurant: an order operation, a cash payment of an amount in
+/// cents, or a read of the balance of a table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RestaurantOp {
+    Order(Op),
+    Pay(u32, u64),
+    Balance(u32),
+}
+
+/// The successful result of an operation.
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    Done,
+    Ids(Vec<u32>),
+    Id(u32),
+    Balance(Money),
+}
+
+type OpResult = Result<Outcome, RestaurantError>;
+
+/// Generates operations on few tables and items, so that sequences often hit the same
+/// table and item, e.g. removing from a table that has just been emptied.
+fn op() -> impl Strategy<Value = Op> {
+    let table = proptest::sample::select(TABLES.to_vec());
+    let item = 0..=MAX_ITEM_ID;
+    prop_oneof![
+        3 => (table.clone(), item.clone()).prop_map(|(t, i)| Op::Add(t, i)),
+        3 => (table.clone(), item.clone()).prop_map(|(t, i)| Op::Remove(t, i)),
+        1 => table.clone().prop_map(Op::GetItemIds),
+        1 => (table, item).prop_map(|(t, i)| Op::GetItemId(t, i)),
+    ]
+}
+
+/// Generates order operations, payments and balance reads on the tables of `op`.
+fn restaurant_op() -> impl Strategy<Value = RestaurantOp> {
+    let table = proptest::sample::select(TABLES.to_vec());
+    let amount = proptest::sample::select(PAYMENT_CENTS.to_vec());
+    prop_oneof![
+        6 => op().prop_map(RestaurantOp::Order),
+        2 => (table.clone(), amount).prop_map(|(t, a)| RestaurantOp::Pay(t, a)),
+        1 => table.prop_map(RestaurantOp::Balance),
+    ]
+}
+
+/// A reference model that operations are applied to one at a time.
+trait Model: Clone {
+    type Op: Copy + Debug;
+
+    fn apply(&mut self, op: Self::Op) -> OpResult;
+}
+
+/// The reference model of an order store: the items of each table that has ever had an order.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct OrderModel {
+    orders: HashMap<u32, Vec<u32>>,
+}
+
+impl Model for OrderModel {
+    type Op = Op;
+
+    fn apply(&mut self, op: Op) -> OpResult {
+        match op {
+            Op::Add(table_id, item_id) => {
+                self.orders.entry(table_id).or_default().push(item_id);
+                Ok(Outcome::Done)
+            }
+            Op::Remove(table_id, item_id) => {
+                let items = self
+                    .orders
+                    .get_mut(&table_id)
+                    .ok_or(RestaurantError::NoMenusForTable(table_id))?;
+                let position = items
+                    .iter()
+                    .position(|&id| id == item_id)
+                    .ok_or(RestaurantError::NoMenuForTable(table_id, item_id))?;
+                items.remove(position);
+                Ok(Outcome::Done)
+            }
+            Op::GetItemIds(table_id) => self
+                .orders
+                .get(&table_id)
+                .map(|items| Outcome::Ids(items.clone()))
+                .ok_or(RestaurantError::NoMenusForTable(table_id)),
+            Op::GetItemId(table_id, item_id) => match self.orders.get(&table_id) {
+                Some(items) if items.contains(&item_id) => Ok(Outcome::Id(item_id)),
+                _ => Err(RestaurantError::NoMenuForTable(table_id, item_id)),
+            },
+        }
+    }
+}
+
+/// The reference model of the restaurant: the order model behind table and menu checks,
+/// and the amount paid towards the bill of each table.
+#[derive(Debug, Clone, Default)]
+struct RestaurantModel {
+    orders: OrderModel,
+    paid: HashMap<u32, u64>,
+}
+
+impl RestaurantModel {
+    /// Returns the total and the balance of the bill of a table, in cents.
+    fn account(&self, table_id: u32) -> Result<(u64, u64), RestaurantError> {
+        let items = self
+            .orders
+            .orders
+            .get(&table_id)
+            .ok_or(RestaurantError::NoMenusForTable(table_id))?;
+        let total = items.len() as u64 * PRICE_CENTS;
+        let paid = self.paid.get(&table_id).copied().unwrap_or(0);
+        Ok((total, total.saturating_sub(paid)))
+    }
+}
+
+impl Model for RestaurantModel {
+    type Op = RestaurantOp;
+
+    fn apply(&mut self, op: RestaurantOp) -> OpResult {
+        let table_id = match op {
+            RestaurantOp::Order(
+                Op::Add(t, _) | Op::Remove(t, _) | Op::GetItemIds(t) | Op::GetItemId(t, _),
+            )
+            | RestaurantOp::Pay(t, _)
+            | RestaurantOp::Balance(t) => t,
+        };
+        if !(1..=100).contains(&table_id) {
+            return Err(RestaurantError::TableNotFound(table_id));
+        }
+        match op {
+            RestaurantOp::Order(Op::Add(_, item_id)) if !menu_ids().contains(&item_id) => {
+                Err(RestaurantError::MenuNotFound(item_id))
+            }
+            // The restaurant returns menu items; the outcome keeps their IDs
+            RestaurantOp::Order(op) => self.orders.apply(op),
+            RestaurantOp::Pay(_, amount) => {
+                let (total, balance) = self.account(table_id)?;
+                if total == 0 {
+                    return Err(RestaurantError::InvalidPayment(format!(
+                        "Table {} has nothing to pay",
+                        table_id
+                    )));
+                }
+                if amount > balance {
+                    return Err(RestaurantError::Overpayment(
+                        table_id,
+                        Money::from_cents(amount),
+                        Money::from_cents(balance),
+                    ));
+                }
+                if amount == balance {
+                    // A settled table is freed: its order is emptied and its payments removed
+                    self.orders.orders.insert(table_id, Vec::new());
+                    self.paid.remove(&table_id);
+                } else {
+                    *self.paid.entry(table_id).or_default() += amount;
+                }
+                Ok(Outcome::Done)
+            }
+            RestaurantOp::Balance(_) => self
+                .account(table_id)
+                .map(|(_, balance)| Outcome::Balance(Money::from_cents(balance))),
+        }
+    }
+}
+
+fn menu_ids() -> Vec<u32> {
+    (1..MAX_ITEM_ID).collect()
+}
+
+fn menu() -> Vec<MenuItem> {
+    menu_ids()
+        .into_iter()
+        .map(|id| {
+            MenuItem::new(
+                id,
+                &format!("Item {}", id),
+                u64::from(id),
+                Money::from_cents(PRICE_CENTS),
+            )
+        })
+        .collect()
+}
+
+fn apply_to_store(store: &dyn OrderStore, op: Op) -> OpResult {
+    match op {
+        Op::Add(t, i) => store.add_item(t, i).map(|_| Outcome::Done),
+        Op::Remove(t, i) => store.remove_item(t, i).map(|_| Outcome::Done),
+        Op::GetItemIds(t) => store.get_item_ids(t).map(Outcome::Ids),
+        Op::GetItemId(t, i) => store.get_item_id(t, i).map(Outcome::Id),
+    }
+}
+
+fn apply_to_restaurant(restaurant: &dyn Restaurant, op: RestaurantOp) -> OpResult {
+    match op {
+        RestaurantOp::Order(Op::Add(t, i)) => restaurant.add_item(t, i).map(|_| Outcome::Done),
+        RestaurantOp::Order(Op::Remove(t, i)) => {
+            restaurant.remove_item(t, i).map(|_| Outcome::Done)
+        }
+        RestaurantOp::Order(Op::GetItemIds(t)) => restaurant
+            .get_items(t)
+            .map(|items| Outcome::Ids(items.iter().map(|item| item.id).collect())),
+        RestaurantOp::Order(Op::GetItemId(t, i)) => {
+            restaurant.get_item(t, i).map(|item| Outcome::Id(item.id))
+        }
+        RestaurantOp::Pay(t, amount) => restaurant
+            .pay(
+                t,
+                &PaymentRequest {
+                    method: PaymentMethod::Cash,
+                    amount: Money::from_cents(amount),
+                    tip: Money::ZERO,
+                    check_id: None,
+                    card_number: None,
+                },
+            )
+            .map(|_| Outcome::Done),
+        RestaurantOp::Balance(t) => restaurant
+            .get_account(t)
+            .map(|account| Outcome::Balance(account.balance)),
+    }
+}
+
+fn new_restaurant() -> SimpleRestaurant {
+    SimpleRestaurant::new(
+        Box::new(InMemoryMenuStore::new(menu())),
+        Box::new(InMemoryOrderStore::new()),
+        Box::new(InMemoryTableStore::new()),
+    )
+}
+
+/// Runs the operations of each thread concurrently, all threads starting together, and
+/// returns the operations of each thread with their results.
+fn run_concurrently<O: Copy + Send + Sync>(
+    threads: &[Vec<O>],
+    apply: impl Fn(O) -> OpResult + Sync,
+) -> Vec<Vec<(O, OpResult)>> {
+    let barrier = Barrier::new(threads.len());
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = threads
+            .iter()
+            .map(|ops| {
+                let (apply, barrier) = (&apply, &barrier);
+                scope.spawn(move || {
+                    barrier.wait();
+                    ops.iter().map(|&op| (op, apply(op))).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("thread panicked"))
+            .collect()
+    })
+}
+
+/// Returns `true` if the results observed by the threads can be explained by running
+/// their operations one at a time in some interleaving that keeps each thread's order,
+/// and that interleaving ends in the final state observed.
+///
+/// # Arguments
+/// * `model` - The model state before the remaining operations.
+/// * `threads` - The operations of each thread with their observed results.
+/// * `positions` - The number of operations of each thread already placed in the interleaving.
+/// * `final_reads` - Reads of every table made after all threads finished, with their results.
+fn linearizable<M: Model>(
+    model: &M,
+    threads: &[Vec<(M::Op, OpResult)>],
+    positions: &mut Vec<usize>,
+    final_reads: &[(M::Op, OpResult)],
+) -> bool {
+    if positions
+        .iter()
+        .zip(threads)
+        .all(|(&position, ops)| position == ops.len())
+    {
+        let mut model = model.clone();
+        return final_reads
+            .iter()
+            .all(|(op, result)| model.apply(*op) == *result);
+    }
+
+    for thread in 0..threads.len() {
+        let position = positions[thread];
+        let Some((op, observed)) = threads[thread].get(position) else {
+            continue;
+        };
+        let mut next = model.clone();
+        if next.apply(*op) != *observed {
+            continue;
+        }
+        positions[thread] += 1;
+        let found = linearizable(&next, threads, positions, final_reads);
+        positions[thread] -= 1;
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+proptest! {
+    #[test]
+    fn test_order_store_matches_model(ops in proptest::collection::vec(op(), 1..60)) {
+        let store = InMemoryOrderStore::new();
+        let mut model = OrderModel::default();
+        for (step, op) in ops.into_iter().enumerate() {
+            prop_assert_eq!(
+                apply_to_store(&store, op),
+                model.apply(op),
+                "step {}: {:?}",
+                step,
+                op
+            );
+        }
+        for table_id in TABLES {
+            prop_assert_eq!(
+                apply_to_store(&store, Op::GetItemIds(table_id)),
+                model.apply(Op::GetItemIds(table_id))
+            );
+        }
+    }
+
+    #[test]
+    fn test_restaurant_matches_model(ops in proptest::collection::vec(restaurant_op(), 1..60)) {
+        let restaurant = new_restaurant();
+        let mut model = RestaurantModel::default();
+        for (step, op) in ops.into_iter().enumerate() {
+            prop_assert_eq!(
+                apply_to_restaurant(&restaurant, op),
+                model.apply(op),
+                "step {}: {:?}",
+                step,
+                op
+            );
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_concurrent_order_store_is_linearizable(
+        threads in proptest::collection::vec(proptest::collection::vec(op(), 1..5), 2..4)
+    ) {
+        let store = InMemoryOrderStore::new();
+        let observed = run_concurrently(&threads, |op| apply_to_store(&store, op));
+        let final_reads: Vec<(Op, OpResult)> = TABLES
+            .iter()
+            .map(|&table_id| Op::GetItemIds(table_id))
+            .map(|op| (op, apply_to_store(&store, op)))
+            .collect();
+
+        prop_assert!(
+            linearizable(
+                &OrderModel::default(),
+                &observed,
+                &mut vec![0; observed.len()],
+                &final_reads
+            ),
+            "no interleaving explains the results {:?} and final orders {:?}",
+            observed,
+            final_reads
+        );
+    }
+
+    #[test]
+    fn test_concurrent_restaurant_is_linearizable(
+        threads in proptest::collection::vec(
+            proptest::collection::vec(restaurant_op(), 1..5),
+            2..4
+        )
+    ) {
+        let restaurant = new_restaurant();
+        let observed = run_concurrently(&threads, |op| apply_to_restaurant(&restaurant, op));
+        let final_reads: Vec<(RestaurantOp, OpResult)> = TABLES
+            .iter()
+            .flat_map(|&table_id| {
+                [
+                    RestaurantOp::Order(Op::GetItemIds(table_id)),
+                    RestaurantOp::Balance(table_id),
+                ]
+            })
+            .map(|op| (op, apply_to_restaurant(&restaurant, op)))
+            .collect();
+
+        prop_assert!(
+            linearizable(
+                &RestaurantModel::default(),
+                &observed,
+                &mut vec![0; observed.len()],
+                &final_reads
+            ),
+            "no interleaving explains the results {:?} and final state {:?}",
+            observed,
+            final_reads
+        );
+    }
+}
+
+#[test]
+fn test_linearizable_rejects_lost_update() {
+    let observed = vec![
+        vec![(Op::Add(1, 1), Ok(Outcome::Done))],
+        vec![(Op::Add(1, 2), Ok(Outcome::Done))],
+    ];
+    let lost = [(Op::GetItemIds(1), Ok(Outcome::Ids(vec![2])))];
+    assert!(!linearizable(
+        &OrderModel::default(),
+        &observed,
+        &mut vec![0, 0],
+        &lost
+    ));
+    let either = [(Op::GetItemIds(1), Ok(Outcome::Ids(vec![2, 1])))];
+    assert!(linearizable(
+        &OrderModel::default(),
+        &observed,
+        &mut vec![0, 0],
+        &either
+    ));
+}
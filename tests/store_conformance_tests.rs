@@ -0,0 +1,70 @@
+#![deny(warnings)]
+#![deny(clippy::all)]
+
+use restaurant_menu_app::server::data_model::models::{MenuItem, MenuStore};
+use restaurant_menu_app::server::data_model::money::Money;
+use restaurant_menu_app::server::data_store::conformance;
+use restaurant_menu_app::server::data_store::in_memory_check_store::InMemoryCheckStore;
+use restaurant_menu_app::server::data_store::in_memory_inventory_store::InMemoryInventoryStore;
+use restaurant_menu_app::server::data_store::in_memory_menu_store::InMemoryMenuStore;
+use restaurant_menu_app::server::data_store::in_memory_order_store::InMemoryOrderStore;
+use restaurant_menu_app::server::data_store::in_memory_payment_store::InMemoryPaymentStore;
+use restaurant_menu_app::server::data_store::in_memory_reservation_store::InMemoryReservationStore;
+use restaurant_menu_app::server::data_store::in_memory_session_store::InMemorySessionStore;
+use restaurant_menu_app::server::data_store::in_memory_stock_store::InMemoryStockStore;
+use restaurant_menu_app::server::data_store::in_memory_table_store::InMemoryTableStore;
+
+#[test]
+fn test_in_memory_menu_store_conformance() {
+    let menus = vec![
+        MenuItem::new(2, "Soup", 5, Money::from_cents(650)),
+        MenuItem::new(1, "Salad", 1, Money::from_cents(850)),
+    ];
+    conformance::check_menu_store(&InMemoryMenuStore::new(menus.clone()), &menus);
+    conformance::check_menu_store(&InMemoryMenuStore::new(vec![]), &[]);
+
+    let predefined = InMemoryMenuStore::with_predefined_recipes();
+    let menus = predefined.get_all_menus().unwrap();
+    conformance::check_menu_store(&predefined, &menus);
+}
+
+#[test]
+fn test_in_memory_table_store_conformance() {
+    let expected: Vec<u32> = (1..=100).collect();
+    conformance::check_table_store(&InMemoryTableStore::new(), &expected);
+}
+
+#[test]
+fn test_in_memory_order_store_conformance() {
+    conformance::check_order_store(InMemoryOrderStore::new);
+}
+
+#[test]
+fn test_in_memory_check_store_conformance() {
+    conformance::check_check_store(InMemoryCheckStore::new);
+}
+
+#[test]
+fn test_in_memory_payment_store_conformance() {
+    conformance::check_payment_store(InMemoryPaymentStore::new);
+}
+
+#[test]
+fn test_in_memory_session_store_conformance() {
+    conformance::check_session_store(InMemorySessionStore::new);
+}
+
+#[test]
+fn test_in_memory_stock_store_conformance() {
+    conformance::check_stock_store(InMemoryStockStore::new);
+}
+
+#[test]
+fn test_in_memory_inventory_store_conformance() {
+    conformance::check_inventory_store(InMemoryInventoryStore::new);
+}
+
+#[test]
+fn test_in_memory_reservation_store_conformance() {
+    conformance::check_reservation_store(InMemoryReservationStore::new);
+}